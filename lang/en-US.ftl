@@ -7,12 +7,61 @@ file-size = Size
 file-location = Location
 overall = Overall
 status = Status
+summary-failed-games = Failed
+# Games with nothing to back up, such as when all of their saves are disabled.
+summary-empty-games = Empty
+# Shown when save files were intentionally skipped by `--only-registry`.
+summary-registry-only = Files skipped (registry only)
+# Shown when the cloud was not synchronized after a backup because nothing changed.
+summary-cloud-sync-skipped = Cloud sync skipped (no changes)
+summary-backups = Backups
+# How much space is used by the backups.
+summary-disk-usage = Disk usage
+summary-cloud-changes = Cloud changes
+summary-duration = Duration
+cloud-transferred = Transferred
+cloud-transferred-files = Files
+cloud-transfer-speed = Speed
+cloud-deleted = Deleted
 
 cli-unrecognized-games = No info for these games:
+cli-retried-games = These games only succeeded after retrying:
+cli-disabled-games = These games are disabled, but were included anyway:
+cli-uninstalled-games = These games were skipped because they don't appear to be installed:
+cli-incomplete-games = These games were still in progress when the maximum duration was reached, so they may not have been backed up:
+cli-identical-files = These files have identical content in multiple games, using an extra {$size}:
+# Example: "1.00 KiB, 2.00 KiB wasted"
+cli-identical-files-item = {$size}, {$wasted} wasted
+cli-rename-plan = Backups for {$game} will be moved to {$name}:
+cli-rename-plan-merge = Backups for {$game} will be merged into the existing backups for {$name}:
 cli-unable-to-request-confirmation = Unable to request confirmation.
     .winpty-workaround = If you are using a Bash emulator (like Git Bash), try running winpty.
 cli-backup-id-with-multiple-games = Cannot specify backup ID when restoring multiple games.
+cli-cloud-purge-without-target = Specify which games to delete from the cloud, or use --all to delete everything.
 cli-invalid-backup-id = Invalid backup ID.
+cli-invalid-games-file = The games file is invalid.
+cli-unknown-config-key = Unknown config key: {$key}. Valid keys:
+cli-invalid-config-value = Invalid value for config key: {$key}
+cli-excluded-store-games = Skipped games from excluded store ({$store}): {$total}
+cli-resumed-games = Skipped games already completed by an interrupted backup: {$total}
+cli-aborted-after-failure = Stopped early because a game failed. Skipped games: {$total}
+cli-aborted-after-max-duration = Stopped early because the backup took longer than the maximum duration. Skipped games: {$total}
+cli-watch-started = Watching saves of {$total} games for changes. Press Ctrl+C to stop.
+cli-watch-backed-up = Backed up: {$name}
+cli-watch-backup-failed = Backup failed: {$name}
+cli-secondary-manifest-games = Games added by secondary manifest ({$name}): {$total}
+cli-only-registry-unsupported = The registry is only available on Windows, so nothing can be backed up with --only-registry.
+cli-select-games-to-back-up = Which games do you want to back up?
+cli-select-games-to-restore = Which games do you want to restore?
+cli-cloud-conflict-prompt = How do you want to resolve the conflict?
+cli-cloud-conflict-game-prompt = How do you want to resolve the conflict for {$game}?
+cli-cloud-conflict-upload = Upload (replace cloud files with local files)
+cli-cloud-conflict-download = Download (replace local files with cloud files)
+cli-cloud-conflict-upload-all = Upload for all games
+cli-cloud-conflict-download-all = Download for all games
+cli-cloud-conflict-choose-per-game = Choose for each game
+cli-cloud-conflict-skip = Skip (don't synchronize after the backup)
+cli-manifest-is-stale = Warning: The manifest was last updated {$total} days ago, so some newer games may not be found. Check whether Ludusavi can download manifest updates.
 
 badge-failed = FAILED
 badge-duplicates = DUPLICATES
@@ -20,11 +69,42 @@ badge-duplicated = DUPLICATED
 badge-ignored = IGNORED
 badge-redirected-from = FROM: {$path}
 badge-redirecting-to = TO: {$path}
+badge-not-found = NOT FOUND
+# This appears next to a root that Ludusavi found automatically, but which is not in your config.
+badge-detected = DETECTED
+# This appears next to a root that came from an environment variable instead of your config.
+badge-environment = ENVIRONMENT
 
 some-entries-failed = Some entries failed to process; look for {badge-failed} in the output for details. Double check whether you can access those files or whether their paths are very long.
 
 cli-game-line-item-redirected = Redirected from: {$path}
 cli-game-line-item-redirecting = Redirecting to: {$path}
+cli-game-line-item-preserved = Preserved existing value: {$name}
+cli-game-line-item-duplicate-owner = Duplicate assigned by rule to: {$game}
+cli-game-line-item-other-os = From a save location for: {$os}
+cli-game-line-item-oversized = Skipped because it is larger than the maximum file size: {$size}
+cli-game-globally-ignored = Skipped by global ignore rules: {$total} {$total ->
+    [one] file
+    *[other] files
+}
+cli-game-system-files-excluded = Skipped system files: {$total} {$total ->
+    [one] file
+    *[other] files
+}
+cli-game-restored-from-backup = Backup: {$backup}
+cli-game-restored-into-prefix = Proton prefix: {$path}
+cli-game-would-overwrite = Would overwrite on disk: {$total} {$total ->
+    [one] file
+    *[other] files
+} ({$size})
+cli-game-would-orphan = Not in the backup, but found on disk: {$total} {$total ->
+    [one] file
+    *[other] files
+}
+cli-game-cleaned = Deleted because they are not in the backup: {$total} {$total ->
+    [one] file
+    *[other] files
+}
 
 button-backup = Back up
 button-preview = Preview
@@ -56,10 +136,27 @@ no-roots-are-configured = Add some roots to back up even more data.
 config-is-invalid = Error: The config file is invalid.
 manifest-is-invalid = Error: The manifest file is invalid.
 manifest-cannot-be-updated = Error: Unable to check for an update to the manifest file. Is your Internet connection down?
+manifest-checksum-mismatch = Error: The downloaded manifest did not match the expected checksum, so the previous manifest will be kept.
+    .details = Expected: {$expected} | Actual: {$actual}
+manifest-not-found = Error: The manifest file has not been downloaded yet. Try again without disabling manifest updates.
 cannot-prepare-backup-target = Error: Unable to prepare backup target (either creating or emptying the folder). If you have the folder open in your file browser, try closing it: {$path}
+backup-rename-conflict = Error: Unable to rename the backups for {$game} because these files already exist under the new name:
+cannot-rename-backups = Error: Unable to move the backups for {$game}. If you have the folder open in your file browser, try closing it: {$path}
 restoration-source-is-invalid = Error: The restoration source is invalid (either doesn't exist or isn't a directory). Please double check the location: {$path}
+cannot-create-bundle = Error: Unable to create the archive of exported backups. Please double check the location: {$path}
+bundle-is-invalid = Error: Unable to read the archive of exported backups (either doesn't exist or isn't a valid archive). Please double check the location: {$path}
+cannot-export-settings = Error: Unable to write the exported settings. Please double check the location: {$path}
+cannot-write-report = Error: Unable to write the report. Please double check the location: {$path}
+cannot-open-log-file = Error: Unable to open the log file. Please double check the location: {$path}
+cannot-write-manifest-snapshot = Error: Unable to save a copy of the manifest in the backup folder: {$path}
+undefined-environment-variable = Error: The environment variable {$name} is not set, but it is used by this path: {$path}
+settings-are-invalid = Error: Unable to read the exported settings (either doesn't exist or isn't valid). Please double check the location: {$path}
 registry-issue = Error: Some registry entries were skipped.
 unable-to-browse-file-system = Error: Unable to browse on your system.
+unable-to-watch-files = Error: Unable to watch for file changes.
+git-unavailable = Error: The Git backup format requires Git, but it is not installed or could not be found.
+git-command-failed = Unable to use the Git repository of the backups.
+max-backup-duration-exceeded = Error: The backup was stopped because it took longer than the maximum duration.
 unable-to-open-directory = Error: Unable to open directory:
 unable-to-open-url = Error: Unable to open URL:
 unable-to-configure-cloud = Unable to configure cloud.
@@ -113,12 +210,18 @@ label-removed = Removed
 label-comment = Comment
 label-unchanged = Unchanged
 label-scan = Scan
+# Estimated time remaining
+label-eta = ETA
 label-filter  = Filter
 label-unique = Unique
 label-complete = Complete
 label-partial = Partial
 label-enabled = Enabled
 label-disabled = Disabled
+# This refers to a game that was backed up or restored without any errors.
+label-succeeded = Succeeded
+# This refers to a game that had errors while being backed up or restored.
+label-failed = Failed
 # https://en.wikipedia.org/wiki/Thread_(computing)
 label-threads = Threads
 label-cloud = Cloud
@@ -126,6 +229,11 @@ label-cloud = Cloud
 label-remote = Remote
 label-remote-name = Remote name
 label-folder = Folder
+# This appears next to a differential backup, followed by the name of its full backup.
+label-based-on = Based on
+# How much space is used by a game's backups.
+label-disk-usage = Disk usage
+label-note = Note
 # An executable file
 label-executable = Executable
 # Options given to a command line program
@@ -137,6 +245,11 @@ label-host = Host
 label-port = Port
 label-username = Username
 label-password = Password
+# https://en.wikipedia.org/wiki/Amazon_S3
+label-bucket = Bucket
+label-region = Region
+label-access-key = Access key
+label-secret-key = Secret key
 # This is a specific website or service that provides some cloud functionality.
 # For example, Nextcloud and Owncloud are providers of WebDAV services.
 label-provider = Provider
@@ -157,6 +270,7 @@ label-primary-manifest = Primary manifest
 
 store-ea = EA
 store-epic = Epic
+store-flatpak = Flatpak
 store-gog = GOG
 store-gog-galaxy = GOG Galaxy
 store-heroic = Heroic
@@ -180,9 +294,13 @@ store-other-linux = Linux drive
 # like "Applications" and "Users".
 store-other-mac = Mac drive
 store-other = Other
+# Used to group games that were not detected in any store.
+store-group-custom = Custom games
+store-group-unknown = Unknown
 
 backup-format-simple = Simple
 backup-format-zip = Zip
+backup-format-git = Git
 
 compression-none = None
 # "Deflate" is a proper noun: https://en.wikipedia.org/wiki/Deflate
@@ -223,22 +341,34 @@ confirm-restore =
     Are you sure you want to proceed with the restoration?
     This will overwrite any current files with the backups from here:
 
+confirm-restore-clean =
+    Any other files in the games' save locations that are not part of the backups will be deleted.
+
 confirm-cloud-upload =
     Do you want to replace your cloud files with your local files?
     Your cloud files ({$cloud-path}) will become an exact copy of your local files ({$local-path}).
     Files in the cloud will be updated or deleted as necessary.
 
+confirm-cloud-purge =
+    Do you want to permanently delete these folders from the cloud?
+    This cannot be undone.
+
 confirm-cloud-download =
     Do you want to replace your local files with your cloud files?
     Your local files ({$local-path}) will become an exact copy of your cloud files ({$cloud-path}).
     Local files will be updated or deleted as necessary.
 
 confirm-add-missing-roots = Add these roots?
+confirm-settings-import =
+    Do you want to replace your current settings with the ones from this file?
+    Ludusavi's config and cached state will be overwritten: {$path}
 no-missing-roots = No additional roots found.
 loading = Loading...
 preparing-backup-target = Preparing backup directory...
 updating-manifest = Updating manifest...
 no-cloud-changes = No changes to synchronize
+no-cloud-folders-to-delete = No matching folders found in the cloud
+no-manifest-changes = No differences between the manifests
 backups-are-valid =
     Your backups are valid.
 backups-are-invalid =
@@ -261,6 +391,13 @@ prefix-warning = Warning: {$message}
 cloud-app-unavailable = Cloud backups are disabled because {$app} is not available.
 cloud-not-configured = Cloud backups are disabled because no cloud system is configured.
 cloud-path-invalid = Cloud backups are disabled because the backup path is invalid.
+cloud-remote-invalid = Invalid Rclone remote name: {$remote}
+cloud-remote-incomplete = The cloud system is missing these required settings:
+cloud-remote-read-only = The cloud system is read-only, so backups can only be downloaded from it.
+cloud-upload-mismatched = These uploaded files do not match the local backups:
+cloud-ignore-file-invalid-rule = Invalid rule on line {$line} of the cloud ignore file: {$path}
+    Each rule must start with "+ " to include files or "- " to exclude them.
+cloud-ignore-file-unreadable = Unable to read the cloud ignore file: {$path}
 
 game-is-unrecognized = Ludusavi does not recognize this game.
 game-has-nothing-to-restore = This game does not have a backup to restore.
@@ -1,9 +1,20 @@
 mod api;
+mod games_file;
 mod parse;
+mod progress;
 mod report;
 mod ui;
 
-use std::{collections::BTreeSet, process::Command, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    num::NonZeroUsize,
+    process::Command,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use clap::CommandFactory;
 use indicatif::{ParallelProgressIterator, ProgressBar};
@@ -14,19 +25,37 @@ use rayon::{
 
 use crate::{
     cli::{
-        parse::{Cli, CompletionShell, ManifestSubcommand, Subcommand},
-        report::{report_cloud_changes, Reporter},
+        games_file::GamesFile,
+        parse::{Cli, CliGroupBy, CliWinePrefix, CompletionShell, ManifestSubcommand, Subcommand},
+        progress::{ProgressEvents, ProgressStep},
+        report::{report_cloud_changes, report_cloud_purge, report_found_line, ListedGame, Reporter},
     },
-    cloud::{CloudChange, Rclone, Remote},
+    cloud::{CloudChange, CloudStats, Rclone, Remote},
     lang::{Language, TRANSLATOR},
     prelude::{
-        app_dir, get_threads_from_env, initialize_rayon, register_sigint, unregister_sigint, Error, Finality,
-        StrictPath, SyncDirection,
+        app_dir, get_threads_from_env, initialize_color, initialize_rayon, register_sigint, should_assume_yes,
+        unregister_sigint, Error, Finality, StrictPath, SyncDirection,
+    },
+    resource::{
+        cache::Cache,
+        config::{expand_env_var, Config, GlobalIgnore, Root},
+        manifest::Manifest,
+        settings::Settings,
+        ResourceFile, SaveableResourceFile,
     },
-    resource::{cache::Cache, config::Config, manifest::Manifest, ResourceFile, SaveableResourceFile},
     scan::{
-        layout::BackupLayout, prepare_backup_target, scan_game_for_backup, BackupId, DuplicateDetector, Launchers,
-        OperationStepDecision, SteamShortcuts, TitleFinder, TitleQuery,
+        bundle::{self, BundledGame},
+        find_game_paths_containing,
+        git::{self, GitCheckout},
+        layout::BackupLayout,
+        manifest_snapshot::ManifestSnapshots,
+        prepare_backup_target, rename,
+        restore_map::RestoreMap,
+        resume::ResumeJournal,
+        scan_game_for_backup,
+        watch::{Debouncer, WatchTargets},
+        BackupId, BackupInfo, BackupOptions, BackupPipeline, DuplicateDetector, Launchers, OperationStatus,
+        OperationStepDecision, RestoreImpact, ScanChange, ScanInfo, SteamShortcuts, TitleFinder, TitleQuery,
     },
     wrap,
 };
@@ -43,14 +72,112 @@ fn negatable_flag(on: bool, off: bool, default: bool) -> bool {
     }
 }
 
+/// Repeat an operation for any of its entries that failed, up to the number of `retries`.
+/// Also returns whether the operation only succeeded because of a retry.
+fn retry_failures(
+    name: &str,
+    retries: u8,
+    mut info: BackupInfo,
+    attempt: impl Fn(&BackupInfo) -> BackupInfo,
+) -> (BackupInfo, bool) {
+    let mut attempts = 0;
+    while !info.successful() && attempts < retries {
+        attempts += 1;
+        log::info!("[{name}] retrying failed entries, attempt {attempts} of {retries}");
+        info = attempt(&info);
+    }
+    let retried = attempts > 0 && info.successful();
+    (info, retried)
+}
+
+/// With `--wine-prefix auto`, find the launchers that know each game's prefix,
+/// even if they aren't configured as roots.
+fn wine_prefix_launchers(
+    config: &Config,
+    roots: &[Root],
+    manifest: &Manifest,
+    games: &[String],
+    title_finder: &TitleFinder,
+    wine_prefix: &Option<CliWinePrefix>,
+) -> Launchers {
+    match wine_prefix {
+        Some(CliWinePrefix::Auto) => {
+            let launcher_roots: Vec<_> = roots
+                .iter()
+                .cloned()
+                .chain(config.find_missing_roots())
+                .filter(|root| matches!(root, Root::Heroic(_) | Root::Lutris(_)))
+                .collect();
+            Launchers::scan(&launcher_roots, manifest, games, title_finder, None)
+        }
+        _ => Launchers::default(),
+    }
+}
+
+fn wine_prefix_for_game(
+    wine_prefix: &Option<CliWinePrefix>,
+    prefix_launchers: &Launchers,
+    name: &str,
+) -> Option<StrictPath> {
+    match wine_prefix {
+        Some(CliWinePrefix::Auto) => prefix_launchers.find_prefix(name),
+        Some(CliWinePrefix::Path(path)) => Some(path.clone()),
+        None => None,
+    }
+}
+
+/// Show the games handled by the `export` and `import` commands.
+fn print_bundle(config: &Config, games: &[BundledGame], skipped: &[BundledGame], location: &StrictPath) {
+    let mut status = OperationStatus::default();
+    let mut parts = vec![];
+
+    for (game, processed) in games
+        .iter()
+        .map(|x| (x, true))
+        .chain(skipped.iter().map(|x| (x, false)))
+    {
+        status.total_games += 1;
+        status.total_bytes += game.bytes;
+        if processed {
+            status.processed_games += 1;
+            status.processed_bytes += game.bytes;
+        }
+
+        let decision = if processed {
+            OperationStepDecision::Processed
+        } else {
+            OperationStepDecision::Ignored
+        };
+        parts.push(TRANSLATOR.cli_game_header(
+            config.display_name(&game.name),
+            game.bytes,
+            &decision,
+            false,
+            ScanChange::Same,
+        ));
+        for file in &game.files {
+            parts.push(format!("  - {file}"));
+        }
+        parts.push("".to_string());
+    }
+
+    parts.push(TRANSLATOR.cli_summary(&status, location));
+    println!("{}", parts.join("\n"));
+}
+
 fn load_manifest(
     config: &Config,
     cache: &mut Cache,
     no_manifest_update: bool,
     try_manifest_update: bool,
 ) -> Result<Manifest, Error> {
-    if no_manifest_update {
-        Ok(Manifest::load().unwrap_or_default().with_extensions(config))
+    if let Some(path) = &config.manifest.cli_path {
+        Manifest::load_file(path).map(|x| x.with_extensions(config))
+    } else if no_manifest_update {
+        if !Manifest::path().exists() {
+            return Err(Error::ManifestNotFound);
+        }
+        Manifest::load().map(|x| x.with_extensions(config))
     } else if try_manifest_update {
         if let Err(e) = Manifest::update_mut(config, cache, false) {
             eprintln!("{}", TRANSLATOR.handle_error(&e));
@@ -62,6 +189,37 @@ fn load_manifest(
     }
 }
 
+/// Apply the `--root` and `--roots-replace` options to the configured roots.
+fn roots_for_invocation(config: &Config, extra: &[Root], replace: bool) -> Result<Vec<Root>, Error> {
+    let extra = extra.iter().flat_map(|x| x.glob());
+    if replace {
+        Ok(extra.collect())
+    } else {
+        for root in &config.roots {
+            expand_env_var(root.path())?;
+        }
+        Ok(config.expanded_roots().into_iter().chain(extra).collect())
+    }
+}
+
+/// Warn if the primary manifest has not been checked for longer than the threshold.
+fn warn_if_manifest_is_stale(config: &Config, cache: &Cache, threshold: Option<u32>, reporter: &mut Reporter) {
+    let Some(threshold) = threshold else {
+        return;
+    };
+    // A local manifest file isn't downloaded, so its age isn't tracked.
+    if config.manifest.cli_path.is_some() {
+        return;
+    }
+    let Some(days) = cache.manifest_age_days(config.manifest.url()) else {
+        return;
+    };
+    if days > threshold as i64 {
+        eprintln!("{}", TRANSLATOR.cli_manifest_is_stale(days));
+        reporter.set_manifest_age_days(days);
+    }
+}
+
 fn parse_games(games: Vec<String>) -> Vec<String> {
     if !games.is_empty() {
         games
@@ -110,13 +268,40 @@ pub fn evaluate_games(
 }
 
 pub fn parse() -> Cli {
-    use clap::Parser;
+    use clap::{FromArgMatches, Parser};
+
+    // Clap already handles `NO_COLOR` and non-terminal output for the help text,
+    // but it needs to know about `--no-color` before parsing.
+    if std::env::args_os().any(|arg| arg == "--no-color") {
+        let matches = Cli::command().color(clap::ColorChoice::Never).get_matches();
+        return Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    }
+
     Cli::parse()
 }
 
-pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool) -> Result<(), Error> {
+pub fn run(
+    sub: Subcommand,
+    no_manifest_update: bool,
+    try_manifest_update: bool,
+    json_indent: Option<usize>,
+    custom_games: Vec<StrictPath>,
+    manifest_enforce_checksum: bool,
+    manifest_extra: Vec<String>,
+    manifest_path: Option<StrictPath>,
+    threads: Option<usize>,
+    no_color: bool,
+) -> Result<(), Error> {
+    initialize_color(no_color);
     let mut config = Config::load()?;
-    if let Some(threads) = get_threads_from_env().or(config.runtime.threads) {
+    config.cli_custom_game_files = custom_games.clone();
+    config.manifest.enforce_checksum = manifest_enforce_checksum;
+    config.manifest.cli_secondary = manifest_extra.clone();
+    config.manifest.cli_path = manifest_path.clone();
+    let threads = threads.map(|threads| {
+        NonZeroUsize::new(threads).unwrap_or_else(|| std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN))
+    });
+    if let Some(threads) = threads.or_else(get_threads_from_env).or(config.runtime.scan_threads) {
         initialize_rayon(threads);
     }
     let mut cache = Cache::load().unwrap_or_default().migrate_config(&mut config);
@@ -132,31 +317,78 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             preview,
             path,
             force,
+            include_disabled,
             wine_prefix,
+            root,
+            roots_replace,
+            exclude_store,
+            skip_empty,
+            only_new_games,
+            only_registry,
+            include_other_os_paths,
+            interactive,
             api,
+            summary_only,
+            report_to,
+            preserve_permissions,
+            preserve_empty_dirs,
+            include_manifest_snapshot,
+            include_restore_map,
+            follow_symlinks,
+            no_follow_symlinks,
+            max_file_size,
+            global_ignore,
+            no_exclude_system_files,
+            dedupe_report,
+            retries,
+            abort_on_first_failure,
+            max_backup_duration,
+            resume,
+            manifest_age_warning,
             sort,
+            group_by,
+            progress_format,
             format,
             compression,
             compression_level,
+            hash_algorithm,
+            backup_id_format,
+            game_format,
             full_limit,
             differential_limit,
+            game_retention,
             cloud_sync,
             no_cloud_sync,
+            force_cloud_sync,
             games,
         } => {
+            let started = Instant::now();
             let games = parse_games(games);
 
-            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            if only_registry && !cfg!(target_os = "windows") {
+                return Err(Error::CliOnlyRegistryUnsupported);
+            }
+
+            let mut reporter = if api {
+                Reporter::json()
+                    .with_json_indent(json_indent)
+                    .with_summary_only(summary_only)
+            } else {
+                Reporter::standard().with_summary_only(summary_only)
+            }
+            .with_group_by_store(group_by == Some(CliGroupBy::Store));
 
             let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
+            warn_if_manifest_is_stale(&config, &cache, manifest_age_warning, &mut reporter);
 
             let backup_dir = match path {
                 None => config.backup.path.clone(),
                 Some(p) => p,
             };
-            let roots = config.expanded_roots();
+            let backup_dir = expand_env_var(&backup_dir)?;
+            let roots = roots_for_invocation(&config, &root, roots_replace)?;
 
-            if !preview && !force {
+            if !preview && !force && !should_assume_yes() {
                 match dialoguer::Confirm::new()
                     .with_prompt(TRANSLATOR.confirm_backup(&backup_dir, backup_dir.exists(), false))
                     .interact()
@@ -169,6 +401,9 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 
             if !preview {
                 prepare_backup_target(&backup_dir)?;
+                if include_manifest_snapshot {
+                    ManifestSnapshots::new(&backup_dir).record(&manifest, &chrono::Utc::now())?;
+                }
             }
 
             let mut retention = config.backup.retention.clone();
@@ -179,11 +414,15 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 retention.differential = differential_limit;
             }
 
-            let layout = BackupLayout::new(backup_dir.clone(), retention);
+            let mut per_game_retention = config.backup.per_game_retention.clone();
+            per_game_retention.extend(game_retention);
+
+            let mut layout = BackupLayout::new(backup_dir.clone(), retention.clone())
+                .with_per_game_retention(per_game_retention.clone());
             let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
 
             let games_specified = !games.is_empty();
-            let games = match evaluate_games(manifest.primary_titles(), games, &title_finder) {
+            let mut games = match evaluate_games(manifest.primary_titles(), games, &title_finder) {
                 Ok(games) => games,
                 Err(games) => {
                     reporter.trip_unknown_games(games.clone());
@@ -193,9 +432,69 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             };
 
             let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
-            let filter = config.backup.filter.clone();
-            let toggled_paths = config.backup.toggled_paths.clone();
-            let toggled_registry = config.backup.toggled_registry.clone();
+            let excluded_stores = launchers.exclude_stores(&mut games, &exclude_store);
+            for (store, total) in &excluded_stores {
+                eprintln!("{}", TRANSLATOR.cli_excluded_store_games(store, *total));
+            }
+            reporter.set_excluded_stores(excluded_stores);
+            if group_by == Some(CliGroupBy::Store) {
+                for game in &games {
+                    reporter.add_game_store(game, launchers.store_group(game, config.is_game_customized(game)));
+                }
+            }
+
+            let prefix_launchers =
+                wine_prefix_launchers(&config, &roots, &manifest, &games, &title_finder, &wine_prefix);
+            let mut options = BackupOptions::new(&config);
+            if let Some(hash_algorithm) = hash_algorithm {
+                options.hash_algorithm = hash_algorithm;
+            }
+            if backup_id_format.is_some() {
+                options.id_format = backup_id_format;
+            }
+            options.preserve_empty_dirs = preserve_empty_dirs || config.backup.preserve_empty_dirs;
+            options.follow_symlinks =
+                negatable_flag(follow_symlinks, no_follow_symlinks, config.backup.follow_symlinks);
+            options.only_registry = only_registry;
+            options.include_other_os_paths = include_other_os_paths;
+            if max_file_size.is_some() {
+                options.max_file_size = max_file_size;
+            }
+            reporter.set_max_file_size(options.max_file_size);
+            options.exclude_system_files = !no_exclude_system_files && config.backup.exclude_system_files;
+            if only_registry {
+                reporter.set_registry_only();
+            }
+            if !global_ignore.is_empty() {
+                options.global_ignore = GlobalIgnore::new(
+                    &config
+                        .backup
+                        .global_ignore
+                        .iter()
+                        .cloned()
+                        .chain(global_ignore)
+                        .collect::<Vec<_>>(),
+                    &config.backup.global_force_include,
+                );
+            }
+            if let Some(format) = format {
+                options.format.chosen = format;
+            }
+            if let Some(compression) = compression {
+                options.format.zip.compression = compression;
+            }
+            if let Some(level) = compression_level {
+                options
+                    .format
+                    .compression
+                    .set_level(&options.format.zip.compression, level);
+            }
+            options.per_game_format.extend(game_format);
+            options.preserve_permissions = preserve_permissions;
+            options.retries = retries;
+            if !preview && options.uses_git() {
+                git::validate()?;
+            }
             let steam_shortcuts = SteamShortcuts::scan();
 
             let cloud_sync = negatable_flag(
@@ -203,7 +502,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 no_cloud_sync,
                 config.cloud.synchronize
                     && !preview
-                    && crate::cloud::validate_cloud_config(&config, &config.cloud.path).is_ok(),
+                    && crate::cloud::validate_cloud_config(&config, &config.cloud.path, SyncDirection::Upload).is_ok(),
             );
             let mut should_sync_cloud_after = cloud_sync && !preview;
             if cloud_sync {
@@ -214,12 +513,38 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     SyncDirection::Upload,
                     Finality::Preview,
                     if games_specified { &games } else { &[] },
-                );
+                )
+                .into_result();
                 match changes {
-                    Ok(changes) => {
+                    Ok((changes, _)) => {
                         if !changes.is_empty() {
-                            should_sync_cloud_after = false;
-                            reporter.trip_cloud_conflict();
+                            if force || api {
+                                should_sync_cloud_after = false;
+                                reporter.trip_cloud_conflict();
+                            } else {
+                                match resolve_cloud_conflicts(
+                                    &config,
+                                    &backup_dir,
+                                    &layout,
+                                    &changes,
+                                    if games_specified { &games } else { &[] },
+                                ) {
+                                    Ok((resolved, synced)) => {
+                                        reporter.add_cloud_changes(&synced);
+                                        if !resolved {
+                                            should_sync_cloud_after = false;
+                                            reporter.trip_cloud_conflict();
+                                        }
+                                        // Downloaded backups need to be picked up by the layout.
+                                        layout = BackupLayout::new(backup_dir.clone(), retention.clone())
+                                            .with_per_game_retention(per_game_retention.clone());
+                                    }
+                                    Err(_) => {
+                                        should_sync_cloud_after = false;
+                                        reporter.trip_cloud_sync_failed();
+                                    }
+                                }
+                            }
                         }
                     }
                     Err(_) => {
@@ -229,82 +554,200 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
+            if only_new_games {
+                let backed_up = layout.restorable_game_set();
+                games.retain(|game| !backed_up.contains(game));
+                log::info!("only backing up {} games without existing backups", games.len());
+            }
+
+            // This is shared with the scans so that they can stop partway through a game.
+            let deadline = Arc::new(AtomicBool::new(false));
+            if let Some(max) = max_backup_duration {
+                let deadline = deadline.clone();
+                let remaining = max.saturating_sub(started.elapsed());
+                std::thread::spawn(move || {
+                    std::thread::sleep(remaining);
+                    deadline.store(true, Ordering::Relaxed);
+                });
+            }
+            let over_time = || deadline.load(Ordering::Relaxed);
+
+            let pipeline = BackupPipeline::new(
+                &config,
+                &manifest,
+                &layout,
+                &roots,
+                &launchers,
+                &steam_shortcuts,
+                &options,
+            )
+            .with_cancel(&deadline);
+            let scan_game = |name: &String| {
+                let wine_prefix = wine_prefix_for_game(&wine_prefix, &prefix_launchers, name);
+                pipeline.scan_game(name, games_specified, &wine_prefix)
+            };
+
+            // In interactive mode, we scan everything up front so that the user can pick from what was found.
+            // The selected scans are then reused for the actual backup.
+            let mut selected = HashMap::new();
+            if interactive {
+                let scanned: Vec<_> = games
+                    .par_iter()
+                    .progress_with(scan_progress_bar(games.len() as u64))
+                    .filter_map(|name| scan_game(name).filter(|x| x.can_report_game()))
+                    .collect();
+                selected = select_games(&config, scanned, TRANSLATOR.cli_select_games_to_back_up())?;
+                games.retain(|game| selected.contains_key(game));
+            }
+
+            let journal = (!preview).then(|| {
+                let fingerprint = ResumeJournal::fingerprint(&games, &options.format, &options.per_game_format);
+                ResumeJournal::open(&backup_dir, &fingerprint, resume)
+            });
+            if let Some(journal) = journal.as_ref().filter(|x| x.completed() > 0) {
+                eprintln!("{}", TRANSLATOR.cli_resumed_games(journal.completed()));
+            }
+            // These were backed up by the interrupted run, which may not have reached its cloud sync.
+            let resumed_games: Vec<_> = match &journal {
+                Some(journal) => games.iter().filter(|x| journal.is_completed(x)).cloned().collect(),
+                None => vec![],
+            };
+
             log::info!("beginning backup with {} steps", games.len());
 
+            let events = ProgressEvents::new(progress_format.unwrap_or_default(), games.len() as u64);
+            let progress = operation_progress_bar(games.len() as u64, events.as_ref());
+            let processed_bytes = AtomicU64::new(0);
+            let empty_games = AtomicUsize::new(0);
+            let aborted = AtomicBool::new(false);
+            let timed_out = AtomicBool::new(false);
+            let unprocessed_games = AtomicUsize::new(0);
+            let incomplete_games = Mutex::new(BTreeSet::new());
             let mut info: Vec<_> = games
                 .par_iter()
                 .enumerate()
-                .progress_with(scan_progress_bar(games.len() as u64))
+                .progress_with(progress.clone())
                 .filter_map(|(i, name)| {
-                    log::trace!("step {i} / {}: {name}", games.len());
-                    let game = &manifest.0[name];
+                    log::trace!(game = name.as_str(), step = i; "step {i} / {}: {name}", games.len());
+                    let mut step = ProgressStep::new(events.as_ref(), i, name);
 
-                    let previous = layout.latest_backup(name, false, &config.redirects, &config.restore.toggled_paths);
+                    if journal.as_ref().is_some_and(|x| x.is_completed(name)) {
+                        log::trace!("[{name}] already completed by interrupted backup");
+                        return None;
+                    }
 
-                    if filter.excludes(games_specified, previous.is_some(), &game.cloud) {
-                        log::trace!("[{name}] excluded by backup filter");
+                    if aborted.load(Ordering::Relaxed) {
+                        log::trace!("[{name}] skipped because another game failed");
+                        unprocessed_games.fetch_add(1, Ordering::Relaxed);
                         return None;
                     }
 
-                    let scan_info = scan_game_for_backup(
-                        game,
-                        name,
-                        &roots,
-                        &app_dir(),
-                        &launchers,
-                        &filter,
-                        &wine_prefix,
-                        &toggled_paths,
-                        &toggled_registry,
-                        previous,
-                        &config.redirects,
-                        &steam_shortcuts,
-                    );
-                    let ignored = !&config.is_game_enabled_for_backup(name) && !games_specified;
+                    if timed_out.load(Ordering::Relaxed) || over_time() {
+                        log::trace!("[{name}] skipped because the maximum duration was reached");
+                        timed_out.store(true, Ordering::Relaxed);
+                        unprocessed_games.fetch_add(1, Ordering::Relaxed);
+                        return None;
+                    }
+
+                    let scan_info = if interactive {
+                        selected.get(name).cloned()
+                    } else {
+                        scan_game(name)
+                    };
+                    let scan_info = scan_info?;
+                    if over_time() {
+                        log::warn!("[{name}] not backed up because the maximum duration was reached during its scan");
+                        timed_out.store(true, Ordering::Relaxed);
+                        incomplete_games.lock().unwrap().insert(name.clone());
+                        return None;
+                    }
+                    let ignored = !&config.is_game_enabled_for_backup(name) && !games_specified && !include_disabled;
+                    if skip_empty && !ignored && scan_info.found_anything() && scan_info.all_ignored() {
+                        log::trace!("[{name}] skipped because it has nothing to back up");
+                        empty_games.fetch_add(1, Ordering::Relaxed);
+                        return None;
+                    }
                     let decision = if ignored {
                         OperationStepDecision::Ignored
                     } else {
                         OperationStepDecision::Processed
                     };
-                    let backup_info = if preview || ignored {
-                        crate::scan::BackupInfo::default()
+                    let (backup_info, retried) = if preview || ignored {
+                        (crate::scan::BackupInfo::default(), false)
                     } else {
-                        let mut backup_format = config.backup.format.clone();
-                        if let Some(format) = format {
-                            backup_format.chosen = format;
+                        let (backup_info, retried) = pipeline.back_up_game(name, &scan_info);
+                        if over_time() {
+                            log::warn!("[{name}] the maximum duration was reached during its backup");
+                            timed_out.store(true, Ordering::Relaxed);
+                            incomplete_games.lock().unwrap().insert(name.clone());
                         }
-                        if let Some(compression) = compression {
-                            backup_format.zip.compression = compression;
+                        if include_restore_map && scan_info.found_anything() {
+                            RestoreMap::new(&scan_info, &backup_info, &roots).save(&layout.game_folder(name));
                         }
-                        if let Some(level) = compression_level {
-                            backup_format
-                                .compression
-                                .set_level(&backup_format.zip.compression, level);
-                        }
-
-                        layout
-                            .game_layout(name)
-                            .back_up(&scan_info, &chrono::Utc::now(), &backup_format)
+                        (backup_info, retried)
                     };
-                    log::trace!("step {i} completed");
+                    report_byte_throughput(&progress, &processed_bytes, scan_info.sum_bytes(Some(&backup_info)));
+                    step.record(scan_info.sum_bytes(Some(&backup_info)), !backup_info.successful());
+                    if backup_info.successful() {
+                        if let Some(journal) = &journal {
+                            journal.record(name);
+                        }
+                    } else if abort_on_first_failure {
+                        aborted.store(true, Ordering::Relaxed);
+                    }
+                    log::trace!(game = name.as_str(), step = i; "step {i} completed");
                     if !scan_info.can_report_game() {
                         None
                     } else {
                         let display_title = config.display_name(name);
-                        Some((display_title, scan_info, backup_info, decision))
+                        Some((display_title, scan_info, backup_info, decision, retried))
                     }
                 })
                 .collect();
             log::info!("completed backup");
+            if let Some(events) = &events {
+                events.finish();
+            }
+
+            let timed_out = timed_out.into_inner();
+            if aborted.into_inner() {
+                eprintln!(
+                    "{}",
+                    TRANSLATOR.cli_aborted_after_failure(unprocessed_games.into_inner())
+                );
+            } else if timed_out {
+                eprintln!(
+                    "{}",
+                    TRANSLATOR.cli_aborted_after_max_duration(unprocessed_games.into_inner())
+                );
+            }
+            for name in incomplete_games.into_inner().unwrap() {
+                reporter.add_incomplete_game(&name);
+            }
+
+            if skip_empty {
+                reporter.set_empty_games(empty_games.into_inner());
+            }
+
+            if let Some(journal) = journal {
+                // Keep the journal after a timeout so that `--resume` can pick up the remaining games.
+                if !timed_out && info.iter().all(|(_, _, backup_info, ..)| backup_info.successful()) {
+                    journal.remove();
+                }
+            }
 
             if should_sync_cloud_after {
                 let changed_games: Vec<_> = info
                     .iter()
-                    .filter(|(_, scan_info, _, _)| scan_info.needs_cloud_sync())
-                    .map(|(_, scan_info, _, _)| scan_info.game_name.clone())
+                    .filter(|(_, scan_info, ..)| scan_info.needs_cloud_sync())
+                    .map(|(_, scan_info, ..)| scan_info.game_name.clone())
+                    .chain(resumed_games)
                     .collect();
-                if !changed_games.is_empty() {
-                    let sync_result = sync_cloud(
+                if changed_games.is_empty() && !force_cloud_sync {
+                    log::info!("skipping cloud sync because no games changed");
+                    reporter.set_cloud_sync_skipped();
+                } else {
+                    let outcome = sync_cloud(
                         &config,
                         &backup_dir,
                         &config.cloud.path,
@@ -312,19 +755,34 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         Finality::Final,
                         &changed_games,
                     );
-                    if sync_result.is_err() {
+                    if outcome.error.is_some() {
                         reporter.trip_cloud_sync_failed();
                     }
+                    if config.cloud.verify_upload {
+                        if let Err(e) = verify_cloud_upload(&config, &backup_dir, &config.cloud.path, &outcome.changes)
+                        {
+                            eprintln!("{}", TRANSLATOR.handle_error(&e));
+                            reporter.trip_cloud_sync_failed();
+                        }
+                    }
+                    reporter.add_cloud_changes(&outcome.changes);
                 }
             }
 
-            for (_, scan_info, _, _) in info.iter() {
+            duplicate_detector.set_owners(config.backup.duplicate_owners.clone());
+            for (_, scan_info, ..) in info.iter() {
                 duplicate_detector.add_game(
                     scan_info,
                     config.is_game_enabled_for_operation(&scan_info.game_name, false),
                 );
             }
 
+            if dedupe_report {
+                reporter.set_identical_files(crate::scan::find_identical_files(
+                    info.iter().map(|(_, scan_info, ..)| scan_info),
+                ));
+            }
+
             let sort = sort.map(From::from).unwrap_or_else(|| config.backup.sort.clone());
             info.sort_by(
                 |(name1, scan_info1, backup_info1, ..), (name2, scan_info2, backup_info2, ..)| {
@@ -343,56 +801,118 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 info.reverse();
             }
 
-            for (name, scan_info, backup_info, decision) in info {
+            for (name, scan_info, backup_info, decision, retried) in info {
+                if retried {
+                    reporter.add_retried_game(name);
+                }
+                if include_disabled && !config.is_game_enabled_for_backup(&scan_info.game_name) {
+                    reporter.add_disabled_game(name);
+                }
+                if let Some(note) = config.game_note(&scan_info.game_name) {
+                    reporter.add_game_note(&scan_info.game_name, note);
+                }
                 if !reporter.add_game(name, &scan_info, &backup_info, &decision, &duplicate_detector) {
                     failed = true;
                 }
             }
+            reporter.set_duration(started.elapsed());
             reporter.print(&backup_dir);
+            if let Some(report_to) = &report_to {
+                reporter.save(&backup_dir, report_to)?;
+            }
+            if timed_out {
+                return Err(Error::MaxBackupDurationExceeded);
+            }
         }
         Subcommand::Restore {
             preview,
             path,
             force,
+            include_disabled,
+            require_installed,
             api,
+            summary_only,
+            report_to,
+            preserve_permissions,
+            clean,
+            merge_registry,
+            retries,
+            abort_on_first_failure,
+            manifest_age_warning,
+            force_different_os,
+            wine_prefix,
+            steam_deck,
+            root,
+            roots_replace,
             sort,
+            group_by,
+            progress_format,
             backup,
+            git_commit,
+            games_file,
+            interactive,
+            to_archive,
             cloud_sync,
             no_cloud_sync,
             games,
         } => {
-            let games = parse_games(games);
+            let started = Instant::now();
+            let games_file = games_file.map(|path| GamesFile::load(&path)).transpose()?;
+            let games = match &games_file {
+                Some(games_file) => games_file.0.keys().cloned().collect(),
+                None => parse_games(games),
+            };
 
-            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            let mut reporter = if api {
+                Reporter::json()
+                    .with_json_indent(json_indent)
+                    .with_summary_only(summary_only)
+            } else {
+                Reporter::standard().with_summary_only(summary_only)
+            }
+            .with_group_by_store(group_by == Some(CliGroupBy::Store));
 
             let restore_dir = match path {
                 None => config.restore.path.clone(),
                 Some(p) => p,
             };
+            let restore_dir = expand_env_var(&restore_dir)?;
 
-            if !preview && !force {
-                match dialoguer::Confirm::new()
-                    .with_prompt(TRANSLATOR.confirm_restore(&restore_dir, false))
-                    .interact()
-                {
+            // Restoring into an archive doesn't overwrite anything.
+            if !preview && !force && to_archive.is_none() && !should_assume_yes() {
+                let mut prompt = TRANSLATOR.confirm_restore(&restore_dir, false);
+                if clean {
+                    prompt = format!("{}\n\n{}", prompt, TRANSLATOR.confirm_restore_clean());
+                }
+                match dialoguer::Confirm::new().with_prompt(prompt).interact() {
                     Ok(true) => (),
                     Ok(false) => return Ok(()),
                     Err(_) => return Err(Error::CliUnableToRequestConfirmation),
                 }
             }
 
-            let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+            // Keep the checkout alive until the restore is done, since it's removed when dropped.
+            let git_checkout = git_commit
+                .map(|commit| GitCheckout::new(&restore_dir, &commit))
+                .transpose()?;
+            let layout = BackupLayout::new(
+                git_checkout
+                    .as_ref()
+                    .map(|x| x.path().clone())
+                    .unwrap_or_else(|| restore_dir.clone()),
+                config.backup.retention.clone(),
+            );
 
             if backup.is_some() && games.len() != 1 {
                 return Err(Error::CliBackupIdWithMultipleGames);
             }
-            let backup_id = backup.as_ref().map(|x| BackupId::Named(x.clone()));
 
             let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            warn_if_manifest_is_stale(&config, &cache, manifest_age_warning, &mut reporter);
             let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
 
             let games_specified = !games.is_empty();
-            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
+            let mut games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
                 Ok(games) => games,
                 Err(games) => {
                     reporter.trip_unknown_games(games.clone());
@@ -401,12 +921,30 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             };
 
+            // The names in the games file may differ from the canonical titles, such as by capitalization.
+            let mut game_options = HashMap::new();
+            let mut unresolved = vec![];
+            for (name, options) in games_file.map(|x| x.0).unwrap_or_default() {
+                match title_finder.find_one_by_name(&name) {
+                    Some(title) => {
+                        game_options.insert(title, options);
+                    }
+                    None => unresolved.push(name),
+                }
+            }
+            if !unresolved.is_empty() {
+                reporter.trip_unknown_games(unresolved.clone());
+                reporter.print_failure();
+                return Err(Error::CliUnrecognizedGames { games: unresolved });
+            }
+
             let cloud_sync = negatable_flag(
                 cloud_sync && !preview,
                 no_cloud_sync,
                 config.cloud.synchronize
                     && !preview
-                    && crate::cloud::validate_cloud_config(&config, &config.cloud.path).is_ok(),
+                    && crate::cloud::validate_cloud_config(&config, &config.cloud.path, SyncDirection::Download)
+                        .is_ok(),
             );
             if cloud_sync {
                 let changes = sync_cloud(
@@ -416,9 +954,10 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     SyncDirection::Upload,
                     Finality::Preview,
                     if games_specified { &games } else { &[] },
-                );
+                )
+                .into_result();
                 match changes {
-                    Ok(changes) => {
+                    Ok((changes, _)) => {
                         if !changes.is_empty() {
                             reporter.trip_cloud_conflict();
                         }
@@ -429,62 +968,219 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
+            if interactive {
+                let scanned: Vec<_> = games
+                    .par_iter()
+                    .progress_with(scan_progress_bar(games.len() as u64))
+                    .filter_map(|name| {
+                        let backup = game_options
+                            .get(name)
+                            .and_then(|x| x.backup.as_ref())
+                            .or(backup.as_ref());
+                        let backup_id = backup.map(|x| BackupId::Named(x.clone()));
+                        let scan_info = layout.game_layout(name).scan_for_restoration(
+                            name,
+                            backup_id.as_ref().unwrap_or(&BackupId::Latest),
+                            &config.redirects,
+                            &config.restore.toggled_paths,
+                            &config.restore.toggled_registry,
+                        );
+                        scan_info.can_report_game().then_some(scan_info)
+                    })
+                    .collect();
+                let selected = select_games(&config, scanned, TRANSLATOR.cli_select_games_to_restore())?;
+                games.retain(|game| selected.contains_key(game));
+            }
+
+            let roots = if require_installed || group_by == Some(CliGroupBy::Store) || preview || clean || steam_deck {
+                roots_for_invocation(&config, &root, roots_replace)?
+            } else {
+                vec![]
+            };
+
+            if require_installed || group_by == Some(CliGroupBy::Store) {
+                let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
+                if require_installed {
+                    let uninstalled = launchers.exclude_uninstalled(&mut games);
+                    for game in &uninstalled {
+                        log::warn!("[{game}] skipped because it does not appear to be installed");
+                    }
+                    reporter.set_uninstalled_games(uninstalled);
+                }
+                if group_by == Some(CliGroupBy::Store) {
+                    for game in &games {
+                        reporter.add_game_store(game, launchers.store_group(game, config.is_game_customized(game)));
+                    }
+                }
+            }
+
+            // In preview mode, we also scan the current saves to show what the restore would affect.
+            // With `--clean`, this also tells us which files to delete afterward.
+            let live_context = (preview || clean).then(|| {
+                let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
+                (launchers, SteamShortcuts::scan())
+            });
+
+            // Each game may have its own Proton prefix to restore into.
+            let steam_prefixes: HashMap<_, _> = if steam_deck {
+                games
+                    .iter()
+                    .filter_map(|name| {
+                        let game = manifest.0.get(name)?;
+                        let prefix = crate::scan::find_steam_prefix(&roots, &crate::scan::steam_ids(game, None))?;
+                        Some((name.clone(), prefix))
+                    })
+                    .collect()
+            } else {
+                HashMap::new()
+            };
+
             log::info!("beginning restore with {} steps", games.len());
 
+            let events = ProgressEvents::new(progress_format.unwrap_or_default(), games.len() as u64);
+            let aborted = AtomicBool::new(false);
+            let unprocessed_games = AtomicUsize::new(0);
             let mut info: Vec<_> = games
                 .par_iter()
                 .enumerate()
-                .progress_with(scan_progress_bar(games.len() as u64))
+                .progress_with(operation_progress_bar(games.len() as u64, events.as_ref()))
                 .filter_map(|(i, name)| {
-                    log::trace!("step {i} / {}: {name}", games.len());
+                    log::trace!(game = name.as_str(), step = i; "step {i} / {}: {name}", games.len());
+                    let mut step = ProgressStep::new(events.as_ref(), i, name);
+
+                    if aborted.load(Ordering::Relaxed) {
+                        log::trace!("[{name}] skipped because another game failed");
+                        unprocessed_games.fetch_add(1, Ordering::Relaxed);
+                        return None;
+                    }
+                    let options = game_options.get(name);
+                    let backup = options.and_then(|x| x.backup.as_ref()).or(backup.as_ref());
+                    let backup_id = backup.map(|x| BackupId::Named(x.clone()));
                     let mut layout = layout.game_layout(name);
-                    let scan_info = layout.scan_for_restoration(
+                    let mut scan_info = layout.scan_for_restoration(
                         name,
                         backup_id.as_ref().unwrap_or(&BackupId::Latest),
                         &config.redirects,
                         &config.restore.toggled_paths,
                         &config.restore.toggled_registry,
                     );
-                    let ignored = !&config.is_game_enabled_for_restore(name) && !games_specified;
+                    let wine_prefix = if let Some(wine_prefix) = options.and_then(|x| x.wine_prefix.as_ref()) {
+                        Some(wine_prefix)
+                    } else if force_different_os {
+                        wine_prefix.as_ref()
+                    } else {
+                        steam_prefixes.get(name)
+                    };
+                    if let Some(wine_prefix) = wine_prefix {
+                        // The first scan tells us which Windows paths need to be moved into the prefix.
+                        let mut redirects = config.redirects.clone();
+                        redirects.extend(crate::scan::wine_prefix_redirects(
+                            wine_prefix,
+                            scan_info.found_files.iter().map(|x| x.effective().clone()),
+                        ));
+                        scan_info = layout.scan_for_restoration(
+                            name,
+                            backup_id.as_ref().unwrap_or(&BackupId::Latest),
+                            &redirects,
+                            &config.restore.toggled_paths,
+                            &config.restore.toggled_registry,
+                        );
+                    }
+                    if merge_registry {
+                        scan_info.find_preserved_registry_values();
+                    }
+                    let ignored = !&config.is_game_enabled_for_restore(name) && !games_specified && !include_disabled;
                     let decision = if ignored {
                         OperationStepDecision::Ignored
                     } else {
                         OperationStepDecision::Processed
                     };
 
-                    if let Some(backup) = &backup {
+                    if let Some(backup) = backup {
                         if let Some(BackupId::Named(scanned_backup)) = scan_info.backup.as_ref().map(|x| x.id()) {
                             if backup != &scanned_backup {
-                                log::trace!("step {i} completed (backup mismatch)");
+                                log::trace!(game = name.as_str(), step = i; "step {i} completed (backup mismatch)");
                                 let display_title = config.display_name(name);
                                 return Some((
                                     display_title,
                                     scan_info,
                                     Default::default(),
                                     decision,
+                                    false,
+                                    None,
                                     Some(Err(Error::CliInvalidBackupId)),
                                 ));
                             }
                         }
                     }
 
-                    let restore_info = if scan_info.backup.is_none() || preview || ignored {
-                        crate::scan::BackupInfo::default()
-                    } else {
-                        layout.restore(&scan_info, &config.restore.toggled_registry)
-                    };
-                    log::trace!("step {i} completed");
+                    let mut impact = live_context.as_ref().filter(|_| scan_info.backup.is_some()).map(
+                        |(launchers, steam_shortcuts)| {
+                            let live = manifest.0.get(name).map(|game| {
+                                scan_game_for_backup(
+                                    game,
+                                    name,
+                                    &roots,
+                                    &app_dir(),
+                                    launchers,
+                                    &None,
+                                    None,
+                                    &config.redirects,
+                                    steam_shortcuts,
+                                    &BackupOptions {
+                                        preserve_empty_dirs: false,
+                                        max_file_size: None,
+                                        ..BackupOptions::new(&config)
+                                    },
+                                    None,
+                                )
+                            });
+                            RestoreImpact::evaluate(&scan_info, live.as_ref())
+                        },
+                    );
+
+                    let (restore_info, retried) =
+                        if scan_info.backup.is_none() || preview || ignored || to_archive.is_some() {
+                            (crate::scan::BackupInfo::default(), false)
+                        } else {
+                            let attempt = |scan_info: &ScanInfo| {
+                                layout.restore(scan_info, &config.restore.toggled_registry, preserve_permissions)
+                            };
+                            retry_failures(name, retries, attempt(&scan_info), |failed| {
+                                attempt(&scan_info.only_failures(failed))
+                            })
+                        };
+                    step.record(scan_info.sum_bytes(Some(&restore_info)), !restore_info.successful());
+                    if abort_on_first_failure && !restore_info.successful() {
+                        aborted.store(true, Ordering::Relaxed);
+                    }
+                    if clean && !preview && !ignored && restore_info.successful() {
+                        if let Some(impact) = impact.as_mut() {
+                            impact.clean(name);
+                        }
+                    }
+                    log::trace!(game = name.as_str(), step = i; "step {i} completed");
                     if !scan_info.can_report_game() {
                         None
                     } else {
                         let display_title = config.display_name(name);
-                        Some((display_title, scan_info, restore_info, decision, None))
+                        Some((display_title, scan_info, restore_info, decision, retried, impact, None))
                     }
                 })
                 .collect();
             log::info!("completed restore");
+            if let Some(events) = &events {
+                events.finish();
+            }
 
-            for (_, scan_info, _, _, failure) in info.iter() {
+            if aborted.into_inner() {
+                eprintln!(
+                    "{}",
+                    TRANSLATOR.cli_aborted_after_failure(unprocessed_games.into_inner())
+                );
+            }
+
+            for (_, scan_info, _, _, _, _, failure) in info.iter() {
                 if let Some(failure) = failure {
                     return failure.clone();
                 }
@@ -494,6 +1190,22 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 );
             }
 
+            if let Some(to_archive) = &to_archive {
+                let scans: Vec<_> = info
+                    .into_iter()
+                    .filter(|(_, _, _, decision, ..)| *decision == OperationStepDecision::Processed)
+                    .map(|(_, scan_info, ..)| scan_info)
+                    .collect();
+                let items = bundle::plan_restore_to_archive(&layout, &scans);
+                if !preview {
+                    bundle::restore_to_archive(&items, to_archive)?;
+                }
+
+                let games: Vec<_> = items.into_iter().map(|x| x.game).collect();
+                print_bundle(&config, &games, &[], to_archive);
+                return Ok(());
+            }
+
             let sort = sort.map(From::from).unwrap_or_else(|| config.restore.sort.clone());
             info.sort_by(
                 |(name1, scan_info1, backup_info1, ..), (name2, scan_info2, backup_info2, ..)| {
@@ -512,12 +1224,36 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 info.reverse();
             }
 
-            for (name, scan_info, backup_info, decision, _) in info {
+            for (name, scan_info, backup_info, decision, retried, impact, _) in info {
+                if retried {
+                    reporter.add_retried_game(name);
+                }
+                if include_disabled && !config.is_game_enabled_for_restore(&scan_info.game_name) {
+                    reporter.add_disabled_game(name);
+                }
+                if !game_options.is_empty() {
+                    if let Some(backup) = &scan_info.backup {
+                        reporter.add_restore_backup(&scan_info.game_name, backup.name());
+                    }
+                }
+                if let Some(prefix) = steam_prefixes.get(&scan_info.game_name) {
+                    reporter.add_restore_prefix(&scan_info.game_name, prefix);
+                }
+                if let Some(note) = config.game_note(&scan_info.game_name) {
+                    reporter.add_game_note(&scan_info.game_name, note);
+                }
+                if let Some(impact) = impact {
+                    reporter.add_restore_impact(&scan_info.game_name, impact);
+                }
                 if !reporter.add_game(name, &scan_info, &backup_info, &decision, &duplicate_detector) {
                     failed = true;
                 }
             }
+            reporter.set_duration(started.elapsed());
             reporter.print(&restore_dir);
+            if let Some(report_to) = &report_to {
+                reporter.save(&restore_dir, report_to)?;
+            }
         }
         Subcommand::Complete { shell } => {
             let clap_shell = match shell {
@@ -534,18 +1270,48 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 &mut std::io::stdout(),
             )
         }
-        Subcommand::Backups { path, api, games } => {
+        Subcommand::Backups {
+            path,
+            api,
+            summary_only,
+            report_to,
+            pin,
+            unpin,
+            since_backup,
+            rename,
+            preview,
+            games,
+        } => {
+            let started = Instant::now();
             let games = parse_games(games);
 
-            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            let mut reporter = if api {
+                Reporter::json()
+                    .with_json_indent(json_indent)
+                    .with_summary_only(summary_only)
+            } else {
+                Reporter::standard().with_summary_only(summary_only)
+            };
             reporter.suppress_overall();
 
             let restore_dir = match path {
                 None => config.restore.path.clone(),
                 Some(p) => p,
             };
+            let restore_dir = expand_env_var(&restore_dir)?;
 
             let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
+
+            if let Some((old_name, new_name)) = rename {
+                let plan = rename::plan_rename(&layout, &old_name, &new_name)?;
+                println!("{}", TRANSLATOR.cli_rename_plan(&plan));
+                // This also reports any conflicts, even when previewing.
+                if !preview || !plan.conflicts.is_empty() {
+                    rename::rename(&plan)?;
+                }
+                return Ok(());
+            }
+
             let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
             let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
 
@@ -558,6 +1324,86 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             };
 
+            let pin_change = match (pin, unpin) {
+                (Some(id), _) => Some((id, true)),
+                (_, Some(id)) => Some((id, false)),
+                _ => None,
+            };
+            if let Some((id, locked)) = pin_change {
+                if games.len() != 1 {
+                    return Err(Error::CliBackupIdWithMultipleGames);
+                }
+                let mut game_layout = layout.game_layout(&games[0]);
+                if game_layout.find_by_id(&BackupId::Named(id.clone())).is_none() {
+                    return Err(Error::CliInvalidBackupId);
+                }
+                game_layout.set_backup_locked(&id, locked);
+                game_layout.save();
+            }
+
+            if let Some(id) = since_backup {
+                if games.len() != 1 {
+                    return Err(Error::CliBackupIdWithMultipleGames);
+                }
+                let name = &games[0];
+                let id = BackupId::Named(id);
+
+                // The current files must be hashed the same way as the files in the backup.
+                let Some(hash_algorithm) = layout
+                    .game_layout(name)
+                    .find_by_id(&id)
+                    .map(|(full, _)| full.hash_algorithm)
+                else {
+                    return Err(Error::CliInvalidBackupId);
+                };
+                let Some(game) = manifest.0.get(name) else {
+                    return Err(Error::CliUnrecognizedGames {
+                        games: vec![name.clone()],
+                    });
+                };
+
+                let roots = config.expanded_roots();
+                let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
+                let previous = layout.backup(name, &id, false, &config.redirects, &config.restore.toggled_paths);
+
+                let mut scan_info = scan_game_for_backup(
+                    game,
+                    name,
+                    &roots,
+                    &app_dir(),
+                    &launchers,
+                    &None,
+                    previous,
+                    &config.redirects,
+                    &SteamShortcuts::scan(),
+                    &BackupOptions {
+                        hash_algorithm,
+                        preserve_empty_dirs: false,
+                        max_file_size: None,
+                        ..BackupOptions::new(&config)
+                    },
+                    None,
+                );
+                scan_info.found_files.retain(|x| x.change != ScanChange::Same);
+                scan_info.found_registry_keys.retain(|x| {
+                    x.change != ScanChange::Same || x.values.values().any(|v| v.change != ScanChange::Same)
+                });
+
+                reporter.add_game(
+                    name,
+                    &scan_info,
+                    &BackupInfo::default(),
+                    &OperationStepDecision::Processed,
+                    &DuplicateDetector::default(),
+                );
+                reporter.set_duration(started.elapsed());
+                reporter.print(&restore_dir);
+                if let Some(report_to) = &report_to {
+                    reporter.save(&restore_dir, report_to)?;
+                }
+                return Ok(());
+            }
+
             let info: Vec<_> = games
                 .par_iter()
                 .progress_count(games.len() as u64)
@@ -571,26 +1417,102 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 .collect();
 
             for (name, display_title, backup_dir, backups) in info {
+                if let Some(note) = config.game_note(name) {
+                    reporter.add_game_note(name, note);
+                }
                 reporter.add_backups(name, display_title, backup_dir, &backups);
             }
+            reporter.set_duration(started.elapsed());
             reporter.print(&restore_dir);
+            if let Some(report_to) = &report_to {
+                reporter.save(&restore_dir, report_to)?;
+            }
+        }
+        Subcommand::Export {
+            path,
+            to,
+            preview,
+            games,
+        } => {
+            let games = parse_games(games);
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+            let restore_dir = expand_env_var(&restore_dir)?;
+
+            let layout = BackupLayout::new(restore_dir, config.backup.retention.clone());
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => return Err(Error::CliUnrecognizedGames { games }),
+            };
+
+            let items = bundle::plan_export(&layout, &games);
+            if !preview {
+                bundle::export(&items, &to)?;
+            }
+
+            let games: Vec<_> = items.into_iter().map(|x| x.game).collect();
+            print_bundle(&config, &games, &[], &to);
+        }
+        Subcommand::Import { from, path, preview } => {
+            let backup_dir = match path {
+                None => config.backup.path.clone(),
+                Some(p) => p,
+            };
+            let backup_dir = expand_env_var(&backup_dir)?;
+
+            let layout = BackupLayout::new(backup_dir.clone(), config.backup.retention.clone());
+
+            let (skipped, games): (Vec<_>, Vec<_>) = bundle::plan_import(&from)?
+                .into_iter()
+                .partition(|game| layout.try_game_layout(&game.name).is_some_and(|x| x.has_backups()));
+            for game in &skipped {
+                log::warn!("[{}] skipping import because it already has backups", game.name);
+            }
+
+            if !preview {
+                if !games.is_empty() {
+                    prepare_backup_target(&backup_dir)?;
+                }
+                bundle::import(&from, &games, &layout)?;
+            }
+
+            print_bundle(&config, &games, &skipped, &backup_dir);
         }
         Subcommand::Find {
             api,
+            json_lines,
             path,
             backup,
             restore,
             steam_id,
             gog_id,
             lutris_id,
+            by_path,
+            root,
+            roots_replace,
             normalized,
             disabled,
             partial,
+            manifest_language,
+            show_manifest_paths,
             names,
         } => {
             let names = parse_games(names);
 
-            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            // With `--json-lines`, a failure is reported as one more line of JSON.
+            let mut reporter = if api {
+                Reporter::json().with_json_indent(json_indent)
+            } else if json_lines {
+                Reporter::json().with_json_indent(Some(0))
+            } else {
+                Reporter::standard()
+            };
             reporter.suppress_overall();
 
             let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
@@ -599,6 +1521,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 None => config.restore.path.clone(),
                 Some(p) => p,
             };
+            let restore_dir = expand_env_var(&restore_dir)?;
             let layout = BackupLayout::new(restore_dir.clone(), config.backup.retention.clone());
 
             let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
@@ -613,9 +1536,69 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 disabled,
                 partial,
             });
-            reporter.add_found_titles(&found);
-
-            if found.is_empty() {
+            let localized: BTreeMap<_, _> = match &manifest_language {
+                Some(language) => found
+                    .iter()
+                    .filter_map(|name| {
+                        let title = manifest.0.get(name)?.localized_title(language)?;
+                        (title != name).then(|| (name.clone(), title.to_string()))
+                    })
+                    .collect(),
+                None => BTreeMap::new(),
+            };
+            let found = match &by_path {
+                Some(needle) => {
+                    let roots = roots_for_invocation(&config, &root, roots_replace)?;
+                    let found: Vec<_> = found.into_iter().collect();
+                    let launchers = Launchers::scan(&roots, &manifest, &found, &title_finder, None);
+                    let steam_shortcuts = SteamShortcuts::scan();
+
+                    let matched: BTreeMap<_, _> = found
+                        .par_iter()
+                        .filter_map(|name| {
+                            let paths = find_game_paths_containing(
+                                manifest.0.get(name)?,
+                                name,
+                                &roots,
+                                &app_dir(),
+                                &launchers,
+                                &steam_shortcuts,
+                                needle,
+                            );
+                            if paths.is_empty() {
+                                return None;
+                            }
+                            if json_lines {
+                                report_found_line(
+                                    name,
+                                    localized.get(name),
+                                    &paths,
+                                    show_manifest_paths.then_some(&manifest),
+                                );
+                            }
+                            Some((name.clone(), paths))
+                        })
+                        .collect();
+                    reporter.add_found_paths(&matched, &localized);
+                    matched.into_keys().collect()
+                }
+                None => {
+                    if json_lines {
+                        for name in &found {
+                            report_found_line(
+                                name,
+                                localized.get(name),
+                                &BTreeSet::new(),
+                                show_manifest_paths.then_some(&manifest),
+                            );
+                        }
+                    }
+                    reporter.add_found_titles(&found, &localized);
+                    found
+                }
+            };
+
+            if found.is_empty() {
                 let mut invalid = names;
                 if let Some(steam_id) = steam_id {
                     invalid.push(steam_id.to_string());
@@ -623,25 +1606,238 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 if let Some(gog_id) = gog_id {
                     invalid.push(gog_id.to_string());
                 }
+                if let Some(by_path) = by_path {
+                    invalid.push(by_path);
+                }
                 reporter.trip_unknown_games(invalid.clone());
                 reporter.print_failure();
                 return Err(Error::CliUnrecognizedGames { games: invalid });
             }
 
-            reporter.print(&restore_dir);
+            if show_manifest_paths {
+                reporter.add_found_manifest_paths(&manifest);
+            }
+
+            // With `--json-lines`, each match has already been printed.
+            if !json_lines {
+                reporter.print(&restore_dir);
+            }
         }
         Subcommand::Manifest { sub: manifest_sub } => match manifest_sub {
-            ManifestSubcommand::Show { api } => {
+            ManifestSubcommand::Show {
+                api,
+                normalize_names,
+                manifest_language,
+            } => {
                 let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
 
-                if api {
-                    println!("{}", serde_json::to_string(&manifest).unwrap());
+                if normalize_names {
+                    show_manifest(&manifest.with_normalized_names(), api, json_indent);
+                } else if let Some(language) = manifest_language {
+                    show_manifest(&manifest.with_localized_titles(&language), api, json_indent);
                 } else {
-                    println!("{}", serde_yaml::to_string(&manifest).unwrap());
+                    show_manifest(&manifest, api, json_indent);
                 }
             }
+            ManifestSubcommand::List {
+                with_steam_id,
+                custom_only,
+                api,
+            } => {
+                let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+
+                let games: BTreeMap<_, _> = manifest
+                    .primary_titles()
+                    .into_iter()
+                    .filter_map(|name| {
+                        let game = &manifest.0[&name];
+                        let listed = ListedGame {
+                            steam_id: game.steam.id,
+                            gog_id: game.gog.id,
+                            custom: config.find_custom_game(&name).is_some_and(|x| !x.ignore),
+                        };
+                        if (with_steam_id && listed.steam_id.is_none()) || (custom_only && !listed.custom) {
+                            return None;
+                        }
+                        Some((name, listed))
+                    })
+                    .collect();
+
+                report::report_listed_games(games, api, json_indent);
+            }
             ManifestSubcommand::Update { force } => {
                 Manifest::update_mut(&config, &mut cache, force)?;
+
+                let manifest = Manifest::load().unwrap_or_default().with_extensions(&config);
+                for (id, total) in manifest.secondary_contributions() {
+                    println!("{}", TRANSLATOR.cli_secondary_manifest_games(&id, total));
+                }
+            }
+            ManifestSubcommand::Diff { from, to, api } => {
+                let older = Manifest::load_file(&from)?;
+                let newer = match to {
+                    Some(to) => Manifest::load_file(&to)?,
+                    None => match &config.manifest.cli_path {
+                        Some(path) => Manifest::load_file(path)?,
+                        None => {
+                            if !Manifest::path().exists() {
+                                return Err(Error::ManifestNotFound);
+                            }
+                            Manifest::load()?
+                        }
+                    },
+                };
+
+                report::report_manifest_diff(older.diff(&newer), api, json_indent);
+            }
+            ManifestSubcommand::Test { name, file, api } => {
+                if let Some(file) = file {
+                    config.cli_custom_game_files.push(file);
+                }
+                let Some(custom_game) = config.find_custom_game(&name) else {
+                    return Err(Error::CliUnrecognizedGames { games: vec![name] });
+                };
+
+                let mut reporter = if api {
+                    Reporter::json().with_json_indent(json_indent)
+                } else {
+                    Reporter::standard()
+                };
+
+                let manifest = Manifest::from_custom_game(custom_game);
+                let roots = config.expanded_roots();
+                let title_finder = TitleFinder::new(&config, &manifest, Default::default());
+                let launchers = Launchers::scan(&roots, &manifest, std::slice::from_ref(&name), &title_finder, None);
+                let layout = BackupLayout::new(config.backup_path(), config.backup.retention.clone());
+                let steam_shortcuts = SteamShortcuts::scan();
+                let options = BackupOptions::new(&config);
+
+                // The game was named explicitly, so the backup filter never excludes it.
+                let scan_info = BackupPipeline::new(
+                    &config,
+                    &manifest,
+                    &layout,
+                    &roots,
+                    &launchers,
+                    &steam_shortcuts,
+                    &options,
+                )
+                .scan_game(&name, true, &None)
+                .unwrap_or_default();
+                reporter.set_max_file_size(config.backup.max_file_size);
+
+                reporter.add_game(
+                    &name,
+                    &scan_info,
+                    &BackupInfo::default(),
+                    &OperationStepDecision::Processed,
+                    &duplicate_detector,
+                );
+                reporter.print(&config.backup.path);
+            }
+        },
+        Subcommand::Config { sub: config_sub } => match config_sub {
+            parse::ConfigSubcommand::Get { key } => match config.get_key(&key)? {
+                serde_yaml::Value::String(value) => println!("{value}"),
+                value => println!(
+                    "{}",
+                    serde_yaml::to_string(&value)
+                        .unwrap()
+                        .trim_start_matches("---\n")
+                        .trim_end()
+                ),
+            },
+            parse::ConfigSubcommand::Set { key, value } => {
+                config.set_key(&key, &value)?;
+                config.save();
+            }
+        },
+        Subcommand::Settings { sub: settings_sub } => match settings_sub {
+            parse::SettingsSubcommand::Export { to, include_secrets } => {
+                let mut settings = Settings::new(&config, &cache);
+
+                if include_secrets {
+                    if let Some(remote) = config.cloud.remote.clone() {
+                        settings.rclone_remote = Rclone::new(config.apps.rclone.clone(), remote)
+                            .export_remote()
+                            .map_err(Error::UnableToConfigureCloud)?;
+                    }
+                }
+
+                settings.save_to(&to)?;
+            }
+            parse::SettingsSubcommand::Import { from, force } => {
+                let settings = Settings::load_from(&from)?;
+
+                if !ask(TRANSLATOR.confirm_settings_import(&from), Finality::Final, force)? {
+                    return Ok(());
+                }
+
+                let rclone_remote = settings.rclone_remote.clone();
+                let config = settings.apply(&mut cache);
+
+                if let (Some(rclone_remote), Some(remote)) = (rclone_remote, config.cloud.remote.clone()) {
+                    Rclone::new(config.apps.rclone.clone(), remote)
+                        .import_remote(&rclone_remote)
+                        .map_err(Error::UnableToConfigureCloud)?;
+                }
+
+                config.save();
+                cache.save_maybe_compressed(config.runtime.compress_cache);
+            }
+        },
+        Subcommand::Roots { sub: roots_sub } => match roots_sub {
+            parse::RootsSubcommand::List { api } => {
+                let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
+
+                let roots = config.all_roots();
+                let scan_roots: Vec<_> = roots.iter().map(|(root, _)| root.clone()).collect();
+
+                let title_finder = TitleFinder::new(&config, &manifest, Default::default());
+                let games: Vec<_> = manifest.primary_titles().into_iter().collect();
+                let launchers = Launchers::scan(&scan_roots, &manifest, &games, &title_finder, None);
+                let steam_shortcuts = SteamShortcuts::scan();
+                let options = BackupOptions::new(&config);
+
+                let matches: Vec<Vec<usize>> = games
+                    .par_iter()
+                    .progress_with(scan_progress_bar(games.len() as u64))
+                    .map(|name| {
+                        let scan_info = scan_game_for_backup(
+                            &manifest.0[name],
+                            name,
+                            &scan_roots,
+                            &app_dir(),
+                            &launchers,
+                            &None,
+                            None,
+                            &config.redirects,
+                            &steam_shortcuts,
+                            &options,
+                            None,
+                        );
+                        scan_roots
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, root)| scan_info.found_files.iter().any(|x| root.path().is_prefix_of(&x.path)))
+                            .map(|(i, _)| i)
+                            .collect()
+                    })
+                    .collect();
+
+                let roots = roots
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (root, source))| report::ApiRoot {
+                        path: root.path().render(),
+                        store: root.store(),
+                        source,
+                        exists: root.path().is_dir(),
+                        games: matches.iter().filter(|x| x.contains(&i)).count(),
+                    })
+                    .collect();
+
+                report::report_roots(roots, api, json_indent);
             }
         },
         Subcommand::Cloud { sub: cloud_sub } => match cloud_sub {
@@ -736,22 +1932,59 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         },
                     )?;
                 }
+                parse::CloudSetSubcommand::S3 {
+                    endpoint,
+                    bucket,
+                    access_key,
+                    secret_key,
+                    region,
+                } => {
+                    configure_cloud(
+                        &mut config,
+                        Remote::S3 {
+                            id: Remote::generate_id(),
+                            endpoint,
+                            bucket,
+                            access_key,
+                            secret_key,
+                            region,
+                        },
+                    )?;
+                }
+                parse::CloudSetSubcommand::Http { url } => {
+                    configure_cloud(
+                        &mut config,
+                        Remote::Http {
+                            id: Remote::generate_id(),
+                            url,
+                        },
+                    )?;
+                }
             },
             parse::CloudSubcommand::Upload {
                 local,
                 cloud,
+                remote,
                 force,
                 preview,
+                print_command,
+                verify_upload,
                 api,
                 games,
             } => {
                 let games = parse_games(games);
 
                 let local = local.unwrap_or(config.backup.path.clone());
+                let local = expand_env_var(&local)?;
                 let cloud = cloud.unwrap_or(config.cloud.path.clone());
+                if let Some(remote) = remote {
+                    // This only applies to the current operation, so the config isn't saved.
+                    config.cloud.remote = Some(Remote::Custom { id: remote });
+                }
 
                 let finality = if preview { Finality::Preview } else { Finality::Final };
                 let direction = SyncDirection::Upload;
+                let verify_upload = (verify_upload || config.cloud.verify_upload) && !preview;
 
                 let layout = BackupLayout::new(config.restore.path.clone(), config.backup.retention.clone());
                 let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
@@ -760,13 +1993,22 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
                     Ok(games) => games,
                     Err(games) => {
-                        let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+                        let mut reporter = if api {
+                            Reporter::json().with_json_indent(json_indent)
+                        } else {
+                            Reporter::standard()
+                        };
                         reporter.trip_unknown_games(games.clone());
                         reporter.print_failure();
                         return Err(Error::CliUnrecognizedGames { games });
                     }
                 };
 
+                if print_command {
+                    print_cloud_sync_commands(&config, &local, &cloud, direction, finality, &games)?;
+                    return Ok(());
+                }
+
                 if !ask(
                     TRANSLATOR.confirm_cloud_upload(&local.render(), &cloud),
                     finality,
@@ -775,21 +2017,35 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     return Ok(());
                 }
 
-                let changes = sync_cloud(&config, &local, &cloud, direction, finality, &games)?;
-                report_cloud_changes(&changes, api);
+                let outcome = sync_cloud(&config, &local, &cloud, direction, finality, &games);
+                report_cloud_changes(&outcome.changes, outcome.stats.as_ref(), api, json_indent);
+
+                if verify_upload {
+                    verify_cloud_upload(&config, &local, &cloud, &outcome.changes)?;
+                }
+                if let Some(e) = outcome.error {
+                    return Err(e);
+                }
             }
             parse::CloudSubcommand::Download {
                 local,
                 cloud,
+                remote,
                 force,
                 preview,
+                print_command,
                 api,
                 games,
             } => {
                 let games = parse_games(games);
 
                 let local = local.unwrap_or(config.backup.path.clone());
+                let local = expand_env_var(&local)?;
                 let cloud = cloud.unwrap_or(config.cloud.path.clone());
+                if let Some(remote) = remote {
+                    // This only applies to the current operation, so the config isn't saved.
+                    config.cloud.remote = Some(Remote::Custom { id: remote });
+                }
 
                 let finality = if preview { Finality::Preview } else { Finality::Final };
                 let direction = SyncDirection::Download;
@@ -801,13 +2057,22 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
                     Ok(games) => games,
                     Err(games) => {
-                        let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+                        let mut reporter = if api {
+                            Reporter::json().with_json_indent(json_indent)
+                        } else {
+                            Reporter::standard()
+                        };
                         reporter.trip_unknown_games(games.clone());
                         reporter.print_failure();
                         return Err(Error::CliUnrecognizedGames { games });
                     }
                 };
 
+                if print_command {
+                    print_cloud_sync_commands(&config, &local, &cloud, direction, finality, &games)?;
+                    return Ok(());
+                }
+
                 if !ask(
                     TRANSLATOR.confirm_cloud_download(&local.render(), &cloud),
                     finality,
@@ -816,8 +2081,81 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     return Ok(());
                 }
 
-                let changes = sync_cloud(&config, &local, &cloud, direction, finality, &games)?;
-                report_cloud_changes(&changes, api);
+                let outcome = sync_cloud(&config, &local, &cloud, direction, finality, &games);
+                report_cloud_changes(&outcome.changes, outcome.stats.as_ref(), api, json_indent);
+                if let Some(e) = outcome.error {
+                    return Err(e);
+                }
+            }
+            parse::CloudSubcommand::Purge {
+                local,
+                cloud,
+                remote,
+                force,
+                preview,
+                all,
+                api,
+                games,
+            } => {
+                let games = parse_games(games);
+                if games.is_empty() && !all {
+                    return Err(Error::CliCloudPurgeWithoutTarget);
+                }
+
+                let local = local.unwrap_or(config.backup.path.clone());
+                let local = expand_env_var(&local)?;
+                let cloud = cloud.unwrap_or(config.cloud.path.clone());
+                if let Some(remote) = remote {
+                    // This only applies to the current operation, so the config isn't saved.
+                    config.cloud.remote = Some(Remote::Custom { id: remote });
+                }
+
+                let finality = if preview { Finality::Preview } else { Finality::Final };
+
+                let remote = crate::cloud::validate_cloud_config(&config, &cloud, SyncDirection::Upload)?;
+                let rclone = Rclone::new(config.apps.rclone.clone(), remote);
+
+                let targets = if all {
+                    vec![cloud.clone()]
+                } else {
+                    let layout = BackupLayout::new(local.clone(), config.backup.retention.clone());
+                    let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+                    let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+                    games
+                        .iter()
+                        .filter_map(|name| {
+                            let name = title_finder.find_one_by_name(name).unwrap_or(name.clone());
+                            let leaf = layout.game_folder(&name).leaf()?;
+                            Some(format!("{}/{}", cloud.trim_end_matches('/'), leaf))
+                        })
+                        .collect()
+                };
+
+                let mut found = BTreeMap::new();
+                for target in targets {
+                    if let Some(size) = rclone.size(&target).map_err(Error::UnableToSynchronizeCloud)? {
+                        found.insert(target, size);
+                    }
+                }
+
+                if !found.is_empty() {
+                    let folders: Vec<_> = found.keys().cloned().collect();
+                    if !ask(TRANSLATOR.confirm_cloud_purge(&folders), finality, force)? {
+                        return Ok(());
+                    }
+
+                    if finality.preview() {
+                        log::info!("skipping cloud purge in preview mode");
+                    } else {
+                        for folder in &folders {
+                            log::info!("purging cloud folder: {folder}");
+                            rclone.purge(folder).map_err(Error::UnableToSynchronizeCloud)?;
+                        }
+                    }
+                }
+
+                report_cloud_purge(&found, api, json_indent);
             }
         },
         Subcommand::Wrap {
@@ -913,16 +2251,44 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     Subcommand::Restore {
                         games: vec![game_name.clone()],
                         force: true,
+                        include_disabled: Default::default(),
+                        require_installed: Default::default(),
                         preview: Default::default(),
                         path: Default::default(),
                         api: Default::default(),
+                        summary_only: Default::default(),
+                        report_to: Default::default(),
+                        preserve_permissions: Default::default(),
+                        clean: Default::default(),
+                        merge_registry: Default::default(),
+                        retries: Default::default(),
+                        abort_on_first_failure: Default::default(),
+                        manifest_age_warning: None,
+                        force_different_os: false,
+                        wine_prefix: None,
+                        steam_deck: Default::default(),
+                        root: Default::default(),
+                        roots_replace: Default::default(),
                         sort: Default::default(),
+                        group_by: Default::default(),
+                        progress_format: Default::default(),
                         backup: Default::default(),
+                        git_commit: None,
+                        games_file: Default::default(),
+                        interactive: Default::default(),
+                        to_archive: Default::default(),
                         cloud_sync: Default::default(),
                         no_cloud_sync: Default::default(),
                     },
                     no_manifest_update,
                     try_manifest_update,
+                    json_indent,
+                    custom_games.clone(),
+                    manifest_enforce_checksum,
+                    manifest_extra.clone(),
+                    manifest_path.clone(),
+                    threads.map(NonZeroUsize::get),
+                    no_color,
                 ) {
                     log::error!("WRAP::restore: failed for game {:?} with: {:?}", wrap_game_info, err);
                     ui::alert_with_error(gui, force, &TRANSLATOR.restore_one_game_failed(game_name), &err)?;
@@ -962,21 +2328,61 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     Subcommand::Backup {
                         games: vec![game_name.clone()],
                         force: true,
+                        include_disabled: Default::default(),
                         preview: Default::default(),
                         path: Default::default(),
                         wine_prefix: Default::default(),
+                        root: Default::default(),
+                        roots_replace: Default::default(),
+                        exclude_store: Default::default(),
+                        skip_empty: Default::default(),
+                        only_new_games: Default::default(),
+                        only_registry: Default::default(),
+                        include_other_os_paths: Default::default(),
+                        interactive: Default::default(),
                         api: Default::default(),
+                        summary_only: Default::default(),
+                        report_to: Default::default(),
+                        preserve_permissions: Default::default(),
+                        preserve_empty_dirs: Default::default(),
+                        include_manifest_snapshot: Default::default(),
+                        include_restore_map: Default::default(),
+                        follow_symlinks: Default::default(),
+                        no_follow_symlinks: Default::default(),
+                        max_file_size: Default::default(),
+                        global_ignore: Default::default(),
+                        no_exclude_system_files: Default::default(),
+                        dedupe_report: Default::default(),
+                        retries: Default::default(),
+                        abort_on_first_failure: Default::default(),
+                        max_backup_duration: None,
+                        resume: Default::default(),
+                        manifest_age_warning: None,
                         sort: Default::default(),
+                        group_by: Default::default(),
+                        progress_format: Default::default(),
                         format: Default::default(),
                         compression: Default::default(),
                         compression_level: Default::default(),
+                        hash_algorithm: Default::default(),
+                        backup_id_format: Default::default(),
+                        game_format: Default::default(),
                         full_limit: Default::default(),
                         differential_limit: Default::default(),
+                        game_retention: Default::default(),
                         cloud_sync: Default::default(),
                         no_cloud_sync: Default::default(),
+                        force_cloud_sync: Default::default(),
                     },
                     no_manifest_update,
                     try_manifest_update,
+                    json_indent,
+                    custom_games.clone(),
+                    manifest_enforce_checksum,
+                    manifest_extra.clone(),
+                    manifest_path.clone(),
+                    threads.map(NonZeroUsize::get),
+                    no_color,
                 ) {
                     log::error!("WRAP::backup: failed with: {:#?}", err);
                     ui::alert_with_error(gui, force, &TRANSLATOR.back_up_one_game_failed(game_name), &err)?;
@@ -984,6 +2390,67 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
         }
+        Subcommand::Watch {
+            path,
+            debounce,
+            wine_prefix,
+            root,
+            roots_replace,
+            retries,
+            cloud_sync,
+            no_cloud_sync,
+            games,
+        } => {
+            let games = parse_games(games);
+            let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
+
+            let backup_dir = match path {
+                None => config.backup.path.clone(),
+                Some(p) => p,
+            };
+            let backup_dir = expand_env_var(&backup_dir)?;
+            prepare_backup_target(&backup_dir)?;
+            let roots = roots_for_invocation(&config, &root, roots_replace)?;
+
+            let layout = BackupLayout::new(backup_dir.clone(), config.backup.retention.clone());
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let games_specified = !games.is_empty();
+            let mut games = match evaluate_games(manifest.primary_titles(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => return Err(Error::CliUnrecognizedGames { games }),
+            };
+            if !games_specified {
+                games.retain(|game| config.is_game_enabled_for_backup(game));
+            }
+
+            let mut options = BackupOptions::new(&config);
+            options.retries = retries;
+            if options.uses_git() {
+                git::validate()?;
+            }
+
+            let cloud_sync = negatable_flag(
+                cloud_sync,
+                no_cloud_sync,
+                config.cloud.synchronize
+                    && crate::cloud::validate_cloud_config(&config, &config.cloud.path, SyncDirection::Upload).is_ok(),
+            );
+
+            watch_for_changes(
+                &config,
+                &manifest,
+                &title_finder,
+                &backup_dir,
+                &roots,
+                &games,
+                games_specified,
+                &wine_prefix,
+                &options,
+                cloud_sync,
+                Duration::from_secs(debounce),
+            )?;
+        }
         Subcommand::Api { input } => {
             TRANSLATOR.set_language(Language::English);
 
@@ -996,7 +2463,8 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 
             match api::process(input, &config, &manifest) {
                 Ok(data) => {
-                    let output = serde_json::to_string_pretty(&data).unwrap();
+                    let output =
+                        crate::serialization::to_json_string(&data, json_indent.unwrap_or(report::DEFAULT_JSON_INDENT));
                     println!("{output}");
                 }
                 Err(e) => {
@@ -1028,6 +2496,8 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 }
 
 fn configure_cloud(config: &mut Config, remote: Remote) -> Result<(), Error> {
+    crate::cloud::validate_remote_fields(&remote)?;
+
     if let Some(old_remote) = config.cloud.remote.as_ref() {
         _ = Rclone::new(config.apps.rclone.clone(), old_remote.clone()).unconfigure_remote();
     }
@@ -1042,7 +2512,7 @@ fn configure_cloud(config: &mut Config, remote: Remote) -> Result<(), Error> {
 }
 
 fn ask(question: String, finality: Finality, force: bool) -> Result<bool, Error> {
-    if finality.preview() || force {
+    if finality.preview() || force || should_assume_yes() {
         Ok(true)
     } else {
         dialoguer::Confirm::new()
@@ -1052,10 +2522,49 @@ fn ask(question: String, finality: Finality, force: bool) -> Result<bool, Error>
     }
 }
 
+/// Let the user pick which of the scanned games to process.
+/// Every game starts out selected.
+fn select_games(
+    config: &Config,
+    mut scanned: Vec<ScanInfo>,
+    prompt: String,
+) -> Result<HashMap<String, ScanInfo>, Error> {
+    scanned.sort_by(|x, y| {
+        config
+            .display_name(&x.game_name)
+            .to_lowercase()
+            .cmp(&config.display_name(&y.game_name).to_lowercase())
+    });
+
+    let items: Vec<_> = scanned
+        .iter()
+        .map(|x| {
+            format!(
+                "{} [{}]",
+                config.display_name(&x.game_name),
+                TRANSLATOR.adjusted_size(x.total_possible_bytes())
+            )
+        })
+        .collect();
+
+    let chosen = dialoguer::MultiSelect::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .defaults(&vec![true; items.len()])
+        .interact()
+        .map_err(|_| Error::CliUnableToRequestConfirmation)?;
+
+    Ok(chosen
+        .into_iter()
+        .map(|i| (scanned[i].game_name.clone(), scanned[i].clone()))
+        .collect())
+}
+
 fn scan_progress_bar(length: u64) -> ProgressBar {
     let template = format!(
-        "{} ({{elapsed_precise}}) {{wide_bar}} {}: {{pos}} / {{len}}",
+        "{} ({{elapsed_precise}}, {} {{eta_precise}}) {{wide_bar}} {}: {{pos}} / {{len}} ({{per_sec}}) {{msg}}",
         TRANSLATOR.scan_label(),
+        TRANSLATOR.eta_label(),
         TRANSLATOR.total_games()
     );
     let style = indicatif::ProgressStyle::default_bar()
@@ -1066,6 +2575,24 @@ fn scan_progress_bar(length: u64) -> ProgressBar {
     bar
 }
 
+/// Progress events replace the bar, so that they don't get mixed together on stderr.
+fn operation_progress_bar(length: u64, events: Option<&ProgressEvents>) -> ProgressBar {
+    let bar = scan_progress_bar(length);
+    if events.is_some() {
+        bar.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+    }
+    bar
+}
+
+/// Add to the running byte count and show the overall throughput so far.
+fn report_byte_throughput(bar: &ProgressBar, processed: &AtomicU64, bytes: u64) {
+    let processed = processed.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    let elapsed = bar.elapsed().as_secs_f64();
+    if elapsed > 0.0 {
+        bar.set_message(TRANSLATOR.throughput((processed as f64 / elapsed) as u64));
+    }
+}
+
 fn cloud_progress_bar() -> ProgressBar {
     let template = format!(
         "{} ({{elapsed_precise}}) {{wide_bar}} {{msg}}",
@@ -1079,6 +2606,17 @@ fn cloud_progress_bar() -> ProgressBar {
     bar
 }
 
+fn show_manifest<T: serde::Serialize>(manifest: &T, api: bool, json_indent: Option<usize>) {
+    if api {
+        println!(
+            "{}",
+            crate::serialization::to_json_string(manifest, json_indent.unwrap_or(0))
+        );
+    } else {
+        println!("{}", serde_yaml::to_string(manifest).unwrap());
+    }
+}
+
 fn sync_cloud(
     config: &Config,
     local: &StrictPath,
@@ -1086,14 +2624,12 @@ fn sync_cloud(
     sync: SyncDirection,
     finality: Finality,
     games: &[String],
-) -> Result<Vec<CloudChange>, Error> {
+) -> CloudSyncOutcome {
     match finality {
         Finality::Preview => log::info!("checking cloud sync"),
         Finality::Final => log::info!("performing cloud sync"),
     }
 
-    let remote = crate::cloud::validate_cloud_config(config, cloud)?;
-
     let games = if !games.is_empty() {
         let layout = BackupLayout::new(local.clone(), config.backup.retention.clone());
         let games: Vec<_> = games.iter().filter_map(|x| layout.game_folder(x).leaf()).collect();
@@ -1102,48 +2638,515 @@ fn sync_cloud(
         vec![]
     };
 
+    sync_cloud_dirs(config, local, cloud, sync, finality, &games)
+}
+
+/// Print the Rclone commands that `sync_cloud` would run, without running them.
+fn print_cloud_sync_commands(
+    config: &Config,
+    local: &StrictPath,
+    cloud: &str,
+    sync: SyncDirection,
+    finality: Finality,
+    games: &[String],
+) -> Result<(), Error> {
+    let direction = match finality {
+        Finality::Preview => SyncDirection::Download,
+        Finality::Final => sync,
+    };
+    let remote = crate::cloud::validate_cloud_config(config, cloud, direction)?;
+
+    let rclone = Rclone::new(config.apps.rclone.clone(), remote).with_transfers(config.runtime.cloud_threads);
+    let ignore_rules = crate::cloud::load_ignore_rules(local)?;
+
+    let layout = BackupLayout::new(local.clone(), config.backup.retention.clone());
+    let game_dirs: Vec<_> = games.iter().filter_map(|x| layout.game_folder(x).leaf()).collect();
+    let groups = if game_dirs.is_empty() {
+        vec![vec![]]
+    } else {
+        crate::cloud::partition_game_dirs(&game_dirs, config.cloud.parallel_games.get())
+    };
+
+    for group in &groups {
+        println!(
+            "{}",
+            rclone.sync_command(local, cloud, sync, finality, group, &ignore_rules)
+        );
+    }
+
+    Ok(())
+}
+
+/// Download the files from an upload again and check that they match the local backups.
+fn verify_cloud_upload(config: &Config, local: &StrictPath, cloud: &str, changes: &[CloudChange]) -> Result<(), Error> {
+    let remote = crate::cloud::validate_cloud_config(config, cloud, SyncDirection::Upload)?;
     let rclone = Rclone::new(config.apps.rclone.clone(), remote);
-    let mut process = match rclone.sync(local, cloud, sync, finality, &games) {
-        Ok(p) => p,
-        Err(e) => return Err(Error::UnableToSynchronizeCloud(e)),
+
+    log::info!("verifying cloud upload");
+    let mismatched = rclone
+        .verify_upload(local, cloud, changes)
+        .map_err(Error::UnableToSynchronizeCloud)?;
+
+    if mismatched.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::CloudUploadMismatched { paths: mismatched })
+    }
+}
+
+/// Ask how to resolve differences between the local and cloud backups before a backup,
+/// either for all games at once or one game at a time.
+/// Returns whether every difference was resolved, along with the synchronized changes.
+fn resolve_cloud_conflicts(
+    config: &Config,
+    local: &StrictPath,
+    layout: &BackupLayout,
+    changes: &[CloudChange],
+    games: &[String],
+) -> Result<(bool, Vec<CloudChange>), Error> {
+    let cloud = &config.cloud.path;
+
+    eprintln!("{}", TRANSLATOR.cloud_synchronize_conflict());
+    report_cloud_changes(changes, None, false, None);
+
+    let choice = dialoguer::Select::new()
+        .with_prompt(TRANSLATOR.cli_cloud_conflict_prompt())
+        .items(&[
+            TRANSLATOR.cli_cloud_conflict_upload_all(),
+            TRANSLATOR.cli_cloud_conflict_download_all(),
+            TRANSLATOR.cli_cloud_conflict_choose_per_game(),
+            TRANSLATOR.cli_cloud_conflict_skip(),
+        ])
+        .default(0)
+        .interact()
+        .ok();
+
+    let direction = match choice {
+        Some(0) => SyncDirection::Upload,
+        Some(1) => SyncDirection::Download,
+        Some(2) => {
+            return resolve_cloud_conflicts_per_game(config, local, layout, changes);
+        }
+        _ => return Ok((false, vec![])),
+    };
+
+    let (synced, _) = sync_cloud(config, local, cloud, direction, Finality::Final, games).into_result()?;
+    Ok((true, synced))
+}
+
+fn resolve_cloud_conflicts_per_game(
+    config: &Config,
+    local: &StrictPath,
+    layout: &BackupLayout,
+    changes: &[CloudChange],
+) -> Result<(bool, Vec<CloudChange>), Error> {
+    let mut by_dir = std::collections::BTreeMap::<&str, Vec<CloudChange>>::new();
+    let mut resolved = true;
+    for change in changes {
+        match change.game_dir() {
+            Some(dir) => by_dir.entry(dir).or_default().push(change.clone()),
+            // Files outside of a game folder can only be synchronized with everything else.
+            None => resolved = false,
+        }
+    }
+
+    let mut upload = vec![];
+    let mut download = vec![];
+    for (dir, changes) in by_dir {
+        let game = layout.game_in_folder(dir).unwrap_or_else(|| dir.to_string());
+        eprintln!();
+        report_cloud_changes(&changes, None, false, None);
+
+        let choice = dialoguer::Select::new()
+            .with_prompt(TRANSLATOR.cli_cloud_conflict_game_prompt(&game))
+            .items(&[
+                TRANSLATOR.cli_cloud_conflict_upload(),
+                TRANSLATOR.cli_cloud_conflict_download(),
+                TRANSLATOR.cli_cloud_conflict_skip(),
+            ])
+            .default(0)
+            .interact()
+            .ok();
+
+        match choice {
+            Some(0) => upload.push(dir.to_string()),
+            Some(1) => download.push(dir.to_string()),
+            _ => resolved = false,
+        }
+    }
+
+    let mut synced = vec![];
+    for (direction, dirs) in [(SyncDirection::Upload, upload), (SyncDirection::Download, download)] {
+        if !dirs.is_empty() {
+            let (changes, _) =
+                sync_cloud_dirs(config, local, &config.cloud.path, direction, Finality::Final, &dirs).into_result()?;
+            synced.extend(changes);
+        }
+    }
+
+    Ok((resolved, synced))
+}
+
+/// What a cloud sync did.
+/// When the folders are split across several Rclone processes,
+/// the changes from the ones that succeeded are kept even if another one failed.
+#[derive(Debug, Default)]
+struct CloudSyncOutcome {
+    changes: Vec<CloudChange>,
+    stats: Option<CloudStats>,
+    error: Option<Error>,
+}
+
+impl CloudSyncOutcome {
+    fn failed(error: Error) -> Self {
+        Self {
+            error: Some(error),
+            ..Default::default()
+        }
+    }
+
+    /// For callers that can only use the changes if everything succeeded.
+    fn into_result(self) -> Result<(Vec<CloudChange>, Option<CloudStats>), Error> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok((self.changes, self.stats)),
+        }
+    }
+}
+
+/// Like `sync_cloud`, but limited to specific folders under the backup path.
+/// The folders may be split across several Rclone processes, per `cloud.parallelGames`.
+fn sync_cloud_dirs(
+    config: &Config,
+    local: &StrictPath,
+    cloud: &str,
+    sync: SyncDirection,
+    finality: Finality,
+    game_dirs: &[String],
+) -> CloudSyncOutcome {
+    // Previews don't write anything, so they're allowed even for read-only remotes.
+    let direction = match finality {
+        Finality::Preview => SyncDirection::Download,
+        Finality::Final => sync,
+    };
+    let remote = match crate::cloud::validate_cloud_config(config, cloud, direction) {
+        Ok(remote) => remote,
+        Err(e) => return CloudSyncOutcome::failed(e),
     };
 
+    let rclone = Rclone::new(config.apps.rclone.clone(), remote).with_transfers(config.runtime.cloud_threads);
+    let ignore_rules = match crate::cloud::load_ignore_rules(local) {
+        Ok(rules) => rules,
+        Err(e) => return CloudSyncOutcome::failed(e),
+    };
+
+    // Without specific folders, everything has to be synchronized together.
+    let groups = if game_dirs.is_empty() {
+        vec![vec![]]
+    } else {
+        crate::cloud::partition_game_dirs(game_dirs, config.cloud.parallel_games.get())
+    };
+
+    let mut failure = None;
+    let mut processes = vec![];
+    for group in &groups {
+        match rclone.sync(local, cloud, sync, finality, group, &ignore_rules) {
+            Ok(p) => processes.push(Some(p)),
+            Err(e) => {
+                log::error!("unable to start cloud sync for {:?}: {:?}", group, e);
+                failure.get_or_insert(e);
+            }
+        }
+    }
+    if processes.is_empty() {
+        if let Some(e) = failure {
+            return CloudSyncOutcome::failed(Error::UnableToSynchronizeCloud(e));
+        }
+    }
+
     let interrupted = register_sigint();
 
     let progress_bar = cloud_progress_bar();
     let mut changes = vec![];
+    let mut progress = vec![(0.0, 0.0); processes.len()];
+    let mut stats: Vec<Option<CloudStats>> = vec![None; processes.len()];
     loop {
         if interrupted.load(std::sync::atomic::Ordering::Relaxed) {
-            if let Err(e) = process.kill() {
-                eprintln!("Unable to stop Rclone: {e:?}");
+            for process in processes.iter_mut().flatten() {
+                if let Err(e) = process.kill() {
+                    eprintln!("Unable to stop Rclone: {e:?}");
+                }
             }
             std::process::exit(1);
         }
 
-        let events = process.events();
-        for event in events {
-            match event {
+        let mut progressed = false;
+        for (i, slot) in processes.iter_mut().enumerate() {
+            let Some(process) = slot else {
+                continue;
+            };
+
+            let mut handle_event = |event| match event {
                 crate::cloud::RcloneProcessEvent::Progress { current, max } => {
-                    progress_bar.set_length(max as u64);
-                    progress_bar.set_position(current as u64);
-                    progress_bar.set_message(TRANSLATOR.cloud_progress(current as u64, max as u64))
+                    progress[i] = (current, max);
+                    progressed = true;
                 }
                 crate::cloud::RcloneProcessEvent::Change(change) => {
                     changes.push(change);
                 }
+                crate::cloud::RcloneProcessEvent::Stats(latest) => {
+                    stats[i] = Some(latest);
+                }
+            };
+
+            for event in process.events() {
+                handle_event(event);
+            }
+            match process.succeeded() {
+                Some(Ok(_)) => {
+                    for event in process.remaining_events() {
+                        handle_event(event);
+                    }
+                    *slot = None;
+                }
+                Some(Err(e)) => {
+                    // Let any other processes finish their own games.
+                    log::error!("cloud sync failed: {:?}", e);
+                    failure.get_or_insert(e);
+                    *slot = None;
+                }
+                None => (),
             }
         }
-        match process.succeeded() {
-            Some(Ok(_)) => {
-                unregister_sigint();
-                return Ok(changes);
+
+        if progressed {
+            let (current, max) = progress
+                .iter()
+                .fold((0.0, 0.0), |(current, max), (x, y)| (current + x, max + y));
+            progress_bar.set_length(max as u64);
+            progress_bar.set_position(current as u64);
+            progress_bar.set_message(TRANSLATOR.cloud_progress(current as u64, max as u64))
+        }
+
+        if processes.iter().all(Option::is_none) {
+            break;
+        }
+    }
+
+    unregister_sigint();
+
+    if failure.is_some() {
+        progress_bar.finish_and_clear();
+    }
+
+    let stats = stats.into_iter().flatten().reduce(|mut total, latest| {
+        total.merge(&latest);
+        total
+    });
+
+    CloudSyncOutcome {
+        changes,
+        stats,
+        error: failure.map(Error::UnableToSynchronizeCloud),
+    }
+}
+
+/// Back up games whenever their save files change, until interrupted.
+fn watch_for_changes(
+    config: &Config,
+    manifest: &Manifest,
+    title_finder: &TitleFinder,
+    backup_dir: &StrictPath,
+    roots: &[Root],
+    games: &[String],
+    games_specified: bool,
+    wine_prefix: &Option<CliWinePrefix>,
+    options: &BackupOptions,
+    mut cloud_sync: bool,
+    debounce: Duration,
+) -> Result<(), Error> {
+    use notify::Watcher;
+
+    let launchers = Launchers::scan(roots, manifest, games, title_finder, None);
+    let prefix_launchers = wine_prefix_launchers(config, roots, manifest, games, title_finder, wine_prefix);
+    let steam_shortcuts = SteamShortcuts::scan();
+    let ignored_dir = backup_dir.as_std_path_buf().ok();
+
+    // Only upload if the cloud was already in sync, so that we don't overwrite another device's backups.
+    if cloud_sync {
+        match sync_cloud(
+            config,
+            backup_dir,
+            &config.cloud.path,
+            SyncDirection::Upload,
+            Finality::Preview,
+            games,
+        )
+        .into_result()
+        {
+            Ok((changes, _)) => {
+                if !changes.is_empty() {
+                    eprintln!(
+                        "{}",
+                        TRANSLATOR.prefix_warning(&TRANSLATOR.cloud_synchronize_conflict())
+                    );
+                    cloud_sync = false;
+                }
             }
-            Some(Err(e)) => {
-                unregister_sigint();
-                progress_bar.finish_and_clear();
-                return Err(Error::UnableToSynchronizeCloud(e));
+            Err(e) => {
+                eprintln!("{}", TRANSLATOR.handle_error(&e));
+                cloud_sync = false;
             }
-            None => (),
         }
     }
+
+    let scan_game = |layout: &BackupLayout, name: &str| {
+        let pipeline = BackupPipeline::new(config, manifest, layout, roots, &launchers, &steam_shortcuts, options);
+        let wine_prefix = wine_prefix_for_game(wine_prefix, &prefix_launchers, name);
+        pipeline.scan_game(name, games_specified, &wine_prefix)
+    };
+
+    // Returns the scan and whether a new backup was made.
+    let back_up_game = |layout: &BackupLayout, name: &str| {
+        let scan_info = scan_game(layout, name)?;
+        if matches!(scan_info.overall_change(), ScanChange::Same | ScanChange::Unknown) {
+            log::info!("[{name}] saves changed, but there is nothing new to back up");
+            return Some((scan_info, false));
+        }
+
+        let pipeline = BackupPipeline::new(config, manifest, layout, roots, &launchers, &steam_shortcuts, options);
+        let (backup_info, _) = pipeline.back_up_game(name, &scan_info);
+        if backup_info.successful() {
+            eprintln!("{}", TRANSLATOR.cli_watch_backed_up(config.display_name(name)));
+        } else {
+            eprintln!("{}", TRANSLATOR.cli_watch_backup_failed(config.display_name(name)));
+        }
+        Some((scan_info, true))
+    };
+
+    let new_layout = || {
+        BackupLayout::new(backup_dir.clone(), config.backup.retention.clone())
+            .with_per_game_retention(config.backup.per_game_retention.clone())
+    };
+    let mut layout = new_layout();
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(sender).map_err(|e| Error::UnableToWatchFiles { why: e.to_string() })?;
+    let mut watch_dirs = |targets: &mut WatchTargets, scan_info: &ScanInfo| {
+        for target in targets.add(scan_info) {
+            log::debug!(
+                "[{}] watching: {:?}, recursive: {}",
+                scan_info.game_name,
+                &target.path,
+                target.recursive
+            );
+            let mode = if target.recursive {
+                notify::RecursiveMode::Recursive
+            } else {
+                notify::RecursiveMode::NonRecursive
+            };
+            if let Err(e) = watcher.watch(&target.path, mode) {
+                log::warn!("[{}] unable to watch: {:?} | {e}", scan_info.game_name, &target.path);
+            }
+        }
+    };
+
+    let mut targets = WatchTargets::default();
+    let scanned: Vec<_> = games
+        .par_iter()
+        .progress_with(scan_progress_bar(games.len() as u64))
+        .filter_map(|name| scan_game(&layout, name))
+        .collect();
+    for scan_info in &scanned {
+        watch_dirs(&mut targets, scan_info);
+    }
+    eprintln!("{}", TRANSLATOR.cli_watch_started(targets.games().len()));
+
+    let interrupted = register_sigint();
+    let mut debouncer = Debouncer::new(debounce);
+    loop {
+        let stopping = interrupted.load(Ordering::Relaxed);
+
+        match receiver.recv_timeout(Duration::from_millis(250)) {
+            Ok(Ok(event)) => {
+                if !event.kind.is_access() && !event.kind.is_other() {
+                    for path in &event.paths {
+                        if ignored_dir.as_ref().is_some_and(|dir| path.starts_with(dir)) {
+                            continue;
+                        }
+                        for game in targets.games_for(path) {
+                            log::trace!("[{game}] change detected: {:?}", path);
+                            debouncer.note(game, Instant::now());
+                        }
+                    }
+                }
+            }
+            Ok(Err(e)) => log::warn!("error while watching for changes: {e}"),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        // When stopping, flush any pending backups instead of waiting out their delay.
+        let due = if stopping {
+            debouncer.drain()
+        } else {
+            debouncer.due(Instant::now())
+        };
+        if !due.is_empty() {
+            let mut changed_games = vec![];
+            for name in &due {
+                let Some((scan_info, backed_up)) = back_up_game(&layout, name) else {
+                    continue;
+                };
+                if backed_up && scan_info.needs_cloud_sync() {
+                    changed_games.push(name.clone());
+                }
+                watch_dirs(&mut targets, &scan_info);
+            }
+            // Newly backed up games need to be picked up by the layout.
+            layout = new_layout();
+
+            if cloud_sync && !changed_games.is_empty() {
+                if let Some(e) = sync_cloud(
+                    config,
+                    backup_dir,
+                    &config.cloud.path,
+                    SyncDirection::Upload,
+                    Finality::Final,
+                    &changed_games,
+                )
+                .error
+                {
+                    eprintln!("{}", TRANSLATOR.handle_error(&e));
+                }
+            }
+        }
+
+        if stopping {
+            break;
+        }
+    }
+    unregister_sigint();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::repo;
+
+    #[test]
+    fn can_load_manifest_from_cli_path() {
+        let mut config = Config::default();
+        config.manifest.cli_path = Some(StrictPath::new(format!("{}/tests/manifest.yaml", repo())));
+
+        let manifest = load_manifest(&config, &mut Cache::default(), false, false).unwrap();
+
+        assert_eq!(vec!["game1"], manifest.0.keys().collect::<Vec<_>>());
+        assert!(manifest.0["game1"].files.contains_key("<base>/saves"));
+    }
 }
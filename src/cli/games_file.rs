@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use crate::prelude::{Error, StrictPath};
+
+/// Options for one game in a `restore --games-file`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct GameOptions {
+    /// Restore a specific backup, using an ID returned by the `backups` command.
+    pub backup: Option<String>,
+    /// Restore a backup that was made on Windows into this Wine prefix.
+    pub wine_prefix: Option<StrictPath>,
+}
+
+/// Games to restore, each with its own options.
+/// Each key is the name of a game.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GamesFile(pub BTreeMap<String, GameOptions>);
+
+impl GamesFile {
+    pub fn load(path: &StrictPath) -> Result<Self, Error> {
+        let content = path
+            .read()
+            .ok_or_else(|| Error::CliInvalidGamesFile { why: path.render() })?;
+        Self::load_from_string(&content)
+    }
+
+    pub fn load_from_string(content: &str) -> Result<Self, Error> {
+        // A game can be listed without any options.
+        let games: BTreeMap<String, Option<GameOptions>> =
+            serde_yaml::from_str(content).map_err(|e| Error::CliInvalidGamesFile { why: e.to_string() })?;
+        Ok(Self(
+            games
+                .into_iter()
+                .map(|(name, options)| (name, options.unwrap_or_default()))
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::s;
+
+    #[test]
+    fn can_load_games_file() {
+        let file = GamesFile::load_from_string(
+            r#"
+            game1:
+              backup: backup-1
+            game2:
+              winePrefix: /wine
+            game3:
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            GamesFile(BTreeMap::from([
+                (
+                    s("game1"),
+                    GameOptions {
+                        backup: Some(s("backup-1")),
+                        ..Default::default()
+                    }
+                ),
+                (
+                    s("game2"),
+                    GameOptions {
+                        wine_prefix: Some(StrictPath::new(s("/wine"))),
+                        ..Default::default()
+                    }
+                ),
+                (s("game3"), GameOptions::default()),
+            ])),
+            file,
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        assert!(GamesFile::load_from_string("game1:\n  foo: bar").is_err());
+    }
+}
@@ -3,7 +3,12 @@ use std::path::PathBuf;
 use crate::{
     cloud::WebDavProvider,
     prelude::StrictPath,
-    resource::config::{BackupFormat, Sort, SortKey, ZipCompression},
+    resource::{
+        config::{
+            BackupFormat, FileSize, GameFormat, GameRetention, HashAlgorithm, Root, Sort, SortKey, ZipCompression,
+        },
+        manifest::Store,
+    },
 };
 
 use clap::{ArgGroup, Args, ValueEnum};
@@ -13,6 +18,10 @@ macro_rules! possible_values {
         use clap::builder::{PossibleValuesParser, TypedValueParser};
         PossibleValuesParser::new(<$t>::$options).map(|s| s.parse::<$t>().unwrap())
     }};
+    ($t: ty, $options: ident()) => {{
+        use clap::builder::{PossibleValuesParser, TypedValueParser};
+        PossibleValuesParser::new(<$t>::$options()).map(|s| s.parse::<$t>().unwrap())
+    }};
 }
 
 fn parse_strict_path(path: &str) -> Result<StrictPath, std::io::Error> {
@@ -27,6 +36,70 @@ fn parse_existing_strict_path(path: &str) -> Result<StrictPath, std::io::Error>
     Ok(sp)
 }
 
+fn parse_zip_path(path: &str) -> Result<StrictPath, String> {
+    if !path.to_lowercase().ends_with(".zip") {
+        return Err(format!("expected a .zip file: {}", path));
+    }
+    parse_strict_path(path).map_err(|e| e.to_string())
+}
+
+fn parse_wine_prefix(value: &str) -> Result<CliWinePrefix, std::io::Error> {
+    if value == CliWinePrefix::AUTO {
+        Ok(CliWinePrefix::Auto)
+    } else {
+        parse_strict_path(value).map(CliWinePrefix::Path)
+    }
+}
+
+fn parse_game_format(value: &str) -> Result<(String, GameFormat), String> {
+    let Some((game, format)) = value.rsplit_once('=') else {
+        return Err(format!("expected GAME=FORMAT: {}", value));
+    };
+    Ok((game.to_string(), format.parse()?))
+}
+
+fn parse_game_retention(value: &str) -> Result<(String, GameRetention), String> {
+    let Some((game, retention)) = value.rsplit_once('=') else {
+        return Err(format!("expected GAME=FULL:DIFFERENTIAL: {}", value));
+    };
+    Ok((game.to_string(), retention.parse()?))
+}
+
+fn parse_rename(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((old, new)) if !old.is_empty() && !new.is_empty() => Ok((old.to_string(), new.to_string())),
+        _ => Err(format!("expected OLD=NEW: {}", value)),
+    }
+}
+
+fn parse_root(value: &str) -> Result<Root, String> {
+    let Some((store, path)) = value.split_once(':') else {
+        return Err(format!("expected STORE:PATH: {}", value));
+    };
+    let store = store
+        .parse::<Store>()
+        .map_err(|e| format!("{e} (expected one of: {})", Store::all_names().join(", ")))?;
+    let path = parse_strict_path(path).map_err(|e| e.to_string())?;
+    Ok(Root::new(path, store))
+}
+
+fn parse_duration(value: &str) -> Result<std::time::Duration, String> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => value.split_at(i),
+        None => (value, "s"),
+    };
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("expected a number with an optional unit (s, m, h): {}", value))?;
+    let seconds = match unit {
+        "s" => number,
+        "m" => number * 60,
+        "h" => number * 60 * 60,
+        _ => return Err(format!("unknown unit, expected s, m, or h: {}", value)),
+    };
+    Ok(std::time::Duration::from_secs(seconds))
+}
+
 fn styles() -> clap::builder::styling::Styles {
     use clap::builder::styling::{AnsiColor, Effects, Styles};
 
@@ -113,6 +186,17 @@ impl From<CliSort> for Sort {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CliWinePrefix {
+    /// Detect each game's prefix from the Heroic and Lutris configs.
+    Auto,
+    Path(StrictPath),
+}
+
+impl CliWinePrefix {
+    pub const AUTO: &'static str = "auto";
+}
+
 /// Supported launchers for wrap --infer command
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Launcher {
@@ -121,6 +205,23 @@ pub enum Launcher {
     Steam,
 }
 
+/// How to group games in the report
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum CliGroupBy {
+    /// Group by the store where each game was detected.
+    Store,
+}
+
+/// How to show progress during an operation
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum CliProgressFormat {
+    /// Draw a progress bar for people.
+    #[default]
+    Bar,
+    /// Print progress events as JSON for other programs.
+    Json,
+}
+
 /// Serialization format
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum SerializationFormat {
@@ -149,27 +250,205 @@ pub enum Subcommand {
         #[clap(long)]
         force: bool,
 
+        /// Process games that are disabled in the config file, as if they were enabled.
+        /// The output notes which games are normally disabled.
+        #[clap(long)]
+        include_disabled: bool,
+
         /// Extra Wine/Proton prefix to check for saves. This should be a folder
         /// with an immediate child folder named "drive_c" (or another letter).
-        #[clap(long, value_parser = parse_strict_path)]
-        wine_prefix: Option<StrictPath>,
+        ///
+        /// Use `auto` to detect each game's prefix from your Heroic and Lutris configs,
+        /// even if those launchers are not configured as roots.
+        #[clap(long, value_parser = parse_wine_prefix)]
+        wine_prefix: Option<CliWinePrefix>,
+
+        /// Scan this additional root, formatted as `STORE:PATH`
+        /// (e.g., `steam:/mnt/games/steam`).
+        /// This may be repeated for multiple roots.
+        #[clap(long, value_name = "STORE:PATH", value_parser = parse_root)]
+        root: Vec<Root>,
+
+        /// Only scan the roots from `--root`,
+        /// instead of adding them to the roots from the config file and environment.
+        #[clap(long, requires = "root")]
+        roots_replace: bool,
+
+        /// Skip games that were only detected in roots of this store.
+        /// Games detected in multiple stores are only skipped if all of those stores are excluded.
+        /// This may be repeated to exclude multiple stores.
+        #[clap(long, value_parser = possible_values!(Store, all_names()))]
+        exclude_store: Vec<Store>,
+
+        /// Leave out games that have nothing to back up, such as when all of their saves are disabled.
+        /// They are not listed or counted as processed,
+        /// but the overall summary shows how many were left out.
+        #[clap(long)]
+        skip_empty: bool,
+
+        /// Only back up games that do not have any backups yet in the target folder.
+        /// Games that were already backed up are not scanned at all.
+        #[clap(long)]
+        only_new_games: bool,
+
+        /// Only back up registry entries, skipping all save files.
+        /// This only has an effect on Windows;
+        /// on other platforms, nothing will be backed up.
+        #[clap(long)]
+        only_registry: bool,
+
+        /// Also look for saves in locations that the manifest defines for other operating systems,
+        /// such as `%APPDATA%` inside of a game's Wine/Proton prefix on Linux,
+        /// if they happen to exist on this system.
+        /// Those files are labeled with their operating system in the backup,
+        /// which is useful when migrating between operating systems.
+        #[clap(long)]
+        include_other_os_paths: bool,
+
+        /// After scanning, show a checklist of the games that were found,
+        /// so that you can choose which ones to actually back up.
+        /// This cannot be used with `--api`.
+        #[clap(long, conflicts_with = "api")]
+        interactive: bool,
 
         /// Print information to stdout in machine-readable JSON.
         /// This replaces the default, human-readable output.
         #[clap(long)]
         api: bool,
 
+        /// Only print the overall summary, without the details of each game.
+        /// This can be combined with `--api`.
+        #[clap(long)]
+        summary_only: bool,
+
+        /// After printing the report, also write it to this file.
+        /// This is the same content as the console output, so it can be combined with `--api`.
+        /// The file is replaced all at once, so a reader never sees a partial report.
+        #[clap(long, value_name = "FILE", value_parser = parse_strict_path)]
+        report_to: Option<StrictPath>,
+
+        /// Record each file's Unix permissions and symbolic link targets,
+        /// so that they can be reapplied with `restore --preserve-permissions`.
+        /// This has no effect on Windows.
+        #[clap(long)]
+        preserve_permissions: bool,
+
+        /// Record empty folders within the save locations,
+        /// so that they can be recreated on restore.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        preserve_empty_dirs: bool,
+
+        /// Save a copy of the manifest used for this backup in the backup folder,
+        /// including any secondary manifests and custom games.
+        /// Each distinct manifest is only stored once,
+        /// along with an index of which manifest each backup run used.
+        #[clap(long)]
+        include_manifest_snapshot: bool,
+
+        /// Save a `restore-map.yaml` file in each game's backup folder,
+        /// listing the original location of every backed up file
+        /// along with the root and store that it came from.
+        /// This is meant for restoring by hand or on a different machine.
+        #[clap(long)]
+        include_restore_map: bool,
+
+        /// Descend into symbolic links to other directories while scanning for saves.
+        /// Loops are detected and skipped.
+        /// When not specified, this defers to the config file, which does so by default.
+        #[clap(long)]
+        follow_symlinks: bool,
+
+        /// Don't descend into symbolic links to other directories while scanning for saves.
+        /// Paths from the manifest that are themselves symbolic links are still followed.
+        /// When not specified, this defers to the config file.
+        #[clap(long, conflicts_with("follow_symlinks"))]
+        no_follow_symlinks: bool,
+
+        /// Skip files larger than this size, such as huge caches or logs.
+        /// This may be a number of bytes or include a unit, like `500MiB` or `2GB`.
+        /// Registry entries are not affected.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_name = "SIZE")]
+        max_file_size: Option<FileSize>,
+
+        /// Skip files matching this glob pattern in every game, like `*.log`.
+        /// This may be repeated for multiple patterns,
+        /// and it adds to the `backup.globalIgnore` patterns from the config file.
+        #[clap(long, value_name = "PATTERN")]
+        global_ignore: Vec<String>,
+
+        /// Back up OS clutter like `Thumbs.db` and `.DS_Store`,
+        /// which are normally skipped.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        no_exclude_system_files: bool,
+
+        /// After scanning, hash the files of every game and list any content
+        /// that is byte-identical across different games, along with the wasted space.
+        /// This is informational and reads every candidate file, so it may be slow.
+        /// In `--api` mode, this is reported as `identicalFiles`.
+        #[clap(long)]
+        dedupe_report: bool,
+
+        /// Retry each game that had any failed entries up to this many times.
+        /// Games that only succeed on a retry are listed in the output.
+        #[clap(long, default_value_t = 0)]
+        retries: u8,
+
+        /// Stop as soon as any game fails instead of processing the rest.
+        /// Games that already finished are still reported.
+        #[clap(long)]
+        abort_on_first_failure: bool,
+
+        /// Stop starting new games once the backup has been running for this long,
+        /// like `90s`, `30m`, or `2h`. A number without a unit is in seconds.
+        /// Any scans still running at that point are cut short.
+        /// Games that already finished are still reported,
+        /// and games that were still being scanned or backed up are reported as incomplete.
+        /// The command then exits with an error.
+        #[clap(long, value_name = "DURATION", value_parser = parse_duration)]
+        max_backup_duration: Option<std::time::Duration>,
+
+        /// Skip games that were already completed by a previous backup
+        /// that was interrupted before it could finish.
+        /// Progress is only reused when the game list and format settings are unchanged.
+        #[clap(long)]
+        resume: bool,
+
+        /// Warn if the manifest was last checked for updates more than this many days ago.
+        /// In `--api` mode, this is also reported as `overall.manifestAgeDays`.
+        #[clap(long, value_name = "DAYS")]
+        manifest_age_warning: Option<u32>,
+
         /// Sort the game list by different criteria.
         /// When not specified, this defers to the config file.
         #[clap(long, value_parser = possible_values!(CliSort, ALL))]
         sort: Option<CliSort>,
 
+        /// Group games under headings with subtotals.
+        /// With `store`, games are grouped by the store where they were detected,
+        /// or as custom/unknown if they weren't detected in any store.
+        /// In `--api` mode, games are nested under `stores` instead of `games`.
+        #[clap(long, value_enum, value_name = "KEY")]
+        group_by: Option<CliGroupBy>,
+
+        /// How to show progress while processing games.
+        /// With `json`, no progress bar is drawn;
+        /// instead, each event is printed to stderr as a separate line of JSON
+        /// (`started`, `gameStarted`, `gameFinished`, and `finished`).
+        /// This is separate from the final report on stdout.
+        #[clap(long, value_enum, value_name = "FORMAT")]
+        progress_format: Option<CliProgressFormat>,
+
         /// Format in which to store new backups.
         /// When not specified, this defers to the config file.
         #[clap(long, value_parser = possible_values!(BackupFormat, ALL_NAMES))]
         format: Option<BackupFormat>,
 
         /// Compression method to use for new zip backups.
+        /// Use `none` to keep the zip container without compressing the files,
+        /// which is fastest for saves that are already compressed.
         /// When not specified, this defers to the config file.
         #[clap(long, value_parser = possible_values!(ZipCompression, ALL_NAMES))]
         compression: Option<ZipCompression>,
@@ -180,6 +459,26 @@ pub enum Subcommand {
         #[clap(long, allow_hyphen_values(true))]
         compression_level: Option<i32>,
 
+        /// Algorithm to use when hashing files for new backups.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_parser = possible_values!(HashAlgorithm, ALL_NAMES))]
+        hash_algorithm: Option<HashAlgorithm>,
+
+        /// Template for the names of new backups, like `{date}_{time}_{tag}`.
+        /// Supported tokens are `{date}`, `{time}`, and `{tag}` (`full` or `diff`).
+        /// Names always start with `backup-`, and a number is added if needed to keep them unique.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_name = "TEMPLATE")]
+        backup_id_format: Option<String>,
+
+        /// Use a different format for a specific game, like `--game-format "Game Name=zstd:19"`.
+        /// The format can be `simple`, `zip`, or a compression method for zip backups,
+        /// optionally followed by `:` and a compression level.
+        /// This can be repeated for multiple games,
+        /// and it takes precedence over the config file and the other format options.
+        #[clap(long, value_name = "GAME=FORMAT", value_parser = parse_game_format)]
+        game_format: Vec<(String, GameFormat)>,
+
         /// Maximum number of full backups to retain per game.
         /// Must be between 1 and 255 (inclusive).
         /// When not specified, this defers to the config file.
@@ -192,6 +491,14 @@ pub enum Subcommand {
         #[clap(long)]
         differential_limit: Option<u8>,
 
+        /// Use different retention limits for a specific game, like `--game-retention "Game Name=5:10"`
+        /// for 5 full backups and 10 differential backups.
+        /// Either limit may be omitted to use the general one, like `5` or `:10`.
+        /// This can be repeated for multiple games,
+        /// and it takes precedence over the config file and the other limit options.
+        #[clap(long, value_name = "GAME=FULL:DIFFERENTIAL", value_parser = parse_game_retention)]
+        game_retention: Vec<(String, GameRetention)>,
+
         /// Upload any changes to the cloud when the backup is complete.
         /// If the local and cloud backups are not in sync to begin with,
         /// then nothing will be uploaded.
@@ -205,6 +512,11 @@ pub enum Subcommand {
         #[clap(long, conflicts_with("cloud_sync"))]
         no_cloud_sync: bool,
 
+        /// Synchronize with the cloud after the backup even if no games had new or changed files.
+        /// By default, the synchronization is skipped in that case.
+        #[clap(long, conflicts_with("no_cloud_sync"))]
+        force_cloud_sync: bool,
+
         /// Only back up these specific games.
         /// Alternatively supports stdin (one value per line).
         #[clap()]
@@ -213,6 +525,8 @@ pub enum Subcommand {
     /// Restore data
     Restore {
         /// List out what would be included, but don't actually perform the operation.
+        /// This also shows which files on disk would be overwritten,
+        /// and which files in the save locations are not part of the backup.
         #[clap(long)]
         preview: bool,
 
@@ -225,21 +539,154 @@ pub enum Subcommand {
         #[clap(long)]
         force: bool,
 
+        /// Process games that are disabled in the config file, as if they were enabled.
+        /// The output notes which games are normally disabled.
+        #[clap(long)]
+        include_disabled: bool,
+
+        /// Skip games that don't appear to be installed,
+        /// so that restoring doesn't create save folders for games you no longer have.
+        /// A game counts as installed if its install folder or launcher entry is found in any root.
+        /// The output lists which games were skipped.
+        #[clap(long)]
+        require_installed: bool,
+
         /// Print information to stdout in machine-readable JSON.
         /// This replaces the default, human-readable output.
         #[clap(long)]
         api: bool,
 
+        /// Only print the overall summary, without the details of each game.
+        /// This can be combined with `--api`.
+        #[clap(long)]
+        summary_only: bool,
+
+        /// After printing the report, also write it to this file.
+        /// This is the same content as the console output, so it can be combined with `--api`.
+        /// The file is replaced all at once, so a reader never sees a partial report.
+        #[clap(long, value_name = "FILE", value_parser = parse_strict_path)]
+        report_to: Option<StrictPath>,
+
+        /// Reapply each file's Unix permissions and recreate symbolic links,
+        /// if they were recorded with `backup --preserve-permissions`.
+        /// This has no effect on Windows.
+        #[clap(long)]
+        preserve_permissions: bool,
+
+        /// After restoring each game, delete any other files in its save locations
+        /// that are not part of the backup, so that the result exactly matches the backup.
+        /// Files outside of the game's save locations are never touched.
+        /// Use `--preview` to see which files would be deleted.
+        #[clap(long)]
+        clean: bool,
+
+        /// When restoring registry keys, list any values that are already under each key
+        /// but are not part of the backup.
+        /// Only the values from the backup are written, like `reg import`,
+        /// so these other values are preserved.
+        #[clap(long)]
+        merge_registry: bool,
+
+        /// Retry each game that had any failed entries up to this many times.
+        /// Games that only succeed on a retry are listed in the output.
+        #[clap(long, default_value_t = 0)]
+        retries: u8,
+
+        /// Stop as soon as any game fails instead of processing the rest.
+        /// Games that already finished are still reported.
+        #[clap(long)]
+        abort_on_first_failure: bool,
+
+        /// Warn if the manifest was last checked for updates more than this many days ago.
+        /// In `--api` mode, this is also reported as `overall.manifestAgeDays`.
+        #[clap(long, value_name = "DAYS")]
+        manifest_age_warning: Option<u32>,
+
+        /// Restore a backup that was made on Windows into a Wine prefix.
+        /// Each recorded Windows path is moved into the prefix from `--wine-prefix`,
+        /// such as `C:/Users/<user>` into `drive_c/users/<user>`.
+        #[clap(long, requires = "wine_prefix")]
+        force_different_os: bool,
+
+        /// Wine prefix to use with `--force-different-os`.
+        #[clap(long, value_parser = parse_strict_path, requires = "force_different_os")]
+        wine_prefix: Option<StrictPath>,
+
+        /// Restore backups that were made on Windows into each game's Proton prefix,
+        /// as on a Steam Deck.
+        /// For games with a known Steam ID, the prefix is found in your Steam roots
+        /// (`steamapps/compatdata/<id>/pfx`),
+        /// and each recorded Windows path is moved into it like with `--force-different-os`.
+        /// Other games are restored normally.
+        #[clap(long, conflicts_with = "force_different_os")]
+        steam_deck: bool,
+
+        /// Use this additional root, formatted as `STORE:PATH`
+        /// (e.g., `steam:/mnt/games/steam`),
+        /// when checking `--require-installed`, finding prefixes for `--steam-deck`,
+        /// and scanning the current saves for a preview or `--clean`.
+        /// This may be repeated for multiple roots.
+        #[clap(long, value_name = "STORE:PATH", value_parser = parse_root)]
+        root: Vec<Root>,
+
+        /// Only use the roots from `--root`,
+        /// instead of adding them to the roots from the config file and environment.
+        #[clap(long, requires = "root")]
+        roots_replace: bool,
+
         /// Sort the game list by different criteria.
         /// When not specified, this defers to Ludusavi's config file.
         #[clap(long, value_parser = possible_values!(CliSort, ALL))]
         sort: Option<CliSort>,
 
+        /// Group games under headings with subtotals.
+        /// With `store`, games are grouped by the store where they were detected,
+        /// or as custom/unknown if they weren't detected in any store.
+        /// In `--api` mode, games are nested under `stores` instead of `games`.
+        #[clap(long, value_enum, value_name = "KEY")]
+        group_by: Option<CliGroupBy>,
+
+        /// How to show progress while processing games.
+        /// With `json`, no progress bar is drawn;
+        /// instead, each event is printed to stderr as a separate line of JSON
+        /// (`started`, `gameStarted`, `gameFinished`, and `finished`).
+        /// This is separate from the final report on stdout.
+        #[clap(long, value_enum, value_name = "FORMAT")]
+        progress_format: Option<CliProgressFormat>,
+
         /// Restore a specific backup, using an ID returned by the `backups` command.
         /// This is only valid when restoring a single game.
         #[clap(long)]
         backup: Option<String>,
 
+        /// When using the Git backup format,
+        /// restore the backups as they were at this commit of the backup folder's repository.
+        /// Accepts anything that Git accepts as a revision, like a commit hash or `HEAD~1`.
+        #[clap(long, value_name = "COMMIT")]
+        git_commit: Option<String>,
+
+        /// Restore the games listed in this YAML file,
+        /// where each key is a game name and each value can set per-game options:
+        /// `backup` (a backup ID to restore)
+        /// and `winePrefix` (a Wine prefix to restore a Windows backup into).
+        #[clap(long, value_name = "PATH", value_parser = parse_existing_strict_path, conflicts_with_all = ["games", "backup"])]
+        games_file: Option<StrictPath>,
+
+        /// After scanning, show a checklist of the games that were found,
+        /// so that you can choose which ones to actually restore.
+        /// This cannot be used with `--api`.
+        #[clap(long, conflicts_with = "api")]
+        interactive: bool,
+
+        /// Instead of restoring the files in place, write them into this zip archive,
+        /// organized by game and drive (e.g., `game/drive-C/Users/foo/save.dat`).
+        /// Differential backups are resolved into the files that would be restored.
+        /// On Windows, registry data is included as `registry.reg` in each game's folder.
+        /// The file name must end with `.zip`.
+        /// This cannot be used with `--clean` or `--api`.
+        #[clap(long, value_name = "FILE", value_parser = parse_zip_path, conflicts_with_all = ["clean", "api"])]
+        to_archive: Option<StrictPath>,
+
         /// Warn if the local and cloud backups are out of sync.
         /// The restore will still proceed regardless.
         /// This has no effect on previews.
@@ -274,11 +721,99 @@ pub enum Subcommand {
         #[clap(long)]
         api: bool,
 
+        /// Only print the overall summary, without the details of each game.
+        /// This can be combined with `--api`.
+        #[clap(long)]
+        summary_only: bool,
+
+        /// After printing the report, also write it to this file.
+        /// This is the same content as the console output, so it can be combined with `--api`.
+        /// The file is replaced all at once, so a reader never sees a partial report.
+        #[clap(long, value_name = "FILE", value_parser = parse_strict_path)]
+        report_to: Option<StrictPath>,
+
+        /// Pin a backup, using an ID returned by this command,
+        /// so that it will never be deleted by the retention limits.
+        /// This is the same as locking the backup in the GUI.
+        /// This is only valid when specifying a single game.
+        #[clap(long, value_name = "BACKUP", conflicts_with("unpin"))]
+        pin: Option<String>,
+
+        /// Unpin a backup, using an ID returned by this command,
+        /// so that it counts toward the retention limits again.
+        /// This is only valid when specifying a single game.
+        #[clap(long, value_name = "BACKUP")]
+        unpin: Option<String>,
+
+        /// Compare the current files on disk against a backup, using an ID returned by this command,
+        /// and show which files have been added, changed, or removed since then.
+        /// This is only valid when specifying a single game.
+        #[clap(long, value_name = "BACKUP", conflicts_with_all(["pin", "unpin"]))]
+        since_backup: Option<String>,
+
+        /// Move a game's backups to a new name, formatted as `OLD=NEW`,
+        /// such as after the manifest renames the game.
+        /// If the new name already has backups, then they are merged,
+        /// unless both names have a backup or file at the same location.
+        #[clap(
+            long,
+            value_name = "OLD=NEW",
+            value_parser = parse_rename,
+            conflicts_with_all(["pin", "unpin", "since_backup", "games"])
+        )]
+        rename: Option<(String, String)>,
+
+        /// With `--rename`, show the planned moves and any conflicts
+        /// without actually changing anything.
+        #[clap(long, requires("rename"))]
+        preview: bool,
+
         /// Only report these specific games.
         /// Alternatively supports stdin (one value per line).
         #[clap()]
         games: Vec<String>,
     },
+    /// Package the latest backups into a single portable archive
+    ///
+    /// This is different from the zip backup format:
+    /// the archive contains each selected game's latest backup along with its metadata,
+    /// so that it can be moved to another machine and unpacked with the `import` command.
+    Export {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Archive file to create.
+        #[clap(long, value_parser = parse_strict_path)]
+        to: StrictPath,
+
+        /// List the files that would be exported, without actually creating the archive.
+        #[clap(long)]
+        preview: bool,
+
+        /// Only export these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Unpack an archive created by the `export` command
+    ///
+    /// Games that already have backups in the target directory are skipped.
+    Import {
+        /// Archive file to unpack.
+        #[clap(long, value_parser = parse_strict_path)]
+        from: StrictPath,
+
+        /// Directory in which to place the backups.
+        /// When unset, this defaults to the backup path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// List the files that would be imported, without actually unpacking the archive.
+        #[clap(long)]
+        preview: bool,
+    },
     /// Find game titles
     ///
     /// Precedence: Steam ID -> GOG ID -> Lutris ID -> exact names -> normalized names.
@@ -291,12 +826,19 @@ pub enum Subcommand {
     /// Aliases will be resolved to the target title.
     ///
     /// This command automatically updates the manifest if necessary.
+    #[clap(group(ArgGroup::new("find_json_output").args(["api", "json_lines"])))]
     Find {
         /// Print information to stdout in machine-readable JSON.
         /// This replaces the default, human-readable output.
         #[clap(long)]
         api: bool,
 
+        /// Print each match to stdout as a separate line of JSON as soon as it is found,
+        /// instead of a single JSON object at the end.
+        /// If there are no matches, then the errors are printed as a line of JSON instead.
+        #[clap(long, conflicts_with = "api")]
+        json_lines: bool,
+
         /// Directory in which to find backups.
         /// When unset, this defaults to the restore path from the config file.
         #[clap(long, value_parser = parse_strict_path)]
@@ -322,6 +864,25 @@ pub enum Subcommand {
         #[clap(long)]
         lutris_id: Option<String>,
 
+        /// Look up games whose save locations contain this text,
+        /// such as part of a folder name where you found some mystery saves.
+        /// Paths are expanded for the current system,
+        /// ignoring capitalization and the style of path separators.
+        /// This may find multiple games for a single input.
+        #[clap(long, value_name = "SUBSTRING")]
+        by_path: Option<String>,
+
+        /// With `--by-path`, scan this additional root, formatted as `STORE:PATH`
+        /// (e.g., `steam:/mnt/games/steam`).
+        /// This may be repeated for multiple roots.
+        #[clap(long, value_name = "STORE:PATH", value_parser = parse_root, requires = "by_path")]
+        root: Vec<Root>,
+
+        /// With `--by-path`, only scan the roots from `--root`,
+        /// instead of adding them to the roots from the config file and environment.
+        #[clap(long, requires = "root")]
+        roots_replace: bool,
+
         /// Look up game by an approximation of the title.
         /// Ignores capitalization, "edition" suffixes, year suffixes, and some special symbols.
         /// This may find multiple games for a single input.
@@ -336,6 +897,17 @@ pub enum Subcommand {
         #[clap(long)]
         partial: bool,
 
+        /// Also show each game's title in this language (e.g., `de-DE`),
+        /// if the manifest has one.
+        /// Games are still identified by their normal names.
+        #[clap(long, value_name = "CODE")]
+        manifest_language: Option<String>,
+
+        /// In the `--api` or `--json-lines` output, include each game's save paths and registry keys
+        /// as written in the manifest, before expanding any placeholders like `<base>`.
+        #[clap(long, requires = "find_json_output")]
+        show_manifest_paths: bool,
+
         /// Look up game by an exact title.
         /// With multiple values, they will be checked in the order given.
         /// Alternatively supports stdin (one value per line).
@@ -347,6 +919,21 @@ pub enum Subcommand {
         #[clap(subcommand)]
         sub: ManifestSubcommand,
     },
+    /// Information about roots.
+    Roots {
+        #[clap(subcommand)]
+        sub: RootsSubcommand,
+    },
+    /// Read or change individual settings in the config file.
+    Config {
+        #[clap(subcommand)]
+        sub: ConfigSubcommand,
+    },
+    /// Copy Ludusavi's own settings to or from a portable file.
+    Settings {
+        #[clap(subcommand)]
+        sub: SettingsSubcommand,
+    },
     /// Cloud sync.
     Cloud {
         #[clap(subcommand)]
@@ -371,6 +958,66 @@ pub enum Subcommand {
         #[clap(required = true)]
         commands: Vec<String>,
     },
+    /// Watch save folders and back up games when their saves change
+    ///
+    /// This runs until you stop it with Ctrl+C.
+    /// Each game's save locations are found with an initial scan,
+    /// and those folders are watched for changes, including any subfolders.
+    /// Games whose save locations don't exist yet are not watched.
+    /// The normal backup settings from the config file are used,
+    /// including the retention limits.
+    Watch {
+        /// Directory in which to store the backups.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// After a game's saves change, wait until they have been quiet for this many seconds
+        /// before backing up the game, so that rapid writes only trigger one backup.
+        #[clap(long, value_name = "SECONDS", default_value_t = 10)]
+        debounce: u64,
+
+        /// Extra Wine/Proton prefix to check for saves. This should be a folder
+        /// with an immediate child folder named "drive_c" (or another letter).
+        ///
+        /// Use `auto` to detect each game's prefix from your Heroic and Lutris configs,
+        /// even if those launchers are not configured as roots.
+        #[clap(long, value_parser = parse_wine_prefix)]
+        wine_prefix: Option<CliWinePrefix>,
+
+        /// Scan this additional root, formatted as `STORE:PATH`
+        /// (e.g., `steam:/mnt/games/steam`).
+        /// This may be repeated for multiple roots.
+        #[clap(long, value_name = "STORE:PATH", value_parser = parse_root)]
+        root: Vec<Root>,
+
+        /// Only scan the roots from `--root`,
+        /// instead of adding them to the roots from the config file and environment.
+        #[clap(long, requires = "root")]
+        roots_replace: bool,
+
+        /// Retry each game that had any failed entries up to this many times.
+        #[clap(long, default_value_t = 0)]
+        retries: u8,
+
+        /// Upload any changes to the cloud after each backup.
+        /// If the local and cloud backups are not in sync to begin with,
+        /// then nothing will be uploaded.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        cloud_sync: bool,
+
+        /// Don't perform any cloud checks or synchronization.
+        /// When not specified, this defers to the config file.
+        #[clap(long, conflicts_with("cloud_sync"))]
+        no_cloud_sync: bool,
+
+        /// Only watch these specific games.
+        /// When not specified, all games that are enabled for backup are watched.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
     /// Execute bulk requests using JSON input.
     ///
     /// If there is a problem with the entire input
@@ -407,6 +1054,33 @@ pub enum ManifestSubcommand {
         /// Print information to stdout in machine-readable JSON.
         #[clap(long)]
         api: bool,
+
+        /// Add a `normalized` field to each game with the simplified form of its name,
+        /// as used by the `find --normalized` option.
+        #[clap(long)]
+        normalize_names: bool,
+
+        /// Add a `localized` field to each game with its title in this language (e.g., `de-DE`).
+        /// When the manifest has no title for that language, this is the normal name.
+        #[clap(long, value_name = "CODE", conflicts_with = "normalize_names")]
+        manifest_language: Option<String>,
+    },
+    /// List the names of all games in the manifest, including any custom entries,
+    /// without scanning for them.
+    /// Aliases are not included.
+    List {
+        /// Only list games that have a Steam ID.
+        #[clap(long)]
+        with_steam_id: bool,
+
+        /// Only list custom games.
+        #[clap(long)]
+        custom_only: bool,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This includes each game's Steam and GOG IDs.
+        #[clap(long)]
+        api: bool,
     },
     /// Check for any manifest updates and download if available.
     /// By default, does nothing if the most recent check was within the last 24 hours.
@@ -415,6 +1089,99 @@ pub enum ManifestSubcommand {
         #[clap(long)]
         force: bool,
     },
+    /// Compare two versions of the manifest,
+    /// listing added and removed games
+    /// and any changed paths or registry keys for the other games.
+    Diff {
+        /// The older manifest file.
+        #[clap(long, value_name = "FILE", value_parser = parse_strict_path)]
+        from: StrictPath,
+
+        /// The newer manifest file.
+        /// When not specified, this defaults to the currently downloaded primary manifest.
+        #[clap(long, value_name = "FILE", value_parser = parse_strict_path)]
+        to: Option<StrictPath>,
+
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+    },
+    /// Scan for a single custom game, without the rest of the manifest,
+    /// and show what a backup would capture.
+    /// This is useful for checking a custom game's paths while you write them.
+    Test {
+        /// Name of the custom game.
+        #[clap(long)]
+        name: String,
+
+        /// Also load custom games from this YAML file,
+        /// in the same format as `customGames` in the config file.
+        /// Definitions from this file take precedence over the config file.
+        #[clap(long, value_name = "FILE", value_parser = parse_strict_path)]
+        file: Option<StrictPath>,
+
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+    },
+}
+
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum RootsSubcommand {
+    /// List each configured and automatically detected root,
+    /// whether its folder exists, and how many games have data in it.
+    /// This performs a full scan, so it may take a while.
+    List {
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+    },
+}
+
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigSubcommand {
+    /// Print the value of a setting.
+    /// Simple values are printed as-is, while nested settings are printed as YAML.
+    Get {
+        /// Dotted path to the setting (e.g., `backup.path` or `backup.retention.full`).
+        key: String,
+    },
+    /// Change the value of a setting and save the config file.
+    /// Values for text settings are used as-is,
+    /// while other values are parsed as YAML (e.g., `5`, `true`, or `[a, b]`).
+    Set {
+        /// Dotted path to the setting (e.g., `backup.path` or `backup.retention.full`).
+        /// Per-game settings use the quoted game name (e.g., `games."Some Game".note`).
+        key: String,
+
+        /// New value for the setting.
+        value: String,
+    },
+}
+
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum SettingsSubcommand {
+    /// Write the config and cached state (e.g., known roots and recent games) to a file.
+    Export {
+        /// File to create.
+        #[clap(long, value_parser = parse_strict_path)]
+        to: StrictPath,
+
+        /// Also include Rclone's stored settings for the cloud remote.
+        /// These may contain credentials or access tokens, so keep the file private.
+        #[clap(long)]
+        include_secrets: bool,
+    },
+    /// Replace the current settings with those from a file created by `settings export`.
+    Import {
+        /// File to read.
+        #[clap(long, value_parser = parse_strict_path)]
+        from: StrictPath,
+
+        /// Don't ask for confirmation.
+        #[clap(long)]
+        force: bool,
+    },
 }
 
 #[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
@@ -436,6 +1203,12 @@ pub enum CloudSubcommand {
         #[clap(long)]
         cloud: Option<String>,
 
+        /// Name of an existing Rclone remote to use for this operation only,
+        /// such as when moving your backups to a different cloud provider.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        remote: Option<String>,
+
         /// Don't ask for confirmation.
         #[clap(long)]
         force: bool,
@@ -444,6 +1217,19 @@ pub enum CloudSubcommand {
         #[clap(long)]
         preview: bool,
 
+        /// Print the Rclone command that would be run, instead of running it,
+        /// so that you can run it yourself (e.g., with extra verbosity for troubleshooting).
+        /// Any custom Rclone arguments that look like credentials are replaced with `<REDACTED>`,
+        /// which you will need to fill in before running the command.
+        #[clap(long)]
+        print_command: bool,
+
+        /// After uploading, download the uploaded files again
+        /// and check that they match the local backups.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        verify_upload: bool,
+
         /// Print information to stdout in machine-readable JSON.
         /// This replaces the default, human-readable output.
         #[clap(long)]
@@ -466,6 +1252,12 @@ pub enum CloudSubcommand {
         #[clap(long)]
         cloud: Option<String>,
 
+        /// Name of an existing Rclone remote to use for this operation only,
+        /// such as when moving your backups to a different cloud provider.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        remote: Option<String>,
+
         /// Don't ask for confirmation.
         #[clap(long)]
         force: bool,
@@ -474,6 +1266,13 @@ pub enum CloudSubcommand {
         #[clap(long)]
         preview: bool,
 
+        /// Print the Rclone command that would be run, instead of running it,
+        /// so that you can run it yourself (e.g., with extra verbosity for troubleshooting).
+        /// Any custom Rclone arguments that look like credentials are replaced with `<REDACTED>`,
+        /// which you will need to fill in before running the command.
+        #[clap(long)]
+        print_command: bool,
+
         /// Print information to stdout in machine-readable JSON.
         /// This replaces the default, human-readable output.
         #[clap(long)]
@@ -484,27 +1283,68 @@ pub enum CloudSubcommand {
         #[clap()]
         games: Vec<String>,
     },
-}
+    /// Delete the cloud backups of specific games.
+    /// You must either list the games or use `--all`.
+    Purge {
+        /// Local folder path for backups,
+        /// which is used to find each game's folder name.
+        /// When not specified, this defers to the backup path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        local: Option<StrictPath>,
 
-#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
-pub enum CloudSetSubcommand {
-    /// Disable cloud backups.
-    None,
-    /// Use a pre-existing Rclone remote.
-    Custom {
+        /// Cloud folder path for backups.
+        /// When not specified, this defers to the config file.
         #[clap(long)]
-        id: String,
-    },
-    /// Use Box.
-    Box,
-    /// Use Dropbox.
-    Dropbox,
-    /// Use Google Drive.
-    #[clap(name = "google-drive")]
-    GoogleDrive,
-    /// Use OneDrive.
-    #[clap(name = "onedrive")]
-    OneDrive,
+        cloud: Option<String>,
+
+        /// Name of an existing Rclone remote to use for this operation only.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        remote: Option<String>,
+
+        /// Don't ask for confirmation.
+        #[clap(long)]
+        force: bool,
+
+        /// List what would be deleted, but don't actually delete it.
+        #[clap(long)]
+        preview: bool,
+
+        /// Delete the entire cloud folder instead of specific games.
+        #[clap(long, conflicts_with = "games")]
+        all: bool,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Delete these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+}
+
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum CloudSetSubcommand {
+    /// Disable cloud backups.
+    None,
+    /// Use a pre-existing Rclone remote.
+    Custom {
+        #[clap(long)]
+        id: String,
+    },
+    /// Use Box.
+    Box,
+    /// Use Dropbox.
+    Dropbox,
+    /// Use Google Drive.
+    #[clap(name = "google-drive")]
+    GoogleDrive,
+    /// Use OneDrive.
+    #[clap(name = "onedrive")]
+    OneDrive,
     /// Use an FTP server.
     Ftp {
         /// Host URL.
@@ -551,6 +1391,32 @@ pub enum CloudSetSubcommand {
         #[clap(long, default_value = WebDavProvider::OTHER, value_parser = possible_values!(WebDavProvider, ALL_CLI))]
         provider: WebDavProvider,
     },
+    /// Use Amazon S3 or an S3-compatible service, like MinIO.
+    #[clap(name = "s3")]
+    S3 {
+        /// Endpoint URL.
+        #[clap(long)]
+        endpoint: String,
+        /// Bucket name.
+        #[clap(long)]
+        bucket: String,
+        /// Access key ID for authentication.
+        #[clap(long)]
+        access_key: String,
+        /// Secret access key for authentication.
+        #[clap(long)]
+        secret_key: String,
+        /// Region, if required by the service.
+        #[clap(long, default_value = "")]
+        region: String,
+    },
+    /// Use an HTTP server.
+    /// This is read-only, so it can only be used to download backups.
+    Http {
+        /// URL.
+        #[clap(long)]
+        url: String,
+    },
 }
 
 #[derive(Args, Clone, Debug, PartialEq, Eq)]
@@ -591,13 +1457,68 @@ pub struct Cli {
     pub config: Option<PathBuf>,
 
     /// Disable automatic/implicit manifest update checks.
-    #[clap(long)]
+    /// The existing manifest will be used as-is,
+    /// and commands that need it will fail if it has not been downloaded yet.
+    #[clap(long, global = true, conflicts_with("try_manifest_update"))]
     pub no_manifest_update: bool,
 
     /// Ignore any errors during automatic/implicit manifest update checks.
-    #[clap(long)]
+    #[clap(long, global = true)]
     pub try_manifest_update: bool,
 
+    /// When updating the manifest, verify its SHA-256 checksum before using it.
+    /// The expected value comes from the `manifest.checksum` config setting,
+    /// or else from a sibling URL with a `.sha256` extension.
+    /// If the checksum does not match, the previous manifest is kept.
+    #[clap(long, global = true)]
+    pub manifest_enforce_checksum: bool,
+
+    /// Also download this secondary manifest and merge it into the available games,
+    /// in addition to any `manifest.secondary` entries in the config file.
+    /// The primary manifest takes precedence when they define the same game.
+    /// This can be repeated.
+    #[clap(long, value_name = "URL", global = true)]
+    pub manifest_extra: Vec<String>,
+
+    /// Use this local manifest file instead of the downloaded one.
+    /// The manifest will not be checked for updates,
+    /// but secondary manifests and custom games still apply.
+    #[clap(long, value_name = "FILE", value_parser = parse_existing_strict_path, global = true)]
+    pub manifest_path: Option<StrictPath>,
+
+    /// Number of spaces per indentation level in JSON output
+    /// (`--api` mode, `manifest show --api`, and the `api` command).
+    /// Use 0 for compact output on a single line.
+    /// When not specified, `manifest show --api` is compact and everything else uses 2 spaces.
+    #[clap(long, value_name = "N")]
+    pub json_indent: Option<usize>,
+
+    /// Load additional custom games from this YAML file,
+    /// in the same format as `customGames` in the config file.
+    /// This can be repeated, and later files override earlier ones.
+    #[clap(long, value_name = "FILE", value_parser = parse_strict_path, global = true)]
+    pub custom_games: Vec<StrictPath>,
+
+    /// Also write log messages to this file as newline-delimited JSON,
+    /// with one object per line containing the timestamp, level, target, message,
+    /// and any extra fields (such as the game being processed).
+    /// The log level is controlled by the `RUST_LOG` environment variable, as with the normal log file.
+    #[clap(long, value_name = "PATH", value_parser = parse_strict_path, global = true)]
+    pub log_file: Option<StrictPath>,
+
+    /// How many threads to use for parallel scanning in this run.
+    /// Use 0 to use all available CPU cores.
+    /// This overrides the `LUDUSAVI_THREADS` environment variable
+    /// and the `runtime.scanThreads` setting in the config file.
+    #[clap(long, value_name = "N", global = true)]
+    pub threads: Option<usize>,
+
+    /// Don't use colors or other styling in the output, including help text and progress bars.
+    /// This is also the case when the `NO_COLOR` environment variable is set,
+    /// and for any output that is not going to a terminal.
+    #[clap(long, global = true)]
+    pub no_color: bool,
+
     #[clap(subcommand)]
     pub sub: Option<Subcommand>,
 }
@@ -619,6 +1540,19 @@ mod tests {
         assert_eq!(error, result.unwrap_err().kind());
     }
 
+    #[test]
+    fn rejects_cli_backup_with_invalid_root() {
+        check_args_err(
+            &["ludusavi", "backup", "--root", "fake:/games"],
+            clap::error::ErrorKind::ValueValidation,
+        );
+        check_args_err(
+            &["ludusavi", "backup", "--root", "/games"],
+            clap::error::ErrorKind::ValueValidation,
+        );
+        assert!(parse_root("fake:/games").unwrap_err().contains("steam"));
+    }
+
     #[test]
     fn accepts_cli_without_arguments() {
         check_args(
@@ -627,11 +1561,498 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: None,
             },
         );
     }
 
+    #[test]
+    fn accepts_cli_with_json_indent() {
+        check_args(
+            &["ludusavi", "--json-indent", "4", "backups", "--api"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: Some(4),
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backups {
+                    path: None,
+                    api: true,
+                    summary_only: false,
+                    report_to: None,
+                    pin: None,
+                    unpin: None,
+                    since_backup: None,
+                    rename: None,
+                    preview: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_with_manifest_extra() {
+        check_args(
+            &[
+                "ludusavi",
+                "manifest",
+                "update",
+                "--manifest-extra",
+                "https://example.com/a.yaml",
+                "--manifest-extra",
+                "https://example.com/b.yaml",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![s("https://example.com/a.yaml"), s("https://example.com/b.yaml")],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Update { force: false },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_with_manifest_path() {
+        check_args(
+            &["ludusavi", "find", "--manifest-path", "tests/manifest.yaml"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: Some(StrictPath::relative(
+                    s("tests/manifest.yaml"),
+                    Some(StrictPath::cwd().raw()),
+                )),
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Find {
+                    api: false,
+                    json_lines: false,
+                    path: None,
+                    backup: false,
+                    restore: false,
+                    steam_id: None,
+                    gog_id: None,
+                    lutris_id: None,
+                    by_path: None,
+                    root: vec![],
+                    roots_replace: false,
+                    normalized: false,
+                    disabled: false,
+                    partial: false,
+                    manifest_language: None,
+                    show_manifest_paths: false,
+                    names: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_with_manifest_enforce_checksum() {
+        check_args(
+            &["ludusavi", "manifest", "update", "--manifest-enforce-checksum"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: true,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Update { force: false },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_with_log_file() {
+        check_args(
+            &["ludusavi", "manifest", "update", "--log-file", "tests/log.ndjson"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: Some(StrictPath::relative(
+                    s("tests/log.ndjson"),
+                    Some(StrictPath::cwd().interpret().unwrap()),
+                )),
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Update { force: false },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_with_threads() {
+        check_args(
+            &["ludusavi", "manifest", "update", "--threads", "2"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: Some(2),
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Update { force: false },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_with_no_color() {
+        check_args(
+            &["ludusavi", "manifest", "update", "--no-color"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: true,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Update { force: false },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_manifest_show_with_normalized_names() {
+        check_args(
+            &["ludusavi", "manifest", "show", "--api", "--normalize-names"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Show {
+                        api: true,
+                        normalize_names: true,
+                        manifest_language: None,
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_manifest_list() {
+        check_args(
+            &[
+                "ludusavi",
+                "manifest",
+                "list",
+                "--with-steam-id",
+                "--custom-only",
+                "--api",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::List {
+                        with_steam_id: true,
+                        custom_only: true,
+                        api: true,
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_manifest_diff() {
+        check_args(
+            &["ludusavi", "manifest", "diff", "--from", "old.yaml", "--api"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Diff {
+                        from: StrictPath::relative(s("old.yaml"), Some(StrictPath::cwd().interpret().unwrap())),
+                        to: None,
+                        api: true,
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_manifest_test() {
+        check_args(
+            &[
+                "ludusavi",
+                "manifest",
+                "test",
+                "--name",
+                "My Game",
+                "--file",
+                "games.yaml",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Test {
+                        name: s("My Game"),
+                        file: Some(StrictPath::relative(
+                            s("games.yaml"),
+                            Some(StrictPath::cwd().interpret().unwrap()),
+                        )),
+                        api: false,
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_cloud_upload_with_print_command() {
+        check_args(
+            &["ludusavi", "cloud", "upload", "--print-command", "game1"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Cloud {
+                    sub: CloudSubcommand::Upload {
+                        local: None,
+                        cloud: None,
+                        remote: None,
+                        force: false,
+                        preview: false,
+                        print_command: true,
+                        verify_upload: false,
+                        api: false,
+                        games: vec![s("game1")],
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_cloud_download_with_remote() {
+        check_args(
+            &["ludusavi", "cloud", "download", "--remote", "old-drive", "--force"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Cloud {
+                    sub: CloudSubcommand::Download {
+                        local: None,
+                        cloud: None,
+                        remote: Some(s("old-drive")),
+                        force: true,
+                        preview: false,
+                        print_command: false,
+                        api: false,
+                        games: vec![],
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_cloud_purge_with_all() {
+        check_args(
+            &["ludusavi", "cloud", "purge", "--all", "--preview"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Cloud {
+                    sub: CloudSubcommand::Purge {
+                        local: None,
+                        cloud: None,
+                        remote: None,
+                        force: false,
+                        preview: true,
+                        all: true,
+                        api: false,
+                        games: vec![],
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_cloud_purge_with_all_and_games() {
+        check_args_err(
+            &["ludusavi", "cloud", "purge", "--all", "game1"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_with_custom_games() {
+        check_args(
+            &[
+                "ludusavi",
+                "backups",
+                "--custom-games",
+                "tests/custom-games-1.yaml",
+                "--custom-games",
+                "tests/custom-games-2.yaml",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![
+                    StrictPath::relative(
+                        s("tests/custom-games-1.yaml"),
+                        Some(StrictPath::cwd().interpret().unwrap()),
+                    ),
+                    StrictPath::relative(
+                        s("tests/custom-games-2.yaml"),
+                        Some(StrictPath::cwd().interpret().unwrap()),
+                    ),
+                ],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backups {
+                    path: None,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    pin: None,
+                    unpin: None,
+                    since_backup: None,
+                    rename: None,
+                    preview: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
     #[test]
     fn accepts_cli_backup_with_minimal_arguments() {
         check_args(
@@ -640,20 +2061,284 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Backup {
                     preview: false,
                     path: None,
                     force: false,
+                    include_disabled: false,
                     wine_prefix: None,
+                    exclude_store: vec![],
+                    skip_empty: false,
+                    only_new_games: false,
+                    only_registry: false,
+                    include_other_os_paths: false,
+                    interactive: false,
+                    root: vec![],
+                    roots_replace: false,
                     api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    preserve_empty_dirs: false,
+                    include_manifest_snapshot: false,
+                    include_restore_map: false,
+                    follow_symlinks: false,
+                    no_follow_symlinks: false,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    no_exclude_system_files: false,
+                    dedupe_report: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    max_backup_duration: None,
+                    resume: false,
+                    manifest_age_warning: None,
                     sort: None,
+                    group_by: None,
+                    progress_format: None,
                     format: None,
                     compression: None,
                     compression_level: None,
+                    hash_algorithm: None,
+                    backup_id_format: None,
+                    game_format: vec![],
                     full_limit: None,
                     differential_limit: None,
+                    game_retention: vec![],
                     cloud_sync: false,
                     no_cloud_sync: false,
+                    force_cloud_sync: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backup_with_max_backup_duration() {
+        check_args(
+            &["ludusavi", "backup", "--max-backup-duration", "30m"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backup {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    include_disabled: false,
+                    wine_prefix: None,
+                    exclude_store: vec![],
+                    skip_empty: false,
+                    only_new_games: false,
+                    only_registry: false,
+                    include_other_os_paths: false,
+                    interactive: false,
+                    root: vec![],
+                    roots_replace: false,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    preserve_empty_dirs: false,
+                    include_manifest_snapshot: false,
+                    include_restore_map: false,
+                    follow_symlinks: false,
+                    no_follow_symlinks: false,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    no_exclude_system_files: false,
+                    dedupe_report: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    max_backup_duration: Some(std::time::Duration::from_secs(30 * 60)),
+                    resume: false,
+                    manifest_age_warning: None,
+                    sort: None,
+                    group_by: None,
+                    progress_format: None,
+                    format: None,
+                    compression: None,
+                    compression_level: None,
+                    hash_algorithm: None,
+                    backup_id_format: None,
+                    game_format: vec![],
+                    full_limit: None,
+                    differential_limit: None,
+                    game_retention: vec![],
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    force_cloud_sync: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_backup_with_invalid_max_backup_duration() {
+        check_args_err(
+            &["ludusavi", "backup", "--max-backup-duration", "30d"],
+            clap::error::ErrorKind::ValueValidation,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backup_with_no_manifest_update_after_subcommand() {
+        check_args(
+            &["ludusavi", "backup", "--no-manifest-update"],
+            Cli {
+                config: None,
+                no_manifest_update: true,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backup {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    include_disabled: false,
+                    wine_prefix: None,
+                    exclude_store: vec![],
+                    skip_empty: false,
+                    only_new_games: false,
+                    only_registry: false,
+                    include_other_os_paths: false,
+                    interactive: false,
+                    root: vec![],
+                    roots_replace: false,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    preserve_empty_dirs: false,
+                    include_manifest_snapshot: false,
+                    include_restore_map: false,
+                    follow_symlinks: false,
+                    no_follow_symlinks: false,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    no_exclude_system_files: false,
+                    dedupe_report: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    max_backup_duration: None,
+                    resume: false,
+                    manifest_age_warning: None,
+                    sort: None,
+                    group_by: None,
+                    progress_format: None,
+                    format: None,
+                    compression: None,
+                    compression_level: None,
+                    hash_algorithm: None,
+                    backup_id_format: None,
+                    game_format: vec![],
+                    full_limit: None,
+                    differential_limit: None,
+                    game_retention: vec![],
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    force_cloud_sync: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_with_conflicting_manifest_update_flags() {
+        check_args_err(
+            &["ludusavi", "backup", "--no-manifest-update", "--try-manifest-update"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backup_with_auto_wine_prefix() {
+        check_args(
+            &["ludusavi", "backup", "--wine-prefix", "auto"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backup {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    include_disabled: false,
+                    wine_prefix: Some(CliWinePrefix::Auto),
+                    exclude_store: vec![],
+                    skip_empty: false,
+                    only_new_games: false,
+                    only_registry: false,
+                    include_other_os_paths: false,
+                    interactive: false,
+                    root: vec![],
+                    roots_replace: false,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    preserve_empty_dirs: false,
+                    include_manifest_snapshot: false,
+                    include_restore_map: false,
+                    follow_symlinks: false,
+                    no_follow_symlinks: false,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    no_exclude_system_files: false,
+                    dedupe_report: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    max_backup_duration: None,
+                    resume: false,
+                    manifest_age_warning: None,
+                    sort: None,
+                    group_by: None,
+                    progress_format: None,
+                    format: None,
+                    compression: None,
+                    compression_level: None,
+                    hash_algorithm: None,
+                    backup_id_format: None,
+                    game_format: vec![],
+                    full_limit: None,
+                    differential_limit: None,
+                    game_retention: vec![],
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    force_cloud_sync: false,
                     games: vec![],
                 }),
             },
@@ -670,9 +2355,43 @@ mod tests {
                 "--path",
                 "tests/backup",
                 "--force",
+                "--include-disabled",
                 "--wine-prefix",
                 "tests/wine-prefix",
+                "--exclude-store",
+                "heroic",
+                "--exclude-store",
+                "gogGalaxy",
+                "--root",
+                "steam:tests/root1",
+                "--roots-replace",
+                "--skip-empty",
+                "--only-new-games",
+                "--only-registry",
+                "--include-other-os-paths",
                 "--api",
+                "--summary-only",
+                "--report-to",
+                "report.json",
+                "--preserve-permissions",
+                "--preserve-empty-dirs",
+                "--include-manifest-snapshot",
+                "--include-restore-map",
+                "--no-follow-symlinks",
+                "--max-file-size",
+                "1 KiB",
+                "--global-ignore",
+                "*.log",
+                "--global-ignore",
+                "crashdumps",
+                "--retries",
+                "3",
+                "--abort-on-first-failure",
+                "--progress-format",
+                "json",
+                "--resume",
+                "--manifest-age-warning",
+                "30",
                 "--sort",
                 "name",
                 "--format",
@@ -681,11 +2400,24 @@ mod tests {
                 "bzip2",
                 "--compression-level",
                 "5",
+                "--hash-algorithm",
+                "xxh3",
+                "--backup-id-format",
+                "{date}_{tag}",
+                "--game-format",
+                "game1=zstd:19",
+                "--game-format",
+                "game=2=simple",
                 "--full-limit",
                 "1",
                 "--differential-limit",
                 "2",
+                "--game-retention",
+                "game1=5:10",
+                "--game-retention",
+                "game2=:3",
                 "--cloud-sync",
+                "--force-cloud-sync",
                 "game1",
                 "game2",
             ],
@@ -693,20 +2425,98 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Backup {
                     preview: true,
                     path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
                     force: true,
-                    wine_prefix: Some(StrictPath::relative(s("tests/wine-prefix"), Some(repo_raw()))),
+                    include_disabled: true,
+                    wine_prefix: Some(CliWinePrefix::Path(StrictPath::relative(
+                        s("tests/wine-prefix"),
+                        Some(repo_raw()),
+                    ))),
+                    exclude_store: vec![Store::Heroic, Store::GogGalaxy],
+                    skip_empty: true,
+                    only_new_games: true,
+                    only_registry: true,
+                    include_other_os_paths: true,
+                    interactive: false,
+                    root: vec![Root::new(
+                        StrictPath::relative(s("tests/root1"), Some(StrictPath::cwd().raw())),
+                        Store::Steam,
+                    )],
+                    roots_replace: true,
                     api: true,
+                    summary_only: true,
+                    report_to: Some(StrictPath::relative(s("report.json"), Some(repo_raw()))),
+                    preserve_permissions: true,
+                    preserve_empty_dirs: true,
+                    include_manifest_snapshot: true,
+                    include_restore_map: true,
+                    follow_symlinks: false,
+                    no_follow_symlinks: true,
+                    max_file_size: Some(FileSize(1024)),
+                    global_ignore: vec![s("*.log"), s("crashdumps")],
+                    no_exclude_system_files: false,
+                    dedupe_report: false,
+                    retries: 3,
+                    abort_on_first_failure: true,
+                    max_backup_duration: None,
+                    resume: true,
+                    manifest_age_warning: Some(30),
                     sort: Some(CliSort::Name),
+                    group_by: None,
+                    progress_format: Some(CliProgressFormat::Json),
                     format: Some(BackupFormat::Zip),
                     compression: Some(ZipCompression::Bzip2),
                     compression_level: Some(5),
+                    hash_algorithm: Some(HashAlgorithm::Xxh3),
+                    backup_id_format: Some(s("{date}_{tag}")),
+                    game_format: vec![
+                        (
+                            s("game1"),
+                            GameFormat {
+                                compression: Some(ZipCompression::Zstd),
+                                level: Some(19),
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            s("game=2"),
+                            GameFormat {
+                                chosen: Some(BackupFormat::Simple),
+                                ..Default::default()
+                            },
+                        ),
+                    ],
                     full_limit: Some(1),
                     differential_limit: Some(2),
+                    game_retention: vec![
+                        (
+                            s("game1"),
+                            GameRetention {
+                                full: Some(5),
+                                differential: Some(10),
+                            },
+                        ),
+                        (
+                            s("game2"),
+                            GameRetention {
+                                full: None,
+                                differential: Some(3),
+                            },
+                        ),
+                    ],
                     cloud_sync: true,
                     no_cloud_sync: false,
+                    force_cloud_sync: true,
                     games: vec![s("game1"), s("game2")],
                 }),
             },
@@ -721,20 +2531,61 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Backup {
                     preview: false,
                     path: Some(StrictPath::relative(s("tests/fake"), Some(repo_raw()))),
                     force: false,
+                    include_disabled: false,
                     wine_prefix: None,
+                    exclude_store: vec![],
+                    skip_empty: false,
+                    only_new_games: false,
+                    only_registry: false,
+                    include_other_os_paths: false,
+                    interactive: false,
+                    root: vec![],
+                    roots_replace: false,
                     api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    preserve_empty_dirs: false,
+                    include_manifest_snapshot: false,
+                    include_restore_map: false,
+                    follow_symlinks: false,
+                    no_follow_symlinks: false,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    no_exclude_system_files: false,
+                    dedupe_report: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    max_backup_duration: None,
+                    resume: false,
+                    manifest_age_warning: None,
                     sort: None,
+                    group_by: None,
+                    progress_format: None,
                     format: None,
                     compression: None,
                     compression_level: None,
+                    hash_algorithm: None,
+                    backup_id_format: None,
+                    game_format: vec![],
                     full_limit: None,
                     differential_limit: None,
+                    game_retention: vec![],
                     cloud_sync: false,
                     no_cloud_sync: false,
+                    force_cloud_sync: false,
                     games: vec![],
                 }),
             },
@@ -757,20 +2608,61 @@ mod tests {
                     config: None,
                     no_manifest_update: false,
                     try_manifest_update: false,
+                    manifest_enforce_checksum: false,
+                    manifest_extra: vec![],
+                    manifest_path: None,
+                    json_indent: None,
+                    custom_games: vec![],
+                    log_file: None,
+                    threads: None,
+                    no_color: false,
                     sub: Some(Subcommand::Backup {
                         preview: false,
                         path: None,
                         force: false,
+                        include_disabled: false,
                         wine_prefix: None,
+                        exclude_store: vec![],
+                        skip_empty: false,
+                        only_new_games: false,
+                        only_registry: false,
+                        include_other_os_paths: false,
+                        interactive: false,
+                        root: vec![],
+                        roots_replace: false,
                         api: false,
+                        summary_only: false,
+                        report_to: None,
+                        preserve_permissions: false,
+                        preserve_empty_dirs: false,
+                        include_manifest_snapshot: false,
+                        include_restore_map: false,
+                        follow_symlinks: false,
+                        no_follow_symlinks: false,
+                        max_file_size: None,
+                        global_ignore: vec![],
+                        no_exclude_system_files: false,
+                        dedupe_report: false,
+                        retries: 0,
+                        abort_on_first_failure: false,
+                        max_backup_duration: None,
+                        resume: false,
+                        manifest_age_warning: None,
                         sort: Some(sort),
+                        group_by: None,
+                        progress_format: None,
                         format: None,
                         compression: None,
                         compression_level: None,
+                        hash_algorithm: None,
+                        backup_id_format: None,
+                        game_format: vec![],
                         full_limit: None,
                         differential_limit: None,
+                        game_retention: vec![],
                         cloud_sync: false,
                         no_cloud_sync: false,
+                        force_cloud_sync: false,
                         games: vec![],
                     }),
                 },
@@ -779,25 +2671,175 @@ mod tests {
     }
 
     #[test]
-    fn accepts_cli_backup_with_negative_compression_level() {
+    fn accepts_cli_backup_with_negative_compression_level() {
+        check_args(
+            &["ludusavi", "backup", "--compression-level", "-7"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backup {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    include_disabled: false,
+                    wine_prefix: None,
+                    exclude_store: vec![],
+                    skip_empty: false,
+                    only_new_games: false,
+                    only_registry: false,
+                    include_other_os_paths: false,
+                    interactive: false,
+                    root: vec![],
+                    roots_replace: false,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    preserve_empty_dirs: false,
+                    include_manifest_snapshot: false,
+                    include_restore_map: false,
+                    follow_symlinks: false,
+                    no_follow_symlinks: false,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    no_exclude_system_files: false,
+                    dedupe_report: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    max_backup_duration: None,
+                    resume: false,
+                    manifest_age_warning: None,
+                    sort: None,
+                    group_by: None,
+                    progress_format: None,
+                    format: None,
+                    compression: None,
+                    compression_level: Some(-7),
+                    hash_algorithm: None,
+                    backup_id_format: None,
+                    game_format: vec![],
+                    full_limit: None,
+                    differential_limit: None,
+                    game_retention: vec![],
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    force_cloud_sync: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_restore_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "restore"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Restore {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    include_disabled: false,
+                    require_installed: false,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    clean: false,
+                    merge_registry: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    manifest_age_warning: None,
+                    force_different_os: false,
+                    wine_prefix: None,
+                    steam_deck: false,
+                    root: vec![],
+                    roots_replace: false,
+                    sort: None,
+                    group_by: None,
+                    progress_format: None,
+                    backup: None,
+
+                    git_commit: None,
+                    games_file: None,
+                    interactive: false,
+                    to_archive: None,
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_restore_with_games_file() {
         check_args(
-            &["ludusavi", "backup", "--compression-level", "-7"],
+            &["ludusavi", "restore", "--games-file", "tests/games-file.yaml"],
             Cli {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
-                sub: Some(Subcommand::Backup {
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Restore {
                     preview: false,
                     path: None,
                     force: false,
-                    wine_prefix: None,
+                    include_disabled: false,
+                    require_installed: false,
                     api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    clean: false,
+                    merge_registry: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    manifest_age_warning: None,
+                    force_different_os: false,
+                    wine_prefix: None,
+                    steam_deck: false,
+                    root: vec![],
+                    roots_replace: false,
                     sort: None,
-                    format: None,
-                    compression: None,
-                    compression_level: Some(-7),
-                    full_limit: None,
-                    differential_limit: None,
+                    group_by: None,
+                    progress_format: None,
+                    backup: None,
+
+                    git_commit: None,
+                    games_file: Some(StrictPath::relative(
+                        s("tests/games-file.yaml"),
+                        Some(StrictPath::cwd().raw()),
+                    )),
+                    interactive: false,
+                    to_archive: None,
                     cloud_sync: false,
                     no_cloud_sync: false,
                     games: vec![],
@@ -807,20 +2849,50 @@ mod tests {
     }
 
     #[test]
-    fn accepts_cli_restore_with_minimal_arguments() {
+    fn accepts_cli_restore_with_steam_deck() {
         check_args(
-            &["ludusavi", "restore"],
+            &["ludusavi", "restore", "--steam-deck"],
             Cli {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Restore {
                     preview: false,
                     path: None,
                     force: false,
+                    include_disabled: false,
+                    require_installed: false,
                     api: false,
+                    summary_only: false,
+                    report_to: None,
+                    preserve_permissions: false,
+                    clean: false,
+                    merge_registry: false,
+                    retries: 0,
+                    abort_on_first_failure: false,
+                    manifest_age_warning: None,
+                    force_different_os: false,
+                    wine_prefix: None,
+                    steam_deck: true,
+                    root: vec![],
+                    roots_replace: false,
                     sort: None,
+                    group_by: None,
+                    progress_format: None,
                     backup: None,
+
+                    git_commit: None,
+                    games_file: None,
+                    interactive: false,
+                    to_archive: None,
                     cloud_sync: false,
                     no_cloud_sync: false,
                     games: vec![],
@@ -839,11 +2911,30 @@ mod tests {
                 "--path",
                 "tests/backup",
                 "--force",
+                "--include-disabled",
+                "--require-installed",
                 "--api",
+                "--summary-only",
+                "--report-to",
+                "report.json",
+                "--preserve-permissions",
+                "--clean",
+                "--retries",
+                "3",
+                "--abort-on-first-failure",
+                "--progress-format",
+                "json",
+                "--manifest-age-warning",
+                "30",
+                "--force-different-os",
+                "--wine-prefix",
+                "tests/wine-prefix",
                 "--sort",
                 "name",
                 "--backup",
                 ".",
+                "--git-commit",
+                "HEAD~1",
                 "--cloud-sync",
                 "game1",
                 "game2",
@@ -852,6 +2943,14 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Restore {
                     preview: true,
                     path: Some(StrictPath::relative(
@@ -859,9 +2958,34 @@ mod tests {
                         Some(StrictPath::cwd().interpret().unwrap()),
                     )),
                     force: true,
+                    include_disabled: true,
+                    require_installed: true,
                     api: true,
+                    summary_only: true,
+                    report_to: Some(StrictPath::relative(s("report.json"), Some(repo_raw()))),
+                    preserve_permissions: true,
+                    clean: true,
+                    merge_registry: false,
+                    retries: 3,
+                    abort_on_first_failure: true,
+                    manifest_age_warning: Some(30),
+                    force_different_os: true,
+                    wine_prefix: Some(StrictPath::relative(
+                        s("tests/wine-prefix"),
+                        Some(StrictPath::cwd().interpret().unwrap()),
+                    )),
+                    steam_deck: false,
+                    root: vec![],
+                    roots_replace: false,
                     sort: Some(CliSort::Name),
+                    group_by: None,
+                    progress_format: Some(CliProgressFormat::Json),
                     backup: Some(s(".")),
+
+                    git_commit: Some(s("HEAD~1")),
+                    games_file: None,
+                    interactive: false,
+                    to_archive: None,
                     cloud_sync: true,
                     no_cloud_sync: false,
                     games: vec![s("game1"), s("game2")],
@@ -870,6 +2994,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_cli_restore_with_force_different_os_without_wine_prefix() {
+        check_args_err(
+            &["ludusavi", "restore", "--force-different-os"],
+            clap::error::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_interactive_and_api() {
+        check_args_err(
+            &["ludusavi", "restore", "--interactive", "--api"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
     #[test]
     fn rejects_cli_restore_with_nonexistent_path() {
         check_args_err(
@@ -878,6 +3018,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_cli_restore_with_non_zip_archive() {
+        check_args_err(
+            &["ludusavi", "restore", "--to-archive", "saves.tar"],
+            clap::error::ErrorKind::ValueValidation,
+        );
+    }
+
     #[test]
     fn accepts_cli_restore_with_sort_variants() {
         let cases = [
@@ -894,13 +3042,43 @@ mod tests {
                     config: None,
                     no_manifest_update: false,
                     try_manifest_update: false,
+                    manifest_enforce_checksum: false,
+                    manifest_extra: vec![],
+                    manifest_path: None,
+                    json_indent: None,
+                    custom_games: vec![],
+                    log_file: None,
+                    threads: None,
+                    no_color: false,
                     sub: Some(Subcommand::Restore {
                         preview: false,
                         path: None,
                         force: false,
+                        include_disabled: false,
+                        require_installed: false,
                         api: false,
+                        summary_only: false,
+                        report_to: None,
+                        preserve_permissions: false,
+                        clean: false,
+                        merge_registry: false,
+                        retries: 0,
+                        abort_on_first_failure: false,
+                        manifest_age_warning: None,
+                        force_different_os: false,
+                        wine_prefix: None,
+                        steam_deck: false,
+                        root: vec![],
+                        roots_replace: false,
                         sort: Some(sort),
+                        group_by: None,
+                        progress_format: None,
                         backup: None,
+
+                        git_commit: None,
+                        games_file: None,
+                        interactive: false,
+                        to_archive: None,
                         cloud_sync: false,
                         no_cloud_sync: false,
                         games: vec![],
@@ -918,6 +3096,14 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Bash,
                 }),
@@ -933,6 +3119,14 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Fish,
                 }),
@@ -948,6 +3142,14 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Zsh,
                 }),
@@ -963,6 +3165,14 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::PowerShell,
                 }),
@@ -978,6 +3188,14 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Elvish,
                 }),
@@ -985,6 +3203,175 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accepts_cli_roots_list() {
+        check_args(
+            &["ludusavi", "roots", "list", "--api"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Roots {
+                    sub: RootsSubcommand::List { api: true },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_watch() {
+        check_args(
+            &[
+                "ludusavi",
+                "watch",
+                "--path",
+                "tests/backup",
+                "--debounce",
+                "30",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Watch {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(StrictPath::cwd().raw()))),
+                    debounce: 30,
+                    wine_prefix: None,
+                    root: vec![],
+                    roots_replace: false,
+                    retries: 0,
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    games: vec![s("game1")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_config_get_and_set() {
+        check_args(
+            &["ludusavi", "config", "get", "backup.path"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Config {
+                    sub: ConfigSubcommand::Get { key: s("backup.path") },
+                }),
+            },
+        );
+        check_args(
+            &["ludusavi", "config", "set", "backup.retention.full", "2"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Config {
+                    sub: ConfigSubcommand::Set {
+                        key: s("backup.retention.full"),
+                        value: s("2"),
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_settings_export_and_import() {
+        check_args(
+            &[
+                "ludusavi",
+                "settings",
+                "export",
+                "--to",
+                "tests/settings.yaml",
+                "--include-secrets",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Settings {
+                    sub: SettingsSubcommand::Export {
+                        to: StrictPath::relative(s("tests/settings.yaml"), Some(repo_raw())),
+                        include_secrets: true,
+                    },
+                }),
+            },
+        );
+        check_args(
+            &[
+                "ludusavi",
+                "settings",
+                "import",
+                "--from",
+                "tests/settings.yaml",
+                "--force",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Settings {
+                    sub: SettingsSubcommand::Import {
+                        from: StrictPath::relative(s("tests/settings.yaml"), Some(repo_raw())),
+                        force: true,
+                    },
+                }),
+            },
+        );
+    }
+
     #[test]
     fn accepts_cli_backups_with_minimal_arguments() {
         check_args(
@@ -993,9 +3380,24 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Backups {
                     path: None,
                     api: false,
+                    summary_only: false,
+                    report_to: None,
+                    pin: None,
+                    unpin: None,
+                    since_backup: None,
+                    rename: None,
+                    preview: false,
                     games: vec![],
                 }),
             },
@@ -1018,15 +3420,232 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Backups {
                     path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
                     api: true,
+                    summary_only: false,
+                    report_to: None,
+                    pin: None,
+                    unpin: None,
+                    since_backup: None,
+                    rename: None,
+                    preview: false,
                     games: vec![s("game1"), s("game2")],
                 }),
             },
         );
     }
 
+    #[test]
+    fn accepts_cli_backups_with_pin() {
+        check_args(
+            &["ludusavi", "backups", "--pin", "backup-20240101T000000Z", "game1"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backups {
+                    path: None,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    pin: Some(s("backup-20240101T000000Z")),
+                    unpin: None,
+                    since_backup: None,
+                    rename: None,
+                    preview: false,
+                    games: vec![s("game1")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backups_with_unpin() {
+        check_args(
+            &["ludusavi", "backups", "--unpin", "backup-20240101T000000Z", "game1"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backups {
+                    path: None,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    pin: None,
+                    unpin: Some(s("backup-20240101T000000Z")),
+                    since_backup: None,
+                    rename: None,
+                    preview: false,
+                    games: vec![s("game1")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backups_with_since_backup() {
+        check_args(
+            &[
+                "ludusavi",
+                "backups",
+                "--since-backup",
+                "backup-20240101T000000Z",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backups {
+                    path: None,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    pin: None,
+                    unpin: None,
+                    since_backup: Some(s("backup-20240101T000000Z")),
+                    rename: None,
+                    preview: false,
+                    games: vec![s("game1")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backups_with_rename() {
+        check_args(
+            &["ludusavi", "backups", "--rename", "Old Name=New Name", "--preview"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Backups {
+                    path: None,
+                    api: false,
+                    summary_only: false,
+                    report_to: None,
+                    pin: None,
+                    unpin: None,
+                    since_backup: None,
+                    rename: Some((s("Old Name"), s("New Name"))),
+                    preview: true,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_export_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "export",
+                "--path",
+                "tests/backup",
+                "--to",
+                "tests/export.zip",
+                "--preview",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Export {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    to: StrictPath::relative(s("tests/export.zip"), Some(repo_raw())),
+                    preview: true,
+                    games: vec![s("game1")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_import_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "import",
+                "--from",
+                "tests/export.zip",
+                "--path",
+                "tests/backup",
+                "--preview",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Import {
+                    from: StrictPath::relative(s("tests/export.zip"), Some(repo_raw())),
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    preview: true,
+                }),
+            },
+        );
+    }
+
     #[test]
     fn accepts_cli_find_with_minimal_arguments() {
         check_args(
@@ -1035,17 +3654,31 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Find {
                     api: false,
+                    json_lines: false,
                     path: None,
                     backup: false,
                     restore: false,
                     steam_id: None,
                     gog_id: None,
                     lutris_id: None,
+                    by_path: None,
+                    root: vec![],
+                    roots_replace: false,
                     normalized: false,
                     disabled: false,
                     partial: false,
+                    manifest_language: None,
+                    show_manifest_paths: false,
                     names: vec![],
                 }),
             },
@@ -1069,9 +3702,14 @@ mod tests {
                 "102",
                 "--lutris-id",
                 "slug",
+                "--by-path",
+                "Saves/Foo",
                 "--normalized",
                 "--disabled",
                 "--partial",
+                "--manifest-language",
+                "de-DE",
+                "--show-manifest-paths",
                 "game1",
                 "game2",
             ],
@@ -1079,20 +3717,89 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
                 sub: Some(Subcommand::Find {
                     api: true,
+                    json_lines: false,
                     path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
                     backup: true,
                     restore: true,
                     steam_id: Some(101),
                     gog_id: Some(102),
                     lutris_id: Some("slug".to_string()),
+                    by_path: Some(s("Saves/Foo")),
+                    root: vec![],
+                    roots_replace: false,
                     normalized: true,
                     disabled: true,
                     partial: true,
+                    manifest_language: Some(s("de-DE")),
+                    show_manifest_paths: true,
                     names: vec![s("game1"), s("game2")],
                 }),
             },
         );
     }
+
+    #[test]
+    fn accepts_cli_find_with_json_lines_and_manifest_paths() {
+        check_args(
+            &["ludusavi", "find", "--json-lines", "--show-manifest-paths", "game1"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                manifest_enforce_checksum: false,
+                manifest_extra: vec![],
+                manifest_path: None,
+                json_indent: None,
+                custom_games: vec![],
+                log_file: None,
+                threads: None,
+                no_color: false,
+                sub: Some(Subcommand::Find {
+                    api: false,
+                    json_lines: true,
+                    path: None,
+                    backup: false,
+                    restore: false,
+                    steam_id: None,
+                    gog_id: None,
+                    lutris_id: None,
+                    by_path: None,
+                    root: vec![],
+                    roots_replace: false,
+                    normalized: false,
+                    disabled: false,
+                    partial: false,
+                    manifest_language: None,
+                    show_manifest_paths: true,
+                    names: vec![s("game1")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_find_with_manifest_paths_and_text_output() {
+        check_args_err(
+            &["ludusavi", "find", "--show-manifest-paths"],
+            clap::error::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_find_with_json_lines_and_api() {
+        check_args_err(
+            &["ludusavi", "find", "--json-lines", "--api"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
 }
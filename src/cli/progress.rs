@@ -0,0 +1,175 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::cli::parse::CliProgressFormat;
+
+#[derive(Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum ProgressEvent<'a> {
+    #[serde(rename_all = "camelCase")]
+    Started { total_games: u64 },
+    #[serde(rename_all = "camelCase")]
+    GameStarted { game: &'a str, step: usize },
+    /// `processed` is false when the game was skipped without scanning or processing it.
+    #[serde(rename_all = "camelCase")]
+    GameFinished {
+        game: &'a str,
+        step: usize,
+        processed: bool,
+        failed: bool,
+        bytes: u64,
+        completed_games: u64,
+        total_games: u64,
+        processed_bytes: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Finished {
+        completed_games: u64,
+        total_games: u64,
+        processed_bytes: u64,
+    },
+}
+
+/// Progress for `--progress-format json`,
+/// printed to stderr as one line of JSON per event.
+#[derive(Debug)]
+pub struct ProgressEvents {
+    total_games: u64,
+    completed_games: AtomicU64,
+    processed_bytes: AtomicU64,
+}
+
+impl ProgressEvents {
+    pub fn new(format: CliProgressFormat, total_games: u64) -> Option<Self> {
+        match format {
+            CliProgressFormat::Bar => None,
+            CliProgressFormat::Json => {
+                let events = Self {
+                    total_games,
+                    completed_games: AtomicU64::new(0),
+                    processed_bytes: AtomicU64::new(0),
+                };
+                events.emit(&ProgressEvent::Started { total_games });
+                Some(events)
+            }
+        }
+    }
+
+    fn emit(&self, event: &ProgressEvent) {
+        eprintln!("{}", crate::serialization::to_json_string(event, 0));
+    }
+
+    fn finish_game<'a>(&self, step: &ProgressStep<'a>) -> ProgressEvent<'a> {
+        let completed_games = self.completed_games.fetch_add(1, Ordering::Relaxed) + 1;
+        let processed_bytes = self.processed_bytes.fetch_add(step.bytes, Ordering::Relaxed) + step.bytes;
+
+        ProgressEvent::GameFinished {
+            game: step.game,
+            step: step.step,
+            processed: step.processed,
+            failed: step.failed,
+            bytes: step.bytes,
+            completed_games,
+            total_games: self.total_games,
+            processed_bytes,
+        }
+    }
+
+    pub fn finish(&self) {
+        self.emit(&ProgressEvent::Finished {
+            completed_games: self.completed_games.load(Ordering::Relaxed),
+            total_games: self.total_games,
+            processed_bytes: self.processed_bytes.load(Ordering::Relaxed),
+        });
+    }
+}
+
+/// Tracks one game in the operation loop.
+/// The game's finished event is emitted when this is dropped,
+/// so that games which return early are still counted.
+pub struct ProgressStep<'a> {
+    events: Option<&'a ProgressEvents>,
+    game: &'a str,
+    step: usize,
+    processed: bool,
+    failed: bool,
+    bytes: u64,
+}
+
+impl<'a> ProgressStep<'a> {
+    pub fn new(events: Option<&'a ProgressEvents>, step: usize, game: &'a str) -> Self {
+        if let Some(events) = events {
+            events.emit(&ProgressEvent::GameStarted { game, step });
+        }
+
+        Self {
+            events,
+            game,
+            step,
+            processed: false,
+            failed: false,
+            bytes: 0,
+        }
+    }
+
+    pub fn record(&mut self, bytes: u64, failed: bool) {
+        self.processed = true;
+        self.bytes = bytes;
+        self.failed = failed;
+    }
+}
+
+impl Drop for ProgressStep<'_> {
+    fn drop(&mut self) {
+        if let Some(events) = self.events {
+            let event = events.finish_game(self);
+            events.emit(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn can_count_finished_games() {
+        let events = ProgressEvents {
+            total_games: 3,
+            completed_games: AtomicU64::new(0),
+            processed_bytes: AtomicU64::new(0),
+        };
+
+        let mut step = ProgressStep {
+            events: None,
+            game: "game1",
+            step: 0,
+            processed: false,
+            failed: false,
+            bytes: 0,
+        };
+        step.record(100, false);
+        assert_eq!(
+            ProgressEvent::GameFinished {
+                game: "game1",
+                step: 0,
+                processed: true,
+                failed: false,
+                bytes: 100,
+                completed_games: 1,
+                total_games: 3,
+                processed_bytes: 100,
+            },
+            events.finish_game(&step),
+        );
+
+        step.game = "game2";
+        step.step = 1;
+        step.record(50, true);
+        assert_eq!(
+            r#"{"event":"gameFinished","game":"game2","step":1,"processed":true,"failed":true,"bytes":50,"completedGames":2,"totalGames":3,"processedBytes":150}"#,
+            crate::serialization::to_json_string(&events.finish_game(&step), 0),
+        );
+    }
+}
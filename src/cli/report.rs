@@ -1,18 +1,24 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use itertools::Itertools;
 
 use crate::{
-    cloud::CloudChange,
+    cloud::{CloudChange, CloudSize, CloudStats},
     lang::TRANSLATOR,
-    prelude::StrictPath,
-    resource::manifest::Os,
+    prelude::{Error, StrictPath},
+    resource::{
+        config::{FileSize, RootSource},
+        manifest::{Manifest, ManifestDiff, Os, Store},
+    },
     scan::{
-        layout::Backup, BackupError, BackupInfo, DuplicateDetector, OperationStatus, OperationStepDecision, ScanChange,
-        ScanInfo,
+        layout::Backup, BackupError, BackupInfo, DuplicateDetector, Duplication, IdenticalFiles, OperationStatus,
+        OperationStepDecision, RestoreImpact, ScanChange, ScanInfo, StoreGroup,
     },
 };
 
+/// Spaces per indentation level in `--api` output, unless overridden by `--json-indent`.
+pub const DEFAULT_JSON_INDENT: usize = 2;
+
 #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiErrors {
@@ -82,6 +88,9 @@ struct ApiFile {
     /// Whether this entry was ignored.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     ignored: bool,
+    /// Whether this entry was ignored because it is larger than `backup.maxFileSize`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    oversized: bool,
     /// How this item compares to its previous backup (if doing a new backup)
     /// or how its previous backup compares to the current system state (if doing a restore).
     change: ScanChange,
@@ -98,6 +107,27 @@ struct ApiFile {
     /// Any other games that also have the same file path.
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     duplicated_by: BTreeSet<String>,
+    /// If other games also have the same file path, but only one of them will process it,
+    /// then this is how that was decided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicate_resolution: Option<DuplicateResolution>,
+    /// If the `backup.duplicateOwners` config assigns this file to a game,
+    /// then this is that game.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicate_owner: Option<String>,
+    /// If the file came from a save location for a different operating system,
+    /// then this is that operating system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    other_os: Option<Os>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+enum DuplicateResolution {
+    /// The file was assigned to a game by the `backup.duplicateOwners` config.
+    Rule,
+    /// The other games ignored the file or did not have any changes for it.
+    Automatic,
 }
 
 #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
@@ -120,6 +150,10 @@ struct ApiRegistry {
     /// Any registry values inside of the registry key.
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     values: BTreeMap<String, ApiRegistryValue>,
+    /// With `restore --merge-registry`, the values already under the registry key
+    /// that are not in the backup, which restoring leaves as-is.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    preserved_values: BTreeSet<String>,
 }
 
 #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
@@ -150,15 +184,89 @@ enum ApiGame {
         files: BTreeMap<String, ApiFile>,
         /// Each key is a registry path.
         registry: BTreeMap<String, ApiRegistry>,
+        /// How many files were skipped by `backup.globalIgnore` or `--global-ignore`.
+        #[serde(rename = "globallyIgnored", skip_serializing_if = "Option::is_none")]
+        globally_ignored: Option<usize>,
+        /// How many files were skipped by `backup.excludeSystemFiles`.
+        #[serde(rename = "systemFilesExcluded", skip_serializing_if = "Option::is_none")]
+        system_files_excluded: Option<usize>,
+        /// In restore preview mode, the files currently on disk that would be replaced.
+        #[serde(default, rename = "wouldOverwrite", skip_serializing_if = "Vec::is_empty")]
+        would_overwrite: Vec<ApiOverwrite>,
+        /// In restore preview mode, the files currently in the game's save locations
+        /// that are not part of the backup.
+        #[serde(default, rename = "wouldOrphan", skip_serializing_if = "Vec::is_empty")]
+        would_orphan: Vec<String>,
+        /// With `restore --clean`, the files that were deleted
+        /// because they are not part of the backup.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        cleaned: Vec<String>,
+        /// With `restore --steam-deck`, the Proton prefix that the game was restored into.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefix: Option<String>,
+        /// With `restore --games-file`, the backup that the game was restored from.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        backup: Option<String>,
+        /// The game's note from the config file.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
     },
     /// Used by the `backups` command.
     Stored {
         #[serde(rename = "backupPath")]
         backup_path: String,
         backups: Vec<ApiBackup>,
+        /// How many bytes are used on disk by all of this game's backups.
+        /// Content shared between backups is only counted once.
+        #[serde(rename = "diskUsage")]
+        disk_usage: u64,
+        /// The game's note from the config file.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
     },
     /// Used by the `find` command.
-    Found {},
+    Found {
+        /// With `--by-path`, the expanded save paths that matched.
+        #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+        matched_paths: BTreeSet<String>,
+        /// With `--manifest-language`, the game's title in that language.
+        /// This is omitted if the manifest has no different title for the language.
+        #[serde(default, rename = "localizedTitle", skip_serializing_if = "Option::is_none")]
+        localized_title: Option<String>,
+        /// With `--show-manifest-paths`, the game's file paths as written in the manifest,
+        /// before expanding any placeholders.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        paths: Option<Vec<String>>,
+        /// With `--show-manifest-paths`, the game's registry keys as written in the manifest.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        registry: Option<Vec<String>>,
+    },
+    /// Used by the `manifest list` command.
+    Listed {
+        #[serde(rename = "steamId", skip_serializing_if = "Option::is_none")]
+        steam_id: Option<u32>,
+        #[serde(rename = "gogId", skip_serializing_if = "Option::is_none")]
+        gog_id: Option<u64>,
+        /// Whether this is a custom game.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        custom: bool,
+    },
+}
+
+/// A game reported by the `manifest list` command.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListedGame {
+    pub steam_id: Option<u32>,
+    pub gog_id: Option<u64>,
+    pub custom: bool,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ApiOverwrite {
+    path: String,
+    /// Current size of the file on disk.
+    bytes: u64,
 }
 
 #[derive(Debug, serde::Serialize, schemars::JsonSchema)]
@@ -171,6 +279,44 @@ struct ApiBackup {
     #[serde(skip_serializing_if = "Option::is_none")]
     comment: Option<String>,
     pub locked: bool,
+    kind: ApiBackupKind,
+    /// For differential backups, the name of the full backup that it builds upon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    based_on: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum ApiBackupKind {
+    Full,
+    Differential,
+}
+
+/// Aggregate stats, populated in `--summary-only` mode.
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSummary {
+    /// How many games were reported.
+    games: usize,
+    /// How many games failed to process.
+    failed_games: usize,
+    /// How many backups were found, populated by the `backups` command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backups: Option<usize>,
+    /// How many bytes are used on disk by the backups,
+    /// populated by the `backups` command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disk_usage: Option<u64>,
+    /// The blobs already included in `disk_usage`, for each game's backup folder,
+    /// so that they aren't counted again if the same folder is reported twice.
+    #[serde(skip)]
+    counted_blobs: BTreeMap<String, HashSet<String>>,
+    /// How many files were changed by cloud synchronization,
+    /// populated when a synchronization ran.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cloud_changes: Option<usize>,
+    /// How long the operation took, in seconds.
+    duration: f64,
 }
 
 /// General output used by commands in `--api` mode
@@ -183,12 +329,66 @@ pub struct JsonOutput {
     /// Overall stats, populated by the `backup` and `restore` commands.
     #[serde(skip_serializing_if = "Option::is_none")]
     overall: Option<OperationStatus>,
+    /// Aggregate stats, populated in `--summary-only` mode.
+    /// When this is present, the `games` field is left empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<ApiSummary>,
+    /// Names of games that only succeeded after being retried,
+    /// populated by the `--retries` option.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    retried_games: BTreeSet<String>,
+    /// Names of games that are disabled in the config file,
+    /// but were processed anyway because of the `--include-disabled` option.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    disabled_games: BTreeSet<String>,
+    /// Names of games that were skipped because they don't appear to be installed,
+    /// populated by the `--require-installed` option.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    uninstalled_games: BTreeSet<String>,
+    /// Names of games that were still in progress when the `--max-backup-duration` limit was reached.
+    /// If they were still being scanned, then they were not backed up;
+    /// if they were already being backed up, then that backup was allowed to finish.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    incomplete_games: BTreeSet<String>,
+    /// Content that is byte-identical across different games,
+    /// populated by the `--dedupe-report` option.
+    /// This is sorted by wasted space, largest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    identical_files: Vec<IdenticalFiles>,
     /// Each key is the name of a game.
     games: BTreeMap<String, ApiGame>,
+    /// With `--group-by store`, this is populated instead of `games`.
+    /// Each key is the store where the games were detected,
+    /// or `custom`/`unknown` for games that were not detected in any store.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    stores: BTreeMap<String, ApiStoreGroup>,
     /// Each key is the path of a file relative to the cloud folder.
     /// Populated by the `cloud` commands.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     cloud: BTreeMap<String, CloudEntry>,
+    /// Transfer totals reported by Rclone.
+    /// Populated by the `cloud` commands.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cloud_stats: Option<CloudStats>,
+    /// Each key is a cloud folder that was deleted.
+    /// Populated by the `cloud purge` command.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    cloud_purged: BTreeMap<String, CloudSize>,
+    /// Populated by the `roots list` command.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    roots: Vec<ApiRoot>,
+    /// Populated by the `manifest diff` command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manifest_diff: Option<ManifestDiff>,
+}
+
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ApiStoreGroup {
+    /// Subtotals for the games in this group.
+    overall: OperationStatus,
+    /// Each key is the name of a game.
+    games: BTreeMap<String, ApiGame>,
 }
 
 #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
@@ -199,15 +399,59 @@ struct CloudEntry {
     change: ScanChange,
 }
 
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRoot {
+    pub path: String,
+    pub store: Store,
+    pub source: RootSource,
+    /// Whether the root's folder currently exists.
+    pub exists: bool,
+    /// How many games had any data found within this root.
+    pub games: usize,
+}
+
+/// Lines and subtotals for one group of games in the standard reporter.
+#[derive(Debug, Default)]
+pub struct StandardStoreGroup {
+    parts: Vec<String>,
+    status: OperationStatus,
+}
+
+/// Extra information about each game that both kinds of reporter show alongside its scan.
+/// This is looked up when the game itself is added,
+/// so it needs to be recorded before calling `add_game` or `add_backups`.
+#[derive(Debug, Default)]
+pub struct GameDetails {
+    restore_impacts: BTreeMap<String, RestoreImpact>,
+    restore_prefixes: BTreeMap<String, String>,
+    restore_backups: BTreeMap<String, String>,
+    game_notes: BTreeMap<String, String>,
+    /// Only set when grouping by store.
+    game_stores: Option<BTreeMap<String, StoreGroup>>,
+}
+
 #[derive(Debug)]
 pub enum Reporter {
     Standard {
         parts: Vec<String>,
         status: Option<OperationStatus>,
         errors: ApiErrors,
+        summary: Option<ApiSummary>,
+        retried_games: BTreeSet<String>,
+        disabled_games: BTreeSet<String>,
+        uninstalled_games: BTreeSet<String>,
+        incomplete_games: BTreeSet<String>,
+        identical_files: Vec<IdenticalFiles>,
+        max_file_size: Option<u64>,
+        details: GameDetails,
+        store_groups: BTreeMap<StoreGroup, StandardStoreGroup>,
     },
     Json {
         output: JsonOutput,
+        indent: usize,
+        max_file_size: Option<u64>,
+        details: GameDetails,
     },
 }
 
@@ -217,6 +461,15 @@ impl Reporter {
             parts: vec![],
             status: Some(Default::default()),
             errors: Default::default(),
+            summary: None,
+            retried_games: Default::default(),
+            disabled_games: Default::default(),
+            uninstalled_games: Default::default(),
+            incomplete_games: Default::default(),
+            identical_files: Default::default(),
+            max_file_size: None,
+            details: Default::default(),
+            store_groups: Default::default(),
         }
     }
 
@@ -225,16 +478,217 @@ impl Reporter {
             output: JsonOutput {
                 errors: Default::default(),
                 overall: Some(Default::default()),
+                summary: None,
+                retried_games: Default::default(),
+                disabled_games: Default::default(),
+                uninstalled_games: Default::default(),
+                incomplete_games: Default::default(),
+                identical_files: Default::default(),
                 games: Default::default(),
+                stores: Default::default(),
                 cloud: Default::default(),
+                cloud_stats: None,
+                cloud_purged: Default::default(),
+                roots: Default::default(),
+                manifest_diff: None,
             },
+            indent: DEFAULT_JSON_INDENT,
+            max_file_size: None,
+            details: Default::default(),
+        }
+    }
+
+    /// Only report the aggregate stats instead of each game.
+    pub fn with_summary_only(mut self, summary_only: bool) -> Self {
+        if summary_only {
+            match &mut self {
+                Self::Standard { summary, .. } => *summary = Some(Default::default()),
+                Self::Json { output, .. } => output.summary = Some(Default::default()),
+            }
+        }
+        self
+    }
+
+    /// Group games by the store where they were detected, with subtotals for each group.
+    pub fn with_group_by_store(mut self, group_by_store: bool) -> Self {
+        if group_by_store {
+            self.details_mut().game_stores = Some(Default::default());
+        }
+        self
+    }
+
+    fn summary_mut(&mut self) -> Option<&mut ApiSummary> {
+        match self {
+            Self::Standard { summary, .. } => summary.as_mut(),
+            Self::Json { output, .. } => output.summary.as_mut(),
+        }
+    }
+
+    pub fn add_cloud_changes(&mut self, changes: &[CloudChange]) {
+        if let Some(summary) = self.summary_mut() {
+            *summary.cloud_changes.get_or_insert(0) += changes.len();
+        }
+    }
+
+    pub fn add_retried_game(&mut self, name: &str) {
+        match self {
+            Self::Standard { retried_games, .. } => retried_games.insert(name.to_string()),
+            Self::Json { output, .. } => output.retried_games.insert(name.to_string()),
+        };
+    }
+
+    pub fn add_disabled_game(&mut self, name: &str) {
+        match self {
+            Self::Standard { disabled_games, .. } => disabled_games.insert(name.to_string()),
+            Self::Json { output, .. } => output.disabled_games.insert(name.to_string()),
+        };
+    }
+
+    pub fn set_uninstalled_games(&mut self, games: BTreeSet<String>) {
+        match self {
+            Self::Standard { uninstalled_games, .. } => *uninstalled_games = games,
+            Self::Json { output, .. } => output.uninstalled_games = games,
+        }
+    }
+
+    pub fn add_incomplete_game(&mut self, name: &str) {
+        match self {
+            Self::Standard { incomplete_games, .. } => incomplete_games.insert(name.to_string()),
+            Self::Json { output, .. } => output.incomplete_games.insert(name.to_string()),
+        };
+    }
+
+    pub fn set_identical_files(&mut self, identical: Vec<IdenticalFiles>) {
+        match self {
+            Self::Standard { identical_files, .. } => *identical_files = identical,
+            Self::Json { output, .. } => output.identical_files = identical,
+        }
+    }
+
+    pub fn set_manifest_age_days(&mut self, days: i64) {
+        if let Self::Json { output, .. } = self {
+            if let Some(overall) = output.overall.as_mut() {
+                overall.manifest_age_days = Some(days);
+            }
+        }
+    }
+
+    pub fn set_empty_games(&mut self, empty_games: usize) {
+        let status = match self {
+            Self::Standard { status, .. } => status.as_mut(),
+            Self::Json { output, .. } => output.overall.as_mut(),
+        };
+        if let Some(status) = status {
+            status.empty_games = Some(empty_games);
+        }
+    }
+
+    /// Note that save files were intentionally skipped.
+    pub fn set_registry_only(&mut self) {
+        let status = match self {
+            Self::Standard { status, .. } => status.as_mut(),
+            Self::Json { output, .. } => output.overall.as_mut(),
+        };
+        if let Some(status) = status {
+            status.registry_only = true;
+        }
+    }
+
+    /// Note that cloud synchronization was skipped because nothing changed.
+    pub fn set_cloud_sync_skipped(&mut self) {
+        let status = match self {
+            Self::Standard { status, .. } => status.as_mut(),
+            Self::Json { output, .. } => output.overall.as_mut(),
+        };
+        if let Some(status) = status {
+            status.cloud_sync_skipped = true;
+        }
+    }
+
+    fn details_mut(&mut self) -> &mut GameDetails {
+        match self {
+            Self::Standard { details, .. } | Self::Json { details, .. } => details,
+        }
+    }
+
+    /// Show how a restore would affect the files on disk.
+    /// This must be called before `add_game` for the same game.
+    pub fn add_restore_impact(&mut self, name: &str, impact: RestoreImpact) {
+        if impact.is_empty() {
+            return;
+        }
+        self.details_mut().restore_impacts.insert(name.to_string(), impact);
+    }
+
+    /// Show which prefix a game was restored into.
+    /// This must be called before `add_game` for the same game.
+    pub fn add_restore_prefix(&mut self, name: &str, prefix: &StrictPath) {
+        self.details_mut()
+            .restore_prefixes
+            .insert(name.to_string(), prefix.render());
+    }
+
+    /// Show which backup a game was restored from.
+    /// This must be called before `add_game` for the same game.
+    pub fn add_restore_backup(&mut self, name: &str, backup: &str) {
+        self.details_mut()
+            .restore_backups
+            .insert(name.to_string(), backup.to_string());
+    }
+
+    /// Show the note from the config for a game.
+    /// This must be called before `add_game` or `add_backups` for the same game.
+    pub fn add_game_note(&mut self, name: &str, note: &str) {
+        self.details_mut().game_notes.insert(name.to_string(), note.to_string());
+    }
+
+    /// Set which store a game should be grouped under.
+    /// This has no effect unless grouping by store.
+    /// This must be called before `add_game` for the same game.
+    pub fn add_game_store(&mut self, name: &str, group: StoreGroup) {
+        if let Some(game_stores) = &mut self.details_mut().game_stores {
+            game_stores.insert(name.to_string(), group);
+        }
+    }
+
+    pub fn set_excluded_stores(&mut self, excluded_stores: BTreeMap<Store, usize>) {
+        if excluded_stores.is_empty() {
+            return;
+        }
+        if let Self::Json { output, .. } = self {
+            if let Some(overall) = output.overall.as_mut() {
+                overall.excluded_stores = excluded_stores;
+            }
+        }
+    }
+
+    /// Note files that were skipped for exceeding this size.
+    pub fn set_max_file_size(&mut self, limit: Option<FileSize>) {
+        match self {
+            Self::Standard { max_file_size, .. } | Self::Json { max_file_size, .. } => {
+                *max_file_size = limit.map(|FileSize(x)| x);
+            }
+        }
+    }
+
+    pub fn set_duration(&mut self, duration: std::time::Duration) {
+        if let Some(summary) = self.summary_mut() {
+            summary.duration = duration.as_secs_f64();
+        }
+    }
+
+    /// Override the JSON indentation. This has no effect on the standard reporter.
+    pub fn with_json_indent(mut self, json_indent: Option<usize>) -> Self {
+        if let (Self::Json { indent, .. }, Some(json_indent)) = (&mut self, json_indent) {
+            *indent = json_indent;
         }
+        self
     }
 
     fn set_errors(&mut self, f: impl FnOnce(&mut ApiErrors)) {
         match self {
             Reporter::Standard { errors, .. } => f(errors),
-            Reporter::Json { output } => {
+            Reporter::Json { output, .. } => {
                 if let Some(errors) = &mut output.errors.as_mut() {
                     f(errors)
                 } else {
@@ -297,7 +751,22 @@ impl Reporter {
         let restoring = scan_info.restoring();
 
         match self {
-            Self::Standard { parts, status, .. } => {
+            Self::Standard {
+                parts,
+                status,
+                max_file_size,
+                details:
+                    GameDetails {
+                        restore_impacts,
+                        restore_prefixes,
+                        restore_backups,
+                        game_notes,
+                        game_stores,
+                    },
+                store_groups,
+                ..
+            } => {
+                let start = parts.len();
                 parts.push(TRANSLATOR.cli_game_header(
                     name,
                     scan_info.sum_bytes(Some(backup_info)),
@@ -327,6 +796,18 @@ impl Reporter {
                         }
                     }
 
+                    if let Some(owner) = duplicate_detector.file_owner(entry) {
+                        parts.push(TRANSLATOR.cli_game_line_item_duplicate_owner(owner));
+                    }
+
+                    if let Some(os) = scan_info.other_os(entry) {
+                        parts.push(TRANSLATOR.cli_game_line_item_other_os(os));
+                    }
+
+                    if entry.ignored && max_file_size.is_some_and(|limit| entry.size > limit) {
+                        parts.push(TRANSLATOR.cli_game_line_item_oversized(entry.size));
+                    }
+
                     if let Some(error) = backup_info.failed_files.get(entry) {
                         parts.push(TRANSLATOR.cli_game_line_item_error(error));
                     }
@@ -363,6 +844,46 @@ impl Reporter {
                             ),
                         );
                     }
+
+                    if let Some(preserved) = scan_info.preserved_registry_values.get(&entry.path) {
+                        for value_name in preserved {
+                            parts.push(TRANSLATOR.cli_game_line_item_preserved(value_name));
+                        }
+                    }
+                }
+
+                if scan_info.globally_ignored > 0 {
+                    parts.push(TRANSLATOR.cli_game_globally_ignored(scan_info.globally_ignored));
+                }
+
+                if scan_info.system_files_excluded > 0 {
+                    parts.push(TRANSLATOR.cli_game_system_files_excluded(scan_info.system_files_excluded));
+                }
+
+                if let Some(backup) = restore_backups.get(&scan_info.game_name) {
+                    parts.push(TRANSLATOR.cli_game_restored_from_backup(backup));
+                }
+
+                if let Some(note) = game_notes.get(&scan_info.game_name) {
+                    parts.push(TRANSLATOR.cli_game_note(note));
+                }
+
+                if let Some(prefix) = restore_prefixes.get(&scan_info.game_name) {
+                    parts.push(TRANSLATOR.cli_game_restored_into_prefix(prefix));
+                }
+
+                if let Some(impact) = restore_impacts.get(&scan_info.game_name) {
+                    if !impact.overwritten.is_empty() {
+                        parts.push(
+                            TRANSLATOR.cli_game_would_overwrite(impact.overwritten.len(), impact.overwritten_bytes()),
+                        );
+                    }
+                    if !impact.orphaned.is_empty() {
+                        parts.push(TRANSLATOR.cli_game_would_orphan(&impact.orphaned));
+                    }
+                    if !impact.cleaned.is_empty() {
+                        parts.push(TRANSLATOR.cli_game_cleaned(&impact.cleaned));
+                    }
                 }
 
                 // Blank line between games.
@@ -375,8 +896,34 @@ impl Reporter {
                         decision == &OperationStepDecision::Processed,
                     );
                 }
+
+                if let Some(game_stores) = game_stores {
+                    let group = game_stores
+                        .get(&scan_info.game_name)
+                        .copied()
+                        .unwrap_or(StoreGroup::Unknown);
+                    let group = store_groups.entry(group).or_default();
+                    group.parts.extend(parts.drain(start..));
+                    group.status.add_game(
+                        scan_info,
+                        &Some(backup_info.clone()),
+                        decision == &OperationStepDecision::Processed,
+                    );
+                }
             }
-            Self::Json { output } => {
+            Self::Json {
+                output,
+                max_file_size,
+                details:
+                    GameDetails {
+                        restore_impacts,
+                        restore_prefixes,
+                        restore_backups,
+                        game_notes,
+                        game_stores,
+                    },
+                ..
+            } => {
                 let decision = decision.clone();
                 let mut files = BTreeMap::new();
                 let mut registry = BTreeMap::new();
@@ -387,13 +934,26 @@ impl Reporter {
                         failed: backup_info.failed_files.contains_key(entry),
                         error: backup_info.failed_files.get(entry).map(SaveError::from),
                         ignored: entry.ignored,
+                        oversized: entry.ignored && max_file_size.is_some_and(|limit| entry.size > limit),
                         change: entry.change(),
                         ..Default::default()
                     };
-                    if !duplicate_detector.is_file_duplicated(entry).resolved() {
-                        let mut duplicated_by: BTreeSet<_> = duplicate_detector.file(entry).into_keys().collect();
-                        duplicated_by.remove(&scan_info.game_name);
-                        api_file.duplicated_by = duplicated_by;
+                    match duplicate_detector.is_file_duplicated(entry) {
+                        Duplication::Unique => {}
+                        Duplication::Resolved => {
+                            let owner = duplicate_detector.file_owner(entry);
+                            api_file.duplicate_resolution = Some(if owner.is_some() {
+                                DuplicateResolution::Rule
+                            } else {
+                                DuplicateResolution::Automatic
+                            });
+                            api_file.duplicate_owner = owner.map(|x| x.to_string());
+                        }
+                        Duplication::Duplicate => {
+                            let mut duplicated_by: BTreeSet<_> = duplicate_detector.file(entry).into_keys().collect();
+                            duplicated_by.remove(&scan_info.game_name);
+                            api_file.duplicated_by = duplicated_by;
+                        }
                     }
 
                     if let Some(alt) = entry.alt_readable(restoring) {
@@ -403,6 +963,7 @@ impl Reporter {
                             api_file.redirected_path = Some(alt);
                         }
                     }
+                    api_file.other_os = scan_info.other_os(entry);
                     if api_file.failed {
                         successful = false;
                     }
@@ -443,6 +1004,11 @@ impl Reporter {
                                 )
                             })
                             .collect(),
+                        preserved_values: scan_info
+                            .preserved_registry_values
+                            .get(&entry.path)
+                            .cloned()
+                            .unwrap_or_default(),
                         ..Default::default()
                     };
                     if !duplicate_detector.is_registry_duplicated(&entry.path).resolved() {
@@ -466,15 +1032,60 @@ impl Reporter {
                         decision == OperationStepDecision::Processed,
                     );
                 }
-                output.games.insert(
-                    scan_info.game_name.clone(),
-                    ApiGame::Operative {
-                        decision,
-                        change: scan_info.overall_change(),
-                        files,
-                        registry,
-                    },
-                );
+                let impact = restore_impacts.get(&scan_info.game_name);
+                let games = match game_stores {
+                    Some(game_stores) => {
+                        let group = game_stores
+                            .get(&scan_info.game_name)
+                            .copied()
+                            .unwrap_or(StoreGroup::Unknown);
+                        let group = output.stores.entry(group.key()).or_default();
+                        group.overall.add_game(
+                            scan_info,
+                            &Some(backup_info.clone()),
+                            decision == OperationStepDecision::Processed,
+                        );
+                        &mut group.games
+                    }
+                    None => &mut output.games,
+                };
+                if output.summary.is_none() {
+                    games.insert(
+                        scan_info.game_name.clone(),
+                        ApiGame::Operative {
+                            decision,
+                            change: scan_info.overall_change(),
+                            files,
+                            registry,
+                            globally_ignored: (scan_info.globally_ignored > 0).then_some(scan_info.globally_ignored),
+                            system_files_excluded: (scan_info.system_files_excluded > 0)
+                                .then_some(scan_info.system_files_excluded),
+                            would_overwrite: impact
+                                .map(|x| {
+                                    x.overwritten
+                                        .iter()
+                                        .map(|(path, bytes)| ApiOverwrite {
+                                            path: path.clone(),
+                                            bytes: *bytes,
+                                        })
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                            would_orphan: impact.map(|x| x.orphaned.iter().cloned().collect()).unwrap_or_default(),
+                            cleaned: impact.map(|x| x.cleaned.iter().cloned().collect()).unwrap_or_default(),
+                            prefix: restore_prefixes.get(&scan_info.game_name).cloned(),
+                            backup: restore_backups.get(&scan_info.game_name).cloned(),
+                            note: game_notes.get(&scan_info.game_name).cloned(),
+                        },
+                    );
+                }
+            }
+        }
+
+        if let Some(summary) = self.summary_mut() {
+            summary.games += 1;
+            if !successful {
+                summary.failed_games += 1;
             }
         }
 
@@ -491,15 +1102,38 @@ impl Reporter {
         backup_dir: StrictPath,
         available_backups: &[Backup],
     ) {
+        let disk_usage = Backup::disk_usage(available_backups);
+
+        if let Some(summary) = self.summary_mut() {
+            if !available_backups.is_empty() {
+                summary.games += 1;
+                *summary.backups.get_or_insert(0) += available_backups.len();
+                let counted_blobs = summary.counted_blobs.entry(backup_dir.render()).or_default();
+                *summary.disk_usage.get_or_insert(0) += Backup::disk_usage_with_blobs(available_backups, counted_blobs);
+            }
+        }
+
         match self {
-            Self::Standard { parts, .. } => {
+            Self::Standard {
+                parts,
+                details: GameDetails { game_notes, .. },
+                ..
+            } => {
                 if available_backups.is_empty() {
                     return;
                 }
 
                 parts.push(format!("{}:", display_title));
                 parts.push(format!("  {} {}", TRANSLATOR.folder_label(), backup_dir.render()));
-                for backup in available_backups {
+                if let Some(note) = game_notes.get(name) {
+                    parts.push(format!("  {} {}", TRANSLATOR.note_label(), note));
+                }
+                parts.push(format!(
+                    "  {} {}",
+                    TRANSLATOR.disk_usage_label(),
+                    TRANSLATOR.adjusted_size(disk_usage)
+                ));
+                for (backup, based_on) in available_backups.iter().zip(Self::backup_parents(available_backups)) {
                     let mut line = format!(
                         "  - \"{}\" ({})",
                         backup.name(),
@@ -508,6 +1142,9 @@ impl Reporter {
                     if let Some(os) = backup.os() {
                         line += &format!(" [{os:?}]");
                     }
+                    if let Some(based_on) = based_on {
+                        line += &format!(" [{} \"{}\"]", TRANSLATOR.based_on_label(), based_on);
+                    }
                     if backup.locked() {
                         line += " [🔒]";
                     }
@@ -520,62 +1157,227 @@ impl Reporter {
                 // Blank line between games.
                 parts.push("".to_string());
             }
-            Self::Json { output } => {
+            Self::Json {
+                output,
+                details: GameDetails { game_notes, .. },
+                ..
+            } => {
                 if available_backups.is_empty() {
                     return;
                 }
 
                 let mut backups = vec![];
-                for backup in available_backups {
+                for (backup, based_on) in available_backups.iter().zip(Self::backup_parents(available_backups)) {
                     backups.push(ApiBackup {
                         name: backup.name().to_string(),
                         when: *backup.when(),
                         os: backup.os(),
                         comment: backup.comment().cloned(),
                         locked: backup.locked(),
+                        kind: match backup {
+                            Backup::Full(_) => ApiBackupKind::Full,
+                            Backup::Differential(_) => ApiBackupKind::Differential,
+                        },
+                        based_on: based_on.map(|x| x.to_string()),
                     });
                 }
 
-                output.games.insert(
-                    name.to_string(),
-                    ApiGame::Stored {
-                        backup_path: backup_dir.render(),
-                        backups,
-                    },
-                );
+                if output.summary.is_none() {
+                    output.games.insert(
+                        name.to_string(),
+                        ApiGame::Stored {
+                            backup_path: backup_dir.render(),
+                            backups,
+                            disk_usage,
+                            note: game_notes.get(name).cloned(),
+                        },
+                    );
+                }
             }
         }
     }
 
-    pub fn add_found_titles(&mut self, names: &BTreeSet<String>) {
+    /// The backups are flattened with each full backup followed by its differential children,
+    /// so each differential backup is based on the most recent full backup before it.
+    fn backup_parents(backups: &[Backup]) -> Vec<Option<&str>> {
+        let mut parent = None;
+        backups
+            .iter()
+            .map(|backup| match backup {
+                Backup::Full(x) => {
+                    parent = Some(x.name.as_str());
+                    None
+                }
+                Backup::Differential(_) => parent,
+            })
+            .collect()
+    }
+
+    /// `localized` maps game names to their titles from `--manifest-language`.
+    pub fn add_found_titles(&mut self, names: &BTreeSet<String>, localized: &BTreeMap<String, String>) {
         match self {
             Self::Standard { parts, .. } => {
                 for name in names {
-                    parts.push(name.to_owned());
+                    parts.push(found_title(name, localized));
                 }
             }
-            Self::Json { output } => {
+            Self::Json { output, .. } => {
                 for name in names {
-                    output.games.insert(name.to_owned(), ApiGame::Found {});
+                    output.games.insert(
+                        name.to_owned(),
+                        ApiGame::Found {
+                            matched_paths: Default::default(),
+                            localized_title: localized.get(name).cloned(),
+                            paths: None,
+                            registry: None,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn add_found_paths(
+        &mut self,
+        found: &BTreeMap<String, BTreeSet<String>>,
+        localized: &BTreeMap<String, String>,
+    ) {
+        match self {
+            Self::Standard { parts, .. } => {
+                for (name, paths) in found {
+                    parts.push(found_title(name, localized));
+                    for path in paths {
+                        parts.push(format!("  - {path}"));
+                    }
+                }
+            }
+            Self::Json { output, .. } => {
+                for (name, paths) in found {
+                    output.games.insert(
+                        name.to_owned(),
+                        ApiGame::Found {
+                            matched_paths: paths.clone(),
+                            localized_title: localized.get(name).cloned(),
+                            paths: None,
+                            registry: None,
+                        },
+                    );
                 }
             }
         }
     }
 
+    /// Include the unexpanded paths from the manifest for each found game.
+    /// This only applies to the JSON output.
+    pub fn add_found_manifest_paths(&mut self, manifest: &Manifest) {
+        let Self::Json { output, .. } = self else {
+            return;
+        };
+
+        for (name, game) in output.games.iter_mut() {
+            if let ApiGame::Found { paths, registry, .. } = game {
+                let entry = manifest.0.get(name);
+                *paths = Some(entry.map(|x| x.files.keys().cloned().collect()).unwrap_or_default());
+                *registry = Some(entry.map(|x| x.registry.keys().cloned().collect()).unwrap_or_default());
+            }
+        }
+    }
+
     fn render(&self, path: &StrictPath) -> String {
         match self {
-            Self::Standard { parts, status, errors } => match status {
+            Self::Standard {
+                summary: Some(summary),
+                status,
+                errors,
+                retried_games,
+                disabled_games,
+                uninstalled_games,
+                incomplete_games,
+                identical_files,
+                ..
+            } => {
+                let mut out = match status {
+                    Some(status) => TRANSLATOR.cli_summary(status, path),
+                    None => TRANSLATOR.cli_summary_games(summary.games, path),
+                };
+                out += &TRANSLATOR.cli_summary_details(
+                    summary.failed_games,
+                    summary.backups,
+                    summary.disk_usage,
+                    summary.cloud_changes,
+                    summary.duration,
+                );
+                for message in errors.messages() {
+                    out += &format!("\n\n{message}");
+                }
+                if !retried_games.is_empty() {
+                    out += &format!("\n\n{}", TRANSLATOR.cli_retried_games(retried_games));
+                }
+                if !disabled_games.is_empty() {
+                    out += &format!("\n\n{}", TRANSLATOR.cli_disabled_games(disabled_games));
+                }
+                if !uninstalled_games.is_empty() {
+                    out += &format!("\n\n{}", TRANSLATOR.cli_uninstalled_games(uninstalled_games));
+                }
+                if !incomplete_games.is_empty() {
+                    out += &format!("\n\n{}", TRANSLATOR.cli_incomplete_games(incomplete_games));
+                }
+                if !identical_files.is_empty() {
+                    out += &format!("\n\n{}", TRANSLATOR.cli_identical_files(identical_files));
+                }
+                out
+            }
+            Self::Standard {
+                parts,
+                status,
+                errors,
+                retried_games,
+                disabled_games,
+                uninstalled_games,
+                incomplete_games,
+                identical_files,
+                store_groups,
+                ..
+            } => match status {
                 Some(status) => {
-                    let mut out = parts.join("\n") + "\n" + &TRANSLATOR.cli_summary(status, path);
+                    let mut out = Self::grouped_parts(parts, store_groups).join("\n")
+                        + "\n"
+                        + &TRANSLATOR.cli_summary(status, path);
                     for message in errors.messages() {
                         out += &format!("\n\n{message}");
                     }
+                    if !retried_games.is_empty() {
+                        out += &format!("\n\n{}", TRANSLATOR.cli_retried_games(retried_games));
+                    }
+                    if !disabled_games.is_empty() {
+                        out += &format!("\n\n{}", TRANSLATOR.cli_disabled_games(disabled_games));
+                    }
+                    if !uninstalled_games.is_empty() {
+                        out += &format!("\n\n{}", TRANSLATOR.cli_uninstalled_games(uninstalled_games));
+                    }
+                    if !incomplete_games.is_empty() {
+                        out += &format!("\n\n{}", TRANSLATOR.cli_incomplete_games(incomplete_games));
+                    }
+                    if !identical_files.is_empty() {
+                        out += &format!("\n\n{}", TRANSLATOR.cli_identical_files(identical_files));
+                    }
                     out
                 }
-                None => parts.join("\n"),
+                None => Self::grouped_parts(parts, store_groups).join("\n"),
             },
-            Self::Json { output } => serde_json::to_string_pretty(&output).unwrap(),
+            Self::Json { output, indent, .. } => crate::serialization::to_json_string(&output, *indent),
+        }
+    }
+
+    /// Put each group of games under its own heading, after any ungrouped lines.
+    fn grouped_parts(parts: &[String], store_groups: &BTreeMap<StoreGroup, StandardStoreGroup>) -> Vec<String> {
+        let mut out = parts.to_vec();
+        for (group, StandardStoreGroup { parts, status }) in store_groups {
+            out.push(TRANSLATOR.cli_store_group_header(group, status));
+            out.push("".to_string());
+            out.extend(parts.iter().cloned());
         }
+        out
     }
 
     pub fn print_failure(&self) {
@@ -589,22 +1391,57 @@ impl Reporter {
     pub fn print(&self, path: &StrictPath) {
         println!("{}", self.render(path));
     }
+
+    /// Write the same content as `print` to a file.
+    /// This goes through a temporary file so that the target is replaced all at once.
+    pub fn save(&self, path: &StrictPath, target: &StrictPath) -> Result<(), Error> {
+        let temp = StrictPath::new(format!("{}.tmp", target.raw()));
+
+        target
+            .create_parent_dir()
+            .and_then(|_| temp.write_with_content(&format!("{}\n", self.render(path))))
+            .and_then(|_| temp.move_to(target))
+            .map_err(|_| {
+                let _ = temp.remove();
+                Error::CannotWriteReport { path: target.clone() }
+            })
+    }
 }
 
-pub fn report_cloud_changes(changes: &[CloudChange], api: bool) {
+pub fn report_cloud_changes(
+    changes: &[CloudChange],
+    stats: Option<&CloudStats>,
+    api: bool,
+    json_indent: Option<usize>,
+) {
     if api {
         let mut output = JsonOutput {
             errors: None,
             overall: None,
+            summary: None,
+            retried_games: Default::default(),
+            disabled_games: Default::default(),
+            uninstalled_games: Default::default(),
+            incomplete_games: Default::default(),
+            identical_files: Default::default(),
             games: Default::default(),
+            stores: Default::default(),
             cloud: Default::default(),
+            cloud_stats: None,
+            cloud_purged: Default::default(),
+            roots: Default::default(),
+            manifest_diff: None,
         };
 
         output.cloud = changes
             .iter()
             .map(|x| (x.path.clone(), CloudEntry { change: x.change }))
             .collect();
-        eprintln!("{}", serde_json::to_string_pretty(&output).unwrap());
+        output.cloud_stats = stats.cloned();
+        eprintln!(
+            "{}",
+            crate::serialization::to_json_string(&output, json_indent.unwrap_or(DEFAULT_JSON_INDENT))
+        );
         return;
     }
 
@@ -614,25 +1451,245 @@ pub fn report_cloud_changes(changes: &[CloudChange], api: bool) {
         for CloudChange { path, change } in changes.iter().sorted() {
             println!("[{}] {}", change.symbol(), path);
         }
+        if let Some(stats) = stats {
+            println!("\n{}", TRANSLATOR.cli_cloud_stats(stats));
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use pretty_assertions::assert_eq;
-    use velcro::{hash_map, hash_set};
+pub fn report_cloud_purge(purged: &BTreeMap<String, CloudSize>, api: bool, json_indent: Option<usize>) {
+    if api {
+        let output = JsonOutput {
+            cloud_purged: purged.clone(),
+            ..Default::default()
+        };
+        println!(
+            "{}",
+            crate::serialization::to_json_string(&output, json_indent.unwrap_or(DEFAULT_JSON_INDENT))
+        );
+        return;
+    }
 
-    use super::*;
-    use crate::{
-        scan::{registry_compat::RegistryItem, BackupError, ScannedFile, ScannedRegistry},
-        testing::s,
-    };
+    if purged.is_empty() {
+        eprintln!("{}", TRANSLATOR.no_cloud_folders_to_delete());
+        return;
+    }
 
-    #[test]
-    fn can_render_in_standard_mode_with_minimal_input() {
-        let mut reporter = Reporter::standard();
-        reporter.add_game(
-            "foo",
+    let mut total = CloudSize::default();
+    for (folder, size) in purged {
+        println!("[{}] {}", crate::lang::REMOVAL_SYMBOL, folder);
+        total.add(size);
+    }
+    println!("\n{}", TRANSLATOR.cli_cloud_deleted(&total));
+}
+
+pub fn report_roots(roots: Vec<ApiRoot>, api: bool, json_indent: Option<usize>) {
+    if api {
+        let output = JsonOutput {
+            errors: None,
+            overall: None,
+            summary: None,
+            retried_games: Default::default(),
+            disabled_games: Default::default(),
+            uninstalled_games: Default::default(),
+            incomplete_games: Default::default(),
+            identical_files: Default::default(),
+            games: Default::default(),
+            stores: Default::default(),
+            cloud: Default::default(),
+            cloud_stats: None,
+            cloud_purged: Default::default(),
+            roots,
+            manifest_diff: None,
+        };
+        println!(
+            "{}",
+            crate::serialization::to_json_string(&output, json_indent.unwrap_or(DEFAULT_JSON_INDENT))
+        );
+        return;
+    }
+
+    println!("{}", render_roots(&roots));
+}
+
+pub fn report_listed_games(games: BTreeMap<String, ListedGame>, api: bool, json_indent: Option<usize>) {
+    if api {
+        let output = JsonOutput {
+            games: games
+                .into_iter()
+                .map(|(name, game)| {
+                    (
+                        name,
+                        ApiGame::Listed {
+                            steam_id: game.steam_id,
+                            gog_id: game.gog_id,
+                            custom: game.custom,
+                        },
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        };
+        println!(
+            "{}",
+            crate::serialization::to_json_string(&output, json_indent.unwrap_or(DEFAULT_JSON_INDENT))
+        );
+        return;
+    }
+
+    for name in games.keys() {
+        println!("{name}");
+    }
+}
+
+/// A single match from `find --json-lines`.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundLine<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    localized_title: Option<&'a String>,
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    matched_paths: &'a BTreeSet<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paths: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registry: Option<Vec<String>>,
+}
+
+/// Print one match as a line of JSON as soon as it is found.
+/// The manifest is only given with `--show-manifest-paths`.
+pub fn report_found_line(
+    name: &str,
+    localized_title: Option<&String>,
+    matched_paths: &BTreeSet<String>,
+    manifest: Option<&Manifest>,
+) {
+    let entry = manifest.map(|x| x.0.get(name));
+    println!(
+        "{}",
+        crate::serialization::to_json_string(
+            &FoundLine {
+                name,
+                localized_title,
+                matched_paths,
+                paths: entry.map(|x| x.map(|x| x.files.keys().cloned().collect()).unwrap_or_default()),
+                registry: entry.map(|x| x.map(|x| x.registry.keys().cloned().collect()).unwrap_or_default()),
+            },
+            0
+        )
+    );
+}
+
+/// Show a game's localized title, if any, along with its normal name.
+fn found_title(name: &str, localized: &BTreeMap<String, String>) -> String {
+    match localized.get(name) {
+        Some(title) => format!("{title} ({name})"),
+        None => name.to_string(),
+    }
+}
+
+pub fn report_manifest_diff(diff: ManifestDiff, api: bool, json_indent: Option<usize>) {
+    if api {
+        let output = JsonOutput {
+            manifest_diff: Some(diff),
+            ..Default::default()
+        };
+        println!(
+            "{}",
+            crate::serialization::to_json_string(&output, json_indent.unwrap_or(DEFAULT_JSON_INDENT))
+        );
+        return;
+    }
+
+    if diff.is_empty() {
+        eprintln!("{}", TRANSLATOR.no_manifest_changes());
+    } else {
+        println!("{}", render_manifest_diff(&diff));
+    }
+}
+
+fn render_manifest_diff(diff: &ManifestDiff) -> String {
+    let mut parts = vec![];
+
+    for name in &diff.added {
+        parts.push(format!("[{}] {}", ScanChange::New.symbol(), name));
+    }
+    for name in &diff.removed {
+        parts.push(format!("[{}] {}", ScanChange::Removed.symbol(), name));
+    }
+    for (name, game) in &diff.changed {
+        parts.push(format!("[{}] {}", ScanChange::Different.symbol(), name));
+        for (path, change) in game.files.iter().chain(&game.registry) {
+            parts.push(format!("  - [{}] {}", change.symbol(), path));
+        }
+    }
+
+    parts.join("\n")
+}
+
+fn render_roots(roots: &[ApiRoot]) -> String {
+    let mut parts = vec![];
+
+    for root in roots {
+        let mut line = format!("[{}] {}", TRANSLATOR.store(&root.store), root.path);
+        match root.source {
+            RootSource::Config => {}
+            RootSource::Environment => line += &format!(" [{}]", TRANSLATOR.badge_environment()),
+            RootSource::Detected => line += &format!(" [{}]", TRANSLATOR.badge_detected()),
+        }
+        if !root.exists {
+            line += &format!(" [{}]", TRANSLATOR.badge_not_found());
+        }
+        parts.push(line);
+        parts.push(format!("  {}: {}", TRANSLATOR.total_games(), root.games));
+    }
+
+    parts.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::{btree_map, btree_set, hash_map, hash_set};
+
+    use super::*;
+    use crate::{
+        resource::{config::DuplicateOwners, manifest::GameDiff, ResourceFile},
+        scan::{
+            layout::{DifferentialBackup, FullBackup, IndividualMappingFile},
+            registry_compat::RegistryItem,
+            BackupError, ScannedFile, ScannedRegistry,
+        },
+        testing::s,
+    };
+
+    /// A game restored from a single file, for tests that only care about some extra detail.
+    fn restored_game() -> ScanInfo {
+        ScanInfo {
+            game_name: s("foo"),
+            found_files: hash_set! {
+                ScannedFile {
+                    path: StrictPath::new(s("/backup/file1")),
+                    size: 102_400,
+                    hash: "1".to_string(),
+                    original_path: Some(StrictPath::new(s("/original/file1"))),
+                    ignored: false,
+                    change: Default::default(),
+                    container: None,
+                    redirected: None,
+                },
+            },
+            found_registry_keys: hash_set! {},
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_minimal_input() {
+        let mut reporter = Reporter::standard();
+        reporter.add_game(
+            "foo",
             &ScanInfo::default(),
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
@@ -650,6 +1707,114 @@ Overall:
         )
     }
 
+    #[test]
+    fn can_render_in_standard_mode_with_retried_games() {
+        let mut reporter = Reporter::standard();
+        reporter.add_retried_game("foo");
+        reporter.add_retried_game("bar");
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Size: 0 B
+  Location: /dev/null
+
+These games only succeeded after retrying:
+  - bar
+  - foo
+            "#
+            .trim_end(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        )
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_disabled_games() {
+        let mut reporter = Reporter::standard();
+        reporter.add_disabled_game("foo");
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Size: 0 B
+  Location: /dev/null
+
+These games are disabled, but were included anyway:
+  - foo
+            "#
+            .trim_end(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        )
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_uninstalled_games() {
+        let mut reporter = Reporter::standard();
+        reporter.set_uninstalled_games(BTreeSet::from([s("foo")]));
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Size: 0 B
+  Location: /dev/null
+
+These games were skipped because they don't appear to be installed:
+  - foo
+            "#
+            .trim_end(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        )
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_incomplete_games() {
+        let mut reporter = Reporter::standard();
+        reporter.add_incomplete_game("foo");
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Size: 0 B
+  Location: /dev/null
+
+These games were still in progress when the maximum duration was reached, so they may not have been backed up:
+  - foo
+            "#
+            .trim_end(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        )
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_identical_files() {
+        let mut reporter = Reporter::standard();
+        reporter.set_identical_files(vec![IdenticalFiles {
+            hash: s("abc"),
+            size: 1_024,
+            wasted_bytes: 2_048,
+            games: btree_map! {
+                s("bar"): btree_set! { s("/bar/file") },
+                s("foo"): btree_set! { s("/foo/file1"), s("/foo/file2") },
+            },
+        }]);
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Size: 0 B
+  Location: /dev/null
+
+These files have identical content in multiple games, using an extra 2.00 KiB:
+  - 1.00 KiB, 2.00 KiB wasted:
+    - [bar] /bar/file
+    - [foo] /foo/file1
+    - [foo] /foo/file2
+            "#
+            .trim_end(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        )
+    }
+
     #[test]
     fn can_render_in_standard_mode_with_one_game_in_backup_mode() {
         let mut reporter = Reporter::standard();
@@ -720,6 +1885,48 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_in_standard_mode_grouped_by_store() {
+        let mut reporter = Reporter::standard().with_group_by_store(true);
+        reporter.add_game_store("foo", StoreGroup::Detected(Store::Steam));
+
+        for name in ["foo", "bar"] {
+            reporter.add_game(
+                name,
+                &ScanInfo {
+                    game_name: s(name),
+                    found_files: hash_set! {
+                        ScannedFile::new(format!("/{name}"), 1, "1"),
+                    },
+                    ..Default::default()
+                },
+                &BackupInfo::default(),
+                &OperationStepDecision::Processed,
+                &DuplicateDetector::default(),
+            );
+        }
+        assert_eq!(
+            r#"
+=== Steam: 1 game, 1 B ===
+
+foo [1 B]:
+  - /foo
+
+=== Unknown: 1 game, 1 B ===
+
+bar [1 B]:
+  - /bar
+
+Overall:
+  Games: 2
+  Size: 2 B
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
     #[test]
     fn can_render_in_standard_mode_with_multiple_games_in_backup_mode() {
         let mut reporter = Reporter::standard();
@@ -848,47 +2055,62 @@ Overall:
     }
 
     #[test]
-    fn can_render_in_standard_mode_with_duplicated_entries() {
+    fn can_render_in_standard_mode_with_restore_impact() {
         let mut reporter = Reporter::standard();
 
-        let mut duplicate_detector = DuplicateDetector::default();
-        for name in &["foo", "bar"] {
-            duplicate_detector.add_game(
-                &ScanInfo {
-                    game_name: s(name),
-                    found_files: hash_set! {
-                        ScannedFile::new("/file1", 102_400, "1").change_as(ScanChange::New),
-                    },
-                    found_registry_keys: hash_set! {
-                        ScannedRegistry::new("HKEY_CURRENT_USER/Key1").change_as(ScanChange::New),
-                    },
-                    ..Default::default()
-                },
-                true,
-            );
-        }
+        reporter.add_restore_impact(
+            "foo",
+            RestoreImpact {
+                overwritten: btree_map! { s("/original/file1"): 2_048 },
+                orphaned: btree_set! { s("/original/extra1"), s("/original/extra2") },
+                cleaned: btree_set! { s("/original/extra3") },
+            },
+        );
+        reporter.add_game(
+            "foo",
+            &restored_game(),
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+foo [100.00 KiB]:
+  - /original/file1
+  - Would overwrite on disk: 1 file (2.00 KiB)
+  - Not in the backup, but found on disk: 2 files
+    - /original/extra1
+    - /original/extra2
+  - Deleted because they are not in the backup: 1 file
+    - /original/extra3
+
+Overall:
+  Games: 1
+  Size: 100.00 KiB
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_restore_prefix() {
+        let mut reporter = Reporter::standard();
 
+        reporter.add_restore_prefix("foo", &StrictPath::new(s("/steam/steamapps/compatdata/101/pfx")));
         reporter.add_game(
             "foo",
-            &ScanInfo {
-                game_name: s("foo"),
-                found_files: hash_set! {
-                    ScannedFile::new("/file1", 102_400, "1"),
-                },
-                found_registry_keys: hash_set! {
-                    ScannedRegistry::new("HKEY_CURRENT_USER/Key1"),
-                },
-                ..Default::default()
-            },
+            &restored_game(),
             &BackupInfo::default(),
             &OperationStepDecision::Processed,
-            &duplicate_detector,
+            &DuplicateDetector::default(),
         );
         assert_eq!(
             r#"
-foo [100.00 KiB] [DUPLICATES]:
-  - [DUPLICATED] /file1
-  - [DUPLICATED] HKEY_CURRENT_USER/Key1
+foo [100.00 KiB]:
+  - /original/file1
+  - Proton prefix: /steam/steamapps/compatdata/101/pfx
 
 Overall:
   Games: 1
@@ -901,61 +2123,686 @@ Overall:
     }
 
     #[test]
-    fn can_render_in_standard_mode_with_different_file_changes() {
-        let mut reporter = Reporter::standard();
+    fn can_render_in_standard_mode_with_restore_backup() {
+        let mut reporter = Reporter::standard();
+
+        reporter.add_restore_backup("foo", "backup-1");
+        reporter.add_game(
+            "foo",
+            &restored_game(),
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        let output = reporter.render(&StrictPath::new(s("/dev/null")));
+        assert!(output.lines().any(|line| line == "  - Backup: backup-1"));
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_preserved_registry_values() {
+        let mut reporter = Reporter::standard();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_registry_keys: hash_set! {
+                    ScannedRegistry::new("HKEY_CURRENT_USER/Key1").with_value_same("Value1"),
+                },
+                preserved_registry_values: btree_map! {
+                    RegistryItem::new(s("HKEY_CURRENT_USER/Key1")): btree_set! { s("Value2") },
+                },
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+foo [0 B]:
+  - HKEY_CURRENT_USER/Key1
+    - Value1
+    - Preserved existing value: Value2
+
+Overall:
+  Games: 1
+  Size: 0 B
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_preserved_registry_values() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_registry_keys: hash_set! {
+                    ScannedRegistry::new("HKEY_CURRENT_USER/Key1").with_value_same("Value1"),
+                },
+                preserved_registry_values: btree_map! {
+                    RegistryItem::new(s("HKEY_CURRENT_USER/Key1")): btree_set! { s("Value2") },
+                },
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"{"overall":{"totalGames":1,"totalBytes":0,"processedGames":1,"processedBytes":0,"changedGames":{"new":0,"different":0,"same":1}},"games":{"foo":{"decision":"Processed","change":"Same","files":{},"registry":{"HKEY_CURRENT_USER/Key1":{"change":"Unknown","values":{"Value1":{"change":"Same"}},"preservedValues":["Value2"]}}}}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_other_os_files() {
+        let mut reporter = Reporter::standard();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_set! {
+                    ScannedFile::new("/file1", 102_400, "1"),
+                },
+                other_os_files: btree_map! {
+                    StrictPath::new(s("/file1")): Os::Windows,
+                },
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+foo [100.00 KiB]:
+  - /file1
+    - From a save location for: Windows
+
+Overall:
+  Games: 1
+  Size: 100.00 KiB
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_other_os_files() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_set! {
+                    ScannedFile::new("/file1", 102_400, "1"),
+                },
+                other_os_files: btree_map! {
+                    StrictPath::new(s("/file1")): Os::Windows,
+                },
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"{"overall":{"totalGames":1,"totalBytes":102400,"processedGames":1,"processedBytes":102400,"changedGames":{"new":0,"different":0,"same":1}},"games":{"foo":{"decision":"Processed","change":"Same","files":{"/file1":{"change":"Unknown","bytes":102400,"otherOs":"windows"}},"registry":{}}}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_duplicated_entries() {
+        let mut reporter = Reporter::standard();
+
+        let mut duplicate_detector = DuplicateDetector::default();
+        for name in &["foo", "bar"] {
+            duplicate_detector.add_game(
+                &ScanInfo {
+                    game_name: s(name),
+                    found_files: hash_set! {
+                        ScannedFile::new("/file1", 102_400, "1").change_as(ScanChange::New),
+                    },
+                    found_registry_keys: hash_set! {
+                        ScannedRegistry::new("HKEY_CURRENT_USER/Key1").change_as(ScanChange::New),
+                    },
+                    ..Default::default()
+                },
+                true,
+            );
+        }
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_set! {
+                    ScannedFile::new("/file1", 102_400, "1"),
+                },
+                found_registry_keys: hash_set! {
+                    ScannedRegistry::new("HKEY_CURRENT_USER/Key1"),
+                },
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &duplicate_detector,
+        );
+        assert_eq!(
+            r#"
+foo [100.00 KiB] [DUPLICATES]:
+  - [DUPLICATED] /file1
+  - [DUPLICATED] HKEY_CURRENT_USER/Key1
+
+Overall:
+  Games: 1
+  Size: 100.00 KiB
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_different_file_changes() {
+        let mut reporter = Reporter::standard();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_set! {
+                    ScannedFile::new(s("/new"), 1, "1".to_string()).change_as(ScanChange::New),
+                    ScannedFile::new(s("/different"), 1, "1".to_string()).change_as(ScanChange::Different),
+                    ScannedFile::new(s("/same"), 1, "1".to_string()).change_as(ScanChange::Same),
+                    ScannedFile::new(s("/unknown"), 1, "1".to_string()).change_as(ScanChange::Unknown),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            &BackupInfo {
+                failed_files: hash_map! {},
+                failed_registry: hash_map! {},
+            },
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        reporter.add_game(
+            "bar",
+            &ScanInfo {
+                game_name: s("bar"),
+                found_files: hash_set! {
+                    ScannedFile::new(s("/brand-new"), 1, "1".to_string()).change_as(ScanChange::New),
+                },
+                found_registry_keys: hash_set! {},
+                ..Default::default()
+            },
+            &BackupInfo {
+                failed_files: hash_map! {},
+                failed_registry: hash_map! {},
+            },
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+foo [4 B] [Δ]:
+  - [Δ] /different
+  - [+] /new
+  - /same
+  - /unknown
+
+bar [1 B] [+]:
+  - [+] /brand-new
+
+Overall:
+  Games: 2 [+1] [Δ1]
+  Size: 5 B
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_minimal_input() {
+        let mut reporter = Reporter::json();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo::default(),
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 0,
+    "totalBytes": 0,
+    "processedGames": 0,
+    "processedBytes": 0,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 0
+    }
+  },
+  "games": {}
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_grouped_by_store() {
+        let mut reporter = Reporter::json().with_group_by_store(true);
+        reporter.add_game_store("foo", StoreGroup::Detected(Store::Steam));
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_set! {
+                    ScannedFile::new("/foo", 1, "1"),
+                },
+                ..Default::default()
+            },
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 1,
+    "totalBytes": 1,
+    "processedGames": 1,
+    "processedBytes": 1,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 1
+    }
+  },
+  "games": {},
+  "stores": {
+    "steam": {
+      "overall": {
+        "totalGames": 1,
+        "totalBytes": 1,
+        "processedGames": 1,
+        "processedBytes": 1,
+        "changedGames": {
+          "new": 0,
+          "different": 0,
+          "same": 1
+        }
+      },
+      "games": {
+        "foo": {
+          "decision": "Processed",
+          "change": "Same",
+          "files": {
+            "/foo": {
+              "change": "Unknown",
+              "bytes": 1
+            }
+          },
+          "registry": {}
+        }
+      }
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_custom_indent() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo::default(),
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"{"overall":{"totalGames":0,"totalBytes":0,"processedGames":0,"processedBytes":0,"changedGames":{"new":0,"different":0,"same":0}},"games":{}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+
+        let reporter = Reporter::json().with_json_indent(Some(4));
+        assert_eq!(
+            r#"
+{
+    "overall": {
+        "totalGames": 0,
+        "totalBytes": 0,
+        "processedGames": 0,
+        "processedBytes": 0,
+        "changedGames": {
+            "new": 0,
+            "different": 0,
+            "same": 0
+        }
+    },
+    "games": {}
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_manifest_age() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+        reporter.set_manifest_age_days(45);
+
+        assert_eq!(
+            r#"{"overall":{"totalGames":0,"totalBytes":0,"processedGames":0,"processedBytes":0,"changedGames":{"new":0,"different":0,"same":0},"manifestAgeDays":45},"games":{}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_empty_games() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+        reporter.set_empty_games(3);
+
+        assert_eq!(
+            r#"{"overall":{"totalGames":0,"totalBytes":0,"processedGames":0,"processedBytes":0,"changedGames":{"new":0,"different":0,"same":0},"emptyGames":3},"games":{}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_empty_games() {
+        let mut reporter = Reporter::standard();
+        reporter.set_empty_games(3);
+
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Empty: 3
+  Size: 0 B
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim()
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_cloud_sync_skipped() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+        reporter.set_cloud_sync_skipped();
+
+        assert_eq!(
+            r#"{"overall":{"totalGames":0,"totalBytes":0,"processedGames":0,"processedBytes":0,"changedGames":{"new":0,"different":0,"same":0},"cloudSyncSkipped":true},"games":{}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_cloud_sync_skipped() {
+        let mut reporter = Reporter::standard();
+        reporter.set_cloud_sync_skipped();
+
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Cloud sync skipped (no changes)
+  Size: 0 B
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim()
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_registry_only() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+        reporter.set_registry_only();
+
+        assert_eq!(
+            r#"{"overall":{"totalGames":0,"totalBytes":0,"processedGames":0,"processedBytes":0,"changedGames":{"new":0,"different":0,"same":0},"registryOnly":true},"games":{}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_registry_only() {
+        let mut reporter = Reporter::standard();
+        reporter.set_registry_only();
+
+        assert_eq!(
+            r#"
+Overall:
+  Games: 0
+  Files skipped (registry only)
+  Size: 0 B
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null"))).trim()
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_summary_only() {
+        let mut reporter = Reporter::standard().with_summary_only(true);
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_set! {
+                    ScannedFile::new("/file1", 102_400, "1"),
+                    ScannedFile::new("/file2", 51_200, "2"),
+                },
+                ..Default::default()
+            },
+            &BackupInfo {
+                failed_files: hash_map! {
+                    ScannedFile::new("/file2", 51_200, "2"): BackupError::Test,
+                },
+                failed_registry: hash_map! {},
+            },
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        reporter.add_cloud_changes(&[CloudChange {
+            path: s("foo/mapping.yaml"),
+            change: ScanChange::New,
+        }]);
+        reporter.set_duration(std::time::Duration::from_millis(1500));
+
+        assert_eq!(
+            r#"
+Overall:
+  Games: 1
+  Size: 100.00 KiB / 150.00 KiB
+  Location: /dev/null
+  Failed: 1
+  Cloud changes: 1
+  Duration: 1.50s
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_found_games_in_json_mode_with_manifest_paths() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+
+        let manifest = Manifest::load_from_string(
+            r#"
+            foo:
+              files:
+                <base>/saves: {}
+              registry:
+                HKEY_CURRENT_USER/Software/Foo: {}
+            "#,
+        )
+        .unwrap();
+
+        reporter.add_found_titles(&BTreeSet::from([s("foo"), s("bar")]), &BTreeMap::new());
+        reporter.add_found_manifest_paths(&manifest);
+
+        assert_eq!(
+            r#"
+{
+  "games": {
+    "bar": {
+      "paths": [],
+      "registry": []
+    },
+    "foo": {
+      "paths": [
+        "<base>/saves"
+      ],
+      "registry": [
+        "HKEY_CURRENT_USER/Software/Foo"
+      ]
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_backups_in_json_mode_with_summary_only() {
+        let mut reporter = Reporter::json().with_summary_only(true);
+        reporter.suppress_overall();
+
+        reporter.add_backups(
+            "foo",
+            "foo",
+            StrictPath::new(s("/backups/foo")),
+            &[
+                Backup::Full(Default::default()),
+                Backup::Differential(Default::default()),
+            ],
+        );
+        reporter.add_backups("bar", "bar", StrictPath::new(s("/backups/bar")), &[]);
+
+        assert_eq!(
+            r#"
+{
+  "summary": {
+    "games": 1,
+    "failedGames": 0,
+    "backups": 2,
+    "diskUsage": 0,
+    "duration": 0.0
+  },
+  "games": {}
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_backups_in_standard_mode_with_disk_usage() {
+        let mut reporter = Reporter::standard().with_summary_only(true);
+        reporter.suppress_overall();
 
-        reporter.add_game(
+        let files = btree_map! {
+            s("/file1"): IndividualMappingFile { hash: s("1"), size: 1024, ..Default::default() },
+        };
+        reporter.add_backups(
             "foo",
-            &ScanInfo {
-                game_name: s("foo"),
-                found_files: hash_set! {
-                    ScannedFile::new(s("/new"), 1, "1".to_string()).change_as(ScanChange::New),
-                    ScannedFile::new(s("/different"), 1, "1".to_string()).change_as(ScanChange::Different),
-                    ScannedFile::new(s("/same"), 1, "1".to_string()).change_as(ScanChange::Same),
-                    ScannedFile::new(s("/unknown"), 1, "1".to_string()).change_as(ScanChange::Unknown),
-                },
-                found_registry_keys: hash_set! {},
+            "foo",
+            StrictPath::new(s("/backups/foo")),
+            &[Backup::Full(FullBackup {
+                files: files.clone(),
                 ..Default::default()
-            },
-            &BackupInfo {
-                failed_files: hash_map! {},
-                failed_registry: hash_map! {},
-            },
-            &OperationStepDecision::Processed,
-            &DuplicateDetector::default(),
+            })],
         );
-        reporter.add_game(
+        reporter.add_backups(
             "bar",
-            &ScanInfo {
-                game_name: s("bar"),
-                found_files: hash_set! {
-                    ScannedFile::new(s("/brand-new"), 1, "1".to_string()).change_as(ScanChange::New),
-                },
-                found_registry_keys: hash_set! {},
+            "bar",
+            StrictPath::new(s("/backups/bar")),
+            &[Backup::Full(FullBackup {
+                files,
                 ..Default::default()
-            },
-            &BackupInfo {
-                failed_files: hash_map! {},
-                failed_registry: hash_map! {},
-            },
-            &OperationStepDecision::Processed,
-            &DuplicateDetector::default(),
+            })],
         );
+
         assert_eq!(
             r#"
-foo [4 B] [Δ]:
-  - [Δ] /different
-  - [+] /new
-  - /same
-  - /unknown
+Overall:
+  Games: 2
+  Location: /dev/null
+  Failed: 0
+  Backups: 2
+  Disk usage: 2.00 KiB
+  Duration: 0.00s
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
 
-bar [1 B] [+]:
-  - [+] /brand-new
+    #[test]
+    fn can_render_backups_in_standard_mode_with_disk_usage_of_shared_blobs() {
+        let mut reporter = Reporter::standard().with_summary_only(true);
+        reporter.suppress_overall();
+
+        let backups = [Backup::Full(FullBackup {
+            files: btree_map! {
+                s("/file1"): IndividualMappingFile { hash: s("1"), size: 1024, blob: true, ..Default::default() },
+            },
+            ..Default::default()
+        })];
+        reporter.add_backups("foo", "foo", StrictPath::new(s("/backups/foo")), &backups);
+        reporter.add_backups("foo2", "foo2", StrictPath::new(s("/backups/foo")), &backups);
+        reporter.add_backups("bar", "bar", StrictPath::new(s("/backups/bar")), &backups);
 
+        assert_eq!(
+            r#"
 Overall:
-  Games: 2 [+1] [Δ1]
-  Size: 5 B
+  Games: 3
   Location: /dev/null
+  Failed: 0
+  Backups: 3
+  Disk usage: 2.00 KiB
+  Duration: 0.00s
             "#
             .trim(),
             reporter.render(&StrictPath::new(s("/dev/null")))
@@ -963,31 +2810,91 @@ Overall:
     }
 
     #[test]
-    fn can_render_in_json_mode_with_minimal_input() {
+    fn can_render_backups_in_json_mode_with_note() {
         let mut reporter = Reporter::json();
+        reporter.suppress_overall();
 
-        reporter.add_game(
+        reporter.add_game_note("foo", "Disabled because of cloud saves");
+        reporter.add_backups(
             "foo",
-            &ScanInfo::default(),
-            &BackupInfo::default(),
-            &OperationStepDecision::Processed,
-            &DuplicateDetector::default(),
+            "foo",
+            StrictPath::new(s("/backups/foo")),
+            &[Backup::Full(FullBackup {
+                name: s("backup-1"),
+                ..Default::default()
+            })],
         );
+
         assert_eq!(
             r#"
 {
-  "overall": {
-    "totalGames": 0,
-    "totalBytes": 0,
-    "processedGames": 0,
-    "processedBytes": 0,
-    "changedGames": {
-      "new": 0,
-      "different": 0,
-      "same": 0
+  "games": {
+    "foo": {
+      "backupPath": "/backups/foo",
+      "backups": [
+        {
+          "name": "backup-1",
+          "when": "1970-01-01T00:00:00Z",
+          "locked": false,
+          "kind": "full"
+        }
+      ],
+      "diskUsage": 0,
+      "note": "Disabled because of cloud saves"
     }
-  },
-  "games": {}
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_backups_in_json_mode_with_differential_chain() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+
+        reporter.add_backups(
+            "foo",
+            "foo",
+            StrictPath::new(s("/backups/foo")),
+            &[
+                Backup::Full(FullBackup {
+                    name: s("full"),
+                    ..Default::default()
+                }),
+                Backup::Differential(DifferentialBackup {
+                    name: s("diff"),
+                    ..Default::default()
+                }),
+            ],
+        );
+
+        assert_eq!(
+            r#"
+{
+  "games": {
+    "foo": {
+      "backupPath": "/backups/foo",
+      "backups": [
+        {
+          "name": "full",
+          "when": "1970-01-01T00:00:00Z",
+          "locked": false,
+          "kind": "full"
+        },
+        {
+          "name": "diff",
+          "when": "1970-01-01T00:00:00Z",
+          "locked": false,
+          "kind": "differential",
+          "basedOn": "full"
+        }
+      ],
+      "diskUsage": 0
+    }
+  }
 }
             "#
             .trim(),
@@ -1164,6 +3071,136 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_in_json_mode_with_restore_impact() {
+        let mut reporter = Reporter::json();
+
+        reporter.add_restore_impact(
+            "foo",
+            RestoreImpact {
+                overwritten: btree_map! { s("/original/file1"): 2_048 },
+                orphaned: btree_set! { s("/original/extra1"), s("/original/extra2") },
+                cleaned: btree_set! { s("/original/extra3") },
+            },
+        );
+        reporter.add_game(
+            "foo",
+            &restored_game(),
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 1,
+    "totalBytes": 102400,
+    "processedGames": 1,
+    "processedBytes": 102400,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 1
+    }
+  },
+  "games": {
+    "foo": {
+      "decision": "Processed",
+      "change": "Same",
+      "files": {
+        "/original/file1": {
+          "change": "Unknown",
+          "bytes": 102400
+        }
+      },
+      "registry": {},
+      "wouldOverwrite": [
+        {
+          "path": "/original/file1",
+          "bytes": 2048
+        }
+      ],
+      "wouldOrphan": [
+        "/original/extra1",
+        "/original/extra2"
+      ],
+      "cleaned": [
+        "/original/extra3"
+      ]
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_restore_prefix() {
+        let mut reporter = Reporter::json();
+
+        reporter.add_restore_prefix("foo", &StrictPath::new(s("/steam/steamapps/compatdata/101/pfx")));
+        reporter.add_game(
+            "foo",
+            &restored_game(),
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 1,
+    "totalBytes": 102400,
+    "processedGames": 1,
+    "processedBytes": 102400,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 1
+    }
+  },
+  "games": {
+    "foo": {
+      "decision": "Processed",
+      "change": "Same",
+      "files": {
+        "/original/file1": {
+          "change": "Unknown",
+          "bytes": 102400
+        }
+      },
+      "registry": {},
+      "prefix": "/steam/steamapps/compatdata/101/pfx"
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_restore_backup() {
+        let mut reporter = Reporter::json();
+
+        reporter.add_restore_backup("foo", "backup-1");
+        reporter.add_game(
+            "foo",
+            &restored_game(),
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+        );
+        let output: serde_json::Value =
+            serde_json::from_str(&reporter.render(&StrictPath::new(s("/dev/null")))).unwrap();
+        assert_eq!("backup-1", output["games"]["foo"]["backup"]);
+    }
+
     #[test]
     fn can_render_in_json_mode_with_duplicated_entries() {
         let mut reporter = Reporter::json();
@@ -1245,6 +3282,42 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_in_json_mode_with_duplicate_owner() {
+        let mut reporter = Reporter::json().with_json_indent(Some(0));
+
+        let owners = DuplicateOwners::new(btree_map! {
+            StrictPath::new(s("/file1")): s("foo"),
+        });
+        let mut duplicate_detector = DuplicateDetector::default();
+        duplicate_detector.set_owners(owners.clone());
+        let mut scans = vec![];
+        for name in &["foo", "bar"] {
+            let mut scan_info = ScanInfo {
+                game_name: s(name),
+                found_files: hash_set! {
+                    ScannedFile::new("/file1", 100, "1"),
+                },
+                ..Default::default()
+            };
+            scan_info.apply_duplicate_owners(&owners, |owner, path| duplicate_detector.game_has_file(owner, path));
+            duplicate_detector.add_game(&scan_info, true);
+            scans.push(scan_info);
+        }
+
+        reporter.add_game(
+            "foo",
+            &scans[0],
+            &BackupInfo::default(),
+            &OperationStepDecision::Processed,
+            &duplicate_detector,
+        );
+        assert_eq!(
+            r#"{"overall":{"totalGames":1,"totalBytes":100,"processedGames":1,"processedBytes":100,"changedGames":{"new":0,"different":0,"same":1}},"games":{"foo":{"decision":"Processed","change":"Same","files":{"/file1":{"change":"Unknown","bytes":100,"duplicateResolution":"rule","duplicateOwner":"foo"}},"registry":{}}}}"#,
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
     #[test]
     fn can_render_in_json_mode_with_different_file_changes() {
         let mut reporter = Reporter::json();
@@ -1314,4 +3387,74 @@ Overall:
             reporter.render(&StrictPath::new(s("/dev/null")))
         );
     }
+
+    #[test]
+    fn can_render_roots_in_standard_mode() {
+        let roots = vec![
+            ApiRoot {
+                path: s("/steam"),
+                store: Store::Steam,
+                source: RootSource::Config,
+                exists: true,
+                games: 2,
+            },
+            ApiRoot {
+                path: s("/env"),
+                store: Store::Other,
+                source: RootSource::Environment,
+                exists: false,
+                games: 0,
+            },
+            ApiRoot {
+                path: s("/gog"),
+                store: Store::Gog,
+                source: RootSource::Detected,
+                exists: true,
+                games: 1,
+            },
+        ];
+        assert_eq!(
+            r#"
+[Steam] /steam
+  Games: 2
+[Other] /env [ENVIRONMENT] [NOT FOUND]
+  Games: 0
+[GOG] /gog [DETECTED]
+  Games: 1
+            "#
+            .trim(),
+            render_roots(&roots),
+        );
+    }
+
+    #[test]
+    fn can_render_manifest_diff_in_standard_mode() {
+        let diff = ManifestDiff {
+            added: btree_set! { s("new game") },
+            removed: btree_set! { s("old game") },
+            changed: btree_map! {
+                s("changed game"): GameDiff {
+                    files: btree_map! {
+                        s("<base>/new.sav"): ScanChange::New,
+                        s("<base>/old.sav"): ScanChange::Removed,
+                    },
+                    registry: btree_map! {
+                        s("HKEY_CURRENT_USER/Software/Game"): ScanChange::Different,
+                    },
+                },
+            },
+        };
+        assert_eq!(
+            r#"
+[+] new game
+[x] old game
+[Δ] changed game
+  - [+] <base>/new.sav
+  - [x] <base>/old.sav
+  - [Δ] HKEY_CURRENT_USER/Software/Game
+            "#
+            .trim(),
+            render_manifest_diff(&diff),
+        );
+    }
 }
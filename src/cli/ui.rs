@@ -1,4 +1,7 @@
-use crate::{lang::TRANSLATOR, prelude::Error};
+use crate::{
+    lang::TRANSLATOR,
+    prelude::{should_assume_yes, Error},
+};
 
 /// GUI looks nicer with an extra empty line as separator, but for terminals a single
 /// newline is sufficient
@@ -83,6 +86,10 @@ pub fn confirm(gui: bool, force: Option<bool>, msg: &str) -> Result<bool, Error>
         return Ok(force);
     }
 
+    if should_assume_yes() {
+        return Ok(true);
+    }
+
     if gui {
         match native_dialog::MessageDialog::new()
             .set_title(&TRANSLATOR.app_name())
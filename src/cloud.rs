@@ -1,4 +1,8 @@
-use std::io::{BufRead, BufReader};
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader},
+    num::NonZeroUsize,
+};
 
 use crate::{
     lang::TRANSLATOR,
@@ -7,17 +11,51 @@ use crate::{
     scan::ScanChange,
 };
 
-pub fn validate_cloud_config(config: &Config, cloud_path: &str) -> Result<Remote, Error> {
+pub fn validate_cloud_config(config: &Config, cloud_path: &str, direction: SyncDirection) -> Result<Remote, Error> {
     if !config.apps.rclone.is_valid() {
         return Err(Error::RcloneUnavailable);
     }
     let Some(remote) = config.cloud.remote.clone() else {
         return Err(Error::CloudNotConfigured);
     };
+    validate_cloud_remote(remote.id())?;
     validate_cloud_path(cloud_path)?;
+    if direction == SyncDirection::Upload && remote.is_read_only() {
+        return Err(Error::CloudRemoteReadOnly);
+    }
     Ok(remote)
 }
 
+/// Rclone remote names may contain letters, numbers, spaces, and `_-.+@`,
+/// but they can't start with `-` or a space, nor end with a space.
+pub fn validate_cloud_remote(id: &str) -> Result<(), Error> {
+    let valid = !id.is_empty()
+        && !id.starts_with(['-', ' '])
+        && !id.ends_with(' ')
+        && id
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.' | '+' | '@'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::CloudRemoteInvalid { remote: id.to_string() })
+    }
+}
+
+/// This only applies when configuring a remote,
+/// since secrets are stored by Rclone rather than in our config.
+pub fn validate_remote_fields(remote: &Remote) -> Result<(), Error> {
+    let missing = remote.missing_fields();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::CloudRemoteIncomplete {
+            fields: missing.into_iter().map(|x| x.to_string()).collect(),
+        })
+    }
+}
+
 pub fn validate_cloud_path(path: &str) -> Result<(), Error> {
     if path.is_empty() || path == "/" {
         Err(Error::CloudPathInvalid)
@@ -26,16 +64,176 @@ pub fn validate_cloud_path(path: &str) -> Result<(), Error> {
     }
 }
 
+/// Rclone filter rules in this file under the local backup folder are applied to cloud syncs.
+pub const IGNORE_FILE: &str = ".ludusaviignore";
+
+/// Load the Rclone filter rules from the ignore file in the local backup folder, if it exists.
+/// Blank lines and comments are skipped.
+pub fn load_ignore_rules(local: &StrictPath) -> Result<Vec<String>, Error> {
+    let file = local.joined(IGNORE_FILE);
+    if !file.is_file() {
+        return Ok(vec![]);
+    }
+    log::info!("using cloud ignore file: {}", file.render());
+
+    let content = file.try_read().map_err(|_| Error::CloudIgnoreFileInvalid {
+        path: file.clone(),
+        line: None,
+    })?;
+    parse_ignore_rules(&content).map_err(|line| Error::CloudIgnoreFileInvalid {
+        path: file.clone(),
+        line: Some(line),
+    })
+}
+
+/// Each rule must be in Rclone's `--filter` syntax, like `- *.tmp`.
+/// Returns the line number of the first malformed rule, if any.
+fn parse_ignore_rules(content: &str) -> Result<Vec<String>, usize> {
+    let mut rules = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        if line.trim_start().is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if line == "!" || line.starts_with("+ ") || line.starts_with("- ") {
+            rules.push(line.to_string());
+        } else {
+            return Err(i + 1);
+        }
+    }
+    Ok(rules)
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct CloudChange {
     pub path: String,
     pub change: ScanChange,
 }
 
+impl CloudChange {
+    /// The folder directly under the backup path that contains this change.
+    /// This is `None` for files that are not inside of a game's folder.
+    pub fn game_dir(&self) -> Option<&str> {
+        self.path.split_once('/').map(|(dir, _)| dir)
+    }
+}
+
+/// Totals reported by Rclone for a sync.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudStats {
+    /// Number of files transferred.
+    pub files: u64,
+    /// Number of bytes transferred.
+    pub bytes: u64,
+    /// Elapsed time in seconds.
+    pub elapsed: f64,
+    /// Average speed in bytes per second.
+    pub speed: f64,
+}
+
+impl CloudStats {
+    /// Combine the totals of syncs that ran at the same time.
+    pub fn merge(&mut self, other: &Self) {
+        self.files += other.files;
+        self.bytes += other.bytes;
+        self.elapsed = self.elapsed.max(other.elapsed);
+        self.speed += other.speed;
+    }
+}
+
+/// Size of a folder in the cloud, as reported by Rclone.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudSize {
+    /// Number of files.
+    pub files: u64,
+    /// Number of bytes.
+    pub bytes: u64,
+}
+
+impl CloudSize {
+    pub fn add(&mut self, other: &Self) {
+        self.files += other.files;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Parse the output of `rclone size --json`.
+fn parse_size(stdout: &str) -> Option<CloudSize> {
+    let parsed: serde_json::Value = serde_json::from_str(stdout).ok()?;
+    Some(CloudSize {
+        files: parsed.get("count")?.as_u64()?,
+        bytes: parsed.get("bytes")?.as_u64()?,
+    })
+}
+
+/// Placeholder for an argument that was left out of a printed command.
+/// The angle brackets keep the command from running as-is in a shell.
+pub const REDACTED_ARG: &str = "<REDACTED>";
+
+/// Custom Rclone flags whose values are likely to be credentials.
+const SENSITIVE_FLAG_WORDS: &[&str] = &["pass", "secret", "token", "key", "auth", "credential"];
+
+fn is_sensitive_flag(flag: &str) -> bool {
+    let flag = flag.to_lowercase();
+    flag.starts_with("--") && SENSITIVE_FLAG_WORDS.iter().any(|word| flag.contains(word))
+}
+
+/// Replace the values of any sensitive flags,
+/// whether they are given as `--flag=value` or `--flag value`.
+fn redact_args(args: &[String]) -> Vec<String> {
+    let mut redacted = vec![];
+    let mut redact_next = false;
+
+    for arg in args {
+        if std::mem::take(&mut redact_next) && !arg.starts_with('-') {
+            redacted.push(REDACTED_ARG.to_string());
+            continue;
+        }
+
+        match arg.split_once('=') {
+            Some((flag, _)) if is_sensitive_flag(flag) => {
+                redacted.push(format!("{flag}={REDACTED_ARG}"));
+            }
+            None if is_sensitive_flag(arg) => {
+                redacted.push(arg.clone());
+                redact_next = true;
+            }
+            _ => {
+                redacted.push(arg.clone());
+            }
+        }
+    }
+
+    redacted
+}
+
+/// Split game folders into up to `limit` disjoint groups of similar size,
+/// so that each group can be synchronized by a separate Rclone process.
+pub fn partition_game_dirs(game_dirs: &[String], limit: usize) -> Vec<Vec<String>> {
+    let groups = limit.clamp(1, game_dirs.len().max(1));
+    let mut partitioned = vec![vec![]; groups];
+    for (i, game_dir) in game_dirs.iter().enumerate() {
+        partitioned[i % groups].push(game_dir.clone());
+    }
+    partitioned
+}
+
+/// Parse the output of `rclone hashsum`, where each line is a hash and a relative path.
+fn parse_hashsum(stdout: &str) -> BTreeMap<String, String> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split_once("  "))
+        .map(|(hash, path)| (path.to_string(), hash.to_string()))
+        .collect()
+}
+
 #[derive(Clone, Debug)]
 pub enum RcloneProcessEvent {
     Progress { current: f32, max: f32 },
     Change(CloudChange),
+    Stats(CloudStats),
 }
 
 #[derive(Debug)]
@@ -82,6 +280,15 @@ impl RcloneProcess {
     }
 
     pub fn events(&mut self) -> Vec<RcloneProcessEvent> {
+        self.read_events(Some(10))
+    }
+
+    /// Read all of the remaining events once the process has finished.
+    pub fn remaining_events(&mut self) -> Vec<RcloneProcessEvent> {
+        self.read_events(None)
+    }
+
+    fn read_events(&mut self, limit: Option<usize>) -> Vec<RcloneProcessEvent> {
         let mut events = vec![];
 
         #[derive(Debug, serde::Deserialize)]
@@ -97,10 +304,16 @@ impl RcloneProcess {
         struct Stats {
             bytes: f32,
             total_bytes: f32,
+            #[serde(default)]
+            transfers: u64,
+            #[serde(default)]
+            elapsed_time: f64,
+            #[serde(default)]
+            speed: f64,
         }
 
         if let Some(stderr) = self.stderr.as_mut() {
-            for line in stderr.lines().take(10).filter_map(|x| x.ok()) {
+            for line in stderr.lines().take(limit.unwrap_or(usize::MAX)).filter_map(|x| x.ok()) {
                 match serde_json::from_str::<Log>(&line) {
                     Ok(Log::Skip { skipped, object }) => match skipped.as_str() {
                         "copy" => events.push(RcloneProcessEvent::Change(CloudChange {
@@ -133,7 +346,14 @@ impl RcloneProcess {
                         }
                     },
                     Ok(Log::Stats {
-                        stats: Stats { bytes, total_bytes },
+                        stats:
+                            Stats {
+                                bytes,
+                                total_bytes,
+                                transfers,
+                                elapsed_time,
+                                speed,
+                            },
                     }) => {
                         if total_bytes > 0.0 {
                             events.push(RcloneProcessEvent::Progress {
@@ -141,6 +361,12 @@ impl RcloneProcess {
                                 max: total_bytes,
                             });
                         }
+                        events.push(RcloneProcessEvent::Stats(CloudStats {
+                            files: transfers,
+                            bytes: bytes as u64,
+                            elapsed: elapsed_time,
+                            speed,
+                        }));
                     }
                     Err(_) => {
                         log::trace!("Unhandled Rclone message: {line}");
@@ -219,6 +445,8 @@ pub enum RemoteChoice {
     OneDrive,
     Smb,
     WebDav,
+    S3,
+    Http,
 }
 
 impl RemoteChoice {
@@ -231,6 +459,8 @@ impl RemoteChoice {
         Self::Ftp,
         Self::Smb,
         Self::WebDav,
+        Self::S3,
+        Self::Http,
         Self::Custom,
     ];
 }
@@ -247,6 +477,8 @@ impl ToString for RemoteChoice {
             Self::OneDrive => "OneDrive".to_string(),
             Self::Smb => "SMB".to_string(),
             Self::WebDav => "WebDAV".to_string(),
+            Self::S3 => "S3".to_string(),
+            Self::Http => "HTTP".to_string(),
         }
     }
 }
@@ -293,6 +525,21 @@ pub enum Remote {
         password: String,
         provider: WebDavProvider,
     },
+    /// Amazon S3 or a compatible service, like MinIO.
+    S3 {
+        id: String,
+        endpoint: String,
+        bucket: String,
+        access_key: String,
+        #[serde(skip, default)]
+        secret_key: String,
+        #[serde(default)]
+        region: String,
+    },
+    Http {
+        id: String,
+        url: String,
+    },
 }
 
 impl Remote {
@@ -306,6 +553,8 @@ impl Remote {
             Remote::Ftp { id, .. } => id,
             Remote::Smb { id, .. } => id,
             Remote::WebDav { id, .. } => id,
+            Remote::S3 { id, .. } => id,
+            Remote::Http { id, .. } => id,
         }
     }
 
@@ -319,6 +568,8 @@ impl Remote {
             Self::OneDrive { .. } => "onedrive",
             Self::Smb { .. } => "smb",
             Self::WebDav { .. } => "webdav",
+            Self::S3 { .. } => "s3",
+            Self::Http { .. } => "http",
         }
     }
 
@@ -369,6 +620,27 @@ impl Remote {
                 format!("pass={password}"),
                 format!("vendor={}", provider.slug()),
             ]),
+            Self::S3 {
+                id: _,
+                endpoint,
+                bucket: _,
+                access_key,
+                secret_key,
+                region,
+            } => {
+                let mut args = vec![
+                    "provider=Other".to_string(),
+                    "env_auth=false".to_string(),
+                    format!("access_key_id={access_key}"),
+                    format!("secret_access_key={secret_key}"),
+                    format!("endpoint={endpoint}"),
+                ];
+                if !region.is_empty() {
+                    args.push(format!("region={region}"));
+                }
+                Some(args)
+            }
+            Self::Http { id: _, url } => Some(vec![format!("url={url}")]),
         }
     }
 
@@ -381,10 +653,56 @@ impl Remote {
             | Self::GoogleDrive { .. }
             | Self::OneDrive { .. }
             | Self::Smb { .. }
-            | Self::WebDav { .. } => true,
+            | Self::WebDav { .. }
+            | Self::S3 { .. }
+            | Self::Http { .. } => true,
         }
     }
 
+    /// Names of the settings that must be filled in before Rclone can use this remote.
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        match self {
+            Self::S3 {
+                endpoint,
+                bucket,
+                access_key,
+                secret_key,
+                ..
+            } => [
+                ("endpoint", endpoint),
+                ("bucket", bucket),
+                ("access-key", access_key),
+                ("secret-key", secret_key),
+            ]
+            .into_iter()
+            .filter(|(_, value)| value.trim().is_empty())
+            .map(|(field, _)| field)
+            .collect(),
+            Self::Custom { .. }
+            | Self::Box { .. }
+            | Self::Dropbox { .. }
+            | Self::Ftp { .. }
+            | Self::GoogleDrive { .. }
+            | Self::OneDrive { .. }
+            | Self::Smb { .. }
+            | Self::WebDav { .. }
+            | Self::Http { .. } => vec![],
+        }
+    }
+
+    /// Rclone paths for bucket-based backends must start with the bucket name.
+    fn bucket(&self) -> Option<&str> {
+        match self {
+            Self::S3 { bucket, .. } => Some(bucket),
+            _ => None,
+        }
+    }
+
+    /// Whether Rclone can only read from this remote, so it can't be used for uploads.
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, Self::Http { .. })
+    }
+
     pub fn description(&self) -> Option<String> {
         match self {
             Remote::Ftp {
@@ -394,6 +712,8 @@ impl Remote {
                 host, port, username, ..
             } => Some(format!("{}@{}:{}", username, host, port)),
             Remote::WebDav { url, provider, .. } => Some(format!("{} - {}", provider.to_string(), url)),
+            Remote::S3 { endpoint, bucket, .. } => Some(format!("{} - {}", endpoint, bucket)),
+            Remote::Http { url, .. } => Some(url.clone()),
             _ => None,
         }
     }
@@ -415,6 +735,8 @@ impl From<Option<&Remote>> for RemoteChoice {
                 Remote::OneDrive { .. } => RemoteChoice::OneDrive,
                 Remote::Smb { .. } => RemoteChoice::Smb,
                 Remote::WebDav { .. } => RemoteChoice::WebDav,
+                Remote::S3 { .. } => RemoteChoice::S3,
+                Remote::Http { .. } => RemoteChoice::Http,
             }
         } else {
             RemoteChoice::None
@@ -464,6 +786,18 @@ impl TryFrom<RemoteChoice> for Remote {
                 password: String::new(),
                 provider: WebDavProvider::Other,
             }),
+            RemoteChoice::S3 => Ok(Remote::S3 {
+                id: Remote::generate_id(),
+                endpoint: String::new(),
+                bucket: String::new(),
+                access_key: String::new(),
+                secret_key: String::new(),
+                region: String::new(),
+            }),
+            RemoteChoice::Http => Ok(Remote::Http {
+                id: Remote::generate_id(),
+                url: String::new(),
+            }),
         }
     }
 }
@@ -543,17 +877,32 @@ impl std::str::FromStr for WebDavProvider {
 pub struct Rclone {
     app: App,
     remote: Remote,
+    transfers: Option<NonZeroUsize>,
 }
 
 impl Rclone {
     pub fn new(app: App, remote: Remote) -> Self {
-        Self { app, remote }
+        Self {
+            app,
+            remote,
+            transfers: None,
+        }
+    }
+
+    /// Set how many files to transfer at once when synchronizing.
+    pub fn with_transfers(mut self, transfers: Option<NonZeroUsize>) -> Self {
+        self.transfers = transfers;
+        self
     }
 
     fn path(&self, path: &str) -> String {
         // Rclone doesn't allow backslashes in non-local paths:
         // https://rclone.org/docs/#syntax-of-remote-paths
-        format!("{}:{}", self.remote.id(), path.replace('\\', "/"))
+        let path = path.replace('\\', "/");
+        match self.remote.bucket() {
+            Some(bucket) => format!("{}:{}/{}", self.remote.id(), bucket, path.trim_start_matches('/')),
+            None => format!("{}:{}", self.remote.id(), path),
+        }
     }
 
     fn args(&self, args: &[String]) -> Vec<String> {
@@ -593,7 +942,8 @@ impl Rclone {
             | Remote::Box { .. }
             | Remote::Dropbox { .. }
             | Remote::GoogleDrive { .. }
-            | Remote::OneDrive { .. } => {}
+            | Remote::OneDrive { .. }
+            | Remote::Http { .. } => {}
             Remote::Ftp { password, .. } => {
                 privacy = Privacy::Private;
                 *password = self.obscure(password)?;
@@ -606,6 +956,10 @@ impl Rclone {
                 privacy = Privacy::Private;
                 *password = self.obscure(password)?;
             }
+            Remote::S3 { .. } => {
+                // Rclone stores S3 keys as-is, but they still shouldn't be logged.
+                privacy = Privacy::Private;
+            }
         }
 
         let mut args = vec![
@@ -634,6 +988,59 @@ impl Rclone {
         Ok(())
     }
 
+    /// Read Rclone's stored settings for the remote.
+    /// These may include credentials or access tokens.
+    pub fn export_remote(&self) -> Result<Option<BTreeMap<String, String>>, CommandError> {
+        if !self.remote.needs_configuration() {
+            return Ok(None);
+        }
+
+        let args = vec!["config".to_string(), "dump".to_string()];
+        let out = self.run(&args, &[0], Privacy::Private)?;
+
+        let mut remotes =
+            serde_json::from_str::<BTreeMap<String, BTreeMap<String, String>>>(&out.stdout).map_err(|_| {
+                CommandError::Exited {
+                    program: self.app.path.raw(),
+                    args: args.clone(),
+                    code: 0,
+                    stdout: None,
+                    stderr: None,
+                }
+            })?;
+
+        Ok(remotes.remove(self.remote.id()))
+    }
+
+    /// Recreate the remote from settings produced by `export_remote`.
+    pub fn import_remote(&self, settings: &BTreeMap<String, String>) -> Result<(), CommandError> {
+        if !self.remote.needs_configuration() {
+            return Ok(());
+        }
+
+        let mut args = vec![
+            "config".to_string(),
+            "create".to_string(),
+            self.remote.id().to_string(),
+            settings
+                .get("type")
+                .cloned()
+                .unwrap_or_else(|| self.remote.slug().to_string()),
+        ];
+
+        for (key, value) in settings {
+            if key != "type" {
+                args.push(format!("{key}={value}"));
+            }
+        }
+
+        // The exported values are already obscured where applicable.
+        args.push("--no-obscure".to_string());
+
+        self.run(&args, &[0], Privacy::Private)?;
+        Ok(())
+    }
+
     pub fn sync(
         &self,
         local: &StrictPath,
@@ -641,12 +1048,155 @@ impl Rclone {
         direction: SyncDirection,
         finality: Finality,
         game_dirs: &[String],
+        ignore_rules: &[String],
     ) -> Result<RcloneProcess, CommandError> {
         if direction == SyncDirection::Upload && !local.exists() {
             // Rclone will fail with exit code 3 if the local folder does not exist.
             _ = local.create_dirs();
         }
 
+        RcloneProcess::launch(
+            self.app.path.raw(),
+            self.sync_args(local, remote_path, direction, finality, game_dirs, ignore_rules),
+        )
+    }
+
+    /// Render the command that `sync` would run, so that it can be run by hand instead.
+    /// The values of sensitive custom arguments are replaced with `REDACTED_ARG`.
+    pub fn sync_command(
+        &self,
+        local: &StrictPath,
+        remote_path: &str,
+        direction: SyncDirection,
+        finality: Finality,
+        game_dirs: &[String],
+        ignore_rules: &[String],
+    ) -> String {
+        let args = self.sync_args(local, remote_path, direction, finality, game_dirs, ignore_rules);
+
+        // Only the custom arguments from the config can contain credentials.
+        let custom = shlex::split(&self.app.arguments).map(|x| x.len()).unwrap_or(0);
+        let mut redacted = redact_args(&args[..custom]);
+        redacted.extend_from_slice(&args[custom..]);
+
+        std::iter::once(self.app.path.render())
+            .chain(redacted)
+            .map(|arg| {
+                if arg.contains(REDACTED_ARG) {
+                    arg
+                } else {
+                    shlex::try_quote(&arg).map(|x| x.to_string()).unwrap_or(arg)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Compare the uploaded files against the local copies.
+    /// The cloud copies are downloaded to be hashed,
+    /// since not every remote supports the same hash types.
+    /// Returns the paths of any files that do not match.
+    pub fn verify_upload(
+        &self,
+        local: &StrictPath,
+        remote_path: &str,
+        changes: &[CloudChange],
+    ) -> Result<Vec<String>, CommandError> {
+        let uploaded: Vec<_> = changes
+            .iter()
+            .filter(|x| matches!(x.change, ScanChange::New | ScanChange::Different))
+            .collect();
+        if uploaded.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut game_dirs: Vec<_> = uploaded.iter().filter_map(|x| x.game_dir()).map(String::from).collect();
+        game_dirs.sort();
+        game_dirs.dedup();
+        if uploaded.iter().any(|x| x.game_dir().is_none()) {
+            // Some files are outside of any game folder, so we have to check everything.
+            game_dirs.clear();
+        }
+
+        let local_hashes = self.hashsum(local.render(), false, &game_dirs)?;
+        let remote_hashes = self.hashsum(self.path(remote_path), true, &game_dirs)?;
+
+        Ok(uploaded
+            .into_iter()
+            .filter(|x| match remote_hashes.get(&x.path) {
+                Some(remote) => local_hashes.get(&x.path) != Some(remote),
+                None => true,
+            })
+            .map(|x| x.path.clone())
+            .collect())
+    }
+
+    /// Measure a folder in the cloud.
+    /// Returns `None` if the folder does not exist,
+    /// or an error if Rclone's output cannot be parsed.
+    pub fn size(&self, remote_path: &str) -> Result<Option<CloudSize>, CommandError> {
+        // Rclone uses exit code 3 when the directory is not found.
+        let args = self.size_args(remote_path);
+        let out = self.run(&args, &[0, 3], Privacy::Public)?;
+        if out.code == 3 {
+            return Ok(None);
+        }
+        match parse_size(&out.stdout) {
+            Some(size) => Ok(Some(size)),
+            None => Err(CommandError::Exited {
+                program: self.app.path.raw(),
+                args,
+                code: out.code,
+                stdout: Some(out.stdout),
+                stderr: None,
+            }),
+        }
+    }
+
+    fn size_args(&self, remote_path: &str) -> Vec<String> {
+        vec!["size".to_string(), "--json".to_string(), self.path(remote_path)]
+    }
+
+    /// Delete a folder in the cloud, along with everything in it.
+    pub fn purge(&self, remote_path: &str) -> Result<(), CommandError> {
+        self.run(&["purge".to_string(), self.path(remote_path)], &[0], Privacy::Public)?;
+        Ok(())
+    }
+
+    fn hashsum(
+        &self,
+        target: String,
+        download: bool,
+        game_dirs: &[String],
+    ) -> Result<BTreeMap<String, String>, CommandError> {
+        let out = self.run(&self.hashsum_args(target, download, game_dirs), &[0], Privacy::Public)?;
+        Ok(parse_hashsum(&out.stdout))
+    }
+
+    fn hashsum_args(&self, target: String, download: bool, game_dirs: &[String]) -> Vec<String> {
+        let mut args = vec!["hashsum".to_string(), "MD5".to_string()];
+
+        if download {
+            args.push("--download".to_string());
+        }
+
+        for game_dir in game_dirs {
+            args.push(format!("--include=/{game_dir}/**"));
+        }
+
+        args.push(target);
+        args
+    }
+
+    fn sync_args(
+        &self,
+        local: &StrictPath,
+        remote_path: &str,
+        direction: SyncDirection,
+        finality: Finality,
+        game_dirs: &[String],
+        ignore_rules: &[String],
+    ) -> Vec<String> {
         let mut args = vec![
             "sync".to_string(),
             "-v".to_string(),
@@ -654,13 +1204,35 @@ impl Rclone {
             "--stats=100ms".to_string(),
         ];
 
+        if let Some(transfers) = self.transfers {
+            args.push(format!("--transfers={transfers}"));
+        }
+
         if finality.preview() {
-            args.push("--dry-run".to_string());
+            // This comes after the custom arguments from the config,
+            // so that they cannot turn it back off.
+            args.push("--dry-run=true".to_string());
         }
 
-        for game_dir in game_dirs {
-            // Inclusion rules are file-based, so we have to add `**`.
-            args.push(format!("--include=/{game_dir}/**"));
+        if ignore_rules.is_empty() && !local.joined(IGNORE_FILE).is_file() {
+            for game_dir in game_dirs {
+                // Inclusion rules are file-based, so we have to add `**`.
+                args.push(format!("--include=/{game_dir}/**"));
+            }
+        } else {
+            // Rclone checks `--include` before any `--filter` or `--filter-from`,
+            // so the game folders have to be filters as well for the ignore rules to take precedence.
+            // The ignore file itself stays local, so that a download can't delete it.
+            args.push(format!("--filter=- /{IGNORE_FILE}"));
+            for rule in ignore_rules {
+                args.push(format!("--filter={rule}"));
+            }
+            for game_dir in game_dirs {
+                args.push(format!("--filter=+ /{game_dir}/**"));
+            }
+            if !game_dirs.is_empty() {
+                args.push("--filter=- **".to_string());
+            }
         }
 
         match direction {
@@ -674,7 +1246,7 @@ impl Rclone {
             }
         }
 
-        RcloneProcess::launch(self.app.path.raw(), self.args(&args))
+        self.args(&args)
     }
 }
 
@@ -812,3 +1384,406 @@ pub mod rclone_monitor {
         Subscription::run(unfold)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::btree_map;
+
+    use super::*;
+    use crate::testing::{repo, s};
+
+    fn rclone(arguments: &str) -> Rclone {
+        Rclone::new(
+            App {
+                path: StrictPath::new(s("rclone")),
+                arguments: arguments.to_string(),
+            },
+            Remote::Custom { id: s("remote") },
+        )
+    }
+
+    #[test]
+    fn can_validate_cloud_remote() {
+        assert!(validate_cloud_remote("ludusavi-1234").is_ok());
+        assert!(validate_cloud_remote("my drive@work").is_ok());
+        assert!(validate_cloud_remote("").is_err());
+        assert!(validate_cloud_remote("-remote").is_err());
+        assert!(validate_cloud_remote("remote ").is_err());
+        assert!(validate_cloud_remote("remote:path").is_err());
+    }
+
+    #[test]
+    fn rejects_uploads_to_read_only_remote() {
+        let mut config = Config::default();
+        config.apps.rclone.path = StrictPath::new(format!("{}/Cargo.toml", repo()));
+        config.cloud.remote = Some(Remote::Http {
+            id: s("ludusavi-1234"),
+            url: s("https://example.com/saves"),
+        });
+
+        assert_eq!(
+            Err(Error::CloudRemoteReadOnly),
+            validate_cloud_config(&config, "ludusavi-backup", SyncDirection::Upload)
+        );
+        assert_eq!(
+            config.cloud.remote,
+            validate_cloud_config(&config, "ludusavi-backup", SyncDirection::Download).ok()
+        );
+    }
+
+    #[test]
+    fn can_validate_s3_remote_fields() {
+        let remote = Remote::S3 {
+            id: s("ludusavi-1234"),
+            endpoint: s("https://minio.example.com"),
+            bucket: s("saves"),
+            access_key: s("access"),
+            secret_key: s("secret"),
+            region: s(""),
+        };
+        assert_eq!(Ok(()), validate_remote_fields(&remote));
+
+        let remote = Remote::S3 {
+            id: s("ludusavi-1234"),
+            endpoint: s(""),
+            bucket: s("saves"),
+            access_key: s("access"),
+            secret_key: s(" "),
+            region: s("us-east-1"),
+        };
+        assert_eq!(
+            Err(Error::CloudRemoteIncomplete {
+                fields: vec![s("endpoint"), s("secret-key")],
+            }),
+            validate_remote_fields(&remote),
+        );
+    }
+
+    #[test]
+    fn can_validate_s3_remote_after_reloading_config() {
+        let mut config = Config::default();
+        config.apps.rclone.path = StrictPath::new(format!("{}/Cargo.toml", repo()));
+        config.cloud.remote = Some(Remote::S3 {
+            id: s("ludusavi-1234"),
+            endpoint: s("https://minio.example.com"),
+            bucket: s("saves"),
+            access_key: s("access"),
+            secret_key: s("secret"),
+            region: s(""),
+        });
+
+        let reloaded: Config = serde_yaml::from_str(&serde_yaml::to_string(&config).unwrap()).unwrap();
+        assert!(matches!(
+            &reloaded.cloud.remote,
+            Some(Remote::S3 { secret_key, .. }) if secret_key.is_empty()
+        ));
+        assert_eq!(
+            reloaded.cloud.remote,
+            validate_cloud_config(&reloaded, "ludusavi-backup", SyncDirection::Upload).ok()
+        );
+    }
+
+    #[test]
+    fn can_build_s3_config_args() {
+        let remote = Remote::S3 {
+            id: s("ludusavi-1234"),
+            endpoint: s("https://minio.example.com"),
+            bucket: s("saves"),
+            access_key: s("access"),
+            secret_key: s("secret"),
+            region: s("us-east-1"),
+        };
+        assert_eq!(
+            Some(vec![
+                s("provider=Other"),
+                s("env_auth=false"),
+                s("access_key_id=access"),
+                s("secret_access_key=secret"),
+                s("endpoint=https://minio.example.com"),
+                s("region=us-east-1"),
+            ]),
+            remote.config_args(),
+        );
+    }
+
+    #[test]
+    fn can_build_path_within_bucket() {
+        let rclone = Rclone::new(
+            App {
+                path: StrictPath::new(s("rclone")),
+                arguments: s(""),
+            },
+            Remote::S3 {
+                id: s("remote"),
+                endpoint: s("https://minio.example.com"),
+                bucket: s("saves"),
+                access_key: s("access"),
+                secret_key: s("secret"),
+                region: s(""),
+            },
+        );
+        assert_eq!(
+            "remote:saves/ludusavi-backup/game",
+            rclone.path("/ludusavi-backup\\game")
+        );
+        assert_eq!("remote:saves/ludusavi-backup", rclone.path("ludusavi-backup"));
+    }
+
+    #[test]
+    fn can_determine_game_dir_of_change() {
+        let change = |path: &str| CloudChange {
+            path: path.to_string(),
+            change: ScanChange::New,
+        };
+
+        assert_eq!(Some("game1"), change("game1/mapping.yaml").game_dir());
+        assert_eq!(Some("game1"), change("game1/drive-X/file.txt").game_dir());
+        assert_eq!(None, change("file.txt").game_dir());
+    }
+
+    #[test]
+    fn can_partition_game_dirs() {
+        let dirs = vec![s("a"), s("b"), s("c"), s("d"), s("e")];
+
+        assert_eq!(vec![dirs.clone()], partition_game_dirs(&dirs, 1));
+        assert_eq!(
+            vec![vec![s("a"), s("c"), s("e")], vec![s("b"), s("d")]],
+            partition_game_dirs(&dirs, 2),
+        );
+        assert_eq!(
+            vec![vec![s("a")], vec![s("b")], vec![s("c")], vec![s("d")], vec![s("e")]],
+            partition_game_dirs(&dirs, 10),
+        );
+        assert_eq!(vec![Vec::<String>::new()], partition_game_dirs(&[], 3));
+    }
+
+    #[test]
+    fn can_merge_stats() {
+        let mut stats = CloudStats {
+            files: 1,
+            bytes: 100,
+            elapsed: 2.0,
+            speed: 50.0,
+        };
+        stats.merge(&CloudStats {
+            files: 2,
+            bytes: 300,
+            elapsed: 3.0,
+            speed: 100.0,
+        });
+
+        assert_eq!(
+            CloudStats {
+                files: 3,
+                bytes: 400,
+                elapsed: 3.0,
+                speed: 150.0,
+            },
+            stats,
+        );
+    }
+
+    #[test]
+    fn can_build_sync_args_for_preview() {
+        assert_eq!(
+            vec![
+                s("--dry-run=false"),
+                s("sync"),
+                s("-v"),
+                s("--use-json-log"),
+                s("--stats=100ms"),
+                s("--dry-run=true"),
+                s("--include=/game/**"),
+                s("remote:/cloud"),
+                s("/local"),
+            ],
+            rclone("--dry-run=false").sync_args(
+                &StrictPath::new(s("/local")),
+                "/cloud",
+                SyncDirection::Download,
+                Finality::Preview,
+                &[s("game")],
+                &[],
+            ),
+        );
+    }
+
+    #[test]
+    fn can_build_sync_args_for_final() {
+        assert_eq!(
+            vec![
+                s("sync"),
+                s("-v"),
+                s("--use-json-log"),
+                s("--stats=100ms"),
+                s("/local"),
+                s("remote:/cloud"),
+            ],
+            rclone("").sync_args(
+                &StrictPath::new(s("/local")),
+                "/cloud",
+                SyncDirection::Upload,
+                Finality::Final,
+                &[],
+                &[],
+            ),
+        );
+    }
+
+    #[test]
+    fn can_build_sync_args_with_ignore_rules() {
+        assert_eq!(
+            vec![
+                s("sync"),
+                s("-v"),
+                s("--use-json-log"),
+                s("--stats=100ms"),
+                s("--filter=- /.ludusaviignore"),
+                s("--filter=- *.tmp"),
+                s("--filter=+ /game/**"),
+                s("--filter=- **"),
+                s("/local"),
+                s("remote:/cloud"),
+            ],
+            rclone("").sync_args(
+                &StrictPath::new(s("/local")),
+                "/cloud",
+                SyncDirection::Upload,
+                Finality::Final,
+                &[s("game")],
+                &[s("- *.tmp")],
+            ),
+        );
+    }
+
+    #[test]
+    fn can_build_sync_args_with_ignore_file_without_rules() {
+        let local = StrictPath::new(format!("{}/tests/cloud-ignore", repo()));
+        assert_eq!(
+            vec![
+                s("sync"),
+                s("-v"),
+                s("--use-json-log"),
+                s("--stats=100ms"),
+                s("--filter=- /.ludusaviignore"),
+                local.render(),
+                s("remote:/cloud"),
+            ],
+            rclone("").sync_args(&local, "/cloud", SyncDirection::Upload, Finality::Final, &[], &[]),
+        );
+    }
+
+    #[test]
+    fn can_parse_ignore_rules() {
+        assert_eq!(
+            Ok(vec![s("- *.tmp"), s("+ /game/**"), s("!")]),
+            parse_ignore_rules("# comment\n- *.tmp\n\n; comment\n+ /game/**\n!\n"),
+        );
+        assert_eq!(Err(2), parse_ignore_rules("- *.tmp\n*.log\n"));
+    }
+
+    #[test]
+    fn can_redact_sensitive_args() {
+        assert_eq!(
+            vec![
+                s("--drive-client-secret=<REDACTED>"),
+                s("--webdav-pass"),
+                s("<REDACTED>"),
+                s("--fast-list"),
+                s("--bwlimit=1M"),
+                s("--drive-token"),
+                s("--transfers"),
+                s("4"),
+            ],
+            redact_args(&[
+                s("--drive-client-secret=abc"),
+                s("--webdav-pass"),
+                s("xyz"),
+                s("--fast-list"),
+                s("--bwlimit=1M"),
+                s("--drive-token"),
+                s("--transfers"),
+                s("4"),
+            ]),
+        );
+    }
+
+    #[test]
+    fn can_build_sync_command() {
+        assert_eq!(
+            "rclone --fast-list --drive-client-secret=<REDACTED> sync -v --use-json-log '--stats=100ms' '--dry-run=true' '--include=/game 1/**' /local remote:/cloud",
+            rclone("--fast-list --drive-client-secret=abc").sync_command(
+                &StrictPath::new(s("/local")),
+                "/cloud",
+                SyncDirection::Upload,
+                Finality::Preview,
+                &[s("game 1")],
+                &[],
+            ),
+        );
+    }
+
+    #[test]
+    fn can_build_sync_command_with_transfers() {
+        assert_eq!(
+            "rclone sync -v --use-json-log '--stats=100ms' '--transfers=8' /local remote:/cloud",
+            rclone("").with_transfers(NonZeroUsize::new(8)).sync_command(
+                &StrictPath::new(s("/local")),
+                "/cloud",
+                SyncDirection::Upload,
+                Finality::Final,
+                &[],
+                &[],
+            ),
+        );
+    }
+
+    #[test]
+    fn can_build_size_args() {
+        assert_eq!(
+            vec![s("size"), s("--json"), s("remote:/cloud/game")],
+            rclone("").size_args("/cloud/game"),
+        );
+    }
+
+    #[test]
+    fn can_parse_size() {
+        assert_eq!(
+            Some(CloudSize { files: 3, bytes: 1536 }),
+            parse_size(r#"{"count":3,"bytes":1536,"sizeless":0}"#),
+        );
+        assert_eq!(None, parse_size(""));
+    }
+
+    #[test]
+    fn can_build_hashsum_args() {
+        assert_eq!(
+            vec![s("hashsum"), s("MD5"), s("/local")],
+            rclone("").hashsum_args(s("/local"), false, &[]),
+        );
+        assert_eq!(
+            vec![
+                s("hashsum"),
+                s("MD5"),
+                s("--download"),
+                s("--include=/game/**"),
+                s("remote:/cloud"),
+            ],
+            rclone("").hashsum_args(s("remote:/cloud"), true, &[s("game")]),
+        );
+    }
+
+    #[test]
+    fn can_parse_hashsum() {
+        assert_eq!(
+            btree_map! {
+                s("game/mapping.yaml"): s("d41d8cd98f00b204e9800998ecf8427e"),
+                s("game/backup-1/file name.txt"): s("0cc175b9c0f1b6a831c399e269772661"),
+            },
+            parse_hashsum(
+                "d41d8cd98f00b204e9800998ecf8427e  game/mapping.yaml\n0cc175b9c0f1b6a831c399e269772661  game/backup-1/file name.txt\n"
+            ),
+        );
+    }
+}
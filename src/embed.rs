@@ -0,0 +1,174 @@
+//! Entry points for running Ludusavi's operations from another application,
+//! without the CLI's terminal output, progress bars, or prompts.
+
+use rayon::prelude::*;
+
+use crate::{
+    cli::evaluate_games,
+    resource::config::expand_env_var,
+    scan::{
+        git, layout::BackupLayout, prepare_backup_target, BackupOptions, BackupPipeline, Launchers, SteamShortcuts,
+        TitleFinder,
+    },
+};
+pub use crate::{
+    prelude::Error,
+    resource::{config::Config, manifest::Manifest, ResourceFile},
+    scan::{BackupInfo, OperationStepDecision, ScanInfo},
+};
+
+/// Receives progress and results while games are being backed up.
+/// Games are processed in parallel, so these may be called from multiple threads.
+pub trait BackupSink: Sync {
+    /// Called once before any games are scanned.
+    fn started(&self, _total_games: usize) {}
+
+    /// Called after each game is scanned and, unless previewing, backed up.
+    /// This is not called for games that were excluded by the backup filter.
+    fn game_completed(
+        &self,
+        _name: &str,
+        _scan_info: &ScanInfo,
+        _backup_info: &BackupInfo,
+        _decision: &OperationStepDecision,
+    ) {
+    }
+
+    /// Called once after all games have been processed.
+    fn finished(&self) {}
+}
+
+/// Back up games according to the config, reporting each one to the `sink`.
+/// If `games` is empty, then all games in the manifest are considered,
+/// and any that are disabled in the config will be scanned but not backed up.
+pub fn back_up_games(
+    config: &Config,
+    manifest: &Manifest,
+    games: Vec<String>,
+    preview: bool,
+    sink: &dyn BackupSink,
+) -> Result<(), Error> {
+    let backup_dir = expand_env_var(&config.backup.path)?;
+    if !preview {
+        prepare_backup_target(&backup_dir)?;
+        if config.backup.uses_git() {
+            git::validate()?;
+        }
+    }
+
+    let layout = BackupLayout::new(backup_dir, config.backup.retention.clone())
+        .with_per_game_retention(config.backup.per_game_retention.clone());
+    let title_finder = TitleFinder::new(config, manifest, layout.restorable_game_set());
+
+    let games_specified = !games.is_empty();
+    let games = evaluate_games(manifest.primary_titles(), games, &title_finder)
+        .map_err(|games| Error::CliUnrecognizedGames { games })?;
+
+    let roots = config.expanded_roots();
+    let launchers = Launchers::scan(&roots, manifest, &games, &title_finder, None);
+    let steam_shortcuts = SteamShortcuts::scan();
+    let options = BackupOptions::new(config);
+    let pipeline = BackupPipeline::new(
+        config,
+        manifest,
+        &layout,
+        &roots,
+        &launchers,
+        &steam_shortcuts,
+        &options,
+    );
+
+    sink.started(games.len());
+
+    games.par_iter().for_each(|name| {
+        let Some(scan_info) = pipeline.scan_game(name, games_specified, &None) else {
+            return;
+        };
+
+        let ignored = !config.is_game_enabled_for_backup(name) && !games_specified;
+        let decision = if ignored {
+            OperationStepDecision::Ignored
+        } else {
+            OperationStepDecision::Processed
+        };
+
+        let backup_info = if preview || ignored {
+            BackupInfo::default()
+        } else {
+            pipeline.back_up_game(name, &scan_info).0
+        };
+
+        sink.game_completed(name, &scan_info, &backup_info, &decision);
+    });
+
+    sink.finished();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        prelude::StrictPath,
+        resource::{config::Root, manifest::Store},
+        testing::repo,
+    };
+
+    #[derive(Default)]
+    struct CollectingSink {
+        total: Mutex<Option<usize>>,
+        games: Mutex<Vec<(String, usize)>>,
+        finished: Mutex<bool>,
+    }
+
+    impl BackupSink for CollectingSink {
+        fn started(&self, total_games: usize) {
+            *self.total.lock().unwrap() = Some(total_games);
+        }
+
+        fn game_completed(
+            &self,
+            name: &str,
+            scan_info: &ScanInfo,
+            _backup_info: &BackupInfo,
+            _decision: &OperationStepDecision,
+        ) {
+            self.games
+                .lock()
+                .unwrap()
+                .push((name.to_string(), scan_info.found_files.len()));
+        }
+
+        fn finished(&self) {
+            *self.finished.lock().unwrap() = true;
+        }
+    }
+
+    #[test]
+    fn can_back_up_games_in_preview_with_sink() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              files:
+                <base>/subdir/file2.txt: {}
+            "#,
+        )
+        .unwrap();
+        let mut config = Config {
+            roots: vec![Root::new(format!("{}/tests/root1", repo()), Store::Other)],
+            ..Default::default()
+        };
+        config.backup.path = StrictPath::new(format!("{}/tests/backup-nonexistent", repo()));
+
+        let sink = CollectingSink::default();
+        back_up_games(&config, &manifest, vec![], true, &sink).unwrap();
+
+        assert_eq!(Some(1), *sink.total.lock().unwrap());
+        assert_eq!(vec![("game1".to_string(), 1)], *sink.games.lock().unwrap());
+        assert!(*sink.finished.lock().unwrap());
+    }
+}
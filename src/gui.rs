@@ -17,12 +17,27 @@ mod style;
 mod undoable;
 mod widget;
 
-use iced::Size;
+use iced::{window::Position, Point, Size};
 
 use self::app::App;
 pub use crate::gui::common::Flags;
+use crate::resource::config::Config;
+
+const MIN_WINDOW_SIZE: Size = Size::new(800.0, 600.0);
 
 pub fn run(flags: Flags) {
+    // The app reuses this, so that the config is only read once on startup.
+    let config = Config::load();
+    let gui_config = config.as_ref().map(|x| x.gui.clone()).unwrap_or_default();
+    let window_size = gui_config
+        .window_size
+        .map(|x| Size::new(x.width as f32, x.height as f32).max(MIN_WINDOW_SIZE))
+        .unwrap_or(iced::window::Settings::default().size);
+    let window_position = gui_config
+        .window_position
+        .map(|x| Position::Specific(Point::new(x.x as f32, x.y as f32)))
+        .unwrap_or_default();
+
     let app = iced::application(App::title, App::update, App::view)
         .subscription(App::subscription)
         .theme(App::theme)
@@ -32,7 +47,9 @@ pub fn run(flags: Flags) {
             ..Default::default()
         })
         .window(iced::window::Settings {
-            min_size: Some(Size::new(800.0, 600.0)),
+            size: window_size,
+            position: window_position,
+            min_size: Some(MIN_WINDOW_SIZE),
             exit_on_close_request: false,
             #[cfg(target_os = "linux")]
             platform_specific: iced::window::settings::PlatformSpecific {
@@ -55,7 +72,7 @@ pub fn run(flags: Flags) {
             ..Default::default()
         });
 
-    if let Err(e) = app.run_with(move || app::App::new(flags)) {
+    if let Err(e) = app.run_with(move || app::App::new(flags, config)) {
         log::error!("Failed to initialize GUI: {e:?}");
         eprintln!("Failed to initialize GUI: {e:?}");
 
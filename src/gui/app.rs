@@ -18,16 +18,16 @@ use crate::{
         widget::{id, Column, Container, Element, IcedParentExt, Progress, Row, Stack},
     },
     lang::TRANSLATOR,
-    prelude::{app_dir, get_threads_from_env, initialize_rayon, Error, Finality, StrictPath, SyncDirection},
+    prelude::{get_threads_from_env, initialize_rayon, Error, Finality, StrictPath, SyncDirection},
     resource::{
         cache::{self, Cache},
-        config::{self, Config, CustomGame, CustomGameKind, Root},
+        config::{self, Config, CustomGame, CustomGameKind, Root, WindowPosition, WindowSize},
         manifest::Manifest,
         ResourceFile, SaveableResourceFile,
     },
     scan::{
-        layout::BackupLayout, prepare_backup_target, registry_compat::RegistryItem, scan_game_for_backup, BackupId,
-        Launchers, SteamShortcuts, TitleFinder,
+        git, layout::BackupLayout, prepare_backup_target, registry_compat::RegistryItem, BackupId, BackupOptions,
+        BackupPipeline, Launchers, SteamShortcuts, TitleFinder,
     },
 };
 
@@ -38,7 +38,9 @@ impl iced::Executor for Executor {
         let mut builder = tokio::runtime::Builder::new_multi_thread();
         builder.enable_all();
 
-        if let Some(threads) = get_threads_from_env().or_else(|| Config::load().ok().and_then(|x| x.runtime.threads)) {
+        if let Some(threads) =
+            get_threads_from_env().or_else(|| Config::load().ok().and_then(|x| x.runtime.scan_threads))
+        {
             initialize_rayon(threads);
             builder.worker_threads(threads.get());
         }
@@ -162,7 +164,7 @@ impl App {
 
             match item {
                 SaveKind::Config => self.config.save(),
-                SaveKind::Cache => self.cache.save(),
+                SaveKind::Cache => self.cache.save_maybe_compressed(self.config.runtime.compress_cache),
                 SaveKind::Backup(game) => self.restore_screen.log.save_layout(game),
             }
 
@@ -230,7 +232,15 @@ impl App {
         games: Option<&Vec<String>>,
         standalone: bool,
     ) -> Result<(), Error> {
-        let remote = crate::cloud::validate_cloud_config(&self.config, &self.config.cloud.path)?;
+        // Previews don't write anything, so they're allowed even for read-only remotes.
+        let remote = crate::cloud::validate_cloud_config(
+            &self.config,
+            &self.config.cloud.path,
+            match finality {
+                Finality::Preview => SyncDirection::Download,
+                Finality::Final => direction,
+            },
+        )?;
 
         let games = match games {
             Some(games) => {
@@ -241,8 +251,18 @@ impl App {
             None => vec![],
         };
 
-        let rclone = Rclone::new(self.config.apps.rclone.clone(), remote);
-        match rclone.sync(local, &self.config.cloud.path, direction, finality, &games) {
+        let ignore_rules = crate::cloud::load_ignore_rules(local)?;
+
+        let rclone =
+            Rclone::new(self.config.apps.rclone.clone(), remote).with_transfers(self.config.runtime.cloud_threads);
+        match rclone.sync(
+            local,
+            &self.config.cloud.path,
+            direction,
+            finality,
+            &games,
+            &ignore_rules,
+        ) {
             Ok(process) => {
                 if let Some(sender) = self.rclone_monitor_sender.as_mut() {
                     if standalone {
@@ -277,15 +297,23 @@ impl App {
                     self.reset_scroll_position(ScrollSubject::Backup);
                     cleared_log = true;
                 }
+                self.backup_screen
+                    .duplicate_detector
+                    .set_owners(self.config.backup.duplicate_owners.clone());
 
                 self.operation =
                     Operation::new_backup(if preview { Finality::Preview } else { Finality::Final }, games);
                 self.operation.set_force_new_full_backups(repair);
 
                 if !preview {
-                    if let Err(e) = prepare_backup_target(&self.config.backup.path) {
+                    if let Err(e) = prepare_backup_target(&self.config.backup_path()) {
                         return self.show_error(e);
                     }
+                    if self.config.backup.uses_git() {
+                        if let Err(e) = git::validate() {
+                            return self.show_error(e);
+                        }
+                    }
                 }
 
                 Task::batch([
@@ -302,12 +330,13 @@ impl App {
             BackupPhase::CloudCheck => {
                 if self.operation.preview()
                     || !self.config.cloud.synchronize
-                    || crate::cloud::validate_cloud_config(&self.config, &self.config.cloud.path).is_err()
+                    || crate::cloud::validate_cloud_config(&self.config, &self.config.cloud.path, SyncDirection::Upload)
+                        .is_err()
                 {
                     return self.handle_backup(BackupPhase::Load);
                 }
 
-                let local = self.config.backup.path.clone();
+                let local = self.config.backup_path();
                 let games = self.operation.games();
 
                 match self.start_sync_cloud(&local, SyncDirection::Upload, Finality::Preview, games.as_ref(), false) {
@@ -364,7 +393,8 @@ impl App {
                         retention.force_new_full = should_force_new_full_backups;
 
                         let roots = config.expanded_roots();
-                        let layout = BackupLayout::new(config.backup.path.clone(), retention);
+                        let layout = BackupLayout::new(config.backup_path(), retention)
+                            .with_per_game_retention(config.backup.per_game_retention.clone());
                         let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
                         let steam = SteamShortcuts::scan();
                         let launchers = Launchers::scan(&roots, &manifest, &subjects, &title_finder, None);
@@ -421,20 +451,21 @@ impl App {
                 self.register_notify_on_single_game_scanned();
 
                 let config = std::sync::Arc::new(self.config.clone());
+                let manifest = std::sync::Arc::new(manifest);
                 let roots = std::sync::Arc::new(config.expanded_roots());
                 let layout = std::sync::Arc::new(*layout);
                 let launchers = std::sync::Arc::new(launchers);
-                let filter = std::sync::Arc::new(self.config.backup.filter.clone());
+                let options = std::sync::Arc::new(BackupOptions::new(&self.config));
                 let steam_shortcuts = std::sync::Arc::new(steam);
                 let games_specified = self.operation.games_specified();
 
                 for key in subjects {
-                    let game = manifest.0[&key].clone();
                     let config = config.clone();
+                    let manifest = manifest.clone();
                     let roots = roots.clone();
                     let launchers = launchers.clone();
                     let layout = layout.clone();
-                    let filter = filter.clone();
+                    let options = options.clone();
                     let steam_shortcuts = steam_shortcuts.clone();
                     let cancel_flag = self.operation_should_cancel.clone();
                     self.operation_steps.push(Task::perform(
@@ -446,46 +477,35 @@ impl App {
                                 return (None, None);
                             }
 
-                            let previous =
-                                layout.latest_backup(&key, false, &config.redirects, &config.restore.toggled_paths);
-
-                            if filter.excludes(games_specified, previous.is_some(), &game.cloud) {
-                                log::trace!("[{key}] excluded by backup filter");
-                                return (None, None);
-                            }
-
-                            let scan_info = scan_game_for_backup(
-                                &game,
-                                &key,
+                            let pipeline = BackupPipeline::new(
+                                &config,
+                                &manifest,
+                                &layout,
                                 &roots,
-                                &app_dir(),
                                 &launchers,
-                                &filter,
-                                &None,
-                                // &ranking,
-                                &config.backup.toggled_paths,
-                                &config.backup.toggled_registry,
-                                previous,
-                                &config.redirects,
                                 &steam_shortcuts,
+                                &options,
                             );
+
+                            let Some(scan_info) = pipeline.scan_game(&key, games_specified, &None) else {
+                                return (None, None);
+                            };
                             if !config.is_game_enabled_for_backup(&key) && full {
                                 return (Some(scan_info), None);
                             }
 
                             let backup_info = if !preview {
-                                Some(layout.game_layout(&key).back_up(
-                                    &scan_info,
-                                    &chrono::Utc::now(),
-                                    &config.backup.format,
-                                ))
+                                Some(pipeline.back_up_game(&key, &scan_info).0)
                             } else {
                                 None
                             };
                             (Some(scan_info), backup_info)
                         },
                         move |(scan_info, backup_info)| {
-                            Message::Backup(BackupPhase::GameScanned { scan_info, backup_info })
+                            Message::Backup(BackupPhase::GameScanned {
+                                scan_info: scan_info.map(Box::new),
+                                backup_info,
+                            })
                         },
                     ));
                 }
@@ -498,7 +518,7 @@ impl App {
                 let restoring = false;
                 let full = self.operation.full();
 
-                if let Some(scan_info) = scan_info {
+                if let Some(scan_info) = scan_info.map(|x| *x) {
                     log::trace!(
                         "step {} / {}: {}",
                         self.progress.current,
@@ -559,7 +579,7 @@ impl App {
                     return self.handle_backup(BackupPhase::Done);
                 }
 
-                let local = self.config.backup.path.clone();
+                let local = self.config.backup_path();
                 let games = self.operation.games();
 
                 let changed_games: Vec<_> = self
@@ -651,7 +671,7 @@ impl App {
                     return Task::none();
                 }
 
-                let path = self.config.restore.path.clone();
+                let path = self.config.restore_path();
                 if !path.is_dir() {
                     return self.show_modal(Modal::Error {
                         variant: Error::RestorationSourceInvalid { path },
@@ -681,12 +701,17 @@ impl App {
             RestorePhase::CloudCheck => {
                 if self.operation.preview()
                     || !self.config.cloud.synchronize
-                    || crate::cloud::validate_cloud_config(&self.config, &self.config.cloud.path).is_err()
+                    || crate::cloud::validate_cloud_config(
+                        &self.config,
+                        &self.config.cloud.path,
+                        SyncDirection::Download,
+                    )
+                    .is_err()
                 {
                     return self.handle_restore(RestorePhase::Load);
                 }
 
-                let local = self.config.restore.path.clone();
+                let local = self.config.restore_path();
                 let games = self.operation.games();
 
                 match self.start_sync_cloud(&local, SyncDirection::Upload, Finality::Preview, games.as_ref(), false) {
@@ -701,7 +726,7 @@ impl App {
                 }
             }
             RestorePhase::Load => {
-                let restore_path = self.config.restore.path.clone();
+                let restore_path = self.config.restore_path();
 
                 let config = std::sync::Arc::new(self.config.clone());
 
@@ -788,7 +813,7 @@ impl App {
                             }
 
                             let backup_info = if scan_info.backup.is_some() && !preview {
-                                Some(layout.restore(&scan_info, &config.restore.toggled_registry))
+                                Some(layout.restore(&scan_info, &config.restore.toggled_registry, false))
                             } else {
                                 None
                             };
@@ -796,7 +821,7 @@ impl App {
                         },
                         move |(scan_info, backup_info, game_layout)| {
                             Message::Restore(RestorePhase::GameScanned {
-                                scan_info,
+                                scan_info: scan_info.map(Box::new),
                                 backup_info,
                                 game_layout: Box::new(game_layout),
                             })
@@ -816,7 +841,7 @@ impl App {
                 let restoring = true;
                 let full = self.operation.full();
 
-                if let Some(scan_info) = scan_info {
+                if let Some(scan_info) = scan_info.map(|x| *x) {
                     log::trace!(
                         "step {} / {}: {}",
                         self.progress.current,
@@ -927,7 +952,7 @@ impl App {
                     return Task::none();
                 }
 
-                let path = self.config.restore.path.clone();
+                let path = self.config.restore_path();
                 if !path.is_dir() {
                     return self.show_modal(Modal::Error {
                         variant: Error::RestorationSourceInvalid { path },
@@ -942,7 +967,7 @@ impl App {
                 Task::batch([self.close_modal(), self.handle_validation(ValidatePhase::Load)])
             }
             ValidatePhase::Load => {
-                let restore_path = self.config.restore.path.clone();
+                let restore_path = self.config.restore_path();
 
                 let config = std::sync::Arc::new(self.config.clone());
 
@@ -1127,9 +1152,14 @@ impl App {
         self.switch_screen(Screen::CustomGames)
     }
 
-    fn update_manifest(config: config::ManifestConfig, cache: cache::Manifests, force: bool) -> Task<Message> {
+    fn update_manifest(
+        config: config::ManifestConfig,
+        cache: cache::Manifests,
+        force: bool,
+        compress_cache: bool,
+    ) -> Task<Message> {
         Task::perform(
-            async move { tokio::task::spawn_blocking(move || Manifest::update(config, cache, force)).await },
+            async move { tokio::task::spawn_blocking(move || Manifest::update(config, cache, force, compress_cache)).await },
             |join| match join {
                 Ok(x) => Message::ManifestUpdated(x),
                 Err(_) => Message::Ignore,
@@ -1215,11 +1245,11 @@ impl App {
         })
     }
 
-    pub fn new(flags: Flags) -> (Self, Task<Message>) {
+    pub fn new(flags: Flags, config: Result<Config, Error>) -> (Self, Task<Message>) {
         let mut errors = vec![];
 
         let mut modal: Option<Modal> = None;
-        let mut config = match Config::load() {
+        let mut config = match config {
             Ok(x) => x,
             Err(x) => {
                 errors.push(x);
@@ -1258,8 +1288,8 @@ impl App {
                 .collect();
             if !missing.is_empty() {
                 cache.add_roots(&missing);
-                cache.save();
-                modal = Some(Modal::ConfirmAddMissingRoots(missing));
+                cache.save_maybe_compressed(config.runtime.compress_cache);
+                modal = Some(Modal::confirm_add_missing_roots(missing));
             }
         }
 
@@ -1276,6 +1306,7 @@ impl App {
                 config.manifest.clone(),
                 cache.manifests.clone(),
                 false,
+                config.runtime.compress_cache,
             ));
         }
 
@@ -1329,6 +1360,21 @@ impl App {
                 self.save();
                 Task::none()
             }
+            Message::WindowResized(size) => {
+                // Saving is deferred until the window stops changing for a moment.
+                if self.config.gui.window_size != Some(size) {
+                    self.config.gui.window_size = Some(size);
+                    self.save_config();
+                }
+                Task::none()
+            }
+            Message::WindowMoved(position) => {
+                if self.config.gui.window_position != Some(position) {
+                    self.config.gui.window_position = Some(position);
+                    self.save_config();
+                }
+                Task::none()
+            }
             Message::UpdateTime => {
                 self.progress.update_time();
                 Task::none()
@@ -1387,7 +1433,12 @@ impl App {
 
                 self.updating_manifest = true;
                 self.manifest_notification = Some(Notification::new(TRANSLATOR.updating_manifest()));
-                Self::update_manifest(self.config.manifest.clone(), self.cache.manifests.clone(), force)
+                Self::update_manifest(
+                    self.config.manifest.clone(),
+                    self.cache.manifests.clone(),
+                    force,
+                    self.config.runtime.compress_cache,
+                )
             }
             Message::ManifestUpdated(updates) => {
                 self.updating_manifest = false;
@@ -1450,7 +1501,7 @@ impl App {
                 } else {
                     self.cache.add_roots(&missing);
                     self.save_cache();
-                    self.show_modal(Modal::ConfirmAddMissingRoots(missing))
+                    self.show_modal(Modal::confirm_add_missing_roots(missing))
                 }
             }
             Message::ConfirmAddMissingRoots(missing) => {
@@ -1967,6 +2018,15 @@ impl App {
                 search.change.choice = filter;
                 Task::none()
             }
+            Message::EditedSearchFilterOutcome(filter) => {
+                let search = if self.screen == Screen::Backup {
+                    &mut self.backup_screen.log.search
+                } else {
+                    &mut self.restore_screen.log.search
+                };
+                search.outcome.choice = filter;
+                Task::none()
+            }
             Message::EditedSearchFilterManifest(filter) => {
                 let search = if self.screen == Screen::Backup {
                     &mut self.backup_screen.log.search
@@ -2279,6 +2339,10 @@ impl App {
                             ModalInputKind::Port => self.text_histories.modal.port.apply(shortcut),
                             ModalInputKind::Username => self.text_histories.modal.username.apply(shortcut),
                             ModalInputKind::Password => self.text_histories.modal.password.apply(shortcut),
+                            ModalInputKind::Bucket => self.text_histories.modal.bucket.apply(shortcut),
+                            ModalInputKind::Region => self.text_histories.modal.region.apply(shortcut),
+                            ModalInputKind::AccessKey => self.text_histories.modal.access_key.apply(shortcut),
+                            ModalInputKind::SecretKey => self.text_histories.modal.secret_key.apply(shortcut),
                         }
                         return Task::none();
                     }
@@ -2530,6 +2594,27 @@ impl App {
 
                             self.show_modal(Modal::ConfigureWebDavRemote { provider: *provider })
                         }
+                        Remote::S3 {
+                            id: _,
+                            endpoint,
+                            bucket,
+                            access_key,
+                            secret_key,
+                            region,
+                        } => {
+                            self.text_histories.modal.url.initialize(endpoint.clone());
+                            self.text_histories.modal.bucket.initialize(bucket.clone());
+                            self.text_histories.modal.access_key.initialize(access_key.clone());
+                            self.text_histories.modal.secret_key.initialize(secret_key.clone());
+                            self.text_histories.modal.region.initialize(region.clone());
+
+                            self.show_modal(Modal::ConfigureS3Remote)
+                        }
+                        Remote::Http { id: _, url } => {
+                            self.text_histories.modal.url.initialize(url.clone());
+
+                            self.show_modal(Modal::ConfigureHttpRemote)
+                        }
                         Remote::Box { .. }
                         | Remote::Dropbox { .. }
                         | Remote::GoogleDrive { .. }
@@ -2556,7 +2641,7 @@ impl App {
                 self.show_error(Error::UnableToConfigureCloud(error))
             }
             Message::ConfirmSynchronizeCloud { direction } => {
-                let local = self.config.backup.path.clone();
+                let local = self.config.backup_path();
 
                 self.show_modal(Modal::ConfirmCloudSync {
                     local: local.render(),
@@ -2568,7 +2653,7 @@ impl App {
                 })
             }
             Message::SynchronizeCloud { direction, finality } => {
-                let local = self.config.backup.path.clone();
+                let local = self.config.backup_path();
 
                 if let Err(e) = self.start_sync_cloud(&local, direction, finality, None, true) {
                     return self.show_error(e);
@@ -2603,6 +2688,7 @@ impl App {
                                         modal.add_cloud_change(change);
                                     }
                                 }
+                                crate::cloud::RcloneProcessEvent::Stats(_) => {}
                             }
                         }
                     }
@@ -2651,11 +2737,32 @@ impl App {
                     ModalField::Password(new) => {
                         self.text_histories.modal.password.push(&new);
                     }
+                    ModalField::Bucket(new) => {
+                        self.text_histories.modal.bucket.push(&new);
+                    }
+                    ModalField::Region(new) => {
+                        self.text_histories.modal.region.push(&new);
+                    }
+                    ModalField::AccessKey(new) => {
+                        self.text_histories.modal.access_key.push(&new);
+                    }
+                    ModalField::SecretKey(new) => {
+                        self.text_histories.modal.secret_key.push(&new);
+                    }
                     ModalField::WebDavProvider(new) => {
                         if let Some(Modal::ConfigureWebDavRemote { provider }) = self.modal.as_mut() {
                             *provider = new;
                         }
                     }
+                    ModalField::MissingRoot { index, accepted: new } => {
+                        if let Some(Modal::ConfirmAddMissingRoots { accepted, .. }) = self.modal.as_mut() {
+                            if new {
+                                accepted.insert(index);
+                            } else {
+                                accepted.remove(&index);
+                            }
+                        }
+                    }
                 }
                 Task::none()
             }
@@ -2688,6 +2795,14 @@ impl App {
             iced::event::listen_with(|event, _status, _window| match event {
                 iced::Event::Keyboard(event) => Some(Message::KeyboardEvent(event)),
                 iced::Event::Window(iced::window::Event::CloseRequested) => Some(Message::Exit { user: true }),
+                iced::Event::Window(iced::window::Event::Resized(size)) => Some(Message::WindowResized(WindowSize {
+                    width: size.width.round() as u32,
+                    height: size.height.round() as u32,
+                })),
+                iced::Event::Window(iced::window::Event::Moved(point)) => Some(Message::WindowMoved(WindowPosition {
+                    x: point.x.round() as i32,
+                    y: point.y.round() as i32,
+                })),
                 _ => None,
             }),
             rclone_monitor::run().map(Message::RcloneMonitor),
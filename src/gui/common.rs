@@ -13,7 +13,7 @@ use crate::{
     resource::{
         config::{
             BackupFormat, CloudFilter, CustomGameKind, RedirectKind, Root, SecondaryManifestConfigKind, SortKey, Theme,
-            ZipCompression,
+            WindowPosition, WindowSize, ZipCompression,
         },
         manifest::{self, Manifest, ManifestUpdate, Store},
     },
@@ -51,7 +51,7 @@ pub enum BackupPhase {
         launchers: Launchers,
     },
     GameScanned {
-        scan_info: Option<ScanInfo>,
+        scan_info: Option<Box<ScanInfo>>,
         backup_info: Option<BackupInfo>,
     },
     CloudSync,
@@ -74,7 +74,7 @@ pub enum RestorePhase {
         layout: BackupLayout,
     },
     GameScanned {
-        scan_info: Option<ScanInfo>,
+        scan_info: Option<Box<ScanInfo>>,
         backup_info: Option<BackupInfo>,
         game_layout: Box<GameLayout>,
     },
@@ -103,6 +103,8 @@ pub enum Message {
         user: bool,
     },
     Save,
+    WindowResized(WindowSize),
+    WindowMoved(WindowPosition),
     CloseModal,
     UpdateTime,
     PruneNotifications,
@@ -188,6 +190,7 @@ pub enum Message {
     EditedSearchFilterCompleteness(game_filter::Completeness),
     EditedSearchFilterEnablement(game_filter::Enablement),
     EditedSearchFilterChange(game_filter::Change),
+    EditedSearchFilterOutcome(game_filter::Outcome),
     EditedSearchFilterManifest(game_filter::Manifest),
     EditedSortKey {
         screen: Screen,
@@ -740,10 +743,14 @@ impl UndoSubject {
             | UndoSubject::CloudPath
             | UndoSubject::BackupComment(_) => Privacy::Public,
             UndoSubject::ModalField(field) => match field {
-                ModalInputKind::Url | ModalInputKind::Host | ModalInputKind::Port | ModalInputKind::Username => {
-                    Privacy::Public
-                }
-                ModalInputKind::Password => Privacy::Private,
+                ModalInputKind::Url
+                | ModalInputKind::Host
+                | ModalInputKind::Port
+                | ModalInputKind::Username
+                | ModalInputKind::Bucket
+                | ModalInputKind::Region
+                | ModalInputKind::AccessKey => Privacy::Public,
+                ModalInputKind::Password | ModalInputKind::SecretKey => Privacy::Private,
             },
         }
     }
@@ -22,7 +22,10 @@ use crate::{
         config::{Config, Sort},
         manifest::{self, Manifest, Os},
     },
-    scan::{game_filter, layout::GameLayout, BackupInfo, DuplicateDetector, OperationStatus, ScanChange, ScanInfo},
+    scan::{
+        game_filter, layout::GameLayout, BackupInfo, BackupOptions, DuplicateDetector, OperationStatus, ScanChange,
+        ScanInfo,
+    },
 };
 
 #[derive(Default)]
@@ -428,6 +431,7 @@ impl GameList {
                             !self.search.show
                                 || self.search.qualifies(
                                     &x.scan_info,
+                                    x.backup_info.as_ref(),
                                     manifest,
                                     config.is_game_enabled_for_operation(&x.scan_info.game_name, restoring),
                                     config.is_game_customized(&x.scan_info.game_name),
@@ -652,6 +656,9 @@ impl GameList {
                 self.entries[index]
                     .scan_info
                     .update_ignored(&config.backup.toggled_paths, &config.backup.toggled_registry);
+                BackupOptions::new(config).refine_scan(&mut self.entries[index].scan_info, |owner, path| {
+                    duplicate_detector.game_has_file(owner, path)
+                });
             }
 
             let stale = duplicate_detector.add_game(
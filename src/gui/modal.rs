@@ -16,7 +16,7 @@ use crate::{
         icon::Icon,
         shortcuts::TextHistories,
         style,
-        widget::{pick_list, text, Column, Container, Element, IcedParentExt, Row, Space},
+        widget::{checkbox, pick_list, text, Column, Container, Element, IcedParentExt, Row, Space},
     },
     lang::TRANSLATOR,
     prelude::{Error, Finality, SyncDirection},
@@ -41,6 +41,10 @@ pub enum ModalInputKind {
     Port,
     Username,
     Password,
+    Bucket,
+    Region,
+    AccessKey,
+    SecretKey,
 }
 
 #[derive(Debug, Clone)]
@@ -50,7 +54,12 @@ pub enum ModalField {
     Port(String),
     Username(String),
     Password(String),
+    Bucket(String),
+    Region(String),
+    AccessKey(String),
+    SecretKey(String),
     WebDavProvider(WebDavProvider),
+    MissingRoot { index: usize, accepted: bool },
 }
 
 impl ModalField {
@@ -61,6 +70,10 @@ impl ModalField {
             ModalInputKind::Port => TRANSLATOR.port_label(),
             ModalInputKind::Username => TRANSLATOR.username_label(),
             ModalInputKind::Password => TRANSLATOR.password_label(),
+            ModalInputKind::Bucket => TRANSLATOR.bucket_label(),
+            ModalInputKind::Region => TRANSLATOR.region_label(),
+            ModalInputKind::AccessKey => TRANSLATOR.access_key_label(),
+            ModalInputKind::SecretKey => TRANSLATOR.secret_key_label(),
         };
 
         Row::new()
@@ -132,7 +145,11 @@ pub enum Modal {
         games: Option<Vec<String>>,
     },
     NoMissingRoots,
-    ConfirmAddMissingRoots(Vec<Root>),
+    ConfirmAddMissingRoots {
+        roots: Vec<Root>,
+        /// Indices of the roots that the user has chosen to add.
+        accepted: BTreeSet<usize>,
+    },
     BackupValidation {
         games: BTreeSet<String>,
     },
@@ -153,6 +170,8 @@ pub enum Modal {
     ConfigureWebDavRemote {
         provider: WebDavProvider,
     },
+    ConfigureS3Remote,
+    ConfigureHttpRemote,
     GameNotes {
         game: String,
         notes: Vec<manifest::Note>,
@@ -160,6 +179,13 @@ pub enum Modal {
 }
 
 impl Modal {
+    pub fn confirm_add_missing_roots(roots: Vec<Root>) -> Self {
+        Self::ConfirmAddMissingRoots {
+            accepted: (0..roots.len()).collect(),
+            roots,
+        }
+    }
+
     pub fn variant(&self) -> ModalVariant {
         match self {
             Self::Exiting | Self::UpdatingManifest => ModalVariant::Loading,
@@ -168,10 +194,12 @@ impl Modal {
             }
             Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
-            | Self::ConfirmAddMissingRoots(..)
+            | Self::ConfirmAddMissingRoots { .. }
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureHttpRemote
             | Self::AppUpdate { .. } => ModalVariant::Confirm,
             Self::BackupValidation { games } => {
                 if games.is_empty() {
@@ -203,7 +231,7 @@ impl Modal {
             }
             Self::ConfirmRestore { .. } => TRANSLATOR.confirm_restore(&config.restore.path, true),
             Self::NoMissingRoots => TRANSLATOR.no_missing_roots(),
-            Self::ConfirmAddMissingRoots(missing) => TRANSLATOR.confirm_add_missing_roots(missing),
+            Self::ConfirmAddMissingRoots { .. } => TRANSLATOR.confirm_add_missing_roots(),
             Self::AppUpdate { release } => TRANSLATOR.new_version_available(release.version.to_string().as_str()),
             Self::UpdatingManifest => TRANSLATOR.updating_manifest(),
             Self::BackupValidation { games } => {
@@ -232,6 +260,8 @@ impl Modal {
             Self::ConfigureFtpRemote { .. } => RemoteChoice::Ftp.to_string(),
             Self::ConfigureSmbRemote { .. } => RemoteChoice::Smb.to_string(),
             Self::ConfigureWebDavRemote { .. } => RemoteChoice::WebDav.to_string(),
+            Self::ConfigureS3Remote => RemoteChoice::S3.to_string(),
+            Self::ConfigureHttpRemote => RemoteChoice::Http.to_string(),
             Self::GameNotes { game, .. } => game.clone(),
         }
     }
@@ -253,7 +283,15 @@ impl Modal {
                 preview: false,
                 games: games.clone(),
             })),
-            Self::ConfirmAddMissingRoots(missing) => Some(Message::ConfirmAddMissingRoots(missing.clone())),
+            Self::ConfirmAddMissingRoots { roots, accepted } => {
+                let accepted: Vec<_> = roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| accepted.contains(i))
+                    .map(|(_, root)| root.clone())
+                    .collect();
+                (!accepted.is_empty()).then_some(Message::ConfirmAddMissingRoots(accepted))
+            }
             Self::AppUpdate { release } => Some(Message::OpenUrlAndCloseModal(release.url.clone())),
             Self::UpdatingManifest => None,
             Self::ConfirmCloudSync { direction, state, .. } => {
@@ -321,6 +359,34 @@ impl Modal {
                     }))
                 }
             }
+            Self::ConfigureS3Remote => {
+                let remote = Remote::S3 {
+                    id: Remote::generate_id(),
+                    endpoint: histories.modal.url.current(),
+                    bucket: histories.modal.bucket.current(),
+                    access_key: histories.modal.access_key.current(),
+                    secret_key: histories.modal.secret_key.current(),
+                    region: histories.modal.region.current(),
+                };
+
+                if remote.missing_fields().is_empty() {
+                    Some(Message::FinalizeRemote(remote))
+                } else {
+                    None
+                }
+            }
+            Self::ConfigureHttpRemote => {
+                let url = histories.modal.url.current();
+
+                if url.is_empty() {
+                    None
+                } else {
+                    Some(Message::FinalizeRemote(Remote::Http {
+                        id: Remote::generate_id(),
+                        url,
+                    }))
+                }
+            }
         }
     }
 
@@ -359,12 +425,14 @@ impl Modal {
             | Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::NoMissingRoots
-            | Self::ConfirmAddMissingRoots(_)
+            | Self::ConfirmAddMissingRoots { .. }
             | Self::UpdatingManifest
             | Self::AppUpdate { .. }
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureHttpRemote
             | Self::GameNotes { .. } => vec![],
         }
     }
@@ -384,9 +452,25 @@ impl Modal {
             | Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::NoMissingRoots
-            | Self::ConfirmAddMissingRoots(_)
             | Self::AppUpdate { .. }
             | Self::UpdatingManifest => (),
+            Self::ConfirmAddMissingRoots { roots, accepted } => {
+                col = col.push(roots.iter().enumerate().fold(
+                    Column::new().width(Length::Fill).spacing(5).align_x(Alignment::Start),
+                    |parent, (i, root)| {
+                        parent.push(
+                            checkbox(
+                                TRANSLATOR.missing_root_label(root),
+                                accepted.contains(&i),
+                                move |accepted| {
+                                    Message::EditedModalField(ModalField::MissingRoot { index: i, accepted })
+                                },
+                            )
+                            .class(style::Checkbox),
+                        )
+                    },
+                ));
+            }
             Self::BackupValidation { games } => {
                 for game in games.iter().sorted() {
                     col = col.push(text(game))
@@ -452,6 +536,18 @@ impl Modal {
                         ModalField::WebDavProvider,
                     ));
             }
+            Self::ConfigureS3Remote => {
+                col = col
+                    .width(500)
+                    .push(ModalField::view(ModalInputKind::Url, histories))
+                    .push(ModalField::view(ModalInputKind::Bucket, histories))
+                    .push(ModalField::view(ModalInputKind::AccessKey, histories))
+                    .push(ModalField::view(ModalInputKind::SecretKey, histories))
+                    .push(ModalField::view(ModalInputKind::Region, histories));
+            }
+            Self::ConfigureHttpRemote => {
+                col = col.width(500).push(ModalField::view(ModalInputKind::Url, histories));
+            }
             Self::GameNotes { notes, .. } => {
                 col = notes.iter().fold(col, |parent, note| {
                     parent.push(
@@ -527,13 +623,15 @@ impl Modal {
             | Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::NoMissingRoots
-            | Self::ConfirmAddMissingRoots(_)
+            | Self::ConfirmAddMissingRoots { .. }
             | Self::BackupValidation { .. }
             | Self::AppUpdate { .. }
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureHttpRemote
             | Self::GameNotes { .. } => (),
         }
     }
@@ -565,13 +663,15 @@ impl Modal {
             | Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::NoMissingRoots
-            | Self::ConfirmAddMissingRoots(_)
+            | Self::ConfirmAddMissingRoots { .. }
             | Self::BackupValidation { .. }
             | Self::AppUpdate { .. }
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureHttpRemote
             | Self::GameNotes { .. } => (),
         }
     }
@@ -587,13 +687,15 @@ impl Modal {
             | Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::NoMissingRoots
-            | Self::ConfirmAddMissingRoots(_)
+            | Self::ConfirmAddMissingRoots { .. }
             | Self::BackupValidation { .. }
             | Self::AppUpdate { .. }
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureHttpRemote
             | Self::GameNotes { .. } => (),
         }
     }
@@ -607,13 +709,15 @@ impl Modal {
             | Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::NoMissingRoots
-            | Self::ConfirmAddMissingRoots(_)
+            | Self::ConfirmAddMissingRoots { .. }
             | Self::BackupValidation { .. }
             | Self::AppUpdate { .. }
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureHttpRemote
             | Self::GameNotes { .. } => false,
         }
     }
@@ -627,13 +731,15 @@ impl Modal {
             | Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::NoMissingRoots
-            | Self::ConfirmAddMissingRoots(_)
+            | Self::ConfirmAddMissingRoots { .. }
             | Self::BackupValidation { .. }
             | Self::AppUpdate { .. }
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureHttpRemote
             | Self::GameNotes { .. } => 2,
         }
     }
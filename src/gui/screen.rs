@@ -371,11 +371,11 @@ pub fn other<'a>(
                                             .spacing(5)
                                             .push(checkbox(
                                                 TRANSLATOR.override_max_threads(),
-                                                config.runtime.threads.is_some(),
+                                                config.runtime.scan_threads.is_some(),
                                                 Message::OverrideMaxThreads,
                                             ))
                                             .push_maybe({
-                                                config.runtime.threads.map(|threads| {
+                                                config.runtime.scan_threads.map(|threads| {
                                                     Container::new(number_input(
                                                         threads.get() as i32,
                                                         TRANSLATOR.threads_label(),
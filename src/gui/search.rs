@@ -11,7 +11,7 @@ use crate::{
     resource::manifest::Manifest,
     scan::{
         game_filter::{self, FilterKind},
-        Duplication, ScanInfo,
+        BackupInfo, Duplication, ScanInfo,
     },
 };
 
@@ -29,6 +29,7 @@ pub struct FilterComponent {
     pub completeness: Filter<game_filter::Completeness>,
     pub enablement: Filter<game_filter::Enablement>,
     pub change: Filter<game_filter::Change>,
+    pub outcome: Filter<game_filter::Outcome>,
     pub manifest: Filter<game_filter::Manifest>,
 }
 
@@ -93,6 +94,7 @@ impl FilterComponent {
     pub fn qualifies(
         &self,
         scan: &ScanInfo,
+        backup_info: Option<&BackupInfo>,
         manifest: &Manifest,
         enabled: bool,
         customized: bool,
@@ -107,13 +109,14 @@ impl FilterComponent {
         let complete = !self.completeness.active || self.completeness.choice.qualifies(scan);
         let enable = !show_deselected_games || !self.enablement.active || self.enablement.choice.qualifies(enabled);
         let changed = !self.change.active || self.change.choice.qualifies(scan);
+        let outcome = !self.outcome.active || self.outcome.choice.qualifies(backup_info);
         let manifest = !self.manifest.active
             || self
                 .manifest
                 .choice
                 .qualifies(manifest.0.get(&scan.game_name), customized);
 
-        fuzzy && unique && complete && changed && enable && manifest
+        fuzzy && unique && complete && changed && outcome && enable && manifest
     }
 
     pub fn toggle_filter(&mut self, filter: FilterKind, enabled: bool) {
@@ -122,6 +125,7 @@ impl FilterComponent {
             FilterKind::Completeness => self.completeness.active = enabled,
             FilterKind::Enablement => self.enablement.active = enabled,
             FilterKind::Change => self.change.active = enabled,
+            FilterKind::Outcome => self.outcome.active = enabled,
             FilterKind::Manifest => self.manifest.active = enabled,
         }
     }
@@ -173,6 +177,12 @@ impl FilterComponent {
                             game_filter::Change::ALL,
                             Message::EditedSearchFilterChange,
                         ))
+                        .push(template(
+                            &self.outcome,
+                            FilterKind::Outcome,
+                            game_filter::Outcome::ALL,
+                            Message::EditedSearchFilterOutcome,
+                        ))
                         .push_if(show_deselected_games, || {
                             template(
                                 &self.enablement,
@@ -217,6 +217,10 @@ pub struct ModalHistory {
     pub port: TextHistory,
     pub username: TextHistory,
     pub password: TextHistory,
+    pub bucket: TextHistory,
+    pub region: TextHistory,
+    pub access_key: TextHistory,
+    pub secret_key: TextHistory,
 }
 
 #[derive(Default)]
@@ -306,6 +310,10 @@ impl TextHistories {
         self.modal.port.clear();
         self.modal.username.clear();
         self.modal.password.clear();
+        self.modal.bucket.clear();
+        self.modal.region.clear();
+        self.modal.access_key.clear();
+        self.modal.secret_key.clear();
     }
 
     pub fn input<'a>(&self, subject: UndoSubject) -> Element<'a> {
@@ -357,6 +365,10 @@ impl TextHistories {
                 ModalInputKind::Port => self.modal.port.current(),
                 ModalInputKind::Username => self.modal.username.current(),
                 ModalInputKind::Password => self.modal.password.current(),
+                ModalInputKind::Bucket => self.modal.bucket.current(),
+                ModalInputKind::Region => self.modal.region.current(),
+                ModalInputKind::AccessKey => self.modal.access_key.current(),
+                ModalInputKind::SecretKey => self.modal.secret_key.current(),
             },
             UndoSubject::BackupComment(game) => {
                 self.backup_comments.get(&game).map(|x| x.current()).unwrap_or_default()
@@ -412,6 +424,10 @@ impl TextHistories {
                     ModalInputKind::Port => ModalField::Port(value),
                     ModalInputKind::Username => ModalField::Username(value),
                     ModalInputKind::Password => ModalField::Password(value),
+                    ModalInputKind::Bucket => ModalField::Bucket(value),
+                    ModalInputKind::Region => ModalField::Region(value),
+                    ModalInputKind::AccessKey => ModalField::AccessKey(value),
+                    ModalInputKind::SecretKey => ModalField::SecretKey(value),
                 })
             }),
             UndoSubject::BackupComment(game) => Box::new(move |comment| Message::EditedBackupComment {
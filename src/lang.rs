@@ -7,12 +7,13 @@ use regex::Regex;
 use unic_langid::LanguageIdentifier;
 
 use crate::{
+    cloud::{CloudSize, CloudStats},
     prelude::{CommandError, Error, StrictPath, VARIANT},
     resource::{
         config::{BackupFormat, CustomGameKind, RedirectKind, Root, SortKey, Theme, ZipCompression},
-        manifest::Store,
+        manifest::{Os, Store},
     },
-    scan::{game_filter, BackupError, OperationStatus, OperationStepDecision, ScanChange},
+    scan::{game_filter, BackupError, OperationStatus, OperationStepDecision, ScanChange, StoreGroup},
 };
 
 const PATH: &str = "path";
@@ -30,6 +31,17 @@ const MESSAGE: &str = "message";
 const APP: &str = "app";
 const GAME: &str = "game";
 const VERSION: &str = "version";
+const KEY: &str = "key";
+const EXPECTED: &str = "expected";
+const ACTUAL: &str = "actual";
+const STORE: &str = "store";
+const SIZE: &str = "size";
+const REMOTE: &str = "remote";
+const BACKUP: &str = "backup";
+const LINE: &str = "line";
+const NAME: &str = "name";
+const WASTED: &str = "wasted";
+const OS: &str = "os";
 
 pub const TRANSLATOR: Translator = Translator {};
 pub const ADD_SYMBOL: &str = "+";
@@ -372,14 +384,36 @@ impl Translator {
         match error {
             Error::ConfigInvalid { why } => self.config_is_invalid(why),
             Error::ManifestInvalid { why, identifier } => self.manifest_is_invalid(why, identifier.as_deref()),
-            Error::ManifestCannotBeUpdated { identifier } => self.manifest_cannot_be_updated(identifier.as_deref()),
+            Error::ManifestCannotBeUpdated { identifier, why } => {
+                self.manifest_cannot_be_updated(identifier.as_deref(), why.as_deref())
+            }
+            Error::ManifestNotFound => self.manifest_not_found(),
+            Error::ManifestChecksumMismatch {
+                expected,
+                actual,
+                identifier,
+            } => self.manifest_checksum_mismatch(expected, actual, identifier.as_deref()),
             Error::CliUnrecognizedGames { games } => self.cli_unrecognized_games(games),
             Error::CliUnableToRequestConfirmation => self.cli_unable_to_request_confirmation(),
             Error::CliBackupIdWithMultipleGames => self.cli_backup_id_with_multiple_games(),
+            Error::CliCloudPurgeWithoutTarget => self.cli_cloud_purge_without_target(),
             Error::CliInvalidBackupId => self.cli_invalid_backup_id(),
+            Error::CliOnlyRegistryUnsupported => self.cli_only_registry_unsupported(),
+            Error::CliInvalidGamesFile { why } => self.cli_invalid_games_file(why),
+            Error::CliUnknownConfigKey { key, valid } => self.cli_unknown_config_key(key, valid),
+            Error::CliInvalidConfigValue { key, why } => self.cli_invalid_config_value(key, why),
             Error::SomeEntriesFailed => self.some_entries_failed(),
             Error::CannotPrepareBackupTarget { path } => self.cannot_prepare_backup_target(path),
+            Error::BackupRenameConflict { game, conflicts } => self.backup_rename_conflict(game, conflicts),
+            Error::CannotRenameBackups { game, path } => self.cannot_rename_backups(game, path),
             Error::RestorationSourceInvalid { path } => self.restoration_source_is_invalid(path),
+            Error::CannotCreateBundle { path } => self.cannot_create_bundle(path),
+            Error::BundleInvalid { path } => self.bundle_is_invalid(path),
+            Error::CannotExportSettings { path } => self.cannot_export_settings(path),
+            Error::CannotWriteReport { path } => self.cannot_write_report(path),
+            Error::CannotWriteManifestSnapshot { path } => self.cannot_write_manifest_snapshot(path),
+            Error::UndefinedEnvironmentVariable { name, path } => self.undefined_environment_variable(name, path),
+            Error::SettingsInvalid { path } => self.settings_are_invalid(path),
             Error::RegistryIssue => self.registry_issue(),
             Error::UnableToBrowseFileSystem => self.unable_to_browse_file_system(),
             Error::UnableToOpenDir(path) => self.unable_to_open_dir(path),
@@ -387,6 +421,9 @@ impl Translator {
             Error::RcloneUnavailable => self.rclone_unavailable(),
             Error::CloudNotConfigured => self.cloud_not_configured(),
             Error::CloudPathInvalid => self.cloud_path_invalid(),
+            Error::CloudRemoteReadOnly => self.cloud_remote_read_only(),
+            Error::CloudRemoteInvalid { remote } => self.cloud_remote_invalid(remote),
+            Error::CloudRemoteIncomplete { fields } => self.cloud_remote_incomplete(fields),
             Error::UnableToConfigureCloud(error) => {
                 format!(
                     "{}\n\n{}",
@@ -401,8 +438,22 @@ impl Translator {
                     self.handle_command_error(error)
                 )
             }
+            Error::CloudUploadMismatched { paths } => self.cloud_upload_mismatched(paths),
             Error::CloudConflict => TRANSLATOR.prefix_error(&TRANSLATOR.cloud_synchronize_conflict()),
+            Error::CloudIgnoreFileInvalid { path, line } => self.cloud_ignore_file_invalid(path, *line),
             Error::GameDidNotLaunch { why } => format!("{}\n\n{}", self.game_did_not_launch(), self.prefix_error(why)),
+            Error::UnableToWatchFiles { why } => {
+                format!("{}\n\n{}", self.unable_to_watch_files(), self.prefix_error(why))
+            }
+            Error::GitUnavailable => self.git_unavailable(),
+            Error::MaxBackupDurationExceeded => self.max_backup_duration_exceeded(),
+            Error::GitCommandFailed(error) => {
+                format!(
+                    "{}\n\n{}",
+                    self.prefix_error(&self.git_command_failed()),
+                    self.handle_command_error(error)
+                )
+            }
         }
     }
 
@@ -441,6 +492,71 @@ impl Translator {
         format!("{}\n{}", prefix, lines.join("\n"))
     }
 
+    pub fn cli_retried_games(&self, games: &std::collections::BTreeSet<String>) -> String {
+        let prefix = translate("cli-retried-games");
+        let lines: Vec<_> = games.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_disabled_games(&self, games: &std::collections::BTreeSet<String>) -> String {
+        let prefix = translate("cli-disabled-games");
+        let lines: Vec<_> = games.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_uninstalled_games(&self, games: &std::collections::BTreeSet<String>) -> String {
+        let prefix = translate("cli-uninstalled-games");
+        let lines: Vec<_> = games.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_incomplete_games(&self, games: &std::collections::BTreeSet<String>) -> String {
+        let prefix = translate("cli-incomplete-games");
+        let lines: Vec<_> = games.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_identical_files(&self, identical: &[crate::scan::IdenticalFiles]) -> String {
+        let mut args = FluentArgs::new();
+        args.set(SIZE, self.adjusted_size(identical.iter().map(|x| x.wasted_bytes).sum()));
+        let prefix = translate_args("cli-identical-files", &args);
+
+        let mut lines = vec![];
+        for item in identical {
+            let mut args = FluentArgs::new();
+            args.set(SIZE, self.adjusted_size(item.size));
+            args.set(WASTED, self.adjusted_size(item.wasted_bytes));
+            lines.push(format!("  - {}:", translate_args("cli-identical-files-item", &args)));
+            for (game, paths) in &item.games {
+                for path in paths {
+                    lines.push(format!("    - [{}] {}", game, path));
+                }
+            }
+        }
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_rename_plan(&self, plan: &crate::scan::rename::RenamePlan) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, plan.old_name.clone());
+        args.set(NAME, plan.new_name.clone());
+        let prefix = if plan.merge {
+            translate_args("cli-rename-plan-merge", &args)
+        } else {
+            translate_args("cli-rename-plan", &args)
+        };
+
+        let mut lines = vec![format!("  - {} -> {}", plan.source.render(), plan.target.render())];
+        for (file, moved) in &plan.files {
+            if file == moved {
+                lines.push(format!("    - {}", file));
+            } else {
+                lines.push(format!("    - {} -> {}", file, moved));
+            }
+        }
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
     pub fn cli_unable_to_request_confirmation(&self) -> String {
         #[cfg(target_os = "windows")]
         let extra_note = translate("cli-unable-to-request-confirmation.winpty-workaround");
@@ -455,18 +571,177 @@ impl Translator {
         translate("cli-backup-id-with-multiple-games")
     }
 
+    pub fn cli_cloud_purge_without_target(&self) -> String {
+        translate("cli-cloud-purge-without-target")
+    }
+
     pub fn cli_invalid_backup_id(&self) -> String {
         translate("cli-invalid-backup-id")
     }
 
+    pub fn cli_invalid_games_file(&self, why: &str) -> String {
+        format!("{}\n{}", translate("cli-invalid-games-file"), why)
+    }
+
+    pub fn cli_unknown_config_key(&self, key: &str, valid: &[String]) -> String {
+        let mut args = FluentArgs::new();
+        args.set(KEY, key);
+        let prefix = translate_args("cli-unknown-config-key", &args);
+        let lines: Vec<_> = valid.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_invalid_config_value(&self, key: &str, why: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(KEY, key);
+        format!("{}\n{}", translate_args("cli-invalid-config-value", &args), why)
+    }
+
+    pub fn cli_manifest_is_stale(&self, days: i64) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, days);
+        translate_args("cli-manifest-is-stale", &args)
+    }
+
+    pub fn cli_excluded_store_games(&self, store: &Store, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(STORE, self.store(store));
+        args.set(TOTAL, total);
+        translate_args("cli-excluded-store-games", &args)
+    }
+
+    pub fn cli_watch_started(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        translate_args("cli-watch-started", &args)
+    }
+
+    pub fn cli_watch_backed_up(&self, name: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, name);
+        translate_args("cli-watch-backed-up", &args)
+    }
+
+    pub fn cli_watch_backup_failed(&self, name: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, name);
+        translate_args("cli-watch-backup-failed", &args)
+    }
+
+    pub fn cli_secondary_manifest_games(&self, manifest: &str, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, manifest);
+        args.set(TOTAL, total);
+        translate_args("cli-secondary-manifest-games", &args)
+    }
+
+    pub fn cli_cloud_conflict_prompt(&self) -> String {
+        translate("cli-cloud-conflict-prompt")
+    }
+
+    pub fn cli_cloud_conflict_game_prompt(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        translate_args("cli-cloud-conflict-game-prompt", &args)
+    }
+
+    pub fn cli_cloud_conflict_upload(&self) -> String {
+        translate("cli-cloud-conflict-upload")
+    }
+
+    pub fn cli_cloud_conflict_download(&self) -> String {
+        translate("cli-cloud-conflict-download")
+    }
+
+    pub fn cli_cloud_conflict_upload_all(&self) -> String {
+        translate("cli-cloud-conflict-upload-all")
+    }
+
+    pub fn cli_cloud_conflict_download_all(&self) -> String {
+        translate("cli-cloud-conflict-download-all")
+    }
+
+    pub fn cli_cloud_conflict_choose_per_game(&self) -> String {
+        translate("cli-cloud-conflict-choose-per-game")
+    }
+
+    pub fn cli_cloud_conflict_skip(&self) -> String {
+        translate("cli-cloud-conflict-skip")
+    }
+
+    pub fn cli_only_registry_unsupported(&self) -> String {
+        translate("cli-only-registry-unsupported")
+    }
+
+    pub fn cli_select_games_to_back_up(&self) -> String {
+        translate("cli-select-games-to-back-up")
+    }
+
+    pub fn cli_select_games_to_restore(&self) -> String {
+        translate("cli-select-games-to-restore")
+    }
+
+    pub fn cli_resumed_games(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        translate_args("cli-resumed-games", &args)
+    }
+
+    pub fn cli_aborted_after_failure(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        translate_args("cli-aborted-after-failure", &args)
+    }
+
+    pub fn cli_aborted_after_max_duration(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        translate_args("cli-aborted-after-max-duration", &args)
+    }
+
     pub fn cloud_not_configured(&self) -> String {
         translate("cloud-not-configured")
     }
 
+    pub fn cloud_remote_read_only(&self) -> String {
+        translate("cloud-remote-read-only")
+    }
+
     pub fn cloud_path_invalid(&self) -> String {
         translate("cloud-path-invalid")
     }
 
+    pub fn cloud_remote_invalid(&self, remote: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(REMOTE, remote);
+        translate_args("cloud-remote-invalid", &args)
+    }
+
+    pub fn cloud_remote_incomplete(&self, fields: &[String]) -> String {
+        let prefix = self.prefix_error(&translate("cloud-remote-incomplete"));
+        let lines: Vec<_> = fields.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cloud_ignore_file_invalid(&self, path: &StrictPath, line: Option<usize>) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, path.render());
+        let message = match line {
+            Some(line) => {
+                args.set(LINE, line);
+                translate_args("cloud-ignore-file-invalid-rule", &args)
+            }
+            None => translate_args("cloud-ignore-file-unreadable", &args),
+        };
+        self.prefix_error(&message)
+    }
+
+    pub fn cloud_upload_mismatched(&self, paths: &[String]) -> String {
+        let prefix = self.prefix_error(&translate("cloud-upload-mismatched"));
+        let lines: Vec<_> = paths.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
     pub fn some_entries_failed(&self) -> String {
         translate("some-entries-failed")
     }
@@ -523,6 +798,18 @@ impl Translator {
         translate("badge-ignored")
     }
 
+    pub fn badge_not_found(&self) -> String {
+        translate("badge-not-found")
+    }
+
+    pub fn badge_detected(&self) -> String {
+        translate("badge-detected")
+    }
+
+    pub fn badge_environment(&self) -> String {
+        translate("badge-environment")
+    }
+
     pub fn badge_redirected_from(&self, original: &StrictPath) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, original.render());
@@ -607,12 +894,109 @@ impl Translator {
         format!("    - {}", translate_args("cli-game-line-item-redirected", &args),)
     }
 
+    pub fn cli_game_line_item_preserved(&self, item: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, item);
+        format!("    - {}", translate_args("cli-game-line-item-preserved", &args),)
+    }
+
     pub fn cli_game_line_item_redirecting(&self, item: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, item);
         format!("    - {}", translate_args("cli-game-line-item-redirecting", &args),)
     }
 
+    pub fn cli_game_line_item_oversized(&self, bytes: u64) -> String {
+        let mut args = FluentArgs::new();
+        args.set(SIZE, self.adjusted_size(bytes));
+        format!("    - {}", translate_args("cli-game-line-item-oversized", &args),)
+    }
+
+    pub fn cli_game_globally_ignored(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        format!("  - {}", translate_args("cli-game-globally-ignored", &args),)
+    }
+
+    pub fn cli_game_system_files_excluded(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        format!("  - {}", translate_args("cli-game-system-files-excluded", &args),)
+    }
+
+    pub fn cli_store_group_header(&self, group: &StoreGroup, status: &OperationStatus) -> String {
+        let store = match group {
+            StoreGroup::Detected(store) => self.store(store),
+            StoreGroup::Custom => translate("store-group-custom"),
+            StoreGroup::Unknown => translate("store-group-unknown"),
+        };
+        format!(
+            "=== {}: {}, {} ===",
+            store,
+            self.processed_games(status),
+            self.processed_bytes(status)
+        )
+    }
+
+    pub fn cli_game_note(&self, note: &str) -> String {
+        format!("  - {} {}", self.note_label(), note)
+    }
+
+    pub fn cli_game_restored_from_backup(&self, backup: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(BACKUP, backup);
+        format!("  - {}", translate_args("cli-game-restored-from-backup", &args))
+    }
+
+    pub fn cli_game_restored_into_prefix(&self, prefix: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, prefix);
+        format!("  - {}", translate_args("cli-game-restored-into-prefix", &args))
+    }
+
+    pub fn cli_game_would_overwrite(&self, total: usize, bytes: u64) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        args.set(SIZE, self.adjusted_size(bytes));
+        format!("  - {}", translate_args("cli-game-would-overwrite", &args),)
+    }
+
+    pub fn cli_game_would_orphan(&self, paths: &std::collections::BTreeSet<String>) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, paths.len());
+        let prefix = format!("  - {}", translate_args("cli-game-would-orphan", &args));
+        let lines: Vec<_> = paths.iter().map(|x| format!("    - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_game_cleaned(&self, paths: &std::collections::BTreeSet<String>) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, paths.len());
+        let prefix = format!("  - {}", translate_args("cli-game-cleaned", &args));
+        let lines: Vec<_> = paths.iter().map(|x| format!("    - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cli_game_line_item_duplicate_owner(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        format!("    - {}", translate_args("cli-game-line-item-duplicate-owner", &args),)
+    }
+
+    pub fn cli_game_line_item_other_os(&self, os: Os) -> String {
+        let mut args = FluentArgs::new();
+        args.set(
+            OS,
+            match os {
+                Os::Windows => "Windows",
+                Os::Linux => "Linux",
+                Os::Mac => "Mac",
+                Os::Other => "?",
+            },
+        );
+        format!("    - {}", translate_args("cli-game-line-item-other-os", &args),)
+    }
+
     pub fn cli_game_line_item_error(&self, error: &BackupError) -> String {
         format!("    - {}", error.message())
     }
@@ -629,8 +1013,23 @@ impl Translator {
             "".to_string()
         };
 
+        let empty_games = match status.empty_games {
+            Some(empty_games) => format!("\n  {}: {}", translate("summary-empty-games"), empty_games),
+            None => "".to_string(),
+        };
+        let registry_only = if status.registry_only {
+            format!("\n  {}", translate("summary-registry-only"))
+        } else {
+            "".to_string()
+        };
+        let cloud_sync_skipped = if status.cloud_sync_skipped {
+            format!("\n  {}", translate("summary-cloud-sync-skipped"))
+        } else {
+            "".to_string()
+        };
+
         format!(
-            "{}:\n  {}: {}{}{}\n  {}: {}\n  {}: {}",
+            "{}:\n  {}: {}{}{}{}{}{}\n  {}: {}\n  {}: {}",
             translate("overall"),
             translate("total-games"),
             if status.processed_all_games() {
@@ -640,6 +1039,9 @@ impl Translator {
             },
             new_games,
             changed_games,
+            empty_games,
+            registry_only,
+            cloud_sync_skipped,
             translate("file-size"),
             if status.processed_all_bytes() {
                 self.adjusted_size(status.processed_bytes)
@@ -655,6 +1057,73 @@ impl Translator {
         )
     }
 
+    pub fn cli_summary_games(&self, games: usize, location: &StrictPath) -> String {
+        format!(
+            "{}:\n  {}: {}\n  {}: {}",
+            translate("overall"),
+            translate("total-games"),
+            games,
+            translate("file-location"),
+            location.render(),
+        )
+    }
+
+    pub fn cli_summary_details(
+        &self,
+        failed_games: usize,
+        backups: Option<usize>,
+        disk_usage: Option<u64>,
+        cloud_changes: Option<usize>,
+        duration: f64,
+    ) -> String {
+        let mut out = format!("\n  {}: {}", translate("summary-failed-games"), failed_games);
+        if let Some(backups) = backups {
+            out += &format!("\n  {}: {}", translate("summary-backups"), backups);
+        }
+        if let Some(disk_usage) = disk_usage {
+            out += &format!(
+                "\n  {}: {}",
+                translate("summary-disk-usage"),
+                self.adjusted_size(disk_usage)
+            );
+        }
+        if let Some(cloud_changes) = cloud_changes {
+            out += &format!("\n  {}: {}", translate("summary-cloud-changes"), cloud_changes);
+        }
+        out += &format!("\n  {}: {:.2}s", translate("summary-duration"), duration);
+        out
+    }
+
+    pub fn cli_cloud_stats(&self, stats: &CloudStats) -> String {
+        format!(
+            "{}:\n  {}: {}\n  {}: {}\n  {}: {:.2}s\n  {}: {}/s",
+            translate("cloud-transferred"),
+            translate("cloud-transferred-files"),
+            stats.files,
+            translate("file-size"),
+            self.adjusted_size(stats.bytes),
+            translate("summary-duration"),
+            stats.elapsed,
+            translate("cloud-transfer-speed"),
+            self.adjusted_size(stats.speed as u64),
+        )
+    }
+
+    pub fn cli_cloud_deleted(&self, size: &CloudSize) -> String {
+        format!(
+            "{}:\n  {}: {}\n  {}: {}",
+            translate("cloud-deleted"),
+            translate("cloud-transferred-files"),
+            size.files,
+            translate("file-size"),
+            self.adjusted_size(size.bytes),
+        )
+    }
+
+    pub fn no_cloud_folders_to_delete(&self) -> String {
+        translate("no-cloud-folders-to-delete")
+    }
+
     pub fn backup_button(&self) -> String {
         translate("button-backup")
     }
@@ -723,29 +1192,20 @@ impl Translator {
         translate("backups-are-invalid")
     }
 
-    pub fn confirm_add_missing_roots(&self, roots: &[Root]) -> String {
-        use std::fmt::Write;
-        let mut msg = translate("confirm-add-missing-roots") + "\n";
-
-        for root in roots {
-            let path2 = match &root {
-                Root::Lutris(root) => root
-                    .database
-                    .as_ref()
-                    .map(|x| format!(" + {}", x.render()))
-                    .unwrap_or_default(),
-                _ => "".to_string(),
-            };
-            let _ = &write!(
-                msg,
-                "\n[{}] {} {}",
-                self.store(&root.store()),
-                root.path().render(),
-                path2
-            );
-        }
+    pub fn confirm_add_missing_roots(&self) -> String {
+        translate("confirm-add-missing-roots")
+    }
 
-        msg
+    pub fn missing_root_label(&self, root: &Root) -> String {
+        let path2 = match &root {
+            Root::Lutris(root) => root
+                .database
+                .as_ref()
+                .map(|x| format!(" + {}", x.render()))
+                .unwrap_or_default(),
+            _ => "".to_string(),
+        };
+        format!("[{}] {}{}", self.store(&root.store()), root.path().render(), path2)
     }
 
     pub fn add_game_button(&self) -> String {
@@ -812,10 +1272,31 @@ impl Translator {
         format!("{}{}\n{}", message, identifier, why)
     }
 
-    pub fn manifest_cannot_be_updated(&self, identifier: Option<&str>) -> String {
+    pub fn manifest_cannot_be_updated(&self, identifier: Option<&str>, why: Option<&str>) -> String {
         let message = translate("manifest-cannot-be-updated");
         let identifier = identifier.map(|x| format!(" ({})", x)).unwrap_or("".to_string());
-        format!("{}{}", message, identifier)
+        match why {
+            Some(why) => format!("{}{}\n{}", message, identifier, why),
+            None => format!("{}{}", message, identifier),
+        }
+    }
+
+    pub fn manifest_checksum_mismatch(&self, expected: &str, actual: &str, identifier: Option<&str>) -> String {
+        let message = translate("manifest-checksum-mismatch");
+        let identifier = identifier.map(|x| format!(" ({})", x)).unwrap_or("".to_string());
+        let mut args = FluentArgs::new();
+        args.set(EXPECTED, expected);
+        args.set(ACTUAL, actual);
+        format!(
+            "{}{}\n{}",
+            message,
+            identifier,
+            translate_args("manifest-checksum-mismatch.details", &args)
+        )
+    }
+
+    pub fn manifest_not_found(&self) -> String {
+        translate("manifest-not-found")
     }
 
     pub fn cannot_prepare_backup_target(&self, target: &StrictPath) -> String {
@@ -824,12 +1305,82 @@ impl Translator {
         translate_args("cannot-prepare-backup-target", &args)
     }
 
+    pub fn backup_rename_conflict(&self, game: &str, conflicts: &[String]) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        let prefix = translate_args("backup-rename-conflict", &args);
+        let lines: Vec<_> = conflicts.iter().map(|x| format!("  - {}", x)).collect();
+        format!("{}\n{}", prefix, lines.join("\n"))
+    }
+
+    pub fn cannot_rename_backups(&self, game: &str, path: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        args.set(PATH, path.render());
+        translate_args("cannot-rename-backups", &args)
+    }
+
     pub fn restoration_source_is_invalid(&self, source: &StrictPath) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, source.render());
         translate_args("restoration-source-is-invalid", &args)
     }
 
+    pub fn cannot_create_bundle(&self, target: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, target.render());
+        translate_args("cannot-create-bundle", &args)
+    }
+
+    pub fn bundle_is_invalid(&self, source: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, source.render());
+        translate_args("bundle-is-invalid", &args)
+    }
+
+    pub fn cannot_export_settings(&self, target: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, target.render());
+        translate_args("cannot-export-settings", &args)
+    }
+
+    pub fn cannot_write_report(&self, target: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, target.render());
+        translate_args("cannot-write-report", &args)
+    }
+
+    pub fn cannot_open_log_file(&self, target: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, target.render());
+        translate_args("cannot-open-log-file", &args)
+    }
+
+    pub fn cannot_write_manifest_snapshot(&self, target: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, target.render());
+        translate_args("cannot-write-manifest-snapshot", &args)
+    }
+
+    pub fn undefined_environment_variable(&self, name: &str, path: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, name);
+        args.set(PATH, path.raw());
+        translate_args("undefined-environment-variable", &args)
+    }
+
+    pub fn settings_are_invalid(&self, source: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, source.render());
+        translate_args("settings-are-invalid", &args)
+    }
+
+    pub fn confirm_settings_import(&self, source: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, source.render());
+        translate_args("confirm-settings-import", &args)
+    }
+
     pub fn registry_issue(&self) -> String {
         translate("registry-issue")
     }
@@ -838,6 +1389,22 @@ impl Translator {
         translate("unable-to-browse-file-system")
     }
 
+    pub fn unable_to_watch_files(&self) -> String {
+        translate("unable-to-watch-files")
+    }
+
+    pub fn git_unavailable(&self) -> String {
+        translate("git-unavailable")
+    }
+
+    pub fn max_backup_duration_exceeded(&self) -> String {
+        translate("max-backup-duration-exceeded")
+    }
+
+    pub fn git_command_failed(&self) -> String {
+        translate("git-command-failed")
+    }
+
     pub fn unable_to_open_dir(&self, path: &StrictPath) -> String {
         format!("{}\n\n{}", translate("unable-to-open-directory"), path.resolve())
     }
@@ -918,6 +1485,7 @@ impl Translator {
         translate(match store {
             Store::Ea => "store-ea",
             Store::Epic => "store-epic",
+            Store::Flatpak => "store-flatpak",
             Store::Gog => "store-gog",
             Store::GogGalaxy => "store-gog-galaxy",
             Store::Heroic => "store-heroic",
@@ -966,6 +1534,13 @@ impl Translator {
         })
     }
 
+    pub fn filter_outcome(&self, filter: game_filter::Outcome) -> String {
+        translate(match filter {
+            game_filter::Outcome::Succeeded => "label-succeeded",
+            game_filter::Outcome::Failed => "label-failed",
+        })
+    }
+
     pub fn filter_freshness(&self, filter: game_filter::Change) -> String {
         translate(match filter {
             game_filter::Change::New => "label-new",
@@ -979,6 +1554,7 @@ impl Translator {
         translate(match key {
             BackupFormat::Simple => "backup-format-simple",
             BackupFormat::Zip => "backup-format-zip",
+            BackupFormat::Git => "backup-format-git",
         })
     }
 
@@ -1137,6 +1713,10 @@ impl Translator {
         translate("label-scan")
     }
 
+    pub fn eta_label(&self) -> String {
+        translate("label-eta")
+    }
+
     pub fn scan_field(&self) -> String {
         self.field(&self.scan_label())
     }
@@ -1173,6 +1753,18 @@ impl Translator {
         self.field(&translate("label-folder"))
     }
 
+    pub fn disk_usage_label(&self) -> String {
+        self.field(&translate("label-disk-usage"))
+    }
+
+    pub fn note_label(&self) -> String {
+        self.field(&translate("label-note"))
+    }
+
+    pub fn based_on_label(&self) -> String {
+        self.field(&translate("label-based-on"))
+    }
+
     pub fn executable_label(&self) -> String {
         translate("label-executable")
     }
@@ -1209,6 +1801,22 @@ impl Translator {
         self.field(&translate("label-password"))
     }
 
+    pub fn bucket_label(&self) -> String {
+        self.field(&translate("label-bucket"))
+    }
+
+    pub fn region_label(&self) -> String {
+        self.field(&translate("label-region"))
+    }
+
+    pub fn access_key_label(&self) -> String {
+        self.field(&translate("label-access-key"))
+    }
+
+    pub fn secret_key_label(&self) -> String {
+        self.field(&translate("label-secret-key"))
+    }
+
     pub fn provider_label(&self) -> String {
         self.field(&translate("label-provider"))
     }
@@ -1287,6 +1895,10 @@ impl Translator {
         }
     }
 
+    pub fn confirm_restore_clean(&self) -> String {
+        translate("confirm-restore-clean")
+    }
+
     pub fn confirm_cloud_upload(&self, local: &str, cloud: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(LOCAL_PATH, local);
@@ -1294,6 +1906,14 @@ impl Translator {
         translate_args("confirm-cloud-upload", &args)
     }
 
+    pub fn confirm_cloud_purge(&self, folders: &[String]) -> String {
+        let mut out = translate("confirm-cloud-purge");
+        for folder in folders {
+            out += &format!("\n  - {folder}");
+        }
+        out
+    }
+
     pub fn confirm_cloud_download(&self, local: &str, cloud: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(LOCAL_PATH, local);
@@ -1305,6 +1925,10 @@ impl Translator {
         translate("no-cloud-changes")
     }
 
+    pub fn no_manifest_changes(&self) -> String {
+        translate("no-manifest-changes")
+    }
+
     pub fn notify_single_game_status(&self, found: bool) -> String {
         if found {
             translate("saves-found")
@@ -1347,6 +1971,10 @@ impl Translator {
         )
     }
 
+    pub fn throughput(&self, bytes_per_second: u64) -> String {
+        format!("{}/s", self.adjusted_size(bytes_per_second))
+    }
+
     pub fn game_is_unrecognized(&self) -> String {
         translate("game-is-unrecognized")
     }
@@ -1400,7 +2028,7 @@ impl Translator {
 
 #[cfg(test)]
 mod tests {
-    use crate::lang::TRANSLATOR;
+    use super::*;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -1411,4 +2039,30 @@ mod tests {
         assert_eq!("100.00 KiB", &TRANSLATOR.adjusted_size(102_400));
         assert_eq!("114.98 GiB", &TRANSLATOR.adjusted_size(123_456_789_000));
     }
+
+    #[test]
+    fn throughput() {
+        assert_eq!("0 B/s", &TRANSLATOR.throughput(0));
+        assert_eq!("1.03 KiB/s", &TRANSLATOR.throughput(1_050));
+    }
+
+    #[test]
+    fn cli_cloud_stats() {
+        assert_eq!(
+            r#"
+Transferred:
+  Files: 3
+  Size: 1.50 KiB
+  Duration: 2.25s
+  Speed: 682 B/s
+"#
+            .trim(),
+            &TRANSLATOR.cli_cloud_stats(&CloudStats {
+                files: 3,
+                bytes: 1_536,
+                elapsed: 2.25,
+                speed: 682.7,
+            }),
+        );
+    }
 }
@@ -0,0 +1,22 @@
+#![allow(clippy::too_many_arguments, clippy::to_string_trait_impl)]
+
+mod cli;
+mod cloud;
+pub mod embed;
+mod entry;
+mod gui;
+mod lang;
+mod logging;
+mod metadata;
+mod path;
+mod prelude;
+mod resource;
+mod scan;
+mod serialization;
+mod wrap;
+
+#[cfg(test)]
+mod testing;
+
+#[doc(hidden)]
+pub use crate::entry::main;
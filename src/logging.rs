@@ -0,0 +1,130 @@
+use std::{
+    fs::File,
+    io::{LineWriter, Write},
+    sync::Mutex,
+};
+
+use flexi_logger::{writers::LogWriter, DeferredNow};
+
+use crate::prelude::StrictPath;
+
+/// Writes each log record as one line of JSON,
+/// so that long runs can be analyzed afterward.
+pub struct NdjsonLogWriter {
+    file: Mutex<LineWriter<File>>,
+}
+
+impl NdjsonLogWriter {
+    pub fn new(path: &StrictPath) -> std::io::Result<Self> {
+        path.create_parent_dir()?;
+        let file = File::create(path.as_std_path_buf()?)?;
+
+        Ok(Self {
+            file: Mutex::new(LineWriter::new(file)),
+        })
+    }
+}
+
+impl LogWriter for NdjsonLogWriter {
+    fn write(&self, now: &mut DeferredNow, record: &log::Record) -> std::io::Result<()> {
+        let line = render(&now.now_utc_owned(), record);
+        let mut file = self.file.lock().unwrap();
+        writeln!(file, "{line}")
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        self.file.lock().unwrap().flush()
+    }
+}
+
+/// Collects the key-value pairs of a record (e.g., `log::trace!(game = name; "...")`).
+#[derive(Default)]
+struct Fields(serde_json::Map<String, serde_json::Value>);
+
+impl<'kvs> log::kv::VisitSource<'kvs> for Fields {
+    fn visit_pair(&mut self, key: log::kv::Key<'kvs>, value: log::kv::Value<'kvs>) -> Result<(), log::kv::Error> {
+        let value = if let Some(value) = value.to_bool() {
+            serde_json::Value::from(value)
+        } else if let Some(value) = value.to_u64() {
+            serde_json::Value::from(value)
+        } else if let Some(value) = value.to_i64() {
+            serde_json::Value::from(value)
+        } else if let Some(value) = value.to_f64() {
+            serde_json::Value::from(value)
+        } else {
+            serde_json::Value::from(value.to_string())
+        };
+        self.0.insert(key.as_str().to_string(), value);
+        Ok(())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Line<'a> {
+    timestamp: String,
+    level: &'a str,
+    target: &'a str,
+    message: String,
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+fn render(now: &chrono::DateTime<chrono::Utc>, record: &log::Record) -> String {
+    let mut fields = Fields::default();
+    let _ = record.key_values().visit(&mut fields);
+
+    let line = Line {
+        timestamp: now.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+        level: record.level().as_str(),
+        target: record.target(),
+        message: record.args().to_string(),
+        fields: fields.0,
+    };
+
+    serde_json::to_string(&line).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn can_render_record() {
+        let now = chrono::DateTime::parse_from_rfc3339("2000-01-02T03:04:05.678Z")
+            .unwrap()
+            .to_utc();
+        let fields: &[(&str, log::kv::Value)] = &[("game", "foo".into()), ("step", 3u64.into())];
+
+        let record = log::Record::builder()
+            .level(log::Level::Trace)
+            .target("ludusavi::cli")
+            .args(format_args!("step 3 / 5: foo"))
+            .key_values(&fields)
+            .build();
+
+        assert_eq!(
+            r#"{"timestamp":"2000-01-02T03:04:05.678Z","level":"TRACE","target":"ludusavi::cli","message":"step 3 / 5: foo","fields":{"game":"foo","step":3}}"#,
+            render(&now, &record),
+        );
+    }
+
+    #[test]
+    fn can_render_record_without_fields() {
+        let now = chrono::DateTime::parse_from_rfc3339("2000-01-02T03:04:05.678Z")
+            .unwrap()
+            .to_utc();
+
+        let record = log::Record::builder()
+            .level(log::Level::Warn)
+            .target("ludusavi")
+            .args(format_args!("hello"))
+            .build();
+
+        assert_eq!(
+            r#"{"timestamp":"2000-01-02T03:04:05.678Z","level":"WARN","target":"ludusavi","message":"hello"}"#,
+            render(&now, &record),
+        );
+    }
+}
@@ -5,8 +5,11 @@ use itertools::Itertools;
 use once_cell::sync::Lazy;
 
 use crate::{
-    prelude::{AnyError, SKIP},
-    resource::manifest::{placeholder, Os},
+    prelude::{AnyError, Hasher, SKIP},
+    resource::{
+        config::HashAlgorithm,
+        manifest::{placeholder, Os},
+    },
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -66,6 +69,23 @@ pub enum SetFileTimeError {
     InvalidTimestamp,
 }
 
+/// If the text is `$NAME`, `${NAME}`, or `%NAME%`, then get the name.
+fn env_var_reference(text: &str) -> Option<&str> {
+    let name = if let Some(name) = text.strip_prefix("${") {
+        name.strip_suffix('}')?
+    } else if let Some(name) = text.strip_prefix('$') {
+        name
+    } else {
+        text.strip_prefix('%')?.strip_suffix('%')?
+    };
+
+    (!name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '(' | ')')))
+    .then_some(name)
+}
+
 pub fn render_pathbuf(value: &std::path::Path) -> String {
     value.display().to_string()
 }
@@ -183,6 +203,25 @@ impl StrictPath {
         self.raw.to_string()
     }
 
+    /// If the path starts with an environment variable, then replace it with the value from `lookup`.
+    /// Variables are only expanded at the start of the path,
+    /// since other segments may legitimately contain `$` or `%`.
+    /// If the variable isn't set (or is empty), then this returns its name.
+    pub fn expand_env_var(&self, lookup: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let raw = self.raw.trim();
+        let (head, rest) = match raw.find(['/', '\\']) {
+            Some(i) => raw.split_at(i),
+            None => (raw, ""),
+        };
+        let Some(name) = env_var_reference(head) else {
+            return Ok(self.clone());
+        };
+        match lookup(name).filter(|x| !x.is_empty()) {
+            Some(value) => Ok(Self::relative(format!("{value}{rest}"), self.basis.clone())),
+            None => Err(name.to_string()),
+        }
+    }
+
     /// For any paths that we store the entire time the GUI is running, like in the config,
     /// we sometimes want to refresh in case we have stale data.
     pub fn invalidate_cache(&self) {
@@ -467,6 +506,64 @@ impl StrictPath {
         self.as_std_path_buf()?.metadata()
     }
 
+    /// Unix permission bits. This is always `None` on Windows.
+    pub fn unix_mode(&self) -> Option<u32> {
+        #[cfg(target_os = "windows")]
+        {
+            None
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            self.metadata().map(|x| x.permissions().mode() & 0o7777).ok()
+        }
+    }
+
+    /// This is a no-op on Windows.
+    pub fn set_unix_mode(&self, #[allow(unused)] mode: u32) -> Result<(), AnyError> {
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(self.as_std_path_buf()?, std::fs::Permissions::from_mode(mode))?;
+        }
+        Ok(())
+    }
+
+    /// If this is a symbolic link, then get the path that it points to.
+    /// This is always `None` on Windows.
+    pub fn symlink_target(&self) -> Option<String> {
+        #[cfg(target_os = "windows")]
+        {
+            None
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let path = self.as_std_path_buf().ok()?;
+            if !path.symlink_metadata().ok()?.is_symlink() {
+                return None;
+            }
+            std::fs::read_link(path).ok().map(|x| x.to_string_lossy().to_string())
+        }
+    }
+
+    /// Replace this path with a symbolic link to `original`.
+    /// This is not supported on Windows.
+    pub fn create_symlink(&self, #[allow(unused)] original: &str) -> Result<(), AnyError> {
+        #[cfg(target_os = "windows")]
+        {
+            Err("Symbolic links are not supported on Windows".into())
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let path = self.as_std_path_buf()?;
+            if path.symlink_metadata().is_ok() {
+                std::fs::remove_file(&path)?;
+            }
+            std::os::unix::fs::symlink(original, path)?;
+            Ok(())
+        }
+    }
+
     pub fn get_mtime(&self) -> std::io::Result<std::time::SystemTime> {
         self.metadata()?.modified()
     }
@@ -905,15 +1002,17 @@ impl StrictPath {
     }
 
     pub fn sha1(&self) -> String {
-        self.try_sha1().unwrap_or_default()
+        self.hash(HashAlgorithm::Sha1)
     }
 
-    pub fn try_sha1(&self) -> Result<String, Box<dyn std::error::Error>> {
-        use std::io::Read;
+    pub fn hash(&self, algorithm: HashAlgorithm) -> String {
+        self.try_hash(algorithm).unwrap_or_default()
+    }
 
-        use sha1::Digest;
+    pub fn try_hash(&self, algorithm: HashAlgorithm) -> Result<String, Box<dyn std::error::Error>> {
+        use std::io::Read;
 
-        let mut hasher = sha1::Sha1::new();
+        let mut hasher = Hasher::new(algorithm);
 
         let file = self.open()?;
         let mut reader = std::io::BufReader::new(file);
@@ -927,7 +1026,7 @@ impl StrictPath {
             hasher.update(&buffer[..read]);
         }
 
-        Ok(format!("{:x}", hasher.finalize()))
+        Ok(hasher.finalize())
     }
 }
 
@@ -1051,6 +1150,20 @@ mod tests {
             assert!(!StrictPath::new(repo()).is_file());
         }
 
+        #[test]
+        fn can_hash_file_with_each_algorithm() {
+            let path = StrictPath::new(format!("{}/tests/backup/game1/drive-X/file1.txt", repo()));
+            assert_eq!(
+                "3a52ce780950d4d969792a2559cd519d7ee8c727",
+                path.hash(HashAlgorithm::Sha1)
+            );
+            assert_eq!(
+                "cdb4ee2aea69cc6a83331bbe96dc2caa9a299d21329efb0336fc02a82e1839a8",
+                path.hash(HashAlgorithm::Sha256)
+            );
+            assert_eq!("4a810a3f4b01805cab8a815a3d735ad3", path.hash(HashAlgorithm::Xxh3));
+        }
+
         #[test]
         fn can_check_if_it_is_a_directory() {
             assert!(StrictPath::new(repo()).is_dir());
@@ -1457,6 +1570,47 @@ mod tests {
             assert_eq!(Ok(home()), path.access());
         }
 
+        #[test]
+        fn env_var_at_start() {
+            let lookup = |name: &str| (name == "GAMES").then(|| s("/games"));
+            assert_eq!(
+                Ok(StrictPath::from("/games/foo")),
+                StrictPath::from("$GAMES/foo").expand_env_var(lookup)
+            );
+            assert_eq!(
+                Ok(StrictPath::from("/games/foo")),
+                StrictPath::from("${GAMES}/foo").expand_env_var(lookup)
+            );
+            assert_eq!(
+                Ok(StrictPath::from("/games\\foo\\bar")),
+                StrictPath::from("%GAMES%\\foo\\bar").expand_env_var(lookup)
+            );
+            assert_eq!(
+                Ok(StrictPath::from("/games")),
+                StrictPath::from("$GAMES").expand_env_var(lookup)
+            );
+        }
+
+        #[test]
+        fn env_var_undefined() {
+            let lookup = |name: &str| (name == "EMPTY").then(String::new);
+            assert_eq!(Err(s("UNSET")), StrictPath::from("$UNSET/foo").expand_env_var(lookup));
+            assert_eq!(Err(s("EMPTY")), StrictPath::from("$EMPTY/foo").expand_env_var(lookup));
+        }
+
+        #[test]
+        fn env_var_not_at_start() {
+            let lookup = |_: &str| Some(s("/games"));
+            assert_eq!(
+                Ok(StrictPath::from("/foo/$GAMES")),
+                StrictPath::from("/foo/$GAMES").expand_env_var(lookup)
+            );
+            assert_eq!(
+                Ok(StrictPath::from("~/foo")),
+                StrictPath::from("~/foo").expand_env_var(lookup)
+            );
+        }
+
         #[test]
         fn empty() {
             let path = StrictPath::from("");
@@ -7,7 +7,10 @@ use std::{
 use once_cell::sync::Lazy;
 
 pub use crate::path::StrictPath;
-use crate::{path::CommonPath, resource::manifest::Os};
+use crate::{
+    path::CommonPath,
+    resource::{config::HashAlgorithm, manifest::Os},
+};
 
 pub static VERSION: Lazy<&'static str> =
     Lazy::new(|| option_env!("LUDUSAVI_VERSION").unwrap_or(env!("CARGO_PKG_VERSION")));
@@ -44,6 +47,10 @@ static HANDLER_SIGINT: Mutex<Option<signal_hook::SigId>> = Mutex::new(None);
 
 pub const ENV_DEBUG: &str = "LUDUSAVI_DEBUG";
 const ENV_THREADS: &str = "LUDUSAVI_THREADS";
+/// https://no-color.org
+const ENV_NO_COLOR: &str = "NO_COLOR";
+pub const ENV_ROOTS: &str = "LUDUSAVI_ROOTS";
+const ENV_ASSUME_YES: &str = "LUDUSAVI_ASSUME_YES";
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Finality {
@@ -84,6 +91,13 @@ pub enum Error {
     },
     ManifestCannotBeUpdated {
         identifier: Option<String>,
+        why: Option<String>,
+    },
+    ManifestNotFound,
+    ManifestChecksumMismatch {
+        expected: String,
+        actual: String,
+        identifier: Option<String>,
     },
     ConfigInvalid {
         why: String,
@@ -93,14 +107,57 @@ pub enum Error {
     },
     CliUnableToRequestConfirmation,
     CliBackupIdWithMultipleGames,
+    CliCloudPurgeWithoutTarget,
     CliInvalidBackupId,
+    CliOnlyRegistryUnsupported,
+    CliInvalidGamesFile {
+        why: String,
+    },
+    CliUnknownConfigKey {
+        key: String,
+        valid: Vec<String>,
+    },
+    CliInvalidConfigValue {
+        key: String,
+        why: String,
+    },
     SomeEntriesFailed,
     CannotPrepareBackupTarget {
         path: StrictPath,
     },
+    BackupRenameConflict {
+        game: String,
+        conflicts: Vec<String>,
+    },
+    CannotRenameBackups {
+        game: String,
+        path: StrictPath,
+    },
     RestorationSourceInvalid {
         path: StrictPath,
     },
+    CannotCreateBundle {
+        path: StrictPath,
+    },
+    BundleInvalid {
+        path: StrictPath,
+    },
+    CannotExportSettings {
+        path: StrictPath,
+    },
+    CannotWriteReport {
+        path: StrictPath,
+    },
+    CannotWriteManifestSnapshot {
+        path: StrictPath,
+    },
+    UndefinedEnvironmentVariable {
+        name: String,
+        path: StrictPath,
+    },
+    SettingsInvalid {
+        path: StrictPath,
+    },
     #[allow(dead_code)]
     RegistryIssue,
     UnableToBrowseFileSystem,
@@ -109,12 +166,32 @@ pub enum Error {
     RcloneUnavailable,
     CloudNotConfigured,
     CloudPathInvalid,
+    CloudRemoteReadOnly,
+    CloudRemoteInvalid {
+        remote: String,
+    },
+    CloudRemoteIncomplete {
+        fields: Vec<String>,
+    },
     UnableToConfigureCloud(CommandError),
     UnableToSynchronizeCloud(CommandError),
+    CloudUploadMismatched {
+        paths: Vec<String>,
+    },
     CloudConflict,
+    CloudIgnoreFileInvalid {
+        path: StrictPath,
+        line: Option<usize>,
+    },
     GameDidNotLaunch {
         why: String,
     },
+    UnableToWatchFiles {
+        why: String,
+    },
+    GitUnavailable,
+    GitCommandFailed(CommandError),
+    MaxBackupDurationExceeded,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CommandError {
@@ -178,6 +255,45 @@ pub fn sha1(content: String) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Incremental hasher for any of the supported algorithms.
+pub enum Hasher {
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>),
+}
+
+impl Hasher {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        use sha1::Digest;
+
+        match algorithm {
+            HashAlgorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Xxh3 => Self::Xxh3(Box::default()),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        use sha1::Digest;
+
+        match self {
+            Self::Sha1(hasher) => hasher.update(data),
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Xxh3(hasher) => hasher.update(data),
+        }
+    }
+
+    pub fn finalize(self) -> String {
+        use sha1::Digest;
+
+        match self {
+            Self::Sha1(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Xxh3(hasher) => format!("{:032x}", hasher.digest128()),
+        }
+    }
+}
+
 pub fn get_threads_from_env() -> Option<NonZeroUsize> {
     if let Ok(raw) = std::env::var(ENV_THREADS) {
         if let Ok(threads) = raw.parse::<NonZeroUsize>() {
@@ -196,6 +312,42 @@ pub fn get_threads_from_env() -> Option<NonZeroUsize> {
     }
 }
 
+/// Styled output is disabled by `--no-color` or by a non-empty `NO_COLOR` environment variable.
+/// Otherwise, each output stream is styled if it is a terminal
+/// (e.g., not when it is piped or redirected to a file).
+pub fn should_use_color(no_color: bool, is_terminal: bool) -> bool {
+    if no_color {
+        return false;
+    }
+    if std::env::var(ENV_NO_COLOR).is_ok_and(|x| !x.is_empty()) {
+        log::debug!("Disabling color because of {} environment variable", ENV_NO_COLOR);
+        return false;
+    }
+    is_terminal
+}
+
+/// Confirmation prompts are answered automatically by a `LUDUSAVI_ASSUME_YES` environment variable,
+/// unless it is empty, `0`, or `false`.
+pub fn should_assume_yes() -> bool {
+    let assume = std::env::var(ENV_ASSUME_YES).is_ok_and(|x| !matches!(x.trim(), "" | "0" | "false"));
+    if assume {
+        log::debug!(
+            "Skipping confirmation because of {} environment variable",
+            ENV_ASSUME_YES
+        );
+    }
+    assume
+}
+
+/// Prompts and progress bars are drawn on stderr, so they follow its setting
+/// rather than that of stdout.
+pub fn initialize_color(no_color: bool) {
+    use std::io::IsTerminal;
+
+    console::set_colors_enabled(should_use_color(no_color, std::io::stdout().is_terminal()));
+    console::set_colors_enabled_stderr(should_use_color(no_color, std::io::stderr().is_terminal()));
+}
+
 pub fn initialize_rayon(threads: NonZeroUsize) {
     let _ = rayon::ThreadPoolBuilder::new()
         .num_threads(threads.get())
@@ -203,7 +355,6 @@ pub fn initialize_rayon(threads: NonZeroUsize) {
 }
 
 pub struct CommandOutput {
-    #[allow(unused)]
     pub code: i32,
     pub stdout: String,
     #[allow(unused)]
@@ -1,15 +1,61 @@
 pub mod cache;
 pub mod config;
 pub mod manifest;
+pub mod settings;
+
+use std::io::{Read, Write};
 
 use crate::prelude::{app_dir, AnyError, StrictPath};
 
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Read a file as text, decompressing it first if it was saved with gzip.
+pub fn read_maybe_compressed(path: &StrictPath) -> Result<String, AnyError> {
+    let bytes = std::fs::read(path.as_std_path_buf()?)?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut content = String::new();
+        flate2::read::GzDecoder::new(bytes.as_slice()).read_to_string(&mut content)?;
+        Ok(content)
+    } else {
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+/// Write text to a file, compressing it with gzip if requested.
+pub fn write_maybe_compressed(path: &StrictPath, content: &str, compress: bool) -> Result<(), AnyError> {
+    if compress {
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(content.as_bytes())?;
+        std::fs::write(path.as_std_path_buf()?, encoder.finish()?)?;
+    } else {
+        path.write_with_content(content)?;
+    }
+    Ok(())
+}
+
+fn is_compressed(path: &StrictPath) -> bool {
+    let Ok(file) = path.as_std_path_buf().and_then(std::fs::File::open) else {
+        return false;
+    };
+    let mut magic = [0; 2];
+    file.take(2).read_exact(&mut magic).is_ok() && magic == GZIP_MAGIC
+}
+
 pub trait ResourceFile
 where
     Self: Default + serde::de::DeserializeOwned,
 {
     const FILE_NAME: &'static str;
 
+    /// Whether this file may be compressed on disk when `runtime.compressCache` is enabled.
+    /// Files like this can always be loaded whether or not they are compressed.
+    const COMPRESSIBLE: bool = false;
+
+    /// Whether to write this file compressed, given the config's `runtime.compressCache`.
+    fn compressed(compress_cache: bool) -> bool {
+        Self::COMPRESSIBLE && compress_cache
+    }
+
     fn path() -> StrictPath {
         app_dir().joined(Self::FILE_NAME)
     }
@@ -42,7 +88,11 @@ where
     }
 
     fn load_raw(path: &StrictPath) -> Result<String, AnyError> {
-        path.try_read()
+        if Self::COMPRESSIBLE {
+            read_maybe_compressed(path)
+        } else {
+            path.try_read()
+        }
     }
 
     fn load_from_string(content: &str) -> Result<Self, AnyError> {
@@ -55,16 +105,46 @@ where
     Self: ResourceFile + serde::Serialize,
 {
     fn save(&self) {
+        self.save_maybe_compressed(false);
+    }
+
+    /// Like `save`, but `COMPRESSIBLE` files are compressed if `compress_cache` is set.
+    fn save_maybe_compressed(&self, compress_cache: bool) {
         let new_content = serde_yaml::to_string(&self).unwrap();
+        let path = Self::path();
+        let compress = Self::compressed(compress_cache);
 
-        if let Ok(old_content) = Self::load_raw(&Self::path()) {
-            if old_content == new_content {
+        if let Ok(old_content) = Self::load_raw(&path) {
+            if old_content == new_content && is_compressed(&path) == compress {
                 return;
             }
         }
 
-        if Self::path().create_parent_dir().is_ok() {
-            let _ = Self::path().write_with_content(&new_content);
+        if path.create_parent_dir().is_ok() {
+            let _ = write_maybe_compressed(&path, &new_content, compress);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn can_round_trip_compressed_files() {
+        let dir = std::env::temp_dir().join("ludusavi-tests/compressed-resource");
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = StrictPath::from(dir.join("file.yaml"));
+
+        write_maybe_compressed(&path, "foo: bar", true).unwrap();
+        assert!(is_compressed(&path));
+        assert_eq!("foo: bar", read_maybe_compressed(&path).unwrap());
+
+        write_maybe_compressed(&path, "foo: bar", false).unwrap();
+        assert!(!is_compressed(&path));
+        assert_eq!("foo: bar", read_maybe_compressed(&path).unwrap());
+    }
+}
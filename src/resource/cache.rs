@@ -43,6 +43,8 @@ pub type Manifests = HashMap<String, Manifest>;
 #[serde(default)]
 pub struct Manifest {
     pub etag: Option<String>,
+    /// The server's `Last-Modified` header, sent back as `If-Modified-Since`.
+    pub last_modified: Option<String>,
     pub checked: Option<chrono::DateTime<chrono::Utc>>,
     pub updated: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -61,6 +63,7 @@ pub struct Restore {
 
 impl ResourceFile for Cache {
     const FILE_NAME: &'static str = "cache.yaml";
+    const COMPRESSIBLE: bool = true;
 }
 
 impl SaveableResourceFile for Cache {}
@@ -107,7 +110,7 @@ impl Cache {
         }
 
         if updated {
-            self.save();
+            self.save_maybe_compressed(config.runtime.compress_cache);
             config.save();
         }
 
@@ -117,6 +120,7 @@ impl Cache {
     pub fn update_manifest(&mut self, update: ManifestUpdate) {
         let cached = self.manifests.entry(update.url).or_default();
         cached.etag = update.etag;
+        cached.last_modified = update.last_modified;
         cached.checked = Some(update.timestamp);
         if update.modified {
             cached.updated = Some(update.timestamp);
@@ -143,8 +147,96 @@ impl Cache {
         })
     }
 
+    /// How many days have passed since the manifest at this URL was last checked.
+    pub fn manifest_age_days(&self, url: &str) -> Option<i64> {
+        let checked = self.manifests.get(url)?.checked?;
+        Some(chrono::offset::Utc::now().signed_duration_since(checked).num_days())
+    }
+
     pub fn should_check_app_update(&self) -> bool {
         let now = chrono::offset::Utc::now();
         now.signed_duration_since(self.release.checked).num_hours() >= 24
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::s;
+
+    fn timestamp(day: u32) -> chrono::DateTime<chrono::Utc> {
+        chrono::NaiveDate::from_ymd_opt(2000, 1, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_local_timezone(chrono::Utc)
+            .unwrap()
+    }
+
+    #[test]
+    fn can_parse_manifest_validators() {
+        let cache = Cache::load_from_string(
+            r#"
+            manifests:
+              example.com/new:
+                etag: "foo"
+                last_modified: "Sat, 01 Jan 2000 00:00:00 GMT"
+              example.com/old:
+                etag: "bar"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(&Manifest {
+                etag: Some(s("foo")),
+                last_modified: Some(s("Sat, 01 Jan 2000 00:00:00 GMT")),
+                ..Default::default()
+            }),
+            cache.manifests.get("example.com/new"),
+        );
+        assert_eq!(
+            Some(&Manifest {
+                etag: Some(s("bar")),
+                last_modified: None,
+                ..Default::default()
+            }),
+            cache.manifests.get("example.com/old"),
+        );
+
+        let reloaded = Cache::load_from_string(&serde_yaml::to_string(&cache).unwrap()).unwrap();
+        assert_eq!(cache, reloaded);
+    }
+
+    #[test]
+    fn can_update_manifest_validators() {
+        let mut cache = Cache::default();
+
+        cache.update_manifest(ManifestUpdate {
+            url: s("example.com"),
+            etag: Some(s("foo")),
+            last_modified: Some(s("Sat, 01 Jan 2000 00:00:00 GMT")),
+            timestamp: timestamp(1),
+            modified: true,
+        });
+        cache.update_manifest(ManifestUpdate {
+            url: s("example.com"),
+            etag: Some(s("foo")),
+            last_modified: Some(s("Sat, 01 Jan 2000 00:00:00 GMT")),
+            timestamp: timestamp(2),
+            modified: false,
+        });
+
+        assert_eq!(
+            Some(&Manifest {
+                etag: Some(s("foo")),
+                last_modified: Some(s("Sat, 01 Jan 2000 00:00:00 GMT")),
+                checked: Some(timestamp(2)),
+                updated: Some(timestamp(1)),
+            }),
+            cache.manifests.get("example.com"),
+        );
+    }
+}
@@ -10,7 +10,7 @@ use crate::{
     cloud::Remote,
     lang::{Language, TRANSLATOR},
     path::CommonPath,
-    prelude::{app_dir, Error, StrictPath, AVAILABLE_PARALELLISM},
+    prelude::{app_dir, Error, StrictPath, AVAILABLE_PARALELLISM, ENV_ROOTS},
     resource::{
         manifest::{self, CloudMetadata, Manifest, Store},
         ResourceFile, SaveableResourceFile,
@@ -25,6 +25,70 @@ fn default_backup_dir() -> StrictPath {
     StrictPath::new(format!("{}/ludusavi-backup", CommonPath::Home.get().unwrap()))
 }
 
+/// Read additional roots from the `LUDUSAVI_ROOTS` environment variable.
+fn roots_from_env() -> Vec<Root> {
+    match std::env::var(ENV_ROOTS) {
+        Ok(raw) => parse_env_roots(&raw),
+        Err(_) => vec![],
+    }
+}
+
+/// Variables that are set to an empty string are treated as unset.
+fn env_var_value(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|x| !x.is_empty())
+}
+
+/// Expand an environment variable at the start of a configured path, like `$HOME/saves`.
+pub fn expand_env_var(path: &StrictPath) -> Result<StrictPath, Error> {
+    path.expand_env_var(env_var_value)
+        .map_err(|name| Error::UndefinedEnvironmentVariable {
+            name,
+            path: path.clone(),
+        })
+}
+
+/// Parse a JSON array of roots, skipping any invalid entries.
+fn parse_env_roots(raw: &str) -> Vec<Root> {
+    let entries: Vec<serde_json::Value> = match serde_json::from_str(raw) {
+        Ok(x) => x,
+        Err(e) => {
+            log::warn!("Ignoring invalid {} environment variable: {e:?}", ENV_ROOTS);
+            return vec![];
+        }
+    };
+
+    entries
+        .into_iter()
+        .filter_map(|entry| match serde_json::from_value::<Root>(entry.clone()) {
+            Ok(root) => Some(root),
+            Err(e) => {
+                log::warn!(
+                    "Ignoring invalid root {entry} from {} environment variable: {e:?}",
+                    ENV_ROOTS
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+fn collect_keys(prefix: &str, value: &serde_yaml::Value, out: &mut Vec<String>) {
+    if let serde_yaml::Value::Mapping(mapping) = value {
+        for (key, value) in mapping {
+            let Some(key) = key.as_str() else {
+                continue;
+            };
+            let key = if prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            out.push(key.clone());
+            collect_keys(&key, value, out);
+        }
+    }
+}
+
 /// Settings for `config.yaml`
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
@@ -41,14 +105,36 @@ pub struct Config {
     pub scan: Scan,
     pub cloud: Cloud,
     pub apps: Apps,
+    pub gui: Gui,
     pub custom_games: Vec<CustomGame>,
+    /// Files with additional custom games, in the same format as `customGames`.
+    /// These are merged in order after `customGames`,
+    /// so a game in a later file overrides the same game from anywhere earlier.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub custom_game_files: Vec<StrictPath>,
+    /// Extra information about individual games, where each key is a game name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub games: BTreeMap<String, GameConfig>,
+    /// Extra files from the `--custom-games` CLI option,
+    /// which are merged after `custom_game_files`.
+    #[serde(skip)]
+    pub cli_custom_game_files: Vec<StrictPath>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Runtime {
     /// How many threads to use for parallel scanning.
-    pub threads: Option<NonZeroUsize>,
+    /// This was previously called `threads`, which is still accepted.
+    #[serde(alias = "threads")]
+    pub scan_threads: Option<NonZeroUsize>,
+    /// How many files Rclone should transfer at once during cloud synchronization.
+    /// When not set, this defers to Rclone's own default.
+    /// This is separate from `cloud.parallelGames`, which controls how many Rclone processes run at once.
+    pub cloud_threads: Option<NonZeroUsize>,
+    /// Whether to compress the downloaded manifests and the cache file on disk.
+    /// This saves space in the application folder at the cost of a little CPU time.
+    pub compress_cache: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
@@ -75,6 +161,26 @@ pub struct ManifestConfig {
     pub enable: bool,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub secondary: Vec<SecondaryManifestConfig>,
+    /// Expected SHA-256 checksum of the primary manifest.
+    /// When set, a downloaded update that does not match is rejected,
+    /// and the previously downloaded manifest is kept.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Set by the `--manifest-enforce-checksum` CLI option.
+    /// When there is no configured `checksum`, it is downloaded from a sibling `.sha256` URL.
+    #[serde(skip)]
+    pub enforce_checksum: bool,
+    /// Extra secondary manifest URLs from the `--manifest-extra` CLI option,
+    /// which are loaded after `secondary`.
+    #[serde(skip)]
+    pub cli_secondary: Vec<String>,
+    /// A local manifest file from the `--manifest-path` CLI option,
+    /// which is used instead of the downloaded manifest.
+    #[serde(skip)]
+    pub cli_path: Option<StrictPath>,
+    /// How many times to retry a download after a connection problem or server error,
+    /// waiting twice as long before each attempt.
+    pub retries: u8,
 }
 
 impl ManifestConfig {
@@ -89,6 +195,7 @@ impl ManifestConfig {
                 SecondaryManifestConfig::Local { .. } => None,
                 SecondaryManifestConfig::Remote { url, enable } => (*enable || force).then_some(url.as_str()),
             })
+            .chain(self.cli_secondary.iter().map(String::as_str))
             .collect()
     }
 
@@ -115,21 +222,29 @@ impl ManifestConfig {
                     if !enable {
                         return None;
                     }
-
-                    let path = Manifest::path_for(url, false);
-                    let manifest = Manifest::load_from(&path);
-                    if let Err(e) = &manifest {
-                        log::error!("Cannot load manifest: {:?} | {}", &path, e);
-                    }
-                    Some(manifest::Secondary {
-                        id: url.to_string(),
-                        path: path.clone(),
-                        data: manifest.ok()?,
-                    })
+                    Self::load_remote_secondary_manifest(url)
                 }
             })
+            .chain(
+                self.cli_secondary
+                    .iter()
+                    .filter_map(|url| Self::load_remote_secondary_manifest(url)),
+            )
             .collect()
     }
+
+    fn load_remote_secondary_manifest(url: &str) -> Option<manifest::Secondary> {
+        let path = Manifest::path_for(url, false);
+        let manifest = Manifest::load_from(&path);
+        if let Err(e) = &manifest {
+            log::error!("Cannot load manifest: {:?} | {}", &path, e);
+        }
+        Some(manifest::Secondary {
+            id: url.to_string(),
+            path: path.clone(),
+            data: manifest.ok()?,
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -271,6 +386,7 @@ impl ToString for Theme {
 pub enum Root {
     Ea(root::Ea),
     Epic(root::Epic),
+    Flatpak(root::Flatpak),
     Gog(root::Gog),
     GogGalaxy(root::GogGalaxy),
     Heroic(root::Heroic),
@@ -300,6 +416,7 @@ impl Root {
         match store {
             Store::Ea => Self::Ea(root::Ea { path: path.into() }),
             Store::Epic => Self::Epic(root::Epic { path: path.into() }),
+            Store::Flatpak => Self::Flatpak(root::Flatpak { path: path.into() }),
             Store::Gog => Self::Gog(root::Gog { path: path.into() }),
             Store::GogGalaxy => Self::GogGalaxy(root::GogGalaxy { path: path.into() }),
             Store::Heroic => Self::Heroic(root::Heroic { path: path.into() }),
@@ -326,6 +443,7 @@ impl Root {
         match self {
             Self::Ea(_) => Store::Ea,
             Self::Epic(_) => Store::Epic,
+            Self::Flatpak(_) => Store::Flatpak,
             Self::Gog(_) => Store::Gog,
             Self::GogGalaxy(_) => Store::GogGalaxy,
             Self::Heroic(_) => Store::Heroic,
@@ -349,6 +467,7 @@ impl Root {
         match self {
             Self::Ea(root::Ea { path }) => path,
             Self::Epic(root::Epic { path }) => path,
+            Self::Flatpak(root::Flatpak { path }) => path,
             Self::Gog(root::Gog { path }) => path,
             Self::GogGalaxy(root::GogGalaxy { path }) => path,
             Self::Heroic(root::Heroic { path }) => path,
@@ -372,6 +491,7 @@ impl Root {
         match self {
             Self::Ea(root::Ea { path }) => path,
             Self::Epic(root::Epic { path }) => path,
+            Self::Flatpak(root::Flatpak { path }) => path,
             Self::Gog(root::Gog { path }) => path,
             Self::GogGalaxy(root::GogGalaxy { path }) => path,
             Self::Heroic(root::Heroic { path }) => path,
@@ -445,6 +565,18 @@ impl Root {
     }
 }
 
+/// Where Ludusavi learned about a root.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RootSource {
+    /// From the `roots` in the config file.
+    Config,
+    /// From the `LUDUSAVI_ROOTS` environment variable.
+    Environment,
+    /// Found automatically, but not yet added to the config file.
+    Detected,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct RedirectConfig {
@@ -570,18 +702,27 @@ impl BackupFilter {
             ];
 
             for variant in variants {
-                if let Ok(glob) = globset::GlobBuilder::new(&variant)
+                match globset::GlobBuilder::new(&variant)
                     .literal_separator(true)
                     .backslash_escape(false)
                     .case_insensitive(true)
                     .build()
                 {
-                    builder.add(glob);
+                    Ok(glob) => {
+                        builder.add(glob);
+                    }
+                    Err(e) => {
+                        log::warn!("Ignoring invalid glob in backup exclusions: {:?} | {e}", item.raw());
+                        break;
+                    }
                 }
             }
         }
 
-        *path_globs = builder.build().ok();
+        *path_globs = builder
+            .build()
+            .inspect_err(|e| log::warn!("Unable to build backup exclusion globs: {e}"))
+            .ok();
     }
 
     pub fn is_path_ignored(&self, item: &StrictPath) -> bool {
@@ -620,6 +761,137 @@ impl BackupFilter {
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 pub struct ToggledPaths(BTreeMap<String, BTreeMap<StrictPath, bool>>);
 
+/// Preferred games for paths that are found by multiple games.
+/// Each key is a file or folder path,
+/// and each value is the name of the game that should back it up.
+/// Other games will skip those paths when backing up.
+/// Settings on child paths override settings on parent paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct DuplicateOwners(BTreeMap<StrictPath, String>);
+
+/// A size in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "FileSizeInput")]
+pub struct FileSize(pub u64);
+
+/// A number of bytes, or a string with a unit, like `500 MiB` or `2 GB`.
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+enum FileSizeInput {
+    Bytes(u64),
+    Text(String),
+}
+
+impl TryFrom<FileSizeInput> for FileSize {
+    type Error = String;
+
+    fn try_from(value: FileSizeInput) -> Result<Self, Self::Error> {
+        match value {
+            FileSizeInput::Bytes(bytes) => Ok(Self(bytes)),
+            FileSizeInput::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl std::str::FromStr for FileSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        byte_unit::Byte::parse_str(s, true)
+            .map(|x| Self(x.as_u64()))
+            .map_err(|e| format!("invalid file size '{s}': {e}"))
+    }
+}
+
+impl schemars::JsonSchema for FileSize {
+    fn schema_name() -> String {
+        "FileSize".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        FileSizeInput::json_schema(gen)
+    }
+}
+
+/// Compiled form of `backup.globalIgnore` and `backup.globalForceInclude`.
+#[derive(Clone, Debug, Default)]
+pub struct GlobalIgnore {
+    ignore: Option<globset::GlobSet>,
+    force_include: Option<globset::GlobSet>,
+}
+
+impl GlobalIgnore {
+    pub fn new(ignore: &[String], force_include: &[String]) -> Self {
+        Self {
+            ignore: Self::build(ignore),
+            force_include: Self::build(force_include),
+        }
+    }
+
+    fn build(patterns: &[String]) -> Option<globset::GlobSet> {
+        if patterns.is_empty() {
+            return None;
+        }
+
+        let mut builder = globset::GlobSetBuilder::new();
+        for pattern in patterns {
+            let normalized = pattern.trim().trim_end_matches(['/', '\\']).replace('\\', "/");
+            if normalized.is_empty() {
+                continue;
+            }
+
+            let anchored =
+                normalized.starts_with('/') || normalized.starts_with("**") || normalized.chars().nth(1) == Some(':');
+            let normalized = if anchored {
+                normalized
+            } else {
+                format!("**/{normalized}")
+            };
+
+            let variants = vec![
+                normalized.clone(),
+                // If the user has specified a plain folder, we also want to include its children.
+                format!("{}/**", &normalized),
+            ];
+
+            for variant in variants {
+                match globset::GlobBuilder::new(&variant)
+                    .literal_separator(true)
+                    .backslash_escape(false)
+                    .case_insensitive(true)
+                    .build()
+                {
+                    Ok(glob) => {
+                        builder.add(glob);
+                    }
+                    Err(e) => {
+                        log::warn!("Ignoring invalid glob in global ignore rules: {pattern:?} | {e}");
+                        break;
+                    }
+                }
+            }
+        }
+
+        builder
+            .build()
+            .inspect_err(|e| log::warn!("Unable to build global ignore globs: {e}"))
+            .ok()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ignore.is_none()
+    }
+
+    pub fn is_ignored(&self, path: &StrictPath) -> bool {
+        let Some(ignore) = &self.ignore else {
+            return false;
+        };
+
+        let rendered = path.render();
+        ignore.is_match(&rendered) && !self.force_include.as_ref().is_some_and(|x| x.is_match(&rendered))
+    }
+}
+
 /// Allows including/excluding specific registry keys.
 /// Each outer key is a game name,
 /// and each nested key is a registry key path.
@@ -787,17 +1059,85 @@ impl Default for Retention {
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupLayoutStyle {
+    /// Store each backup directly in the game's folder.
+    #[default]
+    Flat,
+    /// Store each backup in `YYYY/MM` folders within the game's folder,
+    /// based on when the backup was made.
+    DatePartitioned,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameRetention {
+    /// Full backups to keep. Range: 1-255.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full: Option<u8>,
+    /// Differential backups to keep. Range: 0-255.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub differential: Option<u8>,
+}
+
+impl GameRetention {
+    pub fn apply(&self, retention: &mut Retention) {
+        if let Some(full) = self.full {
+            retention.full = full;
+        }
+        if let Some(differential) = self.differential {
+            retention.differential = differential;
+        }
+    }
+}
+
+/// Parse limits like `5:10` (full and differential), `5` (only full), or `:10` (only differential).
+impl std::str::FromStr for GameRetention {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (full, differential) = match s.split_once(':') {
+            Some((full, differential)) => (full, Some(differential)),
+            None => (s, None),
+        };
+
+        let full = match full {
+            "" => None,
+            full => match full.parse::<u8>() {
+                Ok(full) if full > 0 => Some(full),
+                _ => return Err(format!("invalid full backup limit: {}", full)),
+            },
+        };
+        let differential = match differential {
+            None | Some("") => None,
+            Some(differential) => Some(
+                differential
+                    .parse::<u8>()
+                    .map_err(|_| format!("invalid differential backup limit: {}", differential))?,
+            ),
+        };
+
+        if full.is_none() && differential.is_none() {
+            return Err(format!("expected FULL:DIFFERENTIAL: {}", s));
+        }
+
+        Ok(Self { full, differential })
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum BackupFormat {
     #[default]
     Simple,
     Zip,
+    Git,
 }
 
 impl BackupFormat {
-    pub const ALL: &'static [Self] = &[Self::Simple, Self::Zip];
-    pub const ALL_NAMES: &'static [&'static str] = &["simple", "zip"];
+    pub const ALL: &'static [Self] = &[Self::Simple, Self::Zip, Self::Git];
+    pub const ALL_NAMES: &'static [&'static str] = &["simple", "zip", "git"];
 }
 
 impl std::str::FromStr for BackupFormat {
@@ -807,6 +1147,7 @@ impl std::str::FromStr for BackupFormat {
         match s {
             "simple" => Ok(Self::Simple),
             "zip" => Ok(Self::Zip),
+            "git" => Ok(Self::Git),
             _ => Err(format!("invalid backup format: {}", s)),
         }
     }
@@ -833,7 +1174,7 @@ pub struct BackupFormats {
 impl BackupFormats {
     pub fn level(&self) -> Option<i32> {
         match self.chosen {
-            BackupFormat::Simple => None,
+            BackupFormat::Simple | BackupFormat::Git => None,
             BackupFormat::Zip => match self.zip.compression {
                 ZipCompression::None => None,
                 ZipCompression::Deflate => Some(self.compression.deflate.level),
@@ -845,7 +1186,7 @@ impl BackupFormats {
 
     pub fn set_level(&mut self, value: i32) {
         match self.chosen {
-            BackupFormat::Simple => {}
+            BackupFormat::Simple | BackupFormat::Git => {}
             BackupFormat::Zip => match self.zip.compression {
                 ZipCompression::None => {}
                 ZipCompression::Deflate => {
@@ -863,7 +1204,7 @@ impl BackupFormats {
 
     pub fn range(&self) -> Option<std::ops::RangeInclusive<i32>> {
         match self.chosen {
-            BackupFormat::Simple => None,
+            BackupFormat::Simple | BackupFormat::Git => None,
             BackupFormat::Zip => match self.zip.compression {
                 ZipCompression::None => None,
                 ZipCompression::Deflate => Some(DeflateCompression::RANGE),
@@ -874,6 +1215,67 @@ impl BackupFormats {
     }
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameFormat {
+    /// Format in which to store new backups.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chosen: Option<BackupFormat>,
+    /// Compression method for zip backups.
+    /// Setting this implies the zip format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<ZipCompression>,
+    /// Compression level for the active compression method.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<i32>,
+}
+
+impl GameFormat {
+    pub fn apply(&self, formats: &mut BackupFormats) {
+        if let Some(chosen) = self.chosen {
+            formats.chosen = chosen;
+        }
+        if let Some(compression) = self.compression {
+            formats.chosen = BackupFormat::Zip;
+            formats.zip.compression = compression;
+        }
+        if let Some(level) = self.level {
+            formats.compression.set_level(&formats.zip.compression, level);
+        }
+    }
+}
+
+/// Parse a format like `zip`, `zstd`, or `zstd:19`.
+impl std::str::FromStr for GameFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, level) = match s.split_once(':') {
+            Some((method, level)) => (
+                method,
+                Some(
+                    level
+                        .parse::<i32>()
+                        .map_err(|_| format!("invalid compression level: {}", level))?,
+                ),
+            ),
+            None => (s, None),
+        };
+
+        let mut format = Self {
+            level,
+            ..Default::default()
+        };
+        if let Ok(chosen) = method.parse::<BackupFormat>() {
+            format.chosen = Some(chosen);
+        } else {
+            format.compression = Some(method.parse::<ZipCompression>()?);
+        }
+
+        Ok(format)
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct ZipConfig {
@@ -995,6 +1397,37 @@ impl ZstdCompression {
     pub const RANGE: std::ops::RangeInclusive<i32> = -7..=22;
 }
 
+/// Algorithm used to hash files for change detection.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Xxh3,
+}
+
+impl HashAlgorithm {
+    pub const ALL_NAMES: &'static [&'static str] = &["sha1", "sha256", "xxh3"];
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha1" => Ok(Self::Sha1),
+            "sha256" => Ok(Self::Sha256),
+            "xxh3" => Ok(Self::Xxh3),
+            _ => Err(format!("invalid hash algorithm: {}", s)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct BackupConfig {
@@ -1008,6 +1441,56 @@ pub struct BackupConfig {
     pub sort: Sort,
     pub retention: Retention,
     pub format: BackupFormats,
+    /// Format overrides for specific games, by name.
+    /// Any settings that are not specified will use the general `format`.
+    pub per_game_format: BTreeMap<String, GameFormat>,
+    /// Retention overrides for specific games, by name.
+    /// Any limits that are not specified will use the general `retention`.
+    pub per_game_retention: BTreeMap<String, GameRetention>,
+    /// Algorithm to use when hashing files for new backups.
+    /// `xxh3` is faster, while `sha256` is stronger.
+    /// Each backup records which algorithm it used.
+    pub hash_algorithm: HashAlgorithm,
+    pub duplicate_owners: DuplicateOwners,
+    /// Whether to descend into symbolic links to other folders while scanning for saves.
+    /// Loops are detected and skipped.
+    /// Paths that are themselves symbolic links are always followed.
+    pub follow_symlinks: bool,
+    /// Skip files larger than this when backing up.
+    /// This may be a number of bytes or include a unit, like `500 MiB` or `2 GB`.
+    /// Registry entries are not affected.
+    /// Default: unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_file_size: Option<FileSize>,
+    /// Glob patterns for files to skip in every game, like `*.log` or `crashdumps`.
+    /// Relative patterns may match anywhere in a file's path,
+    /// and matching a folder also skips everything inside of it.
+    pub global_ignore: Vec<String>,
+    /// Glob patterns for files to back up even if they match `globalIgnore`.
+    pub global_force_include: Vec<String>,
+    /// How to organize new backups within each game's folder.
+    /// Existing backups are not migrated: they are left as-is and can still be restored,
+    /// so you can change this at any time.
+    pub layout_style: BackupLayoutStyle,
+    /// Store identical file content only once within each game's backups,
+    /// even if it was renamed or moved, rather than copying it into every backup.
+    /// This only applies to the simple format.
+    /// Existing backups stay as they are and can still be restored.
+    pub deduplicate: bool,
+    /// Record empty folders within the save locations,
+    /// so that they can be recreated on restore.
+    pub preserve_empty_dirs: bool,
+    /// Skip files that are OS clutter rather than game data,
+    /// like `Thumbs.db`, `desktop.ini`, or `.DS_Store`.
+    /// The list depends on the current platform.
+    pub exclude_system_files: bool,
+    /// Template for the names of new backups, like `{date}_{time}_{tag}`.
+    /// Supported tokens are `{date}`, `{time}`, and `{tag}` (`full` or `diff`).
+    /// Names always start with `backup-`,
+    /// and a number is added if needed to keep them unique.
+    /// Default: `backup-<timestamp>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_format: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
@@ -1055,6 +1538,15 @@ pub struct Cloud {
     /// If true, upload changes automatically after backing up,
     /// as long as there aren't any conflicts.
     pub synchronize: bool,
+    /// When only some games are being synchronized,
+    /// run up to this many Rclone processes at once, each with different games.
+    /// This can be faster for remotes with high latency per file,
+    /// but setting it too high may overwhelm the remote.
+    pub parallel_games: NonZeroUsize,
+    /// After uploading, download the uploaded files again
+    /// and check that they match the local backups.
+    /// This is slower, but it can catch files that were corrupted in transit.
+    pub verify_upload: bool,
 }
 
 impl Default for Cloud {
@@ -1063,10 +1555,35 @@ impl Default for Cloud {
             remote: Default::default(),
             path: "ludusavi-backup".to_string(),
             synchronize: true,
+            parallel_games: NonZeroUsize::MIN,
+            verify_upload: false,
         }
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Gui {
+    /// Size of the GUI's main window, in logical pixels.
+    /// This is updated automatically when you resize the window.
+    pub window_size: Option<WindowSize>,
+    /// Position of the GUI's main window, in logical pixels.
+    /// This is updated automatically when you move the window.
+    pub window_position: Option<WindowPosition>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct WindowSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct WindowPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Apps {
@@ -1104,6 +1621,14 @@ impl App {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameConfig {
+    /// A note for your own reference, such as why the game is disabled.
+    /// This is shown by the `backups` command and in backup/restore output.
+    pub note: Option<String>,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct CustomGame {
@@ -1168,10 +1693,35 @@ impl Default for ManifestConfig {
             url: None,
             enable: true,
             secondary: vec![],
+            checksum: None,
+            enforce_checksum: false,
+            cli_secondary: vec![],
+            cli_path: None,
+            retries: 2,
         }
     }
 }
 
+impl BackupConfig {
+    /// The general format settings, with any overrides for this game.
+    pub fn format_for_game(&self, game: &str) -> BackupFormats {
+        let mut formats = self.format.clone();
+        if let Some(format) = self.per_game_format.get(game) {
+            format.apply(&mut formats);
+        }
+        formats
+    }
+
+    /// Whether any game would be backed up in the Git format.
+    pub fn uses_git(&self) -> bool {
+        self.format.chosen == BackupFormat::Git
+            || self
+                .per_game_format
+                .values()
+                .any(|x| x.chosen == Some(BackupFormat::Git))
+    }
+}
+
 impl Default for BackupConfig {
     fn default() -> Self {
         Self {
@@ -1183,6 +1733,19 @@ impl Default for BackupConfig {
             sort: Default::default(),
             retention: Retention::default(),
             format: Default::default(),
+            per_game_format: Default::default(),
+            per_game_retention: Default::default(),
+            hash_algorithm: Default::default(),
+            duplicate_owners: Default::default(),
+            follow_symlinks: true,
+            max_file_size: None,
+            global_ignore: vec![],
+            global_force_include: vec![],
+            layout_style: Default::default(),
+            deduplicate: false,
+            preserve_empty_dirs: false,
+            exclude_system_files: true,
+            id_format: None,
         }
     }
 }
@@ -1252,6 +1815,126 @@ impl Config {
         Ok(())
     }
 
+    /// Dotted keys that can be used with `get_key` and `set_key` (e.g., `backup.retention.full`).
+    /// Per-game settings are also available as `games."<name>".<setting>` (e.g., `games."Some Game".note`).
+    pub fn keys() -> Vec<String> {
+        let mut out = vec![];
+        if let Ok(value) = serde_yaml::to_value(Self::default()) {
+            collect_keys("", &value, &mut out);
+        }
+        out
+    }
+
+    fn game_keys() -> Vec<String> {
+        let mut out = vec![];
+        if let Ok(value) = serde_yaml::to_value(GameConfig::default()) {
+            collect_keys("", &value, &mut out);
+        }
+        out
+    }
+
+    /// Split a dotted key into its parts.
+    /// A part can be quoted to include dots (e.g., `games."Some Game 2.0".note`).
+    fn split_key(key: &str) -> Vec<String> {
+        let mut parts = vec![];
+        let mut current = String::new();
+        let mut quoted = false;
+        for c in key.chars() {
+            match c {
+                '"' => quoted = !quoted,
+                '.' if !quoted => parts.push(std::mem::take(&mut current)),
+                _ => current.push(c),
+            }
+        }
+        parts.push(current);
+        parts
+    }
+
+    fn check_key(key: &str) -> Result<(), Error> {
+        if let [games, game, rest @ ..] = Self::split_key(key).as_slice() {
+            let game_keys = Self::game_keys();
+            if games == "games" && !game.is_empty() && (rest.is_empty() || game_keys.contains(&rest.join("."))) {
+                return Ok(());
+            }
+        }
+
+        let valid = Self::keys();
+        if !valid.iter().any(|x| x == key) {
+            return Err(Error::CliUnknownConfigKey {
+                key: key.to_string(),
+                valid,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn get_key(&self, key: &str) -> Result<serde_yaml::Value, Error> {
+        Self::check_key(key)?;
+
+        let mut value = serde_yaml::to_value(self).map_err(|e| Error::ConfigInvalid { why: e.to_string() })?;
+        for part in Self::split_key(key) {
+            value = value.get(part.as_str()).cloned().unwrap_or(serde_yaml::Value::Null);
+        }
+        Ok(value)
+    }
+
+    /// If the current value is a string, then the new value is used as-is.
+    /// Otherwise, the new value is parsed as YAML,
+    /// and it must have the right type for the key.
+    /// If there is no current value and the YAML doesn't fit, then the new value is tried as a string.
+    pub fn set_key(&mut self, key: &str, raw: &str) -> Result<(), Error> {
+        let existing = self.get_key(key)?;
+        let invalid = |why: String| Error::CliInvalidConfigValue {
+            key: key.to_string(),
+            why,
+        };
+
+        let as_string = serde_yaml::Value::String(raw.to_string());
+        let updated = if existing.is_string() {
+            self.with_key(key, as_string)
+        } else {
+            let parsed = serde_yaml::from_str(raw).map_err(|e: serde_yaml::Error| e.to_string());
+            match parsed.and_then(|value| self.with_key(key, value)) {
+                Ok(updated) => Ok(updated),
+                Err(e) if existing.is_null() => self.with_key(key, as_string).map_err(|_| e),
+                Err(e) => Err(e),
+            }
+        };
+
+        *self = ResourceFile::migrate(updated.map_err(invalid)?);
+        Ok(())
+    }
+
+    fn with_key(&self, key: &str, value: serde_yaml::Value) -> Result<Self, String> {
+        let mut root = serde_yaml::to_value(self).map_err(|e| e.to_string())?;
+        let mut current = &mut root;
+        for part in Self::split_key(key) {
+            if current.is_null() {
+                *current = serde_yaml::Value::Mapping(Default::default());
+            }
+            if !current.is_mapping() {
+                return Err(format!("Cannot set a field inside of {current:?}"));
+            }
+            let mapping = current.as_mapping_mut().unwrap();
+            let part = serde_yaml::Value::String(part);
+            if !mapping.contains_key(&part) {
+                mapping.insert(part.clone(), serde_yaml::Value::Null);
+            }
+            current = mapping.get_mut(&part).unwrap();
+        }
+        *current = value;
+
+        serde_yaml::from_value(root).map_err(|e| e.to_string())
+    }
+
+    /// The note from `games`, if the game has one.
+    pub fn game_note(&self, name: &str) -> Option<&str> {
+        self.games
+            .get(name)
+            .and_then(|x| x.note.as_deref())
+            .filter(|x| !x.is_empty())
+    }
+
     pub fn find_missing_roots(&self) -> Vec<Root> {
         let mut pf32 = "C:/Program Files (x86)".to_string();
         let mut pf64 = "C:/Program Files".to_string();
@@ -1283,6 +1966,8 @@ impl Config {
             // GOG Galaxy:
             (format!("{}/GOG Galaxy/Games", pf32), Store::GogGalaxy),
             (format!("{}/GOG Galaxy/Games", pf64), Store::GogGalaxy),
+            // Flatpak:
+            ("~/.var/app".to_string(), Store::Flatpak),
             // Heroic:
             ("~/.config/heroic".to_string(), Store::Heroic),
             (
@@ -1526,6 +2211,33 @@ impl Config {
         });
     }
 
+    /// Each group of custom games, labeled by where it came from,
+    /// in the order that they should be merged.
+    pub fn custom_game_sources(&self) -> Vec<(String, Vec<CustomGame>)> {
+        let mut sources = vec![(Self::path().render(), self.custom_games.clone())];
+
+        for path in self.custom_game_files.iter().chain(&self.cli_custom_game_files) {
+            let games = path
+                .try_read()
+                .and_then(|content| Ok(serde_yaml::from_str::<Vec<CustomGame>>(&content)?));
+            match games {
+                Ok(games) => sources.push((path.render(), games)),
+                Err(e) => log::error!("Cannot load custom games file: {:?} | {}", &path, e),
+            }
+        }
+
+        sources
+    }
+
+    /// Find the effective custom game with this name,
+    /// where later sources override earlier ones.
+    pub fn find_custom_game(&self, name: &str) -> Option<CustomGame> {
+        self.custom_game_sources()
+            .into_iter()
+            .flat_map(|(_, games)| games)
+            .rfind(|x| x.name == name)
+    }
+
     pub fn is_game_customized(&self, name: &str) -> bool {
         self.custom_games.iter().any(|x| x.name == name)
     }
@@ -1550,6 +2262,24 @@ impl Config {
         self.custom_games.iter().all(|x| !x.ignore)
     }
 
+    /// The backup folder, with any leading environment variable expanded.
+    /// If the variable is not set, then the path is used as-is.
+    pub fn backup_path(&self) -> StrictPath {
+        expand_env_var(&self.backup.path).unwrap_or_else(|e| {
+            log::warn!("Unable to expand backup path: {e:?}");
+            self.backup.path.clone()
+        })
+    }
+
+    /// The restore folder, with any leading environment variable expanded.
+    /// If the variable is not set, then the path is used as-is.
+    pub fn restore_path(&self) -> StrictPath {
+        expand_env_var(&self.restore.path).unwrap_or_else(|e| {
+            log::warn!("Unable to expand restore path: {e:?}");
+            self.restore.path.clone()
+        })
+    }
+
     pub fn expanded_roots(&self) -> Vec<Root> {
         for root in &self.roots {
             log::trace!(
@@ -1561,7 +2291,33 @@ impl Config {
             );
         }
 
-        let expanded: Vec<Root> = self.roots.iter().flat_map(|x| x.glob()).collect();
+        let env_roots = roots_from_env();
+        for root in &env_roots {
+            log::trace!(
+                "Environment root: {:?} | interpreted: {:?} | exists: {} | is dir: {}",
+                &root,
+                root.path().interpret(),
+                root.path().exists(),
+                root.path().is_dir()
+            );
+        }
+
+        let expanded: Vec<Root> = self
+            .roots
+            .iter()
+            .chain(&env_roots)
+            .filter_map(|x| match x.path().expand_env_var(env_var_value) {
+                Ok(path) => Some(x.with_path(path)),
+                Err(name) => {
+                    log::warn!(
+                        "Skipping root with unset environment variable {name}: {:?}",
+                        x.path().raw()
+                    );
+                    None
+                }
+            })
+            .flat_map(|x| x.glob())
+            .collect();
 
         for root in &expanded {
             log::trace!(
@@ -1576,6 +2332,40 @@ impl Config {
         expanded
     }
 
+    /// Every root that Ludusavi knows about, including ones that have not been added to the config yet.
+    /// Unlike `expanded_roots`, this keeps roots whose paths do not currently exist.
+    pub fn all_roots(&self) -> Vec<(Root, RootSource)> {
+        fn expand(root: &Root) -> Vec<Root> {
+            let globbed = root.glob();
+            if globbed.is_empty() {
+                vec![root.clone()]
+            } else {
+                globbed
+            }
+        }
+
+        let mut roots: Vec<_> = self
+            .roots
+            .iter()
+            .flat_map(expand)
+            .map(|root| (root, RootSource::Config))
+            .chain(
+                roots_from_env()
+                    .iter()
+                    .flat_map(expand)
+                    .map(|root| (root, RootSource::Environment)),
+            )
+            .collect();
+
+        for root in self.find_missing_roots() {
+            if !roots.iter().any(|(known, _)| known.path().equivalent(root.path())) {
+                roots.push((root, RootSource::Detected));
+            }
+        }
+
+        roots
+    }
+
     pub fn should_show_game(&self, name: &str, restoring: bool, changed: bool, scanned: bool) -> bool {
         (self.scan.show_deselected_games || self.is_game_enabled_for_operation(name, restoring))
             && (self.scan.show_unchanged_games || changed || !scanned)
@@ -1584,14 +2374,14 @@ impl Config {
 
     pub fn override_threads(&mut self, overridden: bool) {
         if overridden {
-            self.runtime.threads = *AVAILABLE_PARALELLISM;
+            self.runtime.scan_threads = *AVAILABLE_PARALELLISM;
         } else {
-            self.runtime.threads = None;
+            self.runtime.scan_threads = None;
         }
     }
 
     pub fn set_threads(&mut self, threads: usize) {
-        self.runtime.threads = NonZeroUsize::new(threads);
+        self.runtime.scan_threads = NonZeroUsize::new(threads);
     }
 
     pub fn display_name<'a>(&'a self, official: &'a str) -> &'a str {
@@ -1620,6 +2410,34 @@ impl Config {
     }
 }
 
+impl DuplicateOwners {
+    #[cfg(test)]
+    pub fn new(data: BTreeMap<StrictPath, String>) -> Self {
+        Self(data)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Find the preferred game for this path, based on the path itself or its nearest parent.
+    pub fn owner(&self, path: &StrictPath) -> Option<&str> {
+        let specific = self.0.get(path).or_else(|| {
+            self.0
+                .iter()
+                .find(|(k, _)| path.interpret() == k.interpret())
+                .map(|(_, v)| v)
+        });
+
+        specific
+            .or_else(|| {
+                path.nearest_prefix(self.0.keys().cloned().collect())
+                    .and_then(|prefix| self.0.get(&prefix))
+            })
+            .map(|x| x.as_str())
+    }
+}
+
 impl ToggledPaths {
     #[cfg(test)]
     pub fn new(data: BTreeMap<String, BTreeMap<StrictPath, bool>>) -> Self {
@@ -1911,7 +2729,12 @@ mod tests {
                 manifest: ManifestConfig {
                     url: Some(s("example.com")),
                     enable: true,
-                    secondary: vec![]
+                    secondary: vec![],
+                    checksum: None,
+                    enforce_checksum: false,
+                    cli_secondary: vec![],
+                    cli_path: None,
+                    retries: 2,
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -1929,6 +2752,19 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    per_game_format: Default::default(),
+                    per_game_retention: Default::default(),
+                    hash_algorithm: Default::default(),
+                    duplicate_owners: Default::default(),
+                    follow_symlinks: true,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    global_force_include: vec![],
+                    layout_style: Default::default(),
+                    deduplicate: false,
+                    preserve_empty_dirs: false,
+                    exclude_system_files: true,
+                    id_format: None,
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -1962,6 +2798,7 @@ mod tests {
               etag: "foo"
               secondary:
                 - url: example.com/2
+              checksum: abc123
             roots:
               - path: ~/steam
                 store: steam
@@ -2010,6 +2847,8 @@ mod tests {
                   - Custom Registry 1
                   - Custom Registry 2
                   - Custom Registry 2
+            customGameFiles:
+              - /custom-games.yaml
             "#,
         )
         .unwrap();
@@ -2024,7 +2863,12 @@ mod tests {
                     secondary: vec![SecondaryManifestConfig::Remote {
                         url: s("example.com/2"),
                         enable: true,
-                    }]
+                    }],
+                    checksum: Some(s("abc123")),
+                    enforce_checksum: false,
+                    cli_secondary: vec![],
+                    cli_path: None,
+                    retries: 2,
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -2049,6 +2893,19 @@ mod tests {
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    per_game_format: Default::default(),
+                    per_game_retention: Default::default(),
+                    hash_algorithm: Default::default(),
+                    duplicate_owners: Default::default(),
+                    follow_symlinks: true,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    global_force_include: vec![],
+                    layout_style: Default::default(),
+                    deduplicate: false,
+                    preserve_empty_dirs: false,
+                    exclude_system_files: true,
+                    id_format: None,
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -2071,6 +2928,8 @@ mod tests {
                     }),
                     path: "ludusavi-backup".to_string(),
                     synchronize: false,
+                    parallel_games: NonZeroUsize::MIN,
+                    verify_upload: false,
                 },
                 apps: Apps {
                     rclone: App {
@@ -2078,6 +2937,7 @@ mod tests {
                         arguments: "".to_string(),
                     },
                 },
+                gui: Default::default(),
                 custom_games: vec![
                     CustomGame {
                         name: s("Custom Game 1"),
@@ -2096,23 +2956,252 @@ mod tests {
                         registry: vec![s("Custom Registry 1"), s("Custom Registry 2"), s("Custom Registry 2"),],
                     },
                 ],
+                custom_game_files: vec![StrictPath::new(s("/custom-games.yaml"))],
+                games: Default::default(),
+                cli_custom_game_files: vec![],
             },
             config,
         );
     }
 
+    #[test]
+    fn can_parse_legacy_runtime_threads() {
+        let config = Config::load_from_string(
+            r#"
+            manifest:
+              url: example.com
+            roots: []
+            backup:
+              path: ~/backup
+            restore:
+              path: ~/restore
+            runtime:
+              threads: 4
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Runtime {
+                scan_threads: NonZeroUsize::new(4),
+                cloud_threads: None,
+                compress_cache: false,
+            },
+            config.runtime,
+        );
+    }
+
+    #[test]
+    fn can_parse_env_roots() {
+        assert_eq!(
+            vec![Root::new("/steam", Store::Steam), Root::new("/other", Store::Other),],
+            parse_env_roots(
+                r#"[
+                    {"path": "/steam", "store": "steam"},
+                    {"path": "/invalid", "store": "unknown"},
+                    {"path": 1, "store": "gog"},
+                    {"path": "/other", "store": "other"}
+                ]"#
+            ),
+        );
+    }
+
+    #[test]
+    fn can_parse_invalid_env_roots() {
+        assert_eq!(Vec::<Root>::new(), parse_env_roots("not json"));
+        assert_eq!(
+            Vec::<Root>::new(),
+            parse_env_roots(r#"{"path": "/steam", "store": "steam"}"#)
+        );
+    }
+
+    #[test]
+    fn can_parse_game_format() {
+        assert_eq!(
+            Ok(GameFormat {
+                chosen: Some(BackupFormat::Simple),
+                ..Default::default()
+            }),
+            "simple".parse::<GameFormat>()
+        );
+        assert_eq!(
+            Ok(GameFormat {
+                compression: Some(ZipCompression::Zstd),
+                level: Some(19),
+                ..Default::default()
+            }),
+            "zstd:19".parse::<GameFormat>()
+        );
+        assert!("zstd:high".parse::<GameFormat>().is_err());
+        assert!("rar".parse::<GameFormat>().is_err());
+    }
+
+    #[test]
+    fn can_parse_game_retention() {
+        assert_eq!(
+            Ok(GameRetention {
+                full: Some(5),
+                differential: Some(10),
+            }),
+            "5:10".parse::<GameRetention>()
+        );
+        assert_eq!(
+            Ok(GameRetention {
+                full: Some(5),
+                differential: None,
+            }),
+            "5".parse::<GameRetention>()
+        );
+        assert_eq!(
+            Ok(GameRetention {
+                full: None,
+                differential: Some(0),
+            }),
+            ":0".parse::<GameRetention>()
+        );
+        assert!("0:1".parse::<GameRetention>().is_err());
+        assert!("1:many".parse::<GameRetention>().is_err());
+        assert!(":".parse::<GameRetention>().is_err());
+    }
+
+    #[test]
+    fn can_parse_file_size() {
+        assert_eq!(Ok(FileSize(1000)), "1000".parse::<FileSize>());
+        assert_eq!(Ok(FileSize(500 * 1024 * 1024)), "500 MiB".parse::<FileSize>());
+        assert_eq!(Ok(FileSize(2_000_000_000)), "2gb".parse::<FileSize>());
+        assert!("big".parse::<FileSize>().is_err());
+
+        assert_eq!(FileSize(1024), serde_yaml::from_str("1024").unwrap());
+        assert_eq!(FileSize(1024), serde_yaml::from_str("1 KiB").unwrap());
+        assert!(serde_yaml::from_str::<FileSize>("big").is_err());
+    }
+
+    #[test]
+    fn can_match_global_ignore() {
+        let rules = GlobalIgnore::new(&[s("*.log"), s("crashdumps/"), s("/exact/file.txt")], &[s("keep.log")]);
+
+        assert!(!GlobalIgnore::default().is_ignored(&StrictPath::new(s("/game/debug.log"))));
+        assert!(rules.is_ignored(&StrictPath::new(s("/game/debug.log"))));
+        assert!(rules.is_ignored(&StrictPath::new(s("/game/DEBUG.LOG"))));
+        assert!(rules.is_ignored(&StrictPath::new(s("/game/crashdumps/1.dmp"))));
+        assert!(rules.is_ignored(&StrictPath::new(s("/exact/file.txt"))));
+        assert!(!rules.is_ignored(&StrictPath::new(s("/other/exact/file.txt"))));
+        assert!(!rules.is_ignored(&StrictPath::new(s("/game/save.dat"))));
+        assert!(!rules.is_ignored(&StrictPath::new(s("/game/keep.log"))));
+    }
+
+    #[test]
+    fn can_match_global_ignore_with_an_invalid_glob() {
+        let rules = GlobalIgnore::new(&[s("[invalid"), s("*.log")], &[]);
+
+        assert!(rules.is_ignored(&StrictPath::new(s("/game/debug.log"))));
+        assert!(!rules.is_ignored(&StrictPath::new(s("/game/save.dat"))));
+    }
+
+    #[test]
+    fn can_find_duplicate_owner() {
+        let owners = DuplicateOwners::new(btree_map! {
+            StrictPath::new(s("/shared")): s("game1"),
+            StrictPath::new(s("/shared/specific.txt")): s("game2"),
+        });
+
+        assert_eq!(Some("game1"), owners.owner(&StrictPath::new(s("/shared"))));
+        assert_eq!(Some("game1"), owners.owner(&StrictPath::new(s("/shared/other.txt"))));
+        assert_eq!(Some("game2"), owners.owner(&StrictPath::new(s("/shared/specific.txt"))));
+        assert_eq!(None, owners.owner(&StrictPath::new(s("/unrelated/file.txt"))));
+    }
+
+    #[test]
+    fn can_apply_per_game_format() {
+        let config = BackupConfig {
+            per_game_format: btree_map! {
+                s("compressed"): GameFormat {
+                    compression: Some(ZipCompression::Zstd),
+                    level: Some(19),
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        };
+
+        let formats = config.format_for_game("compressed");
+        assert_eq!(BackupFormat::Zip, formats.chosen);
+        assert_eq!(ZipCompression::Zstd, formats.zip.compression);
+        assert_eq!(Some(19), formats.level());
+
+        assert_eq!(config.format, config.format_for_game("other"));
+    }
+
+    #[test]
+    fn can_get_and_set_config_keys() {
+        let mut config = Config::default();
+
+        config.set_key("backup.path", "/backups").unwrap();
+        assert_eq!(StrictPath::new(s("/backups")), config.backup.path);
+        assert_eq!(
+            serde_yaml::Value::String(s("/backups")),
+            config.get_key("backup.path").unwrap()
+        );
+
+        config.set_key("backup.retention.full", "3").unwrap();
+        assert_eq!(3, config.backup.retention.full);
+        assert_eq!(
+            serde_yaml::Value::from(3),
+            config.get_key("backup.retention.full").unwrap()
+        );
+
+        assert!(matches!(
+            config.set_key("backup.retention.full", "many"),
+            Err(Error::CliInvalidConfigValue { .. })
+        ));
+        assert_eq!(3, config.backup.retention.full);
+
+        match config.get_key("backup.fake") {
+            Err(Error::CliUnknownConfigKey { key, valid }) => {
+                assert_eq!("backup.fake", key);
+                assert!(valid.contains(&s("backup.retention.full")));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn can_get_and_set_game_notes() {
+        let mut config = Config::default();
+
+        config
+            .set_key(r#"games."Some Game 2.0".note"#, "disabled: crashes on launch")
+            .unwrap();
+        assert_eq!(Some("disabled: crashes on launch"), config.game_note("Some Game 2.0"));
+        assert_eq!(
+            serde_yaml::Value::String(s("disabled: crashes on launch")),
+            config.get_key(r#"games."Some Game 2.0".note"#).unwrap()
+        );
+
+        config.set_key("games.other.note", "5").unwrap();
+        assert_eq!(Some("5"), config.game_note("other"));
+
+        assert!(matches!(
+            config.get_key("games.other.fake"),
+            Err(Error::CliUnknownConfigKey { .. })
+        ));
+    }
+
     #[test]
     fn can_be_serialized() {
         assert_eq!(
             r#"
 ---
 runtime:
-  threads: ~
+  scanThreads: ~
+  cloudThreads: ~
+  compressCache: false
 release:
   check: true
 manifest:
   url: example.com
   enable: true
+  retries: 2
 language: en-US
 theme: light
 roots:
@@ -2160,6 +3249,17 @@ backup:
         level: 6
       zstd:
         level: 10
+  perGameFormat: {}
+  perGameRetention: {}
+  hashAlgorithm: sha1
+  duplicateOwners: {}
+  followSymlinks: true
+  globalIgnore: []
+  globalForceInclude: []
+  layoutStyle: flat
+  deduplicate: false
+  preserveEmptyDirs: false
+  excludeSystemFiles: true
 restore:
   path: ~/restore
   ignoredGames:
@@ -2181,10 +3281,15 @@ cloud:
       id: remote-id
   path: ludusavi-backup
   synchronize: true
+  parallelGames: 1
+  verifyUpload: false
 apps:
   rclone:
     path: rclone.exe
     arguments: ""
+gui:
+  windowSize: ~
+  windowPosition: ~
 customGames:
   - name: Custom Game 1
     files: []
@@ -2210,7 +3315,12 @@ customGames:
                 manifest: ManifestConfig {
                     url: Some(s("example.com")),
                     enable: true,
-                    secondary: vec![]
+                    secondary: vec![],
+                    checksum: None,
+                    enforce_checksum: false,
+                    cli_secondary: vec![],
+                    cli_path: None,
+                    retries: 2,
                 },
                 language: Language::English,
                 theme: Theme::Light,
@@ -2236,6 +3346,19 @@ customGames:
                     sort: Default::default(),
                     retention: Retention::default(),
                     format: Default::default(),
+                    per_game_format: Default::default(),
+                    per_game_retention: Default::default(),
+                    hash_algorithm: Default::default(),
+                    duplicate_owners: Default::default(),
+                    follow_symlinks: true,
+                    max_file_size: None,
+                    global_ignore: vec![],
+                    global_force_include: vec![],
+                    layout_style: Default::default(),
+                    deduplicate: false,
+                    preserve_empty_dirs: false,
+                    exclude_system_files: true,
+                    id_format: None,
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -2259,6 +3382,8 @@ customGames:
                     }),
                     path: "ludusavi-backup".to_string(),
                     synchronize: true,
+                    parallel_games: NonZeroUsize::MIN,
+                    verify_upload: false,
                 },
                 apps: Apps {
                     rclone: App {
@@ -2266,6 +3391,7 @@ customGames:
                         arguments: "".to_string(),
                     }
                 },
+                gui: Default::default(),
                 custom_games: vec![
                     CustomGame {
                         name: s("Custom Game 1"),
@@ -2292,6 +3418,9 @@ customGames:
                         registry: vec![],
                     },
                 ],
+                custom_game_files: vec![],
+                games: Default::default(),
+                cli_custom_game_files: vec![],
             })
             .unwrap()
             .trim(),
@@ -2468,6 +3597,32 @@ customGames:
             }
         }
 
+        #[test]
+        fn can_ignore_specific_values_from_config() {
+            let config = Config::load_from_string(
+                r#"
+                roots: []
+                backup:
+                  path: ~/backup
+                  toggledRegistry:
+                    game:
+                      HKEY_CURRENT_USER/Software/Foo:
+                        values:
+                          HardwareId: false
+                restore:
+                  path: ~/restore
+                "#,
+            )
+            .unwrap();
+
+            let toggled = &config.backup.toggled_registry;
+            let path = RegistryItem::new(s("HKEY_CURRENT_USER/Software/Foo"));
+            assert!(!toggled.is_ignored("game", &path, None));
+            assert!(!toggled.is_ignored("game", &path, Some("SaveSlot")));
+            assert!(toggled.is_ignored("game", &path, Some("HardwareId")));
+            assert!(!toggled.is_ignored("other", &path, Some("HardwareId")));
+        }
+
         #[test]
         fn transitively_unset_and_specifically_unset_or_disabled() {
             verify_toggle_registry_bouncing(
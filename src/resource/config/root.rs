@@ -38,6 +38,25 @@ pub struct Epic {
     pub path: StrictPath,
 }
 
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Flatpak {
+    /// Where the root is located on your system.
+    pub path: StrictPath,
+}
+
 #[derive(
     Clone,
     Debug,
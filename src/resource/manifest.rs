@@ -1,14 +1,16 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
+use once_cell::sync::Lazy;
+
 use crate::{
     lang::TRANSLATOR,
-    prelude::{app_dir, Error, StrictPath},
+    prelude::{app_dir, Error, Hasher, StrictPath},
     resource::{
         cache::{self, Cache},
-        config::{Config, CustomGame, ManifestConfig},
+        config::{Config, CustomGame, HashAlgorithm, ManifestConfig},
         ResourceFile, SaveableResourceFile,
     },
-    scan::layout::escape_folder_name,
+    scan::{layout::escape_folder_name, normalize_title, ScanChange},
 };
 
 pub mod placeholder {
@@ -103,6 +105,7 @@ impl From<&str> for Os {
 pub enum Store {
     Ea,
     Epic,
+    Flatpak,
     Gog,
     GogGalaxy,
     Heroic,
@@ -127,6 +130,7 @@ impl Store {
     pub const ALL: &'static [Self] = &[
         Store::Ea,
         Store::Epic,
+        Store::Flatpak,
         Store::Gog,
         Store::GogGalaxy,
         Store::Heroic,
@@ -144,6 +148,33 @@ impl Store {
         Store::OtherMac,
         Store::Other,
     ];
+
+    /// The serialized name of each store in `ALL`, like `gogGalaxy`.
+    pub fn all_names() -> &'static [&'static str] {
+        static OWNED: Lazy<Vec<String>> = Lazy::new(|| {
+            Store::ALL
+                .iter()
+                .map(|store| match serde_json::to_value(store) {
+                    Ok(serde_json::Value::String(name)) => name,
+                    _ => unreachable!("stores serialize as strings"),
+                })
+                .collect()
+        });
+        static NAMES: Lazy<Vec<&'static str>> = Lazy::new(|| OWNED.iter().map(String::as_str).collect());
+        &NAMES
+    }
+}
+
+impl std::str::FromStr for Store {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all_names()
+            .iter()
+            .position(|x| *x == s)
+            .map(|i| Self::ALL[i])
+            .ok_or_else(|| format!("invalid store: {}", s))
+    }
 }
 
 impl ToString for Store {
@@ -201,11 +232,26 @@ pub struct Game {
     pub cloud: CloudMetadata,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub notes: Vec<Note>,
+    /// Localized titles, where each key is a language code like `de-DE`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub titles: BTreeMap<String, String>,
     #[serde(skip)]
     pub sources: BTreeSet<Source>,
 }
 
 impl Game {
+    /// Find the title for a language code like `de-DE`.
+    /// If there is no exact match, then this falls back to the same base language (e.g., `de`).
+    pub fn localized_title(&self, language: &str) -> Option<&str> {
+        let base = |code: &str| code.split(['-', '_']).next().unwrap_or_default().to_lowercase();
+
+        self.titles
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(language))
+            .or_else(|| self.titles.iter().find(|(code, _)| base(code) == base(language)))
+            .map(|(_, title)| title.as_str())
+    }
+
     /// This is intended for secondary manifests.
     fn normalize_relative_paths(&mut self) {
         use placeholder::BASE;
@@ -356,16 +402,83 @@ pub struct Note {
     pub source: Option<String>,
 }
 
+/// Differences between two versions of the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestDiff {
+    /// Games that only exist in the newer manifest.
+    pub added: BTreeSet<String>,
+    /// Games that only exist in the older manifest.
+    pub removed: BTreeSet<String>,
+    /// Games that exist in both manifests, but with different paths or registry keys.
+    pub changed: BTreeMap<String, GameDiff>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Differences in one game's paths and registry keys between two versions of the manifest.
+/// An entry is `Different` when it exists in both versions, but its tags or constraints changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDiff {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, ScanChange>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub registry: BTreeMap<String, ScanChange>,
+}
+
+impl GameDiff {
+    fn new(old: &Game, new: &Game) -> Self {
+        Self {
+            files: Self::compare(&old.files, &new.files),
+            registry: Self::compare(&old.registry, &new.registry),
+        }
+    }
+
+    fn compare<T: PartialEq>(old: &BTreeMap<String, T>, new: &BTreeMap<String, T>) -> BTreeMap<String, ScanChange> {
+        let mut changes = BTreeMap::new();
+
+        for (key, old_entry) in old {
+            match new.get(key) {
+                None => {
+                    changes.insert(key.clone(), ScanChange::Removed);
+                }
+                Some(new_entry) if new_entry != old_entry => {
+                    changes.insert(key.clone(), ScanChange::Different);
+                }
+                Some(_) => {}
+            }
+        }
+        for key in new.keys() {
+            if !old.contains_key(key) {
+                changes.insert(key.clone(), ScanChange::New);
+            }
+        }
+
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.registry.is_empty()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct ManifestUpdate {
     pub url: String,
     pub etag: Option<String>,
+    pub last_modified: Option<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub modified: bool,
 }
 
 impl ResourceFile for Manifest {
     const FILE_NAME: &'static str = "manifest.yaml";
+    const COMPRESSIBLE: bool = true;
 }
 
 impl Manifest {
@@ -400,6 +513,14 @@ impl Manifest {
             })
     }
 
+    /// Load a manifest from an arbitrary file, such as an older copy of the primary manifest.
+    pub fn load_file(path: &StrictPath) -> Result<Self, Error> {
+        ResourceFile::load_from_existing(path).map_err(|e| Error::ManifestInvalid {
+            why: format!("{}", e),
+            identifier: Some(path.render()),
+        })
+    }
+
     pub fn should_update(url: &str, cache: &cache::Manifests, force: bool, primary: bool) -> bool {
         if force {
             return true;
@@ -422,30 +543,56 @@ impl Manifest {
         config: ManifestConfig,
         cache: cache::Manifests,
         force: bool,
+        compress_cache: bool,
     ) -> Vec<Result<Option<ManifestUpdate>, Error>> {
         let mut out = vec![];
 
         if config.enable || force {
-            out.push(Self::update_one(config.url(), &cache, force, true));
+            let checksum = (config.enforce_checksum || config.checksum.is_some()).then_some(config.checksum.as_deref());
+            out.push(Self::update_one(
+                config.url(),
+                &cache,
+                force,
+                true,
+                checksum,
+                config.retries,
+                compress_cache,
+            ));
         }
 
         for secondary in config.secondary_manifest_urls(force) {
-            out.push(Self::update_one(secondary, &cache, force, false));
+            let checksum = config.enforce_checksum.then_some(None);
+            out.push(Self::update_one(
+                secondary,
+                &cache,
+                force,
+                false,
+                checksum,
+                config.retries,
+                compress_cache,
+            ));
         }
 
         out
     }
 
+    /// When `checksum` is set, the download must match it.
+    /// If it is set to `None`, then we look for the expected value at a sibling `.sha256` URL.
     fn update_one(
         url: &str,
         cache: &cache::Manifests,
         force: bool,
         primary: bool,
+        checksum: Option<Option<&str>>,
+        retries: u8,
+        compress_cache: bool,
     ) -> Result<Option<ManifestUpdate>, Error> {
         let identifier = (!primary).then(|| url.to_string());
-        let cannot_update = || Error::ManifestCannotBeUpdated {
+        let cannot_update_because = |why: Option<String>| Error::ManifestCannotBeUpdated {
             identifier: identifier.clone(),
+            why,
         };
+        let cannot_update = || cannot_update_because(None);
 
         if !Self::should_update(url, cache, force, primary) {
             return Ok(None);
@@ -453,16 +600,24 @@ impl Manifest {
 
         let path = Self::path_for(url, primary);
 
-        let mut req = reqwest::blocking::Client::new()
-            .get(url)
-            .header(reqwest::header::USER_AGENT, &*crate::prelude::USER_AGENT);
         let old_etag = cache.get(url).and_then(|x| x.etag.clone());
-        if let Some(etag) = old_etag.as_ref() {
+        let old_last_modified = cache.get(url).and_then(|x| x.last_modified.clone());
+        let request = || {
+            let mut req = reqwest::blocking::Client::new()
+                .get(url)
+                .header(reqwest::header::USER_AGENT, &*crate::prelude::USER_AGENT);
+            // The validators only apply if we still have the file that they describe.
             if path.exists() {
-                req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+                if let Some(etag) = old_etag.as_ref() {
+                    req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+                }
+                if let Some(last_modified) = old_last_modified.as_ref() {
+                    req = req.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+                }
             }
-        }
-        let mut res = req.send().map_err(|_e| cannot_update())?;
+            req
+        };
+        let mut res = Self::send_with_retries(url, retries, request).map_err(|why| cannot_update_because(Some(why)))?;
         match res.status() {
             reqwest::StatusCode::OK => {
                 app_dir().create_dirs().map_err(|_| cannot_update())?;
@@ -470,6 +625,22 @@ impl Manifest {
                 // Ensure that the manifest data is valid before we save it.
                 let mut manifest_bytes = vec![];
                 res.copy_to(&mut manifest_bytes).map_err(|_| cannot_update())?;
+
+                if let Some(checksum) = checksum {
+                    let expected = match checksum {
+                        Some(expected) => expected.to_string(),
+                        None => Self::fetch_checksum(url).ok_or_else(cannot_update)?,
+                    };
+                    if let Err(actual) = Self::verify_checksum(&manifest_bytes, &expected) {
+                        log::error!("Manifest checksum mismatch for {url}: expected {expected}, got {actual}");
+                        return Err(Error::ManifestChecksumMismatch {
+                            expected: expected.trim().to_string(),
+                            actual,
+                            identifier: identifier.clone(),
+                        });
+                    }
+                }
+
                 let manifest_string = String::from_utf8(manifest_bytes).map_err(|_| cannot_update())?;
                 if let Err(e) = Self::load_from_string(&manifest_string) {
                     return Err(Error::ManifestInvalid {
@@ -478,16 +649,19 @@ impl Manifest {
                     });
                 }
 
-                path.write_with_content(&manifest_string).map_err(|_| cannot_update())?;
+                crate::resource::write_maybe_compressed(&path, &manifest_string, Self::compressed(compress_cache))
+                    .map_err(|_| cannot_update())?;
 
-                let new_etag = res
-                    .headers()
-                    .get(reqwest::header::ETAG)
-                    .map(|etag| String::from_utf8_lossy(etag.as_bytes()).to_string());
+                let header = |name| {
+                    res.headers()
+                        .get(name)
+                        .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string())
+                };
 
                 Ok(Some(ManifestUpdate {
                     url: url.to_string(),
-                    etag: new_etag,
+                    etag: header(reqwest::header::ETAG),
+                    last_modified: header(reqwest::header::LAST_MODIFIED),
                     timestamp: chrono::offset::Utc::now(),
                     modified: true,
                 }))
@@ -495,22 +669,86 @@ impl Manifest {
             reqwest::StatusCode::NOT_MODIFIED => Ok(Some(ManifestUpdate {
                 url: url.to_string(),
                 etag: old_etag,
+                last_modified: old_last_modified,
                 timestamp: chrono::offset::Utc::now(),
                 modified: false,
             })),
-            _ => Err(cannot_update()),
+            status => Err(cannot_update_because(Some(status.to_string()))),
+        }
+    }
+
+    /// Send a request, retrying after connection problems or server errors
+    /// with an exponential backoff.
+    /// On failure, this returns the cause of the last attempt.
+    fn send_with_retries(
+        url: &str,
+        retries: u8,
+        request: impl Fn() -> reqwest::blocking::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response, String> {
+        let mut attempt = 0;
+        loop {
+            let why = match request().send() {
+                Ok(res) if !res.status().is_server_error() => return Ok(res),
+                Ok(res) => res.status().to_string(),
+                Err(e) => e.to_string(),
+            };
+            if attempt >= retries {
+                return Err(why);
+            }
+            attempt += 1;
+            let delay = Self::retry_delay(attempt);
+            log::debug!(
+                "Manifest download failed for {url}: {why}; retrying in {delay:?} (attempt {attempt} of {retries})"
+            );
+            std::thread::sleep(delay);
+        }
+    }
+
+    /// How long to wait before a retry, doubling after each attempt.
+    fn retry_delay(attempt: u8) -> std::time::Duration {
+        std::time::Duration::from_secs(1 << attempt.saturating_sub(1).min(6))
+    }
+
+    /// On mismatch, this returns the actual checksum.
+    fn verify_checksum(content: &[u8], expected: &str) -> Result<(), String> {
+        let mut hasher = Hasher::new(HashAlgorithm::Sha256);
+        hasher.update(content);
+        let actual = hasher.finalize();
+        if actual.eq_ignore_ascii_case(expected.trim()) {
+            Ok(())
+        } else {
+            Err(actual)
+        }
+    }
+
+    /// Read the checksum from a sibling URL, in the `sha256sum` output format.
+    fn fetch_checksum(url: &str) -> Option<String> {
+        let res = reqwest::blocking::Client::new()
+            .get(format!("{url}.sha256"))
+            .header(reqwest::header::USER_AGENT, &*crate::prelude::USER_AGENT)
+            .send()
+            .ok()?;
+        if res.status() != reqwest::StatusCode::OK {
+            log::error!("Unable to download manifest checksum for {url}: {}", res.status());
+            return None;
         }
+        res.text().ok()?.split_whitespace().next().map(|x| x.to_string())
     }
 
     pub fn update_mut(config: &Config, cache: &mut Cache, force: bool) -> Result<(), Error> {
         let mut error = None;
 
-        let updates = Self::update(config.manifest.clone(), cache.manifests.clone(), force);
+        let updates = Self::update(
+            config.manifest.clone(),
+            cache.manifests.clone(),
+            force,
+            config.runtime.compress_cache,
+        );
         for update in updates {
             match update {
                 Ok(Some(update)) => {
                     cache.update_manifest(update);
-                    cache.save();
+                    cache.save_maybe_compressed(config.runtime.compress_cache);
                 }
                 Ok(None) => {}
                 Err(e) => {
@@ -578,12 +816,34 @@ impl Manifest {
         self
     }
 
+    /// Build a manifest with only this custom game,
+    /// so that its definition can be tested in isolation.
+    pub fn from_custom_game(custom: CustomGame) -> Self {
+        let mut manifest = Self::default();
+        manifest.add_custom_game(custom);
+        manifest
+    }
+
     fn add_custom_games(&mut self, config: &Config) {
-        for custom_game in &config.custom_games {
-            if custom_game.ignore {
-                continue;
+        let mut winners = HashMap::<String, String>::new();
+
+        for (source, custom_games) in config.custom_game_sources() {
+            for custom_game in custom_games {
+                if custom_game.ignore {
+                    continue;
+                }
+                if let Some(previous) = winners.insert(custom_game.name.clone(), source.clone()) {
+                    if previous != source {
+                        log::info!(
+                            "Custom game from {} overrides the one from {}: {}",
+                            &source,
+                            &previous,
+                            &custom_game.name
+                        );
+                    }
+                }
+                self.add_custom_game(custom_game);
             }
-            self.add_custom_game(custom_game.clone());
         }
     }
 
@@ -612,6 +872,7 @@ impl Manifest {
             // you probably still want to back up your customized versions of such games.
             cloud: CloudMetadata::default(),
             notes: Default::default(),
+            titles: existing.map(|x| x.titles.clone()).unwrap_or_default(),
             sources: Default::default(),
         };
 
@@ -637,6 +898,7 @@ impl Manifest {
     fn incorporate_secondary_manifest(&mut self, secondary: Secondary) {
         log::debug!("incorporating secondary manifest: {}", &secondary.id);
         let manifest = secondary.data.0;
+        let mut added = 0;
 
         for (name, mut game) in manifest {
             game.normalize_relative_paths();
@@ -671,6 +933,10 @@ impl Manifest {
                 }
                 standard.notes.extend(game.notes);
 
+                for (language, title) in game.titles {
+                    standard.titles.entry(language).or_insert(title);
+                }
+
                 standard.sources.insert(Source::Secondary(secondary.id.clone()));
             } else {
                 log::debug!("adding game from secondary manifest: {name}");
@@ -686,8 +952,29 @@ impl Manifest {
                 game.sources.insert(Source::Secondary(secondary.id.clone()));
 
                 self.0.insert(name, game);
+                added += 1;
+            }
+        }
+
+        log::info!("secondary manifest added {} games: {}", added, &secondary.id);
+    }
+
+    /// How many games each secondary manifest defines that are not in the primary manifest.
+    /// Each key is the URL or path of a secondary manifest.
+    pub fn secondary_contributions(&self) -> BTreeMap<String, usize> {
+        let mut contributions = BTreeMap::new();
+        for game in self.0.values() {
+            let primary = game.sources.contains(&Source::Primary);
+            for source in &game.sources {
+                if let Source::Secondary(id) = source {
+                    let total = contributions.entry(id.clone()).or_insert(0);
+                    if !primary {
+                        *total += 1;
+                    }
+                }
             }
         }
+        contributions
     }
 
     pub fn processable_titles(&self) -> impl Iterator<Item = &String> {
@@ -706,6 +993,7 @@ impl Manifest {
                 id,
                 cloud: _,
                 notes: _,
+                titles: _,
                 sources: _,
             } = &v;
             alias.is_none()
@@ -751,6 +1039,81 @@ impl Manifest {
             })
             .collect()
     }
+
+    /// Compare this (older) manifest against a newer one.
+    pub fn diff(&self, newer: &Self) -> ManifestDiff {
+        let mut diff = ManifestDiff::default();
+
+        for (name, old_game) in &self.0 {
+            match newer.0.get(name) {
+                None => {
+                    diff.removed.insert(name.clone());
+                }
+                Some(new_game) => {
+                    let game_diff = GameDiff::new(old_game, new_game);
+                    if !game_diff.is_empty() {
+                        diff.changed.insert(name.clone(), game_diff);
+                    }
+                }
+            }
+        }
+        for name in newer.0.keys() {
+            if !self.0.contains_key(name) {
+                diff.added.insert(name.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Pair each game with its normalized title, as used when searching for games by name.
+    pub fn with_normalized_names(&self) -> BTreeMap<&str, NormalizedGame<'_>> {
+        self.0
+            .iter()
+            .map(|(name, game)| {
+                (
+                    name.as_str(),
+                    NormalizedGame {
+                        game,
+                        normalized: normalize_title(name),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Each game's title for a language code like `de-DE`,
+    /// or its normal name if there is no localized title.
+    pub fn with_localized_titles(&self, language: &str) -> BTreeMap<&str, LocalizedGame<'_>> {
+        self.0
+            .iter()
+            .map(|(name, game)| {
+                (
+                    name.as_str(),
+                    LocalizedGame {
+                        game,
+                        localized: game.localized_title(language).unwrap_or(name).to_string(),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// A manifest entry along with its title in a specific language.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct LocalizedGame<'a> {
+    #[serde(flatten)]
+    pub game: &'a Game,
+    pub localized: String,
+}
+
+/// A manifest entry along with its normalized title.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct NormalizedGame<'a> {
+    #[serde(flatten)]
+    pub game: &'a Game,
+    pub normalized: String,
 }
 
 #[cfg(test)]
@@ -759,7 +1122,20 @@ mod tests {
     use velcro::{btree_map, btree_set, hash_map};
 
     use super::*;
-    use crate::testing::s;
+    use crate::{
+        resource::config::CustomGame,
+        testing::{repo, s},
+    };
+
+    #[test]
+    fn store_names_match_serialization() {
+        assert_eq!(Store::ALL.len(), Store::all_names().len());
+        assert_eq!(Some(&"gogGalaxy"), Store::all_names().get(4));
+        for store in Store::ALL {
+            let name = serde_yaml::to_string(store).unwrap();
+            assert_eq!(Ok(*store), name.trim_start_matches("---").trim().parse::<Store>());
+        }
+    }
 
     #[test]
     fn can_parse_game_with_no_fields() {
@@ -781,6 +1157,7 @@ mod tests {
                 id: Default::default(),
                 cloud: Default::default(),
                 notes: Default::default(),
+                titles: Default::default(),
                 sources: Default::default(),
             },
             manifest.0["game"],
@@ -870,6 +1247,7 @@ mod tests {
                     uplay: true
                 },
                 notes: Default::default(),
+                titles: Default::default(),
                 sources: Default::default(),
             },
             manifest.0["game"],
@@ -1039,6 +1417,119 @@ mod tests {
         assert_eq!(GogMetadata { id: None }, manifest.0["game"].gog);
     }
 
+    #[test]
+    fn can_merge_custom_games_from_files_in_order() {
+        let config = Config {
+            custom_games: vec![
+                CustomGame {
+                    name: s("game1"),
+                    files: vec![s("/inline")],
+                    ..Default::default()
+                },
+                CustomGame {
+                    name: s("game3"),
+                    files: vec![s("/inline")],
+                    ..Default::default()
+                },
+            ],
+            custom_game_files: vec![StrictPath::new(format!("{}/tests/custom-games-1.yaml", repo()))],
+            games: Default::default(),
+            cli_custom_game_files: vec![StrictPath::new(format!("{}/tests/custom-games-2.yaml", repo()))],
+            ..Default::default()
+        };
+
+        let manifest = Manifest::default().with_extensions(&config);
+
+        let files = |name: &str| manifest.0[name].files.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(vec![s("/from-file-2")], files("game1"));
+        assert_eq!(vec![s("/from-file-1")], files("game2"));
+        assert_eq!(vec![s("/inline")], files("game3"));
+    }
+
+    #[test]
+    fn can_get_localized_title() {
+        let game = Game {
+            titles: btree_map! {
+                s("de-DE"): s("Spiel"),
+                s("pt-BR"): s("Jogo"),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(Some("Spiel"), game.localized_title("de-DE"));
+        assert_eq!(Some("Spiel"), game.localized_title("de-de"));
+        assert_eq!(Some("Spiel"), game.localized_title("de"));
+        assert_eq!(Some("Spiel"), game.localized_title("de_AT"));
+        assert_eq!(Some("Jogo"), game.localized_title("pt-PT"));
+        assert_eq!(None, game.localized_title("fr-FR"));
+        assert_eq!(None, Game::default().localized_title("de-DE"));
+    }
+
+    #[test]
+    fn can_count_secondary_contributions() {
+        let mut manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              steam:
+                id: 1
+            "#,
+        )
+        .unwrap();
+        for game in manifest.0.values_mut() {
+            game.sources.insert(Source::Primary);
+        }
+
+        let secondary = |id: &str, content: &str| Secondary {
+            id: s(id),
+            path: StrictPath::new(format!("/{id}/manifest.yaml")),
+            data: Manifest::load_from_string(content).unwrap(),
+        };
+        manifest.incorporate_secondary_manifest(secondary(
+            "a",
+            r#"
+            game1:
+              steam:
+                id: 2
+            game2: {}
+            game3: {}
+            "#,
+        ));
+        manifest.incorporate_secondary_manifest(secondary("b", "game1: {}"));
+
+        assert_eq!(Some(1), manifest.0["game1"].steam.id);
+        assert_eq!(
+            BTreeMap::from([(s("a"), 2), (s("b"), 0)]),
+            manifest.secondary_contributions()
+        );
+    }
+
+    #[test]
+    fn can_verify_checksum() {
+        let hash = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+        assert_eq!(Ok(()), Manifest::verify_checksum(b"foo", hash));
+        assert_eq!(
+            Ok(()),
+            Manifest::verify_checksum(b"foo", &format!(" {}\n", hash.to_uppercase()))
+        );
+        assert_eq!(Err(hash.to_string()), Manifest::verify_checksum(b"foo", "bad"));
+    }
+
+    #[test]
+    fn can_compute_retry_delay() {
+        assert_eq!(std::time::Duration::from_secs(1), Manifest::retry_delay(1));
+        assert_eq!(std::time::Duration::from_secs(2), Manifest::retry_delay(2));
+        assert_eq!(std::time::Duration::from_secs(4), Manifest::retry_delay(3));
+        assert_eq!(std::time::Duration::from_secs(64), Manifest::retry_delay(100));
+    }
+
+    #[test]
+    fn keeps_cause_when_download_fails() {
+        let res = Manifest::send_with_retries("http://127.0.0.1:1", 0, || {
+            reqwest::blocking::Client::new().get("http://127.0.0.1:1")
+        });
+        assert!(res.is_err_and(|why| !why.is_empty()));
+    }
+
     #[test]
     fn can_get_aliases() {
         let manifest = Manifest::load_from_string(
@@ -1060,4 +1551,81 @@ mod tests {
             manifest.aliases(),
         );
     }
+
+    #[test]
+    fn can_diff_manifests() {
+        let old = Manifest::load_from_string(
+            r#"
+            kept: {}
+            removed: {}
+            changed:
+              files:
+                <base>/same.sav: {}
+                <base>/old.sav: {}
+                <base>/tagged.sav:
+                  tags: [save]
+              registry:
+                HKEY_CURRENT_USER/Software/Old: {}
+            "#,
+        )
+        .unwrap();
+        let new = Manifest::load_from_string(
+            r#"
+            kept: {}
+            added: {}
+            changed:
+              files:
+                <base>/same.sav: {}
+                <base>/new.sav: {}
+                <base>/tagged.sav:
+                  tags: [config]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ManifestDiff {
+                added: btree_set! { s("added") },
+                removed: btree_set! { s("removed") },
+                changed: btree_map! {
+                    s("changed"): GameDiff {
+                        files: btree_map! {
+                            s("<base>/new.sav"): ScanChange::New,
+                            s("<base>/old.sav"): ScanChange::Removed,
+                            s("<base>/tagged.sav"): ScanChange::Different,
+                        },
+                        registry: btree_map! {
+                            s("HKEY_CURRENT_USER/Software/Old"): ScanChange::Removed,
+                        },
+                    },
+                },
+            },
+            old.diff(&new),
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn can_pair_games_with_normalized_names() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            "Foo Bar: Game of the Year Edition":
+              files:
+                <base>/foo.sav: {}
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            r#"
+---
+"Foo Bar: Game of the Year Edition":
+  files:
+    "<base>/foo.sav": {}
+  normalized: foo bar
+"#
+            .trim_start(),
+            serde_yaml::to_string(&manifest.with_normalized_names()).unwrap(),
+        );
+    }
 }
@@ -0,0 +1,94 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    prelude::{Error, StrictPath},
+    resource::{
+        cache::Cache,
+        config::{Config, Root},
+        ResourceFile,
+    },
+};
+
+/// A portable copy of Ludusavi's own settings,
+/// created by `settings export` and restored by `settings import`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    pub config: Config,
+    /// Roots that have already been detected, so that they aren't suggested again.
+    pub known_roots: BTreeSet<Root>,
+    pub recent_backup_games: BTreeSet<String>,
+    pub recent_restore_games: BTreeSet<String>,
+    /// Rclone's stored settings for the cloud remote.
+    /// These may contain credentials, so they are only included on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rclone_remote: Option<BTreeMap<String, String>>,
+}
+
+impl Settings {
+    pub fn new(config: &Config, cache: &Cache) -> Self {
+        Self {
+            config: config.clone(),
+            known_roots: cache.roots.clone(),
+            recent_backup_games: cache.backup.recent_games.clone(),
+            recent_restore_games: cache.restore.recent_games.clone(),
+            rclone_remote: None,
+        }
+    }
+
+    pub fn save_to(&self, path: &StrictPath) -> Result<(), Error> {
+        let content = serde_yaml::to_string(self).unwrap();
+
+        path.create_parent_dir()
+            .and_then(|_| path.write_with_content(&content))
+            .map_err(|_| Error::CannotExportSettings { path: path.clone() })
+    }
+
+    pub fn load_from(path: &StrictPath) -> Result<Self, Error> {
+        let invalid = || Error::SettingsInvalid { path: path.clone() };
+
+        let content = path.try_read().map_err(|_| invalid())?;
+        let mut settings: Self = serde_yaml::from_str(&content).map_err(|_| invalid())?;
+        settings.config = settings.config.migrate();
+
+        Ok(settings)
+    }
+
+    /// Copy the cached state into `cache`, and give back the config to save.
+    pub fn apply(self, cache: &mut Cache) -> Config {
+        cache.roots = self.known_roots;
+        cache.backup.recent_games = self.recent_backup_games;
+        cache.restore.recent_games = self.recent_restore_games;
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::resource::manifest::Store;
+
+    #[test]
+    fn can_round_trip_settings() {
+        let mut config = Config::default();
+        config.backup.path = StrictPath::new("/backups".to_string());
+
+        let mut cache = Cache::default();
+        cache.roots.insert(Root::new("/games", Store::Other));
+        cache.backup.recent_games.insert("game1".to_string());
+
+        let settings = Settings::new(&config, &cache);
+        let serialized = serde_yaml::to_string(&settings).unwrap();
+        assert!(!serialized.contains("rcloneRemote"));
+
+        let deserialized: Settings = serde_yaml::from_str(&serialized).unwrap();
+        assert_eq!(settings, deserialized);
+
+        let mut target = Cache::default();
+        assert_eq!(config, deserialized.apply(&mut target));
+        assert_eq!(cache.roots, target.roots);
+        assert_eq!(cache.backup.recent_games, target.backup.recent_games);
+    }
+}
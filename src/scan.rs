@@ -1,27 +1,42 @@
 mod backup;
+pub mod bundle;
 mod change;
 mod duplicate;
 pub mod game_filter;
+pub mod git;
+mod identical;
 pub mod launchers;
 pub mod layout;
+pub mod manifest_snapshot;
+mod pipeline;
 mod preview;
 pub mod registry_compat;
+pub mod rename;
+pub mod restore_map;
+pub mod resume;
 mod saves;
 mod steam;
 mod title;
+pub mod watch;
 
 #[cfg(target_os = "windows")]
 pub mod registry;
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-pub use self::{backup::*, change::*, duplicate::*, launchers::*, preview::*, saves::*, steam::*, title::*};
+pub use self::{
+    backup::*, change::*, duplicate::*, identical::*, launchers::*, pipeline::*, preview::*, saves::*, steam::*,
+    title::*,
+};
 
 use crate::{
     path::{CommonPath, StrictPath},
     prelude::{filter_map_walkdir, Error, SKIP},
     resource::{
-        config::{BackupFilter, RedirectConfig, RedirectKind, Root, SortKey, ToggledPaths, ToggledRegistry},
+        config::{FileSize, RedirectConfig, RedirectKind, Root, SortKey},
         manifest::{Game, GameFileEntry, IdMetadata, Os, Store},
     },
     scan::layout::LatestBackup,
@@ -59,6 +74,98 @@ pub fn game_file_target(original: &StrictPath, redirects: &[RedirectConfig], res
     (original != &redirected).then_some(redirected)
 }
 
+/// Find the Proton prefix (`steamapps/compatdata/<id>/pfx`) for any of these Steam IDs
+/// within the Steam roots.
+pub fn find_steam_prefix(roots: &[Root], steam_ids: &[u32]) -> Option<StrictPath> {
+    roots
+        .iter()
+        .filter(|root| root.store() == Store::Steam)
+        .find_map(|root| {
+            steam_ids.iter().find_map(|id| {
+                let prefix = root.path().joined(&format!("steamapps/compatdata/{id}/pfx"));
+                prefix.is_dir().then_some(prefix)
+            })
+        })
+}
+
+/// Build restore redirects that move Windows paths into a Wine prefix,
+/// using the same layout that we check when backing up from a prefix.
+pub fn wine_prefix_redirects(prefix: &StrictPath, paths: impl IntoIterator<Item = StrictPath>) -> Vec<RedirectConfig> {
+    let with_pfx = prefix.joined("pfx");
+    let prefix = if with_pfx.is_dir() { with_pfx } else { prefix.clone() };
+    let user = wine_prefix_user(&prefix);
+
+    let mut specific = BTreeMap::new();
+    let mut drives = BTreeMap::new();
+
+    for path in paths {
+        if !path.is_absolute() {
+            continue;
+        }
+        let (drive, rest) = path.split_drive();
+        let Some(letter) = drive.strip_suffix(':').filter(|x| x.len() == 1) else {
+            continue;
+        };
+        let drive_folder = prefix.joined(&format!("drive_{}", letter.to_lowercase())).raw();
+
+        let parts: Vec<_> = rest.split('/').collect();
+        match parts.as_slice() {
+            [users, name, _, ..] if users.eq_ignore_ascii_case("users") => {
+                let target_user = if name.eq_ignore_ascii_case("public") {
+                    "Public"
+                } else {
+                    &user
+                };
+                specific.insert(
+                    format!("{drive}/{users}/{name}"),
+                    format!("{drive_folder}/users/{target_user}"),
+                );
+            }
+            [windows, _, ..] if windows.eq_ignore_ascii_case("windows") => {
+                specific.insert(format!("{drive}/{windows}"), format!("{drive_folder}/windows"));
+            }
+            _ => {}
+        }
+        drives.insert(drive, drive_folder);
+    }
+
+    // The more specific redirects must come first,
+    // since each path is only moved out of its drive once.
+    specific
+        .into_iter()
+        .chain(drives)
+        .map(|(source, target)| RedirectConfig {
+            kind: RedirectKind::Restore,
+            source: StrictPath::new(source),
+            target: StrictPath::new(target),
+        })
+        .collect()
+}
+
+/// Prefer Proton's `steamuser`, then any other existing user,
+/// and otherwise fall back to the default user that Wine would create.
+fn wine_prefix_user(prefix: &StrictPath) -> String {
+    let mut users: Vec<_> = prefix
+        .joined("drive_c/users")
+        .read_dir()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.eq_ignore_ascii_case("public"))
+        .collect();
+    users.sort();
+
+    if users.iter().any(|name| name == "steamuser") {
+        return "steamuser".to_string();
+    }
+    users
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| crate::prelude::OS_USERNAME.clone())
+}
+
 fn check_windows_path(path: &str) -> &str {
     match Os::HOST {
         Os::Windows => path,
@@ -73,6 +180,52 @@ fn check_nonwindows_path(path: &str) -> &str {
     }
 }
 
+/// Replace the placeholders that only apply to another operating system
+/// with where a copy of those saves would be on this system,
+/// so that they can be found too.
+/// On Windows, that means the usual Linux folders in the home folder.
+/// Elsewhere, it means the Windows user folders inside of the game's Wine/Proton prefixes.
+fn other_os_path_variants(path: &str, wine_prefixes: &[StrictPath]) -> Vec<(String, Os)> {
+    use crate::resource::manifest::placeholder::*;
+
+    let (os, replacement_sets) = match Os::HOST {
+        Os::Windows => (
+            Os::Linux,
+            vec![vec![
+                (XDG_DATA, format!("{HOME}/.local/share")),
+                (XDG_CONFIG, format!("{HOME}/.config")),
+            ]],
+        ),
+        _ => (
+            Os::Windows,
+            wine_prefixes
+                .iter()
+                .filter_map(|prefix| {
+                    let user = wine_prefix_user(prefix);
+                    let users = format!("{}/drive_c/users/{}", prefix.interpret().ok()?, user);
+                    Some(vec![
+                        (WIN_APP_DATA, format!("{users}/AppData/Roaming")),
+                        (WIN_LOCAL_APP_DATA, format!("{users}/AppData/Local")),
+                        (WIN_DOCUMENTS, format!("{users}/Documents")),
+                    ])
+                })
+                .collect(),
+        ),
+    };
+
+    replacement_sets
+        .into_iter()
+        .filter(|replacements| replacements.iter().any(|(placeholder, _)| path.contains(placeholder)))
+        .map(|replacements| {
+            let mut path = path.to_string();
+            for (placeholder, replacement) in replacements {
+                path = path.replace(placeholder, &replacement);
+            }
+            (path, os)
+        })
+        .collect()
+}
+
 pub fn steam_ids(game: &Game, shortcut: Option<&SteamShortcut>) -> Vec<u32> {
     let mut ids = vec![];
     if let Some(steam_id) = game.steam.id {
@@ -140,6 +293,7 @@ pub fn parse_paths(
                         .unwrap_or_else(|| SKIP.to_string()),
                     Store::Ea
                     | Store::Epic
+                    | Store::Flatpak
                     | Store::Gog
                     | Store::GogGalaxy
                     | Store::Microsoft
@@ -419,6 +573,28 @@ pub fn parse_paths(
                 ));
             }
         }
+
+        if root.store() == Store::Flatpak {
+            // Without a known app ID, we check every app's sandbox.
+            let app = format!("{}/{}", &root_interpreted, ids.flatpak.as_deref().unwrap_or("*"));
+            paths.insert((
+                path.replace(HOME, home)
+                    .replace(STORE_USER_ID, "*")
+                    .replace(OS_USER_NAME, "*")
+                    .replace(XDG_DATA, &format!("{app}/data"))
+                    .replace(XDG_CONFIG, &format!("{app}/config")),
+                platform.is_case_sensitive(),
+            ));
+            // Some apps (e.g., Steam) also keep their home folder inside the sandbox.
+            paths.insert((
+                path.replace(STORE_USER_ID, "*")
+                    .replace(OS_USER_NAME, "*")
+                    .replace(XDG_DATA, &format!("{app}/.local/share"))
+                    .replace(XDG_CONFIG, &format!("{app}/.config"))
+                    .replace(HOME, &app),
+                platform.is_case_sensitive(),
+            ));
+        }
     }
 
     paths
@@ -427,28 +603,161 @@ pub fn parse_paths(
         .collect()
 }
 
+/// Expand one of a game's file paths from the manifest for a given root,
+/// using any launcher information about where the game is installed.
+#[allow(clippy::too_many_arguments)]
+fn game_file_candidates(
+    game: &Game,
+    name: &str,
+    raw_path: &str,
+    path_data: &GameFileEntry,
+    root: &Root,
+    launchers: &Launchers,
+    steam_ids: &[u32],
+    manifest_dir: &StrictPath,
+    steam_shortcuts: &SteamShortcuts,
+) -> HashSet<(StrictPath, bool)> {
+    let mut candidates = HashSet::new();
+    let mut launcher_entries = launchers.get_game(root, name).peekable();
+
+    if launcher_entries.peek().is_none() {
+        let platform = Os::HOST;
+        let install_dir = None;
+        let full_install_dir = None;
+
+        candidates.extend(parse_paths(
+            raw_path,
+            path_data,
+            root,
+            &install_dir,
+            &full_install_dir,
+            steam_ids,
+            &game.id,
+            manifest_dir,
+            steam_shortcuts.get(name),
+            platform,
+        ));
+    } else {
+        for launcher_entry in launcher_entries {
+            log::trace!("[{name}] parsing candidates with launcher info: {:?}", &launcher_entry);
+            let platform = launcher_entry.platform.unwrap_or(Os::HOST);
+            let install_dir = launcher_entry.install_dir.as_ref().and_then(|x| x.leaf());
+            let full_install_dir = launcher_entry.install_dir.as_ref();
+
+            candidates.extend(parse_paths(
+                raw_path,
+                path_data,
+                root,
+                &install_dir,
+                &full_install_dir,
+                steam_ids,
+                &game.id,
+                manifest_dir,
+                steam_shortcuts.get(name),
+                platform,
+            ));
+        }
+    }
+
+    candidates
+}
+
+/// Find a game's file paths from the manifest that contain `needle`,
+/// after expanding them for the current system (but without checking if they exist).
+/// Capitalization and the style of path separators are ignored.
+pub fn find_game_paths_containing(
+    game: &Game,
+    name: &str,
+    roots: &[Root],
+    manifest_dir: &StrictPath,
+    launchers: &Launchers,
+    steam_shortcuts: &SteamShortcuts,
+    needle: &str,
+) -> BTreeSet<String> {
+    let needle = needle.replace('\\', "/").to_lowercase();
+    let steam_ids = steam_ids(game, steam_shortcuts.get(name));
+
+    let mut roots_to_check: Vec<Root> = vec![Root::new(SKIP, Store::Other)];
+    roots_to_check.extend(roots.iter().cloned());
+
+    let mut found = BTreeSet::new();
+    for root in &roots_to_check {
+        if root.path().raw().trim().is_empty() {
+            continue;
+        }
+
+        for (raw_path, path_data) in &game.files {
+            if raw_path.trim().is_empty() {
+                continue;
+            }
+
+            for (candidate, _) in game_file_candidates(
+                game,
+                name,
+                raw_path,
+                path_data,
+                root,
+                launchers,
+                &steam_ids,
+                manifest_dir,
+                steam_shortcuts,
+            ) {
+                if candidate.raw().contains('<') {
+                    continue;
+                }
+                let rendered = candidate.render();
+                if rendered.replace('\\', "/").to_lowercase().contains(&needle) {
+                    found.insert(rendered);
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Files over the `backup.maxFileSize` limit are skipped before hashing,
+/// since hashing them could take a long time.
+fn is_too_large(size: u64, max_file_size: Option<FileSize>) -> bool {
+    max_file_size.is_some_and(|FileSize(limit)| size > limit)
+}
+
 pub fn scan_game_for_backup(
     game: &Game,
     name: &str,
     roots: &[Root],
     manifest_dir: &StrictPath,
     launchers: &Launchers,
-    filter: &BackupFilter,
     wine_prefix: &Option<StrictPath>,
-    ignored_paths: &ToggledPaths,
-    #[allow(unused_variables)] ignored_registry: &ToggledRegistry,
     previous: Option<LatestBackup>,
     redirects: &[RedirectConfig],
     steam_shortcuts: &SteamShortcuts,
+    options: &BackupOptions,
+    cancel: Option<&AtomicBool>,
 ) -> ScanInfo {
     log::trace!("[{name}] beginning scan for backup");
 
+    let filter = &options.filter;
+    let ignored_paths = &options.toggled_paths;
+    #[allow(unused_variables)]
+    let ignored_registry = &options.toggled_registry;
+    let hash_algorithm = options.hash_algorithm;
+    let follow_symlinks = options.follow_symlinks;
+    let preserve_empty_dirs = options.preserve_empty_dirs;
+    let only_registry = options.only_registry;
+    let include_other_os_paths = options.include_other_os_paths;
+    let max_file_size = options.max_file_size;
+
+    let cancelled = || cancel.is_some_and(|x| x.load(Ordering::Relaxed));
+
     let mut found_files = HashSet::new();
+    let mut found_empty_dirs = BTreeSet::new();
     #[allow(unused_mut)]
     let mut found_registry_keys = HashSet::new();
     let has_backups = previous.is_some();
 
     let mut paths_to_check = HashSet::<(StrictPath, Option<bool>)>::new();
+    let mut other_os_paths = HashMap::<StrictPath, Os>::new();
 
     // Add a dummy root for checking paths without `<root>`.
     let mut roots_to_check: Vec<Root> = vec![Root::new(SKIP, Store::Other)];
@@ -478,6 +787,23 @@ pub fn scan_game_for_backup(
         }
     }
 
+    let mut wine_prefixes = vec![];
+    if include_other_os_paths {
+        wine_prefixes.extend(
+            roots_to_check
+                .iter()
+                .filter(|root| root.store() == Store::OtherWine)
+                .map(|root| root.path().clone()),
+        );
+        wine_prefixes.extend(find_steam_prefix(roots, &steam_ids));
+    }
+
+    if only_registry {
+        log::trace!("[{name}] skipping file paths because only the registry was requested");
+        roots_to_check.clear();
+        paths_to_check.clear();
+    }
+
     for root in roots_to_check {
         log::trace!("[{name}] adding candidates from root: {:?}", &root,);
         if root.path().raw().trim().is_empty() {
@@ -494,54 +820,54 @@ pub fn scan_game_for_backup(
                 continue;
             }
 
-            let mut candidates = HashSet::new();
-            let mut launcher_entries = launchers.get_game(&root, name).peekable();
-
-            if launcher_entries.peek().is_none() {
-                let platform = Os::HOST;
-                let install_dir = None;
-                let full_install_dir = None;
-
-                candidates.extend(parse_paths(
-                    raw_path,
-                    path_data,
-                    &root,
-                    &install_dir,
-                    &full_install_dir,
-                    &steam_ids,
-                    &game.id,
-                    manifest_dir,
-                    steam_shortcuts.get(name),
-                    platform,
-                ));
+            let candidates = game_file_candidates(
+                game,
+                name,
+                raw_path,
+                path_data,
+                &root,
+                launchers,
+                &steam_ids,
+                manifest_dir,
+                steam_shortcuts,
+            );
+
+            let other_os_candidates: Vec<_> = if include_other_os_paths {
+                other_os_path_variants(raw_path, &wine_prefixes)
+                    .into_iter()
+                    .flat_map(|(other_path, os)| {
+                        game_file_candidates(
+                            game,
+                            name,
+                            &other_path,
+                            path_data,
+                            &root,
+                            launchers,
+                            &steam_ids,
+                            manifest_dir,
+                            steam_shortcuts,
+                        )
+                        .into_iter()
+                        .map(move |x| (x, Some(os)))
+                    })
+                    .filter(|(x, _)| !candidates.contains(x))
+                    .collect()
             } else {
-                for launcher_entry in launcher_entries {
-                    log::trace!("[{name}] parsing candidates with launcher info: {:?}", &launcher_entry);
-                    let platform = launcher_entry.platform.unwrap_or(Os::HOST);
-                    let install_dir = launcher_entry.install_dir.as_ref().and_then(|x| x.leaf());
-                    let full_install_dir = launcher_entry.install_dir.as_ref();
-
-                    candidates.extend(parse_paths(
-                        raw_path,
-                        path_data,
-                        &root,
-                        &install_dir,
-                        &full_install_dir,
-                        &steam_ids,
-                        &game.id,
-                        manifest_dir,
-                        steam_shortcuts.get(name),
-                        platform,
-                    ));
-                }
-            }
+                vec![]
+            };
 
-            for (candidate, case_sensitive) in candidates {
+            for ((candidate, case_sensitive), os) in
+                candidates.into_iter().map(|x| (x, None)).chain(other_os_candidates)
+            {
                 log::trace!("[{name}] parsed candidate: {candidate:?}");
                 if candidate.raw().contains('<') {
                     // This covers `SKIP` and any other unmatched placeholders.
                     continue;
                 }
+                if let Some(os) = os {
+                    log::trace!("[{name}] candidate is for {os:?}: {candidate:?}");
+                    other_os_paths.insert(candidate.clone(), os);
+                }
                 paths_to_check.insert((candidate, Some(case_sensitive)));
             }
         }
@@ -591,12 +917,23 @@ pub fn scan_game_for_backup(
         })
         .unwrap_or_default();
 
+    let mut other_os_files = BTreeMap::new();
+    let mut save_roots = BTreeSet::new();
+
     for (path, case_sensitive) in paths_to_check {
+        if cancelled() {
+            log::warn!("[{name}] scan was cancelled");
+            break;
+        }
         log::trace!("[{name}] checking: {path:?}");
+        let other_os = other_os_paths.get(&path).copied();
         if filter.is_path_ignored(&path) {
             log::debug!("[{name}] excluded: {path:?}");
             continue;
         }
+        if let Some(root) = save_root(&path) {
+            save_roots.insert(root);
+        }
         let paths = match case_sensitive {
             None => path.glob(),
             Some(cs) => path.glob_case_sensitive(cs),
@@ -612,33 +949,59 @@ pub fn scan_game_for_backup(
                 }
                 let ignored = ignored_paths.is_ignored(name, &p);
                 log::debug!("[{name}] found: {p:?}");
-                let hash = p.sha1();
+                if let Some(os) = other_os {
+                    other_os_files.insert(p.clone(), os);
+                }
+                let size = p.size();
+                let too_large = is_too_large(size, max_file_size);
+                let hash = if too_large {
+                    log::debug!("[{name}] not hashing because of max file size: {p:?}");
+                    String::new()
+                } else {
+                    p.hash(hash_algorithm)
+                };
                 let redirected = game_file_target(&p, redirects, false);
                 found_files.insert(ScannedFile {
-                    change: ScanChange::evaluate_backup(&hash, previous_files.get(redirected.as_ref().unwrap_or(&p))),
-                    size: p.size(),
+                    change: if too_large {
+                        ScanChange::Unknown
+                    } else {
+                        ScanChange::evaluate_backup(&hash, previous_files.get(redirected.as_ref().unwrap_or(&p)))
+                    },
+                    size,
                     hash,
                     redirected,
                     path: p,
                     original_path: None,
-                    ignored,
+                    ignored: ignored || too_large,
                     container: None,
                 });
             } else if p.is_dir() {
                 log::trace!("[{name}] looking for files in: {p:?}");
+                // When following links, WalkDir reports any loops as errors instead of descending forever.
                 for child in walkdir::WalkDir::new(p.as_std_path_buf().unwrap())
                     .max_depth(100)
-                    .follow_links(true)
+                    .follow_links(follow_symlinks)
                     .into_iter()
                     .filter_map(filter_map_walkdir)
                 {
+                    if cancelled() {
+                        break;
+                    }
+
                     #[cfg(not(target_os = "windows"))]
                     if child.path().to_string_lossy().contains('\\') {
                         // TODO: Support names containing a slash.
                         continue;
                     }
 
-                    if child.file_type().is_file() {
+                    let is_file = if child.path_is_symlink() && !follow_symlinks {
+                        // Linked files are still included, but not linked directories.
+                        child.path().is_file()
+                    } else {
+                        child.file_type().is_file()
+                    };
+
+                    if is_file {
                         let Ok(child) = StrictPath::from(&child).interpreted().map(|x| x.rendered()) else {
                             continue;
                         };
@@ -649,21 +1012,49 @@ pub fn scan_game_for_backup(
                         }
                         let ignored = ignored_paths.is_ignored(name, &child);
                         log::debug!("[{name}] found: {child:?}");
-                        let hash = child.sha1();
+                        if let Some(os) = other_os {
+                            other_os_files.insert(child.clone(), os);
+                        }
+                        let size = child.size();
+                        let too_large = is_too_large(size, max_file_size);
+                        let hash = if too_large {
+                            log::debug!("[{name}] not hashing because of max file size: {child:?}");
+                            String::new()
+                        } else {
+                            child.hash(hash_algorithm)
+                        };
                         let redirected = game_file_target(&child, redirects, false);
                         found_files.insert(ScannedFile {
-                            change: ScanChange::evaluate_backup(
-                                &hash,
-                                previous_files.get(redirected.as_ref().unwrap_or(&child)),
-                            ),
-                            size: child.size(),
+                            change: if too_large {
+                                ScanChange::Unknown
+                            } else {
+                                ScanChange::evaluate_backup(
+                                    &hash,
+                                    previous_files.get(redirected.as_ref().unwrap_or(&child)),
+                                )
+                            },
+                            size,
                             hash,
                             redirected,
                             path: child,
                             original_path: None,
-                            ignored,
+                            ignored: ignored || too_large,
                             container: None,
                         });
+                    } else if preserve_empty_dirs
+                        && child.file_type().is_dir()
+                        && std::fs::read_dir(child.path()).is_ok_and(|mut x| x.next().is_none())
+                    {
+                        let Ok(child) = StrictPath::from(&child).interpreted().map(|x| x.rendered()) else {
+                            continue;
+                        };
+
+                        if filter.is_path_ignored(&child) || ignored_paths.is_ignored(name, &child) {
+                            log::debug!("[{name}] excluded empty folder: {child:?}");
+                            continue;
+                        }
+                        log::debug!("[{name}] found empty folder: {child:?}");
+                        found_empty_dirs.insert(game_file_target(&child, redirects, false).unwrap_or(child));
                     }
                 }
             }
@@ -671,6 +1062,8 @@ pub fn scan_game_for_backup(
     }
 
     // Mark removed files.
+    // The files weren't scanned with `--only-registry`, so they aren't really gone.
+    let previous_files = if only_registry { HashMap::new() } else { previous_files };
     let current_files: Vec<_> = found_files
         .iter()
         .map(|x| x.redirected.as_ref().unwrap_or(&x.path).interpret())
@@ -729,6 +1122,11 @@ pub fn scan_game_for_backup(
                 ));
             }
 
+            let candidates = candidates
+                .into_iter()
+                .flat_map(|x| RegistryItem::new(x).expand_glob(&registry::LiveRegistry))
+                .map(|x| x.raw());
+
             for candidate in candidates {
                 log::trace!("[{name}] checking registry: {candidate}");
                 for mut scanned in
@@ -790,10 +1188,45 @@ pub fn scan_game_for_backup(
         game_name: name.to_string(),
         found_files,
         found_registry_keys,
+        found_empty_dirs,
         available_backups: vec![],
         backup: None,
         has_backups,
+        globally_ignored: 0,
+        system_files_excluded: 0,
+        preserved_registry_values: Default::default(),
+        other_os_files,
+        save_roots,
+        only_registry,
+    }
+}
+
+/// Find the folder of a save location before any of its wildcards,
+/// so that new saves in that location can be noticed.
+/// Returns `None` if the folder doesn't exist yet
+/// or if it's too broad to be useful, like the home folder.
+fn save_root(path: &StrictPath) -> Option<StrictPath> {
+    let globbable = path.globbable();
+    let prefix: Vec<_> = globbable
+        .split('/')
+        .take_while(|part| !part.contains(['*', '?', '[', ']', '{', '}']))
+        .collect();
+    if prefix.iter().filter(|part| !part.is_empty()).count() < 2 {
+        return None;
+    }
+
+    let mut root = StrictPath::new(prefix.join("/"));
+    if root.is_file() {
+        root = root.popped();
+    }
+    if !root.is_dir()
+        || CommonPath::Home
+            .get()
+            .is_some_and(|home| root.equivalent(&StrictPath::from(home)))
+    {
+        return None;
     }
+    Some(root)
 }
 
 fn scan_game_for_backup_add_prefix(
@@ -864,13 +1297,17 @@ fn compare_games_by_status(scan_info1: &ScanInfo, scan_info2: &ScanInfo) -> std:
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
-    use velcro::{btree_map, hash_set};
+    use velcro::{btree_map, btree_set, hash_set};
 
     use super::*;
     #[cfg(target_os = "windows")]
     use crate::resource::config::ToggledRegistryEntry;
     use crate::{
-        resource::{config::Config, manifest::Manifest, ResourceFile},
+        resource::{
+            config::{BackupFilter, Config, HashAlgorithm, ToggledPaths, ToggledRegistry},
+            manifest::Manifest,
+            ResourceFile,
+        },
         testing::{repo, s, EMPTY_HASH},
     };
 
@@ -895,6 +1332,21 @@ mod tests {
         .unwrap()
     }
 
+    fn options() -> BackupOptions {
+        BackupOptions {
+            filter: BackupFilter::default(),
+            toggled_paths: ToggledPaths::default(),
+            toggled_registry: ToggledRegistry::default(),
+            hash_algorithm: HashAlgorithm::default(),
+            follow_symlinks: true,
+            preserve_empty_dirs: false,
+            only_registry: false,
+            include_other_os_paths: false,
+            max_file_size: None,
+            ..BackupOptions::new(&Config::default())
+        }
+    }
+
     fn manifest() -> Manifest {
         Manifest::load_from_string(
             r#"
@@ -1005,6 +1457,146 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_find_steam_prefix() {
+        let roots = vec![
+            Root::new(format!("{}/tests/root1", repo()), Store::Other),
+            Root::new(format!("{}/tests/steam-deck", repo()), Store::Steam),
+        ];
+
+        assert_eq!(
+            Some(StrictPath::new(format!(
+                "{}/tests/steam-deck/steamapps/compatdata/101/pfx",
+                repo()
+            ))),
+            find_steam_prefix(&roots, &[100, 101]),
+        );
+        assert_eq!(None, find_steam_prefix(&roots, &[100]));
+        assert_eq!(None, find_steam_prefix(&roots[..1], &[101]));
+    }
+
+    #[test]
+    fn can_compute_game_file_target_with_wine_prefix_redirects() {
+        let prefix = StrictPath::new(format!("{}/tests/wine-prefix", repo()));
+        let originals = [
+            StrictPath::new(s("C:/Users/Alice/AppData/Roaming/game/save.dat")),
+            StrictPath::new(s("C:/Users/Public/game/shared.dat")),
+            StrictPath::new(s("C:/Windows/game.ini")),
+            StrictPath::new(s("D:/Games/game/config.ini")),
+            StrictPath::new(s("/home/alice/game.txt")),
+        ];
+        let redirects = wine_prefix_redirects(&prefix, originals.clone());
+
+        assert_eq!(
+            vec![
+                Some(StrictPath::new(format!(
+                    "{}/tests/wine-prefix/drive_c/users/anyone/AppData/Roaming/game/save.dat",
+                    repo()
+                ))),
+                Some(StrictPath::new(format!(
+                    "{}/tests/wine-prefix/drive_c/users/Public/game/shared.dat",
+                    repo()
+                ))),
+                Some(StrictPath::new(format!(
+                    "{}/tests/wine-prefix/drive_c/windows/game.ini",
+                    repo()
+                ))),
+                Some(StrictPath::new(format!(
+                    "{}/tests/wine-prefix/drive_d/Games/game/config.ini",
+                    repo()
+                ))),
+                None,
+            ],
+            originals
+                .iter()
+                .map(|x| game_file_target(x, &redirects, true))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn can_find_game_paths_containing_substring() {
+        let find = |needle| {
+            find_game_paths_containing(
+                &manifest().0["game1"],
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+                &Default::default(),
+                needle,
+            )
+        };
+
+        assert_eq!(
+            btree_set! {
+                format!("{}/tests/root1/game1/file1.txt", repo()),
+                format!("{}/tests/root2/game1/file1.txt", repo()),
+            },
+            find("GAME1\\FILE1"),
+        );
+        assert_eq!(
+            btree_set! {
+                format!("{}/tests/root1/game1/subdir", repo()),
+            },
+            find("root1/game1/sub"),
+        );
+        assert_eq!(BTreeSet::<String>::new(), find("nonexistent"));
+    }
+
+    #[test]
+    fn can_cancel_scan_game_for_backup() {
+        let scan = scan_game_for_backup(
+            &manifest().0["game1"],
+            "game1",
+            &config().roots,
+            &StrictPath::new(repo()),
+            &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+            &None,
+            None,
+            &[],
+            &Default::default(),
+            &options(),
+            Some(&AtomicBool::new(true)),
+        );
+        assert!(scan.found_files.is_empty());
+    }
+
+    #[test]
+    fn can_skip_hashing_files_over_max_file_size() {
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/root1/game1/subdir/file2.txt", repo()), 2, "").ignored(),
+                    ScannedFile::new(format!("{}/tests/root2/game1/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root1/game1/subdir", repo())),
+                    StrictPath::new(format!("{}/tests/root2/game1", repo())),
+                },
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+                &None,
+                None,
+                &[],
+                &Default::default(),
+                &BackupOptions {
+                    max_file_size: Some(FileSize(1)),
+                    ..options()
+                },
+                None,
+            ),
+        );
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_file_matches() {
         assert_eq!(
@@ -1015,6 +1607,10 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/root2/game1/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root1/game1/subdir", repo())),
+                    StrictPath::new(format!("{}/tests/root2/game1", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1023,13 +1619,12 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
 
@@ -1040,6 +1635,35 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/root2/game2/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root2/game2", repo())),
+                },
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game 2"],
+                "game 2",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game 2".to_string()]),
+                &None,
+                None,
+                &[],
+                &Default::default(),
+                &options(),
+                None,
+            ),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_only_registry() {
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game 2"),
+                found_files: hash_set! {},
+                found_registry_keys: hash_set! {},
+                only_registry: true,
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1048,13 +1672,15 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game 2".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &BackupOptions {
+                    only_registry: true,
+                    ..options()
+                },
+                None,
             ),
         );
     }
@@ -1069,6 +1695,9 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/root3/game5/data/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root3/game5", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1077,17 +1706,116 @@ mod tests {
                 roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
 
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn can_scan_game_for_backup_with_or_without_following_symlinks() {
+        let base = std::env::temp_dir().join("ludusavi-tests/follow-symlinks");
+        _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(base.join("game5/data")).unwrap();
+        std::fs::write(base.join("game5/data/own.txt"), "a").unwrap();
+        std::os::unix::fs::symlink(
+            format!("{}/tests/root2/game2", repo()),
+            base.join("game5/data/linked-dir"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(
+            format!("{}/tests/root3/game_2/file1.txt", repo()),
+            base.join("game5/data/linked-file.txt"),
+        )
+        .unwrap();
+
+        let root = base.to_string_lossy().to_string();
+        let roots = &[Root::new(root.clone(), Store::Other)];
+        let scan = |follow_symlinks| {
+            let mut files: Vec<_> = scan_game_for_backup(
+                &manifest().0["game5"],
+                "game5",
+                roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
+                &None,
+                None,
+                &[],
+                &Default::default(),
+                &BackupOptions {
+                    follow_symlinks,
+                    ..options()
+                },
+                None,
+            )
+            .found_files
+            .into_iter()
+            .map(|x| x.path.render())
+            .collect();
+            files.sort();
+            files
+        };
+
+        // Linked paths are resolved to their targets.
+        assert_eq!(
+            vec![
+                format!("{}/tests/root2/game2/file1.txt", repo()),
+                format!("{}/tests/root3/game_2/file1.txt", repo()),
+                format!("{root}/game5/data/own.txt"),
+            ],
+            scan(true),
+        );
+        assert_eq!(
+            vec![
+                format!("{}/tests/root3/game_2/file1.txt", repo()),
+                format!("{root}/game5/data/own.txt"),
+            ],
+            scan(false),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_or_without_preserving_empty_dirs() {
+        let base = std::env::temp_dir().join("ludusavi-tests/preserve-empty-dirs");
+        _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(base.join("game5/data/empty/nested")).unwrap();
+        std::fs::create_dir_all(base.join("game5/data/filled")).unwrap();
+        std::fs::write(base.join("game5/data/filled/file.txt"), "a").unwrap();
+
+        let root = base.to_string_lossy().replace('\\', "/");
+        let roots = &[Root::new(root.clone(), Store::Other)];
+        let scan = |preserve_empty_dirs| {
+            scan_game_for_backup(
+                &manifest().0["game5"],
+                "game5",
+                roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
+                &None,
+                None,
+                &[],
+                &Default::default(),
+                &BackupOptions {
+                    preserve_empty_dirs,
+                    ..options()
+                },
+                None,
+            )
+            .found_empty_dirs
+        };
+
+        assert_eq!(
+            btree_set! { StrictPath::new(format!("{root}/game5/data/empty/nested")) },
+            scan(true),
+        );
+        assert_eq!(BTreeSet::<StrictPath>::new(), scan(false));
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_redirect_to_symlink() {
         let roots = &[Root::new(format!("{}/tests/root3", repo()), Store::Other)];
@@ -1107,6 +1835,9 @@ mod tests {
                     },
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root3/game5", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1115,10 +1846,7 @@ mod tests {
                 roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game5".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[RedirectConfig {
                     kind: RedirectKind::Bidirectional,
@@ -1126,6 +1854,8 @@ mod tests {
                     target: StrictPath::new(format!("{}/tests/root3/game5/data-symlink", repo())),
                 }],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1140,6 +1870,9 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/root3/game_2/file1.txt", repo()), 1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root3/game_2", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1148,13 +1881,12 @@ mod tests {
                 roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game 2".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1173,6 +1905,12 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/home/Documents/winDocuments.txt", repo()), 0, EMPTY_HASH).change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/home", repo())),
+                    StrictPath::new(format!("{}/tests/home/AppData/Local", repo())),
+                    StrictPath::new(format!("{}/tests/home/AppData/Roaming", repo())),
+                    StrictPath::new(format!("{}/tests/home/Documents", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1181,13 +1919,12 @@ mod tests {
                 roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game4".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1205,6 +1942,125 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/home/.local/share/xdgData.txt", repo()), 0, EMPTY_HASH).change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/home", repo())),
+                    StrictPath::new(format!("{}/tests/home/.config", repo())),
+                    StrictPath::new(format!("{}/tests/home/.local/share", repo())),
+                },
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game4"],
+                "game4",
+                roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(roots, &manifest(), &["game4".to_string()]),
+                &None,
+                None,
+                &[],
+                &Default::default(),
+                &options(),
+                None,
+            ),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_other_os_paths() {
+        let roots = &[Root::new(format!("{}/tests/home", repo()), Store::OtherHome)];
+        let home = format!("{}/tests/home", repo());
+        let prefix = format!("{}/tests/other-os-prefix", repo());
+        let user = format!("{prefix}/drive_c/users/anyone");
+
+        // Linux folders are checked in the home folder on Windows,
+        // and Windows folders are checked in the Wine prefix elsewhere.
+        let (linux_os, windows_os) = match Os::HOST {
+            Os::Windows => (Some(Os::Linux), None),
+            _ => (None, Some(Os::Windows)),
+        };
+        let mut found = vec![
+            (format!("{home}/data.txt"), None),
+            (format!("{home}/.config/xdgConfig.txt"), linux_os),
+            (format!("{home}/.local/share/xdgData.txt"), linux_os),
+            (format!("{user}/AppData/Roaming/winAppData.txt"), windows_os),
+            (format!("{user}/AppData/Local/winLocalAppData.txt"), windows_os),
+            (format!("{user}/Documents/winDocuments.txt"), windows_os),
+        ];
+        let mut save_roots = btree_set! {
+            StrictPath::new(home.clone()),
+            StrictPath::new(format!("{home}/.config")),
+            StrictPath::new(format!("{home}/.local/share")),
+            StrictPath::new(prefix.clone()),
+        };
+        if Os::HOST == Os::Windows {
+            found.extend([
+                (format!("{home}/AppData/Roaming/winAppData.txt"), None),
+                (format!("{home}/AppData/Local/winLocalAppData.txt"), None),
+                (format!("{home}/Documents/winDocuments.txt"), None),
+            ]);
+            save_roots.extend([
+                StrictPath::new(format!("{home}/AppData/Local")),
+                StrictPath::new(format!("{home}/AppData/Roaming")),
+                StrictPath::new(format!("{home}/Documents")),
+            ]);
+        } else {
+            save_roots.extend([
+                StrictPath::new(format!("{user}/AppData/Local")),
+                StrictPath::new(format!("{user}/AppData/Roaming")),
+                StrictPath::new(format!("{user}/Documents")),
+            ]);
+        }
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game4"),
+                found_files: found
+                    .iter()
+                    .map(|(path, _)| ScannedFile::new(path, 0, EMPTY_HASH).change_new())
+                    .collect(),
+                found_registry_keys: hash_set! {},
+                other_os_files: found
+                    .iter()
+                    .filter_map(|(path, os)| os.map(|os| (StrictPath::new(path.clone()), os)))
+                    .collect(),
+                save_roots,
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game4"],
+                "game4",
+                roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(roots, &manifest(), &["game4".to_string()]),
+                &Some(StrictPath::new(prefix.clone())),
+                None,
+                &[],
+                &Default::default(),
+                &BackupOptions {
+                    include_other_os_paths: true,
+                    ..options()
+                },
+                None,
+            ),
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn can_scan_game_for_backup_with_file_matches_in_flatpak_sandbox() {
+        let roots = &[Root::new(format!("{}/tests/flatpak", repo()), Store::Flatpak)];
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game4"),
+                found_files: hash_set! {
+                    ScannedFile::new(format!("{}/tests/flatpak/com.example.Game4/config/xdgConfig.txt", repo()), 0, EMPTY_HASH).change_new(),
+                    ScannedFile::new(format!("{}/tests/flatpak/com.example.Game4/data/xdgData.txt", repo()), 0, EMPTY_HASH).change_new(),
+                    ScannedFile::new(format!("{}/tests/flatpak/com.valvesoftware.Steam/data.txt", repo()), 0, EMPTY_HASH).change_new(),
+                },
+                found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/flatpak", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1213,13 +2069,12 @@ mod tests {
                 roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(roots, &manifest(), &["game4".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1233,6 +2088,9 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/wine-prefix/drive_c/users/anyone/data.txt", repo()), 0, EMPTY_HASH).change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/wine-prefix", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1241,13 +2099,12 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game4".to_string()]),
-                &BackupFilter::default(),
                 &Some(StrictPath::new(format!("{}/tests/wine-prefix", repo()))),
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1261,6 +2118,9 @@ mod tests {
                     ScannedFile::new(format!("{}/tests/wine-prefix/user.reg", repo()), 37, "4a5b7e9de7d84ffb4bb3e9f38667f85741d5fbc0",).change_new(),
                 },
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/wine-prefix", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1269,13 +2129,12 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["fake-registry".to_string()]),
-                &BackupFilter::default(),
                 &Some(StrictPath::new(format!("{}/tests/wine-prefix", repo()))),
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1297,6 +2156,9 @@ mod tests {
                 game_name: s("game1"),
                 found_files: found,
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root2/game1", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1305,13 +2167,16 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
-                &filter,
                 &None,
-                &ignored,
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &BackupOptions {
+                    filter,
+                    toggled_paths: ignored,
+                    ..options()
+                },
+                None,
             ),
         );
     }
@@ -1335,6 +2200,10 @@ mod tests {
                 game_name: s("game1"),
                 found_files: found,
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root1/game1/subdir", repo())),
+                    StrictPath::new(format!("{}/tests/root2/game1", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1343,13 +2212,16 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
-                &filter,
                 &None,
-                &ignored,
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &BackupOptions {
+                    filter,
+                    toggled_paths: ignored,
+                    ..options()
+                },
+                None,
             ),
         );
     }
@@ -1373,6 +2245,10 @@ mod tests {
                 game_name: s("game1"),
                 found_files: found,
                 found_registry_keys: hash_set! {},
+                save_roots: btree_set! {
+                    StrictPath::new(format!("{}/tests/root1/game1/subdir", repo())),
+                    StrictPath::new(format!("{}/tests/root2/game1", repo())),
+                },
                 ..Default::default()
             },
             scan_game_for_backup(
@@ -1381,13 +2257,16 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
-                &filter,
                 &None,
-                &ignored,
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &BackupOptions {
+                    filter,
+                    toggled_paths: ignored,
+                    ..options()
+                },
+                None,
             ),
         );
     }
@@ -1416,13 +2295,12 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game3".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1455,13 +2333,12 @@ mod tests {
                 &config().roots,
                 &StrictPath::new(repo()),
                 &Launchers::scan_dirs(&config().roots, &manifest(), &["game3-outer".to_string()]),
-                &BackupFilter::default(),
                 &None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
                 None,
                 &[],
                 &Default::default(),
+                &options(),
+                None,
             ),
         );
     }
@@ -1554,13 +2431,16 @@ mod tests {
                     &config().roots,
                     &StrictPath::new(repo()),
                     &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
-                    &filter,
                     &None,
-                    &ToggledPaths::default(),
-                    &ignored,
                     None,
                     &[],
                     &Default::default(),
+                    &BackupOptions {
+                        filter,
+                        toggled_registry: ignored,
+                        ..options()
+                    },
+                    None,
                 ),
             );
         }
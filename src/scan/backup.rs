@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::{
     lang::TRANSLATOR,
+    resource::manifest::Store,
     scan::{registry_compat::RegistryItem, ScanChangeCount, ScanInfo, ScannedFile},
 };
 
@@ -71,6 +72,27 @@ pub struct OperationStatus {
     pub processed_bytes: u64,
     /// Total count of `new`, `same`, and `different` games.
     pub changed_games: ScanChangeCount,
+    /// How many days it has been since the manifest was last checked for updates.
+    /// This is only set when it exceeds the `--manifest-age-warning` threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_age_days: Option<i64>,
+    /// How many games were skipped for each store excluded by `--exclude-store`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub excluded_stores: BTreeMap<Store, usize>,
+    /// How many games were left out because they had nothing to back up.
+    /// This is only set when using `--skip-empty`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub empty_games: Option<usize>,
+    /// Whether save files were intentionally skipped
+    /// so that only registry entries were backed up.
+    /// This is only set when using `--only-registry`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub registry_only: bool,
+    /// Whether cloud synchronization was skipped after the backup
+    /// because no games had new or changed files.
+    /// This is only set when cloud synchronization is enabled.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cloud_sync_skipped: bool,
 }
 
 impl OperationStatus {
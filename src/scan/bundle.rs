@@ -0,0 +1,402 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{Read, Write},
+};
+
+use crate::{
+    path::StrictPath,
+    prelude::{AnyError, Error},
+    scan::{
+        layout::{BackupLayout, IndividualMapping},
+        ScanInfo, ScannedFile,
+    },
+};
+
+const MAPPING_FILE: &str = "mapping.yaml";
+const REGISTRY_EXPORT_FILE: &str = "registry.reg";
+
+/// A game's latest backup, as stored in a portable archive
+/// created by the `export` command.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BundledGame {
+    pub name: String,
+    /// Folder for this game within the archive.
+    pub folder: String,
+    /// Files relative to the folder, not including the mapping file.
+    pub files: Vec<String>,
+    pub bytes: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExportItem {
+    pub game: BundledGame,
+    source: StrictPath,
+    mapping: IndividualMapping,
+}
+
+pub fn plan_export(layout: &BackupLayout, games: &[String]) -> Vec<ExportItem> {
+    games
+        .iter()
+        .filter_map(|name| {
+            let game_layout = layout.try_game_layout(name)?;
+            let (mapping, files) = game_layout.plan_export()?;
+            let folder = game_layout.path.leaf()?;
+            let bytes = files.iter().map(|file| game_layout.path.joined(file).size()).sum();
+
+            Some(ExportItem {
+                game: BundledGame {
+                    name: name.clone(),
+                    folder,
+                    files,
+                    bytes,
+                },
+                source: game_layout.path,
+                mapping,
+            })
+        })
+        .collect()
+}
+
+pub fn export(items: &[ExportItem], target: &StrictPath) -> Result<(), Error> {
+    try_export(items, target).map_err(|e| {
+        log::error!("Unable to export backups: {:?} | {e}", target);
+        Error::CannotCreateBundle { path: target.clone() }
+    })
+}
+
+fn try_export(items: &[ExportItem], target: &StrictPath) -> Result<(), AnyError> {
+    target.create_parent_dir()?;
+    let mut zip = zip::ZipWriter::new(target.create()?);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    for item in items {
+        let folder = &item.game.folder;
+
+        zip.start_file(format!("{folder}/{MAPPING_FILE}"), options)?;
+        zip.write_all(item.mapping.serialize().as_bytes())?;
+
+        for file in &item.game.files {
+            let source = item.source.joined(file);
+            log::debug!("[{}] exporting: {:?}", item.game.name, &source);
+
+            let mut options = options;
+            if let Ok(mtime) = source.get_mtime_zip() {
+                options = options.last_modified_time(mtime);
+            }
+            zip.start_file(format!("{folder}/{file}"), options)?;
+            std::io::copy(&mut source.open()?, &mut zip)?;
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// A game's files as they would be restored, for the `restore --to-archive` command.
+#[derive(Clone, Debug)]
+pub struct RestoreArchiveItem {
+    pub game: BundledGame,
+    /// The backed up files, in the same order as `game.files`.
+    sources: Vec<ScannedFile>,
+    /// A `.reg` export of the game's registry data, if any.
+    registry: Option<String>,
+}
+
+/// Files are stored under the game's folder by their restoration target,
+/// using the same drive folders as a simple backup (e.g., `game/drive-C/Users/foo/save.dat`).
+pub fn plan_restore_to_archive(layout: &BackupLayout, scans: &[ScanInfo]) -> Vec<RestoreArchiveItem> {
+    scans
+        .iter()
+        .filter_map(|scan| {
+            #[cfg(target_os = "windows")]
+            let backup = scan.backup.as_ref()?.id();
+            #[cfg(not(target_os = "windows"))]
+            scan.backup.as_ref()?;
+            let folder = layout.game_folder(&scan.game_name).leaf()?;
+
+            let mut planned: Vec<_> = scan
+                .found_files
+                .iter()
+                .filter(|x| !x.ignored)
+                .map(|file| {
+                    let (drive, plain_path) = file.effective().split_drive();
+                    let name = format!("{}/{}", IndividualMapping::new_drive_folder_name(&drive), plain_path);
+                    (name, file.clone())
+                })
+                .collect();
+            planned.sort_by(|(x, _), (y, _)| x.cmp(y));
+            let (mut files, sources): (Vec<_>, Vec<_>) = planned.into_iter().unzip();
+            let mut bytes = sources.iter().map(|x| x.size).sum();
+
+            #[cfg(target_os = "windows")]
+            let registry = layout
+                .game_layout(&scan.game_name)
+                .registry_content(&backup)
+                .and_then(|content| crate::scan::registry::Hives::deserialize(&content))
+                .filter(|hives| !hives.is_empty())
+                .map(|hives| hives.to_reg_export());
+            #[cfg(not(target_os = "windows"))]
+            let registry: Option<String> = None;
+
+            if let Some(registry) = &registry {
+                files.push(REGISTRY_EXPORT_FILE.to_string());
+                bytes += registry.len() as u64;
+            }
+
+            if files.is_empty() {
+                return None;
+            }
+
+            Some(RestoreArchiveItem {
+                game: BundledGame {
+                    name: scan.game_name.clone(),
+                    folder,
+                    files,
+                    bytes,
+                },
+                sources,
+                registry,
+            })
+        })
+        .collect()
+}
+
+pub fn restore_to_archive(items: &[RestoreArchiveItem], target: &StrictPath) -> Result<(), Error> {
+    try_restore_to_archive(items, target).map_err(|e| {
+        log::error!("Unable to restore backups into archive: {:?} | {e}", target);
+        Error::CannotCreateBundle { path: target.clone() }
+    })
+}
+
+fn try_restore_to_archive(items: &[RestoreArchiveItem], target: &StrictPath) -> Result<(), AnyError> {
+    target.create_parent_dir()?;
+    let mut zip = zip::ZipWriter::new(target.create()?);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    let mut containers: HashMap<StrictPath, zip::ZipArchive<std::fs::File>> = HashMap::new();
+
+    for item in items {
+        let folder = &item.game.folder;
+
+        for (file, source) in item.game.files.iter().zip(&item.sources) {
+            log::debug!(
+                "[{}] restoring into archive: {:?} -> {file}",
+                item.game.name,
+                &source.path
+            );
+
+            match &source.container {
+                None => {
+                    let mut options = options;
+                    if let Ok(mtime) = source.path.get_mtime_zip() {
+                        options = options.last_modified_time(mtime);
+                    }
+                    zip.start_file(format!("{folder}/{file}"), options)?;
+                    std::io::copy(&mut source.path.open()?, &mut zip)?;
+                }
+                Some(container) => {
+                    if !containers.contains_key(container) {
+                        containers.insert(container.clone(), zip::ZipArchive::new(container.open()?)?);
+                    }
+                    let Some(archive) = containers.get_mut(container) else {
+                        continue;
+                    };
+                    let mut entry = archive.by_name(&source.path.raw())?;
+                    zip.start_file(
+                        format!("{folder}/{file}"),
+                        options.last_modified_time(entry.last_modified()),
+                    )?;
+                    std::io::copy(&mut entry, &mut zip)?;
+                }
+            }
+        }
+
+        if let Some(registry) = &item.registry {
+            zip.start_file(format!("{folder}/{REGISTRY_EXPORT_FILE}"), options)?;
+            zip.write_all(registry.as_bytes())?;
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Read the games from an archive created by the `export` command.
+pub fn plan_import(source: &StrictPath) -> Result<Vec<BundledGame>, Error> {
+    try_plan_import(source).map_err(|e| {
+        log::error!("Unable to read exported backups: {:?} | {e}", source);
+        Error::BundleInvalid { path: source.clone() }
+    })
+}
+
+fn try_plan_import(source: &StrictPath) -> Result<Vec<BundledGame>, AnyError> {
+    let mut archive = zip::ZipArchive::new(source.open()?)?;
+
+    let mut mappings = BTreeMap::<String, IndividualMapping>::new();
+    let mut files = BTreeMap::<String, Vec<(String, u64)>>::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+        if entry.enclosed_name().is_none() {
+            log::warn!("Ignoring unsafe path in exported backups: {}", entry.name());
+            continue;
+        }
+
+        let Some((folder, file)) = entry.name().split_once('/') else {
+            continue;
+        };
+        let (folder, file) = (folder.to_string(), file.to_string());
+
+        if file == MAPPING_FILE {
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+            mappings.insert(folder, IndividualMapping::load_from_string(&content)?);
+        } else {
+            files.entry(folder).or_default().push((file, entry.size()));
+        }
+    }
+
+    Ok(mappings
+        .into_iter()
+        .map(|(folder, mapping)| {
+            let files = files.remove(&folder).unwrap_or_default();
+            BundledGame {
+                name: mapping.name,
+                bytes: files.iter().map(|(_, size)| size).sum(),
+                files: files.into_iter().map(|(file, _)| file).collect(),
+                folder,
+            }
+        })
+        .collect())
+}
+
+/// Unpack games from an archive created by the `export` command.
+/// The mapping file is written last so that a partially imported game is not picked up as a backup.
+pub fn import(source: &StrictPath, games: &[BundledGame], layout: &BackupLayout) -> Result<(), Error> {
+    try_import(source, games, layout).map_err(|e| {
+        log::error!("Unable to import backups: {:?} -> {:?} | {e}", source, &layout.base);
+        Error::BundleInvalid { path: source.clone() }
+    })
+}
+
+fn try_import(source: &StrictPath, games: &[BundledGame], layout: &BackupLayout) -> Result<(), AnyError> {
+    let mut archive = zip::ZipArchive::new(source.open()?)?;
+
+    for game in games {
+        let target_folder = layout.game_folder(&game.name);
+
+        for file in game.files.iter().chain(std::iter::once(&MAPPING_FILE.to_string())) {
+            let target = target_folder.joined(file);
+            log::debug!("[{}] importing: {:?}", game.name, &target);
+
+            let mut entry = archive.by_name(&format!("{}/{file}", game.folder))?;
+            target.create_parent_dir()?;
+            std::io::copy(&mut entry, &mut target.create()?)?;
+            if let Err(e) = target.set_mtime_zip(entry.last_modified()) {
+                log::warn!("[{}] unable to set modification time: {:?} | {e:?}", game.name, &target);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        resource::config::Retention,
+        scan::BackupId,
+        testing::{repo, s},
+    };
+
+    #[test]
+    fn can_plan_export_of_latest_backups() {
+        let layout = BackupLayout::new(
+            StrictPath::new(format!("{}/tests/backup", repo())),
+            Retention::default(),
+        );
+        let items = plan_export(&layout, &[s("game1"), s("game1-zipped"), s("nonexistent")]);
+
+        assert_eq!(
+            vec![
+                BundledGame {
+                    name: s("game1"),
+                    folder: s("game1"),
+                    files: vec![s("drive-X/file1.txt"), s("drive-X/file2.txt")],
+                    bytes: 3,
+                },
+                BundledGame {
+                    name: s("game1-zipped"),
+                    folder: s("game1-zipped"),
+                    files: vec![s("test.zip")],
+                    bytes: 245,
+                },
+            ],
+            items.into_iter().map(|x| x.game).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn can_restore_to_archive() {
+        let layout = BackupLayout::new(
+            StrictPath::new(format!("{}/tests/backup", repo())),
+            Retention::default(),
+        );
+        let scans: Vec<_> = [s("game1"), s("game1-zipped")]
+            .iter()
+            .map(|name| {
+                layout.game_layout(name).scan_for_restoration(
+                    name,
+                    &BackupId::Latest,
+                    &[],
+                    &Default::default(),
+                    &Default::default(),
+                )
+            })
+            .collect();
+        let items = plan_restore_to_archive(&layout, &scans);
+
+        assert_eq!(
+            vec![
+                BundledGame {
+                    name: s("game1"),
+                    folder: s("game1"),
+                    files: vec![s("drive-X/file1.txt"), s("drive-X/file2.txt")],
+                    bytes: 3,
+                },
+                BundledGame {
+                    name: s("game1-zipped"),
+                    folder: s("game1-zipped"),
+                    files: vec![s("drive-X/file1.txt"), s("drive-X/file2.txt")],
+                    bytes: 3,
+                },
+            ],
+            items.iter().map(|x| x.game.clone()).collect::<Vec<_>>(),
+        );
+
+        let dir = std::env::temp_dir().join("ludusavi-tests/restore-to-archive");
+        _ = std::fs::remove_dir_all(&dir);
+        let target = StrictPath::from(dir.join("restored.zip"));
+        restore_to_archive(&items, &target).unwrap();
+
+        let mut archive = zip::ZipArchive::new(target.open().unwrap()).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name("game1-zipped/drive-X/file2.txt")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(4, archive.len());
+        assert_eq!("..", content);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     lang::{ADD_SYMBOL, CHANGE_SYMBOL, REMOVAL_SYMBOL},
     prelude::StrictPath,
+    resource::config::HashAlgorithm,
 };
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Serialize, schemars::JsonSchema)]
@@ -148,8 +149,8 @@ impl ScanChange {
         }
     }
 
-    pub fn evaluate_restore(original_path: &StrictPath, previous_hash: &str) -> Self {
-        match original_path.try_sha1() {
+    pub fn evaluate_restore(original_path: &StrictPath, previous_hash: &str, algorithm: HashAlgorithm) -> Self {
+        match original_path.try_hash(algorithm) {
             Err(_) => Self::New,
             Ok(current_hash) => {
                 if current_hash == previous_hash {
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{
     prelude::StrictPath,
+    resource::config::DuplicateOwners,
     scan::{registry_compat::RegistryItem, ScanChange, ScanInfo, ScannedFile},
 };
 
@@ -91,9 +92,32 @@ pub struct DuplicateDetector {
     game_registry: HashMap<String, HashSet<RegistryItem>>,
     game_registry_values: HashMap<String, HashMap<RegistryItem, HashSet<String>>>,
     game_duplicated_items: HashMap<String, DuplicateDetectorCount>,
+    owners: DuplicateOwners,
 }
 
 impl DuplicateDetector {
+    /// Remember the user's preferred owners so that they can be reported.
+    /// The owners themselves are applied by `ScanInfo::apply_duplicate_owners`.
+    pub fn set_owners(&mut self, owners: DuplicateOwners) {
+        self.owners = owners;
+    }
+
+    /// If this file is found by multiple games and the user has chosen an owner for it,
+    /// then this is that game.
+    pub fn file_owner(&self, file: &ScannedFile) -> Option<&str> {
+        let path = self.pick_path(file);
+        if self.files.get(&path).is_some_and(|games| games.len() > 1) {
+            self.owners.owner(&path)
+        } else {
+            None
+        }
+    }
+
+    /// Whether this game has been added with this file.
+    pub fn game_has_file(&self, game: &str, path: &StrictPath) -> bool {
+        self.game_files.get(game).is_some_and(|files| files.contains(path))
+    }
+
     pub fn add_game(&mut self, scan_info: &ScanInfo, game_enabled: bool) -> HashSet<String> {
         let mut stale = self.remove_game_and_refresh(&scan_info.game_name, false);
         stale.insert(scan_info.game_name.clone());
@@ -576,6 +600,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn file_with_preferred_owner_is_resolved() {
+        let mut detector = DuplicateDetector::default();
+        let owners = DuplicateOwners::new(velcro::btree_map! {
+            StrictPath::new(s("shared")): s("owner"),
+        });
+        detector.set_owners(owners.clone());
+
+        for game in ["owner", "other"] {
+            let mut scan_info = ScanInfo {
+                game_name: game.into(),
+                found_files: hash_set! {
+                    ScannedFile::with_name("shared/file1").change_as(ScanChange::Different),
+                },
+                ..Default::default()
+            };
+            scan_info.apply_duplicate_owners(&owners, |owner, path| detector.game_has_file(owner, path));
+            assert_eq!(game == "other", scan_info.found_files.iter().all(|file| file.ignored),);
+            detector.add_game(&scan_info, true);
+        }
+
+        let file = ScannedFile::with_name("shared/file1");
+        assert_eq!(Duplication::Resolved, detector.is_file_duplicated(&file));
+        assert_eq!(Some("owner"), detector.file_owner(&file));
+        assert_eq!(None, detector.file_owner(&ScannedFile::with_name("shared/file2")));
+    }
+
+    #[test]
+    fn file_with_preferred_owner_is_kept_when_not_shared() {
+        let mut detector = DuplicateDetector::default();
+        let owners = DuplicateOwners::new(velcro::btree_map! {
+            StrictPath::new(s("shared")): s("owner"),
+        });
+        detector.set_owners(owners.clone());
+
+        detector.add_game(
+            &ScanInfo {
+                game_name: "owner".into(),
+                found_files: hash_set! {
+                    ScannedFile::with_name("shared/file1"),
+                },
+                ..Default::default()
+            },
+            true,
+        );
+
+        let mut scan_info = ScanInfo {
+            game_name: "other".into(),
+            found_files: hash_set! {
+                ScannedFile::with_name("shared/file2"),
+            },
+            ..Default::default()
+        };
+        scan_info.apply_duplicate_owners(&owners, |owner, path| detector.game_has_file(owner, path));
+        assert!(scan_info.found_files.iter().all(|file| !file.ignored));
+    }
+
     #[test]
     fn ignored_file_is_resolved() {
         let mut detector = DuplicateDetector::default();
@@ -1,7 +1,7 @@
 use crate::{
     lang::TRANSLATOR,
     resource::manifest,
-    scan::{Duplication, ScanInfo},
+    scan::{BackupInfo, Duplication, ScanInfo},
 };
 
 use super::ScanChange;
@@ -12,6 +12,7 @@ pub enum FilterKind {
     Completeness,
     Enablement,
     Change,
+    Outcome,
     Manifest,
 }
 
@@ -115,6 +116,31 @@ impl Change {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Outcome {
+    Succeeded,
+    #[default]
+    Failed,
+}
+
+impl ToString for Outcome {
+    fn to_string(&self) -> String {
+        TRANSLATOR.filter_outcome(*self)
+    }
+}
+
+impl Outcome {
+    pub const ALL: &'static [Self] = &[Self::Succeeded, Self::Failed];
+
+    /// Games that have not been processed yet don't qualify either way.
+    pub fn qualifies(&self, info: Option<&BackupInfo>) -> bool {
+        match self {
+            Self::Succeeded => info.is_some_and(|x| x.successful()),
+            Self::Failed => info.is_some_and(|x| !x.successful()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Manifest {
     source: manifest::Source,
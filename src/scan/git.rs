@@ -0,0 +1,177 @@
+use std::sync::Mutex;
+
+use crate::prelude::{run_command, CommandError, CommandOutput, Error, Privacy, StrictPath};
+
+const GIT: &str = "git";
+const CHECKOUT_PREFIX: &str = "ludusavi-git-checkout";
+
+/// Games are backed up in parallel, but they share one repository,
+/// and Git can't update its index from several processes at once.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn git(repo: &StrictPath, args: &[&str]) -> Result<CommandOutput, CommandError> {
+    git_with_success(repo, args, &[0])
+}
+
+fn git_with_success(repo: &StrictPath, args: &[&str], success: &[i32]) -> Result<CommandOutput, CommandError> {
+    let repo = repo.render();
+    let mut full_args = vec!["-C", repo.as_str()];
+    full_args.extend(args);
+    run_command(GIT, &full_args, success, Privacy::Public)
+}
+
+/// Make sure that Git is installed before trying to use it.
+pub fn validate() -> Result<(), Error> {
+    match run_command(GIT, &["--version"], &[0], Privacy::Public) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            log::error!("Git is unavailable: {e:?}");
+            Err(Error::GitUnavailable)
+        }
+    }
+}
+
+fn ensure_repo(repo: &StrictPath) -> Result<(), CommandError> {
+    if repo.joined(".git").exists() {
+        return Ok(());
+    }
+
+    log::info!("Initializing Git repository: {:?}", repo);
+    git(repo, &["init", "--quiet"])?;
+
+    // Commits would fail without an identity, so fall back to one for this repository only.
+    if git_with_success(repo, &["config", "user.email"], &[0, 1])?
+        .stdout
+        .is_empty()
+    {
+        git(repo, &["config", "user.name", "Ludusavi"])?;
+        git(repo, &["config", "user.email", "ludusavi@localhost"])?;
+    }
+
+    Ok(())
+}
+
+/// Commit the current contents of a game's backup folder
+/// into the repository of the overall backup folder, creating it if needed.
+/// Returns whether there was anything to commit.
+pub fn commit(
+    repo: &StrictPath,
+    game_folder: &StrictPath,
+    game: &str,
+    when: &chrono::DateTime<chrono::Utc>,
+) -> Result<bool, CommandError> {
+    let _guard = LOCK.lock();
+
+    ensure_repo(repo)?;
+
+    let folder = game_folder.render();
+    git(repo, &["add", "--all", "--", &folder])?;
+
+    let staged = git_with_success(repo, &["diff", "--cached", "--quiet", "--", &folder], &[0, 1])?;
+    if staged.code == 0 {
+        log::info!("[{game}] nothing to commit");
+        return Ok(false);
+    }
+
+    let message = commit_message(game, when);
+    git(repo, &["commit", "--quiet", "--message", &message, "--", &folder])?;
+    Ok(true)
+}
+
+fn commit_message(game: &str, when: &chrono::DateTime<chrono::Utc>) -> String {
+    format!("Back up {game} ({})", when.format("%Y-%m-%dT%H:%M:%SZ"))
+}
+
+/// A temporary checkout of a specific commit, for restoring older backups.
+/// This does not affect the repository's own files, and it is removed when dropped.
+pub struct GitCheckout {
+    repo: StrictPath,
+    path: StrictPath,
+}
+
+impl GitCheckout {
+    pub fn new(repo: &StrictPath, commit: &str) -> Result<Self, Error> {
+        validate()?;
+
+        let path = StrictPath::from(std::env::temp_dir().join(format!("{CHECKOUT_PREFIX}-{}", std::process::id())));
+        if path.exists() {
+            let _ = path.remove();
+        }
+        let _ = git(repo, &["worktree", "prune"]);
+
+        git(
+            repo,
+            &["worktree", "add", "--detach", "--force", &path.render(), commit],
+        )
+        .map_err(Error::GitCommandFailed)?;
+
+        Ok(Self {
+            repo: repo.clone(),
+            path,
+        })
+    }
+
+    pub fn path(&self) -> &StrictPath {
+        &self.path
+    }
+}
+
+impl Drop for GitCheckout {
+    fn drop(&mut self) {
+        if let Err(e) = git(&self.repo, &["worktree", "remove", "--force", &self.path.render()]) {
+            log::warn!("Unable to remove temporary Git checkout: {:?} | {e:?}", &self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn now() -> chrono::DateTime<chrono::Utc> {
+        chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap()
+            .and_local_timezone(chrono::Utc)
+            .unwrap()
+    }
+
+    #[test]
+    fn can_format_commit_message() {
+        assert_eq!("Back up game1 (2000-01-02T03:04:05Z)", commit_message("game1", &now()));
+    }
+
+    #[test]
+    fn can_commit_and_check_out_backups() {
+        if validate().is_err() {
+            eprintln!("Skipping test because Git is unavailable");
+            return;
+        }
+
+        let dir = std::env::temp_dir().join("ludusavi-tests/git-commit");
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("game1")).unwrap();
+        let repo = StrictPath::from(dir);
+        let game_folder = repo.joined("game1");
+        let file = game_folder.joined("file.txt");
+
+        std::fs::write(file.as_std_path_buf().unwrap(), "old").unwrap();
+        assert!(commit(&repo, &game_folder, "game1", &now()).unwrap());
+        assert!(!commit(&repo, &game_folder, "game1", &now()).unwrap());
+        let first = git(&repo, &["rev-parse", "HEAD"]).unwrap().stdout.trim().to_string();
+
+        std::fs::write(file.as_std_path_buf().unwrap(), "new").unwrap();
+        assert!(commit(&repo, &game_folder, "game1", &now()).unwrap());
+
+        let checkout = GitCheckout::new(&repo, &first).unwrap();
+        let checkout_path = checkout.path().clone();
+        assert_eq!(Some("old".to_string()), checkout_path.joined("game1/file.txt").read());
+        assert_eq!(Some("new".to_string()), file.read());
+
+        drop(checkout);
+        assert!(!checkout_path.exists());
+    }
+}
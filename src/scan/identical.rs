@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use rayon::prelude::*;
+
+use crate::{
+    prelude::StrictPath,
+    resource::config::HashAlgorithm,
+    scan::{ScanChange, ScanInfo},
+};
+
+/// Byte-identical content that was found in more than one game.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IdenticalFiles {
+    /// SHA-256 hash of the content.
+    pub hash: String,
+    /// Size of each copy in bytes.
+    pub size: u64,
+    /// Bytes that would be saved by keeping only one copy.
+    pub wasted_bytes: u64,
+    /// Each key is the name of a game,
+    /// and each value is that game's files with this content.
+    pub games: BTreeMap<String, BTreeSet<String>>,
+}
+
+struct Candidate<'a> {
+    game: &'a str,
+    path: &'a StrictPath,
+    rendered: String,
+    size: u64,
+}
+
+/// Find files with identical content across different games.
+/// The scan's own hashes may use a fast, non-cryptographic algorithm,
+/// so only files whose sizes match across games are rehashed (in parallel) with SHA-256.
+pub fn find_identical_files<'a>(scans: impl IntoIterator<Item = &'a ScanInfo>) -> Vec<IdenticalFiles> {
+    let mut by_size = BTreeMap::<u64, Vec<Candidate>>::new();
+    for scan in scans {
+        for file in &scan.found_files {
+            if file.ignored || file.size == 0 || file.container.is_some() || file.change == ScanChange::Removed {
+                continue;
+            }
+            by_size.entry(file.size).or_default().push(Candidate {
+                game: &scan.game_name,
+                path: &file.path,
+                rendered: file.readable(false),
+                size: file.size,
+            });
+        }
+    }
+
+    let candidates: Vec<_> = by_size
+        .into_values()
+        .filter(|group| spans_multiple_games(group.iter().map(|x| x.game)))
+        .flatten()
+        .collect();
+
+    let hashed: Vec<_> = candidates
+        .par_iter()
+        .filter_map(|candidate| {
+            let hash = candidate.path.try_hash(HashAlgorithm::Sha256).ok()?;
+            Some((hash, candidate))
+        })
+        .collect();
+
+    let mut by_content = BTreeMap::<(u64, String), Vec<&Candidate>>::new();
+    for (hash, candidate) in hashed {
+        by_content.entry((candidate.size, hash)).or_default().push(candidate);
+    }
+
+    let mut identical: Vec<_> = by_content
+        .into_iter()
+        .filter(|(_, group)| spans_multiple_games(group.iter().map(|x| x.game)))
+        .map(|((size, hash), group)| {
+            let mut games = BTreeMap::<String, BTreeSet<String>>::new();
+            for candidate in &group {
+                games
+                    .entry(candidate.game.to_string())
+                    .or_default()
+                    .insert(candidate.rendered.clone());
+            }
+            IdenticalFiles {
+                hash,
+                size,
+                wasted_bytes: size * (group.len() as u64 - 1),
+                games,
+            }
+        })
+        .collect();
+
+    identical.sort_by(|x, y| y.wasted_bytes.cmp(&x.wasted_bytes).then_with(|| x.hash.cmp(&y.hash)));
+    identical
+}
+
+fn spans_multiple_games<'a>(mut games: impl Iterator<Item = &'a str>) -> bool {
+    let Some(first) = games.next() else {
+        return false;
+    };
+    games.any(|x| x != first)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::{btree_map, btree_set};
+
+    use super::*;
+    use crate::{
+        scan::ScannedFile,
+        testing::{repo_file, s},
+    };
+
+    fn scan(game: &str, files: &[&str]) -> ScanInfo {
+        ScanInfo {
+            game_name: s(game),
+            found_files: files
+                .iter()
+                .map(|x| {
+                    let path = StrictPath::new(repo_file(x));
+                    ScannedFile::new(path.raw(), path.size(), "")
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn can_find_identical_files_across_games() {
+        let scans = [
+            scan(
+                "game1",
+                &["tests/root2/game1/file1.txt", "tests/root1/game1/subdir/file2.txt"],
+            ),
+            scan("game2", &["tests/root2/game2/file1.txt"]),
+            scan(
+                "game3",
+                &["tests/root3/game5/data/file1.txt", "tests/root3/game_2/file1.txt"],
+            ),
+        ];
+
+        let identical = find_identical_files(&scans);
+        assert_eq!(
+            vec![IdenticalFiles {
+                hash: s("cdb4ee2aea69cc6a83331bbe96dc2caa9a299d21329efb0336fc02a82e1839a8"),
+                size: 1,
+                wasted_bytes: 3,
+                games: btree_map! {
+                    s("game1"): btree_set! { repo_file("tests/root2/game1/file1.txt") },
+                    s("game2"): btree_set! { repo_file("tests/root2/game2/file1.txt") },
+                    s("game3"): btree_set! {
+                        repo_file("tests/root3/game5/data/file1.txt"),
+                        repo_file("tests/root3/game_2/file1.txt"),
+                    },
+                },
+            }],
+            identical,
+        );
+    }
+
+    #[test]
+    fn ignores_identical_files_within_one_game() {
+        let scans = [scan(
+            "game1",
+            &["tests/root2/game1/file1.txt", "tests/root2/game2/file1.txt"],
+        )];
+
+        assert_eq!(Vec::<IdenticalFiles>::new(), find_identical_files(&scans));
+    }
+}
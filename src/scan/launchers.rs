@@ -3,13 +3,13 @@ pub mod heroic;
 mod legendary;
 mod lutris;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use crate::{
     prelude::StrictPath,
     resource::{
         config::Root,
-        manifest::{Manifest, Os},
+        manifest::{Manifest, Os, Store},
     },
     scan::TitleFinder,
 };
@@ -20,6 +20,31 @@ pub struct Launchers {
     empty: HashSet<LauncherGame>,
 }
 
+/// Where a game was detected, for grouping games in reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StoreGroup {
+    Detected(Store),
+    /// A custom game that was not detected in any store.
+    Custom,
+    Unknown,
+}
+
+impl StoreGroup {
+    /// The key used in `--api` output.
+    pub fn key(&self) -> String {
+        match self {
+            Self::Detected(store) => Store::ALL
+                .iter()
+                .position(|x| x == store)
+                .map(|i| Store::all_names()[i])
+                .unwrap_or("other")
+                .to_string(),
+            Self::Custom => "custom".to_string(),
+            Self::Unknown => "unknown".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct LauncherGame {
     pub install_dir: Option<StrictPath>,
@@ -42,6 +67,87 @@ impl Launchers {
             .iter()
     }
 
+    /// Find the Wine prefix that any launcher has associated with this game.
+    /// For Proton-style prefixes, this returns the nested `pfx` folder.
+    pub fn find_prefix(&self, game: &str) -> Option<StrictPath> {
+        let mut prefixes: Vec<_> = self
+            .games
+            .values()
+            .filter_map(|games| games.get(game))
+            .flatten()
+            .filter_map(|x| x.prefix.clone())
+            .collect();
+        prefixes.sort();
+
+        prefixes.into_iter().next().map(|prefix| {
+            let with_pfx = prefix.joined("pfx");
+            if with_pfx.exists() {
+                with_pfx
+            } else {
+                prefix
+            }
+        })
+    }
+
+    /// Find the stores of every root in which this game was detected.
+    pub fn stores(&self, game: &str) -> BTreeSet<Store> {
+        self.games
+            .iter()
+            .filter(|(_, games)| games.contains_key(game))
+            .map(|(root, _)| root.store())
+            .collect()
+    }
+
+    /// Remove games that were only detected in roots from the excluded stores.
+    /// Games that weren't detected in any root are kept.
+    /// Returns how many games were removed for each store.
+    pub fn exclude_stores(&self, games: &mut Vec<String>, excluded: &[Store]) -> BTreeMap<Store, usize> {
+        let mut dropped = BTreeMap::<Store, usize>::new();
+        if excluded.is_empty() {
+            return dropped;
+        }
+
+        games.retain(|game| {
+            let stores = self.stores(game);
+            if stores.is_empty() || !stores.iter().all(|store| excluded.contains(store)) {
+                return true;
+            }
+            for store in stores {
+                *dropped.entry(store).or_default() += 1;
+            }
+            false
+        });
+
+        dropped
+    }
+
+    /// Remove games that weren't detected in any root,
+    /// meaning that they don't appear to be installed.
+    /// Returns the names of the removed games.
+    pub fn exclude_uninstalled(&self, games: &mut Vec<String>) -> BTreeSet<String> {
+        let mut dropped = BTreeSet::new();
+
+        games.retain(|game| {
+            if !self.stores(game).is_empty() {
+                return true;
+            }
+            dropped.insert(game.clone());
+            false
+        });
+
+        dropped
+    }
+
+    /// Decide which store a game should be grouped under.
+    /// If it was detected in several stores, then the first one in `Store::ALL` is used.
+    pub fn store_group(&self, game: &str, custom: bool) -> StoreGroup {
+        match self.stores(game).into_iter().next() {
+            Some(store) => StoreGroup::Detected(store),
+            None if custom => StoreGroup::Custom,
+            None => StoreGroup::Unknown,
+        }
+    }
+
     pub fn scan(
         roots: &[Root],
         manifest: &Manifest,
@@ -77,3 +183,130 @@ impl Launchers {
         Self::scan(roots, manifest, subjects, &TitleFinder::default(), None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        resource::{config::root, ResourceFile},
+        testing::repo,
+    };
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            windows-game:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn can_find_prefix_from_launcher() {
+        let manifest = manifest();
+        let roots = vec![Root::Lutris(root::Lutris {
+            path: format!("{}/tests/launchers/lutris-spec", repo()).into(),
+            database: None,
+        })];
+        let title_finder = TitleFinder::new(&Default::default(), &manifest, Default::default());
+        let launchers = Launchers::scan(&roots, &manifest, &[], &title_finder, None);
+
+        assert_eq!(
+            Some(StrictPath::new("/home/deck/Games/service/windows-game".to_string())),
+            launchers.find_prefix("windows-game"),
+        );
+        assert_eq!(None, launchers.find_prefix("other-game"));
+    }
+
+    #[test]
+    fn can_exclude_games_by_store() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              files:
+                <base>/file1.txt: {}
+            game2:
+              files:
+                <base>/file1.txt: {}
+            game3:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap();
+        let roots = vec![
+            Root::new(format!("{}/tests/root1", repo()), Store::Epic),
+            Root::new(format!("{}/tests/root2", repo()), Store::Gog),
+        ];
+        let subjects = vec!["game1".to_string(), "game2".to_string(), "game3".to_string()];
+        let launchers = Launchers::scan_dirs(&roots, &manifest, &subjects);
+
+        let mut games = subjects.clone();
+        let dropped = launchers.exclude_stores(&mut games, &[Store::Gog]);
+        assert_eq!(vec!["game1".to_string(), "game3".to_string()], games);
+        assert_eq!(BTreeMap::from([(Store::Gog, 1)]), dropped);
+
+        let mut games = subjects.clone();
+        let dropped = launchers.exclude_stores(&mut games, &[Store::Gog, Store::Epic]);
+        assert_eq!(vec!["game3".to_string()], games);
+        assert_eq!(BTreeMap::from([(Store::Epic, 1), (Store::Gog, 2)]), dropped);
+    }
+
+    #[test]
+    fn can_exclude_uninstalled_games() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              files:
+                <base>/file1.txt: {}
+            game3:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap();
+        let roots = vec![Root::new(format!("{}/tests/root1", repo()), Store::Epic)];
+        let subjects = vec!["game1".to_string(), "game3".to_string()];
+        let launchers = Launchers::scan_dirs(&roots, &manifest, &subjects);
+
+        let mut games = subjects.clone();
+        let dropped = launchers.exclude_uninstalled(&mut games);
+        assert_eq!(vec!["game1".to_string()], games);
+        assert_eq!(BTreeSet::from(["game3".to_string()]), dropped);
+    }
+
+    #[test]
+    fn can_group_games_by_store() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              files:
+                <base>/file1.txt: {}
+            game2:
+              files:
+                <base>/file1.txt: {}
+            game3:
+              files:
+                <base>/file1.txt: {}
+            "#,
+        )
+        .unwrap();
+        let roots = vec![
+            Root::new(format!("{}/tests/root1", repo()), Store::Epic),
+            Root::new(format!("{}/tests/root2", repo()), Store::Gog),
+        ];
+        let subjects = vec!["game1".to_string(), "game2".to_string(), "game3".to_string()];
+        let launchers = Launchers::scan_dirs(&roots, &manifest, &subjects);
+
+        assert_eq!(StoreGroup::Detected(Store::Epic), launchers.store_group("game1", false));
+        assert_eq!(StoreGroup::Detected(Store::Gog), launchers.store_group("game2", true));
+        assert_eq!(StoreGroup::Custom, launchers.store_group("game3", true));
+        assert_eq!(StoreGroup::Unknown, launchers.store_group("game3", false));
+        assert_eq!("gog", StoreGroup::Detected(Store::Gog).key());
+        assert_eq!("otherHome", StoreGroup::Detected(Store::OtherHome).key());
+    }
+}
@@ -7,15 +7,17 @@ use chrono::{Datelike, Timelike};
 
 use crate::{
     path::StrictPath,
-    prelude::{AnyError, INVALID_FILE_CHARS},
+    prelude::{AnyError, Error, INVALID_FILE_CHARS},
     resource::{
         config::{
-            BackupFormat, BackupFormats, RedirectConfig, Retention, ToggledPaths, ToggledRegistry, ZipCompression,
+            BackupFormat, BackupFormats, BackupLayoutStyle, GameRetention, HashAlgorithm, RedirectConfig, Retention,
+            ToggledPaths, ToggledRegistry, ZipCompression,
         },
         manifest::Os,
     },
     scan::{
-        game_file_target, prepare_backup_target, BackupError, BackupId, BackupInfo, ScanChange, ScanInfo, ScannedFile,
+        game_file_target, git, prepare_backup_target, BackupError, BackupId, BackupInfo, ScanChange, ScanInfo,
+        ScannedFile,
     },
 };
 
@@ -23,6 +25,8 @@ use crate::{
 use crate::scan::ScannedRegistry;
 
 const SAFE: &str = "_";
+/// Folder within a game's backups for content stored with `backup.deduplicate`.
+pub const BLOBS: &str = "blobs";
 
 macro_rules! some_or_continue {
     ($maybe:expr) => {
@@ -60,6 +64,11 @@ pub fn escape_folder_name(name: &str) -> String {
     escaped.replace(INVALID_FILE_CHARS, SAFE)
 }
 
+/// Whether a folder name is a year (`YYYY`) or month (`MM`) from `BackupLayoutStyle::DatePartitioned`.
+fn is_date_partition(name: &str, digits: usize) -> bool {
+    name.len() == digits && name.chars().all(|x| x.is_ascii_digit())
+}
+
 pub struct LatestBackup {
     pub scan: ScanInfo,
     #[allow(unused)]
@@ -128,6 +137,137 @@ impl Backup {
         }
     }
 
+    /// Nest the backup under `YYYY/MM` folders, based on when it was made.
+    /// The special `.` backup always stays directly in the game's folder.
+    pub fn partition_by_date(&mut self) {
+        let (name, when) = match self {
+            Self::Full(x) => (&mut x.name, x.when),
+            Self::Differential(x) => (&mut x.name, x.when),
+        };
+        if name != "." {
+            *name = format!("{}/{:02}/{}", when.year(), when.month(), name);
+        }
+    }
+
+    /// Rename the backup from a template like `{date}_{time}_{tag}`.
+    /// The name keeps its `backup-` prefix and any `.zip` extension,
+    /// and a number is added if it would match one of the `taken` names.
+    /// The special `.` backup is never renamed.
+    pub fn apply_id_format(&mut self, id_format: &str, taken: &HashSet<String>) {
+        let (name, when, tag) = match self {
+            Self::Full(x) => (&mut x.name, x.when, "full"),
+            Self::Differential(x) => (&mut x.name, x.when, "diff"),
+        };
+        if name == "." {
+            return;
+        }
+
+        let extension = if name.ends_with(".zip") { ".zip" } else { "" };
+        let stem = id_format
+            .replace(
+                "{date}",
+                &format!("{}-{:02}-{:02}", when.year(), when.month(), when.day()),
+            )
+            .replace(
+                "{time}",
+                &format!("{:02}-{:02}-{:02}", when.hour(), when.minute(), when.second()),
+            )
+            .replace("{tag}", tag)
+            .replace(INVALID_FILE_CHARS, SAFE);
+
+        let mut candidate = format!("backup-{stem}{extension}");
+        let mut counter = 2;
+        while taken.contains(&candidate) {
+            candidate = format!("backup-{stem}-{counter}{extension}");
+            counter += 1;
+        }
+        *name = candidate;
+    }
+
+    /// Files whose content is stored by this backup.
+    /// For a differential backup, this excludes files inherited from its full backup.
+    pub fn stored_files(&self) -> Vec<&IndividualMappingFile> {
+        match self {
+            Self::Full(x) => x.files.values().collect(),
+            Self::Differential(x) => x.files.values().flatten().collect(),
+        }
+    }
+
+    /// How many bytes are used on disk by all of these backups, based on their recorded file sizes.
+    /// Content in the `blobs` folder is only counted once, even if it is shared by several backups.
+    pub fn disk_usage(backups: &[Self]) -> u64 {
+        Self::disk_usage_with_blobs(backups, &mut HashSet::new())
+    }
+
+    /// Like `disk_usage`, but skipping any blobs whose hash is already in `counted_blobs`,
+    /// and then adding the newly counted ones,
+    /// so that a running total for the same game folder only counts each blob once.
+    pub fn disk_usage_with_blobs(backups: &[Self], counted_blobs: &mut HashSet<String>) -> u64 {
+        backups
+            .iter()
+            .flat_map(|backup| backup.stored_files())
+            .filter(|file| !file.blob || counted_blobs.insert(file.hash.clone()))
+            .map(|file| file.size)
+            .sum()
+    }
+
+    /// Store each file's content once in the game's `blobs` folder, keyed by its hash,
+    /// so that identical content can be shared with other backups.
+    /// This only applies to the simple format.
+    pub fn store_as_blobs(&mut self) {
+        let entries: Vec<_> = match self {
+            Self::Full(x) if x.format() == BackupFormat::Simple => x.files.values_mut().collect(),
+            Self::Differential(x) if x.format() == BackupFormat::Simple => x.files.values_mut().flatten().collect(),
+            _ => return,
+        };
+        for entry in entries {
+            entry.blob = !entry.hash.is_empty();
+        }
+    }
+
+    /// File path must be in rendered form.
+    fn file_entry(&self, file: &str) -> Option<&IndividualMappingFile> {
+        match self {
+            Self::Full(backup) => backup.files.get(file),
+            Self::Differential(backup) => backup.files.get(file).and_then(|x| x.as_ref()),
+        }
+    }
+
+    pub fn record_permissions(&mut self, scan: &ScanInfo) {
+        for file in &scan.found_files {
+            let entry = match self {
+                Self::Full(x) => x.files.get_mut(&file.mapping_key()),
+                Self::Differential(x) => x.files.get_mut(&file.mapping_key()).and_then(|x| x.as_mut()),
+            };
+            if let Some(entry) = entry {
+                entry.mode = file.path.unix_mode();
+                entry.symlink = file.path.symlink_target();
+            }
+        }
+    }
+
+    pub fn record_other_os(&mut self, scan: &ScanInfo) {
+        for file in &scan.found_files {
+            let Some(os) = scan.other_os(file) else {
+                continue;
+            };
+            let entry = match self {
+                Self::Full(x) => x.files.get_mut(&file.mapping_key()),
+                Self::Differential(x) => x.files.get_mut(&file.mapping_key()).and_then(|x| x.as_mut()),
+            };
+            if let Some(entry) = entry {
+                entry.os = Some(os);
+            }
+        }
+    }
+
+    pub fn empty_dirs(&self) -> &BTreeSet<String> {
+        match self {
+            Self::Full(x) => &x.empty_dirs,
+            Self::Differential(x) => &x.empty_dirs,
+        }
+    }
+
     pub fn label(&self) -> String {
         match self {
             Self::Full(x) => x.label(),
@@ -242,8 +382,15 @@ pub struct FullBackup {
     /// Locked backups do not count toward retention limits and are never deleted.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub locked: bool,
+    /// Algorithm used for the file hashes in this backup and its differential backups.
+    #[serde(skip_serializing_if = "HashAlgorithm::is_default")]
+    pub hash_algorithm: HashAlgorithm,
     pub files: BTreeMap<String, IndividualMappingFile>,
     pub registry: IndividualMappingRegistry,
+    /// Empty folders to recreate during restore,
+    /// if recorded with `--preserve-empty-dirs`.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub empty_dirs: BTreeSet<String>,
     pub children: VecDeque<DifferentialBackup>,
 }
 
@@ -284,6 +431,11 @@ pub struct DifferentialBackup {
     pub locked: bool,
     pub files: BTreeMap<String, Option<IndividualMappingFile>>,
     pub registry: Option<IndividualMappingRegistry>,
+    /// Empty folders to recreate during restore,
+    /// if recorded with `--preserve-empty-dirs`.
+    /// Unlike the files, this is the complete list rather than changes from the full backup.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub empty_dirs: BTreeSet<String>,
 }
 
 impl DifferentialBackup {
@@ -322,6 +474,21 @@ impl DifferentialBackup {
 pub struct IndividualMappingFile {
     pub hash: String,
     pub size: u64,
+    /// Unix permission bits, if recorded with `--preserve-permissions`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<u32>,
+    /// If the file was a symbolic link, then this is where it pointed,
+    /// if recorded with `--preserve-permissions`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symlink: Option<String>,
+    /// Whether the content is stored in the game's `blobs` folder (keyed by `hash`)
+    /// instead of in the backup's own folder.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub blob: bool,
+    /// If the file came from a save location for a different operating system
+    /// (with `--include-other-os-paths`), then this is that operating system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<Os>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -350,7 +517,7 @@ impl IndividualMapping {
         self.drives.iter().map(|(k, v)| (v.to_owned(), k.to_owned())).collect()
     }
 
-    fn new_drive_folder_name(drive: &str) -> String {
+    pub fn new_drive_folder_name(drive: &str) -> String {
         if drive.is_empty() {
             "drive-0".to_string()
         } else {
@@ -397,6 +564,56 @@ impl IndividualMapping {
         )
     }
 
+    pub fn blob_file(&self, base: &StrictPath, hash: &str) -> StrictPath {
+        StrictPath::relative(format!("{}/{}", BLOBS, hash), base.interpret().ok())
+    }
+
+    /// Where a file's content is stored for a simple backup.
+    fn stored_file(
+        &self,
+        base: &StrictPath,
+        original_file: &StrictPath,
+        backup: &str,
+        entry: &IndividualMappingFile,
+    ) -> StrictPath {
+        if entry.blob {
+            self.blob_file(base, &entry.hash)
+        } else {
+            self.game_file_immutable(base, original_file, backup)
+        }
+    }
+
+    fn referenced_blobs(&self) -> HashSet<&str> {
+        self.backups
+            .iter()
+            .flat_map(|full| {
+                full.files
+                    .values()
+                    .chain(full.children.iter().flat_map(|diff| diff.files.values().flatten()))
+            })
+            .filter(|x| x.blob)
+            .map(|x| x.hash.as_str())
+            .collect()
+    }
+
+    /// Blobs that are no longer used by any backup.
+    pub fn irrelevant_blobs(&self, base: &StrictPath) -> Vec<StrictPath> {
+        let Ok(blobs) = base.joined(BLOBS).interpret() else {
+            return vec![];
+        };
+        let referenced = self.referenced_blobs();
+
+        walkdir::WalkDir::new(blobs)
+            .min_depth(1)
+            .max_depth(1)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(crate::scan::filter_map_walkdir)
+            .filter(|x| !referenced.contains(&*x.file_name().to_string_lossy()))
+            .map(|x| StrictPath::from(&x))
+            .collect()
+    }
+
     fn game_file_for_zip(&mut self, original_file: &StrictPath) -> String {
         let (drive, plain_path) = original_file.split_drive();
         let drive_folder = self.drive_folder_name(&drive);
@@ -506,6 +723,25 @@ impl IndividualMapping {
             if name.starts_with("backup-") && !relevant.clone().any(|x| x == name) {
                 irrelevant.push(StrictPath::from(&child));
             }
+            if is_date_partition(&name, 4) && child.file_type().is_dir() {
+                for nested in walkdir::WalkDir::new(child.path())
+                    .min_depth(2)
+                    .max_depth(2)
+                    .follow_links(false)
+                    .into_iter()
+                    .filter_entry(|x| x.depth() != 1 || is_date_partition(&x.file_name().to_string_lossy(), 2))
+                    .filter_map(crate::scan::filter_map_walkdir)
+                {
+                    let nested_name = nested.file_name().to_string_lossy();
+                    let Some(month) = nested.path().parent().and_then(|x| x.file_name()) else {
+                        continue;
+                    };
+                    let qualified = format!("{}/{}/{}", name, month.to_string_lossy(), nested_name);
+                    if nested_name.starts_with("backup-") && !relevant.clone().any(|x| x == qualified) {
+                        irrelevant.push(StrictPath::from(&nested));
+                    }
+                }
+            }
         }
 
         irrelevant
@@ -592,6 +828,17 @@ impl GameLayout {
         }
     }
 
+    /// File path must be in rendered form.
+    fn find_mapping_file(&self, id: &BackupId, file: &str) -> Option<&IndividualMappingFile> {
+        match self.find_by_id(id)? {
+            (full, None) => full.files.get(file),
+            (full, Some(diff)) => match diff.files.get(file) {
+                Some(info) => info.as_ref(),
+                None => full.files.get(file),
+            },
+        }
+    }
+
     pub fn find_by_id_flattened(&self, id: &BackupId) -> Option<Backup> {
         match self.find_by_id(id) {
             None => None,
@@ -602,23 +849,31 @@ impl GameLayout {
 
     /// When `restoring` is false, we don't check for entries' ScanChange,
     /// because the backup scan will do that separately.
-    pub fn latest_backup(
+    pub fn backup_scan(
         &self,
+        id: &BackupId,
         restoring: bool,
         redirects: &[RedirectConfig],
         toggled_paths: &ToggledPaths,
     ) -> Option<ScanInfo> {
-        if self.mapping.backups.is_empty() {
+        if self.find_by_id(id).is_none() {
             None
         } else {
             Some(ScanInfo {
                 game_name: self.mapping.name.clone(),
-                found_files: self.restorable_files(&BackupId::Latest, restoring, redirects, toggled_paths),
+                found_files: self.restorable_files(id, restoring, redirects, toggled_paths),
                 // Registry is handled separately.
                 found_registry_keys: Default::default(),
+                found_empty_dirs: Default::default(),
                 available_backups: vec![],
                 backup: None,
                 has_backups: true,
+                globally_ignored: 0,
+                system_files_excluded: 0,
+                preserved_registry_values: Default::default(),
+                other_os_files: Default::default(),
+                save_roots: Default::default(),
+                only_registry: false,
             })
         }
     }
@@ -651,7 +906,13 @@ impl GameLayout {
                 files.extend(self.restorable_files_from_full_backup(full, restoring, redirects, toggled_paths));
             }
             Some((full, Some(diff))) => {
-                files.extend(self.restorable_files_from_diff_backup(diff, restoring, redirects, toggled_paths));
+                files.extend(self.restorable_files_from_diff_backup(
+                    diff,
+                    full.hash_algorithm,
+                    restoring,
+                    redirects,
+                    toggled_paths,
+                ));
 
                 for full_file in self.restorable_files_from_full_backup(full, restoring, redirects, toggled_paths) {
                     let original_path = full_file.original_path.as_ref().unwrap().render();
@@ -679,16 +940,18 @@ impl GameLayout {
             let redirected = game_file_target(&original_path, redirects, true);
             let ignorable_path = redirected.as_ref().unwrap_or(&original_path);
             match backup.format() {
-                BackupFormat::Simple => {
+                BackupFormat::Simple | BackupFormat::Git => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            ScanChange::evaluate_restore(
+                                redirected.as_ref().unwrap_or(&original_path),
+                                &v.hash,
+                                backup.hash_algorithm,
+                            )
                         } else {
                             ScanChange::Unknown
                         },
-                        path: self
-                            .mapping
-                            .game_file_immutable(&self.path, &original_path, &backup.name),
+                        path: self.mapping.stored_file(&self.path, &original_path, &backup.name, v),
                         size: v.size,
                         hash: v.hash.clone(),
                         ignored: toggled_paths.is_ignored(&self.mapping.name, ignorable_path),
@@ -700,7 +963,11 @@ impl GameLayout {
                 BackupFormat::Zip => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            ScanChange::evaluate_restore(
+                                redirected.as_ref().unwrap_or(&original_path),
+                                &v.hash,
+                                backup.hash_algorithm,
+                            )
                         } else {
                             ScanChange::Unknown
                         },
@@ -722,6 +989,7 @@ impl GameLayout {
     fn restorable_files_from_diff_backup(
         &self,
         backup: &DifferentialBackup,
+        hash_algorithm: HashAlgorithm,
         restoring: bool,
         redirects: &[RedirectConfig],
         toggled_paths: &ToggledPaths,
@@ -734,16 +1002,18 @@ impl GameLayout {
             let redirected = game_file_target(&original_path, redirects, true);
             let ignorable_path = redirected.as_ref().unwrap_or(&original_path);
             match backup.format() {
-                BackupFormat::Simple => {
+                BackupFormat::Simple | BackupFormat::Git => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            ScanChange::evaluate_restore(
+                                redirected.as_ref().unwrap_or(&original_path),
+                                &v.hash,
+                                hash_algorithm,
+                            )
                         } else {
                             ScanChange::Unknown
                         },
-                        path: self
-                            .mapping
-                            .game_file_immutable(&self.path, &original_path, &backup.name),
+                        path: self.mapping.stored_file(&self.path, &original_path, &backup.name, v),
                         size: v.size,
                         hash: v.hash.clone(),
                         ignored: toggled_paths.is_ignored(&self.mapping.name, ignorable_path),
@@ -755,7 +1025,11 @@ impl GameLayout {
                 BackupFormat::Zip => {
                     restorables.insert(ScannedFile {
                         change: if restoring {
-                            ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
+                            ScanChange::evaluate_restore(
+                                redirected.as_ref().unwrap_or(&original_path),
+                                &v.hash,
+                                hash_algorithm,
+                            )
                         } else {
                             ScanChange::Unknown
                         },
@@ -837,7 +1111,7 @@ impl GameLayout {
 
     fn registry_content_in(&self, backup: &str, format: &BackupFormat) -> Option<String> {
         match format {
-            BackupFormat::Simple => self.path.joined(backup).joined("registry.yaml").read(),
+            BackupFormat::Simple | BackupFormat::Git => self.path.joined(backup).joined("registry.yaml").read(),
             BackupFormat::Zip => {
                 let handle = self.path.joined(backup).open().ok()?;
                 let mut archive = zip::ZipArchive::new(handle).ok()?;
@@ -872,7 +1146,17 @@ impl GameLayout {
         self.path.joined(backup).joined("registry.yaml")
     }
 
-    fn generate_file_friendly_timestamp(now: &chrono::DateTime<chrono::Utc>) -> String {
+    /// Names of the existing backups, without any date partition folders.
+    fn backup_names(&self) -> HashSet<String> {
+        self.mapping
+            .backups
+            .iter()
+            .flat_map(|full| std::iter::once(&full.name).chain(full.children.iter().map(|diff| &diff.name)))
+            .map(|name| name.rsplit('/').next().unwrap_or(name).to_string())
+            .collect()
+    }
+
+    pub fn generate_file_friendly_timestamp(now: &chrono::DateTime<chrono::Utc>) -> String {
         format!(
             "{}{:02}{:02}T{:02}{:02}{:02}Z",
             now.year(),
@@ -892,7 +1176,7 @@ impl GameLayout {
     ) -> String {
         if *kind == BackupKind::Full
             && self.retention.full == 1
-            && format.chosen == BackupFormat::Simple
+            && matches!(format.chosen, BackupFormat::Simple | BackupFormat::Git)
             && self.mapping.backups.iter().all(|x| !x.locked)
         {
             ".".to_string()
@@ -903,7 +1187,7 @@ impl GameLayout {
                 BackupKind::Differential => format!("backup-{}-diff", timestamp),
             };
             match format.chosen {
-                BackupFormat::Simple => name,
+                BackupFormat::Simple | BackupFormat::Git => name,
                 BackupFormat::Zip => format!("{name}.zip"),
             }
         }
@@ -914,15 +1198,23 @@ impl GameLayout {
         scan: &ScanInfo,
         now: &chrono::DateTime<chrono::Utc>,
         format: &BackupFormats,
+        hash_algorithm: HashAlgorithm,
     ) -> Option<Backup> {
         if !scan.found_anything_processable() && !self.retention.force_new_full {
             return None;
         }
 
-        let kind = self.plan_backup_kind();
+        // A differential backup must use the same hashes as its full backup.
+        // A registry-only scan has no files, so it builds on the latest full backup
+        // rather than recording a new one that would rotate out the real ones.
+        let kind = match self.mapping.backups.back() {
+            Some(_) if scan.only_registry => BackupKind::Differential,
+            Some(full) if full.hash_algorithm != hash_algorithm => BackupKind::Full,
+            _ => self.plan_backup_kind(),
+        };
 
         let backup = match kind {
-            BackupKind::Full => Backup::Full(self.plan_full_backup(scan, now, format)),
+            BackupKind::Full => Backup::Full(self.plan_full_backup(scan, now, format, hash_algorithm)),
             BackupKind::Differential => Backup::Differential(self.plan_differential_backup(scan, now, format)),
         };
 
@@ -956,6 +1248,7 @@ impl GameLayout {
         scan: &ScanInfo,
         now: &chrono::DateTime<chrono::Utc>,
         format: &BackupFormats,
+        hash_algorithm: HashAlgorithm,
     ) -> FullBackup {
         let mut files = BTreeMap::new();
         #[allow(unused_mut)]
@@ -969,6 +1262,7 @@ impl GameLayout {
                         IndividualMappingFile {
                             hash: file.hash.clone(),
                             size: file.size,
+                            ..Default::default()
                         },
                     );
                 }
@@ -990,8 +1284,10 @@ impl GameLayout {
             os: Some(Os::HOST),
             comment: None,
             locked: false,
+            hash_algorithm,
             files,
             registry,
+            empty_dirs: scan.found_empty_dirs.iter().map(|x| x.render()).collect(),
             children: VecDeque::new(),
         }
     }
@@ -1014,6 +1310,7 @@ impl GameLayout {
                         (!file.ignored).then(|| IndividualMappingFile {
                             hash: file.hash.clone(),
                             size: file.size,
+                            ..Default::default()
                         }),
                     );
                 }
@@ -1043,7 +1340,7 @@ impl GameLayout {
                     if Some(&prior.hash) == current.as_ref().map(|x| &x.hash) {
                         files.remove(file);
                     }
-                } else {
+                } else if !scan.only_registry {
                     files.insert(file.clone(), None);
                 }
             }
@@ -1062,6 +1359,7 @@ impl GameLayout {
             locked: false,
             files,
             registry,
+            empty_dirs: scan.found_empty_dirs.iter().map(|x| x.render()).collect(),
         }
     }
 
@@ -1075,6 +1373,40 @@ impl GameLayout {
                 continue;
             }
 
+            if let Some(entry) = backup.file_entry(&file.mapping_key()).filter(|x| x.blob) {
+                let target_file = self.mapping.blob_file(&self.path, &entry.hash);
+                if target_file.is_file() && target_file.size() == file.size {
+                    log::info!(
+                        "[{}] already stored: {:?} -> {:?}",
+                        self.mapping.name,
+                        &file.path,
+                        &target_file
+                    );
+                    continue;
+                }
+                // Copy to a temporary file first, so that an interrupted copy
+                // can't leave behind a partial blob that a later backup would reuse.
+                let temp_file = self.mapping.blob_file(&self.path, &format!("{}.tmp", &entry.hash));
+                if let Err(e) = file
+                    .path
+                    .copy_to_path(&self.mapping.name, &temp_file)
+                    .and_then(|_| temp_file.move_to(&target_file))
+                {
+                    let _ = temp_file.remove();
+                    backup_info
+                        .failed_files
+                        .insert(file.clone(), BackupError::Raw(e.to_string()));
+                    continue;
+                }
+                log::info!(
+                    "[{}] backed up: {:?} -> {:?}",
+                    self.mapping.name,
+                    file.path,
+                    target_file
+                );
+                continue;
+            }
+
             let target_file = self.mapping.game_file(&self.path, file.effective(), backup.name());
             if file.path.same_content(&target_file) {
                 log::info!(
@@ -1139,7 +1471,7 @@ impl GameLayout {
         };
 
         let archive_path = self.path.joined(backup.name());
-        let archive_file = match archive_path.create() {
+        let archive_file = match archive_path.create_parent_dir().and_then(|_| archive_path.create()) {
             Ok(x) => x,
             Err(e) => {
                 log::error!(
@@ -1152,6 +1484,7 @@ impl GameLayout {
             }
         };
         let mut zip = zip::ZipWriter::new(archive_file);
+        let stored = format.zip.compression == ZipCompression::None;
         let options = zip::write::FileOptions::default()
             .compression_method(match format.zip.compression {
                 ZipCompression::None => zip::CompressionMethod::Stored,
@@ -1209,7 +1542,7 @@ impl GameLayout {
             }
 
             use std::io::Read;
-            let handle = match file.path.open() {
+            let mut handle = match file.path.open() {
                 Ok(x) => x,
                 Err(e) => {
                     log::error!("[{}] unable to open source: {:?} | {e}", self.mapping.name, &file.path);
@@ -1217,6 +1550,32 @@ impl GameLayout {
                     continue;
                 }
             };
+
+            // Without compression, there's no encoder to feed in small chunks,
+            // so we can hand the whole file to the zip writer at once.
+            if stored {
+                match std::io::copy(&mut handle, &mut zip) {
+                    Ok(_) => {
+                        log::info!(
+                            "[{}] backed up: {:?} -> {}",
+                            self.mapping.name,
+                            &file.path,
+                            &target_file_id
+                        );
+                    }
+                    Err(e) => {
+                        log::error!(
+                            "[{}] unable to copy source to target: {:?} -> {} | {e}",
+                            self.mapping.name,
+                            &file.path,
+                            &target_file_id
+                        );
+                        fail_file(file, &mut backup_info, e.to_string());
+                    }
+                }
+                continue;
+            }
+
             let mut reader = std::io::BufReader::new(handle);
             let mut buffer = [0; 1024];
 
@@ -1341,12 +1700,26 @@ impl GameLayout {
             BackupInfo::default()
         } else {
             match format.chosen {
-                BackupFormat::Simple => self.execute_backup_as_simple(backup, scan),
+                BackupFormat::Simple | BackupFormat::Git => self.execute_backup_as_simple(backup, scan),
                 BackupFormat::Zip => self.execute_backup_as_zip(backup, scan, format),
             }
         }
     }
 
+    fn prune_irrelevant_blobs(&self) {
+        for irrelevant_blob in self.mapping.irrelevant_blobs(&self.path) {
+            log::debug!(
+                "[{}] Removing irrelevant blob: {:?}",
+                &self.mapping.name,
+                &irrelevant_blob
+            );
+            let _ = irrelevant_blob.remove();
+        }
+
+        // This only succeeds if the folder is empty.
+        let _ = self.path.joined(BLOBS).as_std_path_buf().and_then(std::fs::remove_dir);
+    }
+
     fn prune_irrelevant_parents(&self) {
         for irrelevant_parent in self.mapping.irrelevant_parents(&self.path) {
             log::debug!(
@@ -1355,6 +1728,21 @@ impl GameLayout {
                 &irrelevant_parent
             );
             let _ = irrelevant_parent.remove();
+
+            // Clean up any date partitions that are now empty.
+            let month = irrelevant_parent.popped();
+            let year = month.popped();
+            if year.popped().equivalent(&self.path)
+                && month.leaf().is_some_and(|x| is_date_partition(&x, 2))
+                && year.leaf().is_some_and(|x| is_date_partition(&x, 4))
+            {
+                for partition in [month, year] {
+                    // This only succeeds if the folder is empty.
+                    if partition.as_std_path_buf().and_then(std::fs::remove_dir).is_err() {
+                        break;
+                    }
+                }
+            }
         }
     }
 
@@ -1385,6 +1773,7 @@ impl GameLayout {
                 IndividualMappingFile {
                     hash: file.path.sha1(),
                     size: file.path.size(),
+                    ..Default::default()
                 },
             );
         }
@@ -1429,6 +1818,7 @@ impl GameLayout {
             locked,
             files,
             registry,
+            empty_dirs,
         } = initial.children.pop_front()?;
 
         initial.name = name;
@@ -1440,6 +1830,7 @@ impl GameLayout {
         if let Some(registry) = registry {
             initial.registry = registry;
         }
+        initial.empty_dirs = empty_dirs;
 
         if save {
             self.save();
@@ -1448,15 +1839,27 @@ impl GameLayout {
         Some(())
     }
 
+    /// When `preserve_permissions` is set, we record each file's Unix mode
+    /// and symbolic link target so that they can be reapplied on restore.
+    ///
+    /// If any entries fail, then we try again up to `retries` times,
+    /// writing into the same backup rather than planning a new one.
+    /// Also returns whether the backup only succeeded because of a retry.
     pub fn back_up(
         &mut self,
         scan: &ScanInfo,
         now: &chrono::DateTime<chrono::Utc>,
         format: &BackupFormats,
-    ) -> BackupInfo {
+        hash_algorithm: HashAlgorithm,
+        preserve_permissions: bool,
+        layout_style: BackupLayoutStyle,
+        deduplicate: bool,
+        id_format: Option<&str>,
+        retries: u8,
+    ) -> (BackupInfo, bool) {
         if !scan.found_anything() {
             log::trace!("[{}] nothing to back up", &scan.game_name);
-            return BackupInfo::default();
+            return (BackupInfo::default(), false);
         }
 
         log::trace!("[{}] preparing for backup", &scan.game_name);
@@ -1466,14 +1869,20 @@ impl GameLayout {
                 scan.game_name,
                 &self.path
             );
-            return BackupInfo::total_failure(scan, BackupError::App(e));
+            return (BackupInfo::total_failure(scan, BackupError::App(e)), false);
+        }
+
+        // Git keeps the history, so we only need to update one backup in place.
+        if format.chosen == BackupFormat::Git {
+            self.retention.full = 1;
+            self.retention.differential = 0;
         }
 
         self.migrate_backups(true);
-        match self.plan_backup(scan, now, format) {
+        match self.plan_backup(scan, now, format, hash_algorithm) {
             None => {
                 log::info!("[{}] no need for new backup", &scan.game_name);
-                BackupInfo::default()
+                (BackupInfo::default(), false)
             }
             Some(mut backup) => {
                 log::info!(
@@ -1482,7 +1891,30 @@ impl GameLayout {
                     backup.kind(),
                     backup.name()
                 );
-                let backup_info = self.execute_backup(&backup, scan, format);
+                if preserve_permissions {
+                    backup.record_permissions(scan);
+                }
+                backup.record_other_os(scan);
+                if let Some(id_format) = id_format {
+                    backup.apply_id_format(id_format, &self.backup_names());
+                }
+                if layout_style == BackupLayoutStyle::DatePartitioned {
+                    backup.partition_by_date();
+                }
+                if deduplicate {
+                    backup.store_as_blobs();
+                }
+                let mut backup_info = self.execute_backup(&backup, scan, format);
+                let mut attempts = 0;
+                while !backup_info.successful() && attempts < retries {
+                    attempts += 1;
+                    log::info!(
+                        "[{}] retrying failed entries, attempt {attempts} of {retries}",
+                        &scan.game_name
+                    );
+                    backup_info = self.execute_backup(&backup, scan, format);
+                }
+                let retried = attempts > 0 && backup_info.successful();
                 backup.prune_failures(&backup_info);
                 if backup.needed() {
                     self.insert_backup(backup.clone());
@@ -1490,7 +1922,35 @@ impl GameLayout {
                     self.save();
                 }
                 self.prune_irrelevant_parents();
-                backup_info
+                self.prune_irrelevant_blobs();
+                if format.chosen == BackupFormat::Git {
+                    if let Err(e) = self.commit_to_git(&scan.game_name, now) {
+                        // The files are in place, but they aren't part of the repository's history.
+                        let mut failure = BackupInfo::total_failure(scan, BackupError::App(e));
+                        failure.failed_files.extend(backup_info.failed_files);
+                        failure.failed_registry.extend(backup_info.failed_registry);
+                        return (failure, false);
+                    }
+                }
+                (backup_info, retried)
+            }
+        }
+    }
+
+    fn commit_to_git(&self, game: &str, now: &chrono::DateTime<chrono::Utc>) -> Result<(), Error> {
+        let repo = self.path.popped();
+        match git::commit(&repo, &self.path, game, now) {
+            Ok(true) => {
+                log::info!("[{game}] committed backup to Git repository: {:?}", &repo);
+                Ok(())
+            }
+            Ok(false) => Ok(()),
+            Err(e) => {
+                log::error!(
+                    "[{game}] unable to commit backup to Git repository: {:?} | {e:?}",
+                    &repo
+                );
+                Err(Error::GitCommandFailed(e))
             }
         }
     }
@@ -1506,6 +1966,66 @@ impl GameLayout {
         available_backups
     }
 
+    /// Prepare the latest backup for the `export` command.
+    /// This returns a mapping with only that backup (and its full backup, if it is differential),
+    /// along with the files that it needs, relative to the game's backup folder.
+    pub fn plan_export(&self) -> Option<(IndividualMapping, Vec<String>)> {
+        let (full, diff) = self.mapping.latest_backup()?;
+
+        let mut files = BTreeSet::new();
+        let mut add_backup =
+            |name: &str, format: BackupFormat, stored: Vec<(&String, &IndividualMappingFile)>| match format {
+                BackupFormat::Simple | BackupFormat::Git => {
+                    for (file, data) in stored {
+                        if data.blob {
+                            files.insert(format!("{}/{}", BLOBS, data.hash));
+                            continue;
+                        }
+                        let (drive, plain_path) = StrictPath::new(file.to_string()).split_drive();
+                        let drive_folder = self.mapping.drive_folder_name_immutable(&drive);
+                        files.insert(Self::relative_export_path(&format!(
+                            "{name}/{drive_folder}/{plain_path}"
+                        )));
+                    }
+
+                    let registry = Self::relative_export_path(&format!("{name}/registry.yaml"));
+                    if self.path.joined(&registry).is_file() {
+                        files.insert(registry);
+                    }
+                }
+                BackupFormat::Zip => {
+                    files.insert(name.to_string());
+                }
+            };
+
+        add_backup(&full.name, full.format(), full.files.iter().collect());
+        if let Some(diff) = diff {
+            add_backup(
+                &diff.name,
+                diff.format(),
+                diff.files
+                    .iter()
+                    .filter_map(|(file, data)| data.as_ref().map(|data| (file, data)))
+                    .collect(),
+            );
+        }
+
+        let mapping = IndividualMapping {
+            name: self.mapping.name.clone(),
+            drives: self.mapping.drives.clone(),
+            backups: VecDeque::from(vec![FullBackup {
+                children: diff.into_iter().cloned().collect(),
+                ..full.clone()
+            }]),
+        };
+
+        Some((mapping, files.into_iter().collect()))
+    }
+
+    fn relative_export_path(path: &str) -> String {
+        path.replace('\\', "/").trim_start_matches("./").to_string()
+    }
+
     pub fn has_backups(&self) -> bool {
         !self.mapping.backups.is_empty()
     }
@@ -1588,6 +2108,35 @@ impl GameLayout {
             }
         }
 
+        let found_empty_dirs = backup
+            .as_ref()
+            .map(|backup| {
+                backup
+                    .empty_dirs()
+                    .iter()
+                    .map(|dir| {
+                        let original = StrictPath::new(dir.clone());
+                        game_file_target(&original, redirects, true).unwrap_or(original)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let other_os_files = backup
+            .as_ref()
+            .map(|backup| {
+                found_files
+                    .iter()
+                    .filter_map(|file: &ScannedFile| {
+                        let os = self
+                            .find_mapping_file(&backup.id(), &file.original_path().render())?
+                            .os?;
+                        Some((file.original_path().clone(), os))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let has_backups = !available_backups.is_empty();
 
         log::trace!("[{name}] completed scan for restore");
@@ -1596,13 +2145,28 @@ impl GameLayout {
             game_name: name.to_string(),
             found_files,
             found_registry_keys,
+            found_empty_dirs,
             available_backups,
             backup,
             has_backups,
+            globally_ignored: 0,
+            system_files_excluded: 0,
+            preserved_registry_values: Default::default(),
+            other_os_files,
+            save_roots: Default::default(),
+            only_registry: false,
         }
     }
 
-    pub fn restore(&self, scan: &ScanInfo, #[allow(unused)] toggled: &ToggledRegistry) -> BackupInfo {
+    /// When `preserve_permissions` is set, we reapply any recorded Unix modes
+    /// and recreate symbolic links instead of copying their content.
+    /// This has no effect on Windows.
+    pub fn restore(
+        &self,
+        scan: &ScanInfo,
+        #[allow(unused)] toggled: &ToggledRegistry,
+        preserve_permissions: bool,
+    ) -> BackupInfo {
         log::trace!("[{}] beginning restore", &scan.game_name);
 
         let mut failed_files = HashMap::new();
@@ -1673,15 +2237,32 @@ impl GameLayout {
                 }
             }
 
-            let outcome = match &file.container {
-                None => self.restore_file_from_simple(target, file),
-                Some(container) => {
+            let permissions = if preserve_permissions && cfg!(not(target_os = "windows")) {
+                scan.backup
+                    .as_ref()
+                    .and_then(|backup| self.find_mapping_file(&backup.id(), &file.original_path().render()))
+            } else {
+                None
+            };
+
+            let outcome = match (permissions.and_then(|x| x.symlink.as_ref()), &file.container) {
+                (Some(link), _) => self.restore_file_as_symlink(target, file, link),
+                (None, None) => self.restore_file_from_simple(target, file),
+                (None, Some(container)) => {
                     let Some(archive) = containers.get_mut(container) else {
                         continue;
                     };
                     self.restore_file_from_zip(target, file, archive)
                 }
             };
+            let outcome = match permissions {
+                Some(IndividualMappingFile {
+                    mode: Some(mode),
+                    symlink: None,
+                    ..
+                }) => outcome.and_then(|_| target.set_unix_mode(*mode)),
+                _ => outcome,
+            };
 
             match outcome {
                 Ok(_) => {
@@ -1699,6 +2280,20 @@ impl GameLayout {
             }
         }
 
+        for dir in &scan.found_empty_dirs {
+            if dir.is_dir() {
+                continue;
+            }
+            match dir.create_dirs() {
+                Ok(_) => log::info!("[{}] recreated empty folder: {:?}", &self.mapping.name, dir),
+                Err(e) => log::error!(
+                    "[{}] failed to recreate empty folder: {:?} | {e}",
+                    &self.mapping.name,
+                    dir
+                ),
+            }
+        }
+
         #[cfg(target_os = "windows")]
         {
             use crate::scan::registry::Hives;
@@ -1722,6 +2317,19 @@ impl GameLayout {
         }
     }
 
+    fn restore_file_as_symlink(&self, target: &StrictPath, file: &ScannedFile, link: &str) -> Result<(), AnyError> {
+        log::trace!(
+            "[{}] about to restore (symlink): {:?} -> {:?} -> {}",
+            self.mapping.name,
+            &file.path,
+            &target,
+            link
+        );
+
+        target.create_parent_dir()?;
+        target.create_symlink(link)
+    }
+
     fn restore_file_from_simple(&self, target: &StrictPath, file: &ScannedFile) -> Result<(), AnyError> {
         log::trace!(
             "[{}] about to restore (simple): {:?} -> {:?}",
@@ -1894,12 +2502,10 @@ impl GameLayout {
     pub fn validate(&self, backup_id: BackupId) -> bool {
         if let Some((backup, diff)) = self.find_by_id(&backup_id) {
             match backup.format() {
-                BackupFormat::Simple => {
-                    for file in backup.files.keys() {
+                BackupFormat::Simple | BackupFormat::Git => {
+                    for (file, data) in &backup.files {
                         let original_path = StrictPath::new(file.to_string());
-                        let stored = self
-                            .mapping
-                            .game_file_immutable(&self.path, &original_path, &backup.name);
+                        let stored = self.mapping.stored_file(&self.path, &original_path, &backup.name, data);
                         if !stored.is_file() {
                             #[cfg(test)]
                             eprintln!("can't find {}", stored.render());
@@ -1929,17 +2535,15 @@ impl GameLayout {
 
             if let Some(backup) = diff {
                 match backup.format() {
-                    BackupFormat::Simple => {
+                    BackupFormat::Simple | BackupFormat::Git => {
                         for (file, data) in &backup.files {
-                            if data.is_none() {
+                            let Some(data) = data else {
                                 // File is deliberately omitted.
                                 continue;
-                            }
+                            };
 
                             let original_path = StrictPath::new(file.to_string());
-                            let stored = self
-                                .mapping
-                                .game_file_immutable(&self.path, &original_path, &backup.name);
+                            let stored = self.mapping.stored_file(&self.path, &original_path, &backup.name, data);
                             if !stored.is_file() {
                                 #[cfg(test)]
                                 eprintln!("can't find {}", stored.render());
@@ -1991,6 +2595,7 @@ pub struct BackupLayout {
     games: HashMap<String, StrictPath>,
     games_lowercase: HashMap<String, StrictPath>,
     retention: Retention,
+    per_game_retention: BTreeMap<String, GameRetention>,
 }
 
 impl BackupLayout {
@@ -2002,7 +2607,22 @@ impl BackupLayout {
             games,
             games_lowercase,
             retention,
+            per_game_retention: Default::default(),
+        }
+    }
+
+    /// Use different retention limits for specific games.
+    pub fn with_per_game_retention(mut self, per_game_retention: BTreeMap<String, GameRetention>) -> Self {
+        self.per_game_retention = per_game_retention;
+        self
+    }
+
+    fn retention_for_game(&self, name: &str) -> Retention {
+        let mut retention = self.retention.clone();
+        if let Some(overrides) = self.per_game_retention.get(name) {
+            overrides.apply(&mut retention);
         }
+        retention
     }
 
     pub fn load(base: &StrictPath) -> HashMap<String, StrictPath> {
@@ -2040,7 +2660,7 @@ impl BackupLayout {
     pub fn game_layout(&self, name: &str) -> GameLayout {
         let path = self.game_folder(name);
 
-        match GameLayout::load(path.clone(), self.retention.clone()) {
+        match GameLayout::load(path.clone(), self.retention_for_game(name)) {
             Ok(mut x) => {
                 if x.mapping.name != name {
                     // This can happen if the game name changed in the manifest,
@@ -2054,7 +2674,7 @@ impl BackupLayout {
             Err(_) => GameLayout {
                 path,
                 mapping: IndividualMapping::new(name.to_string()),
-                retention: self.retention.clone(),
+                retention: self.retention_for_game(name),
             },
         }
     }
@@ -2062,7 +2682,7 @@ impl BackupLayout {
     pub fn try_game_layout(&self, name: &str) -> Option<GameLayout> {
         let path = self.game_folder(name);
 
-        GameLayout::load(path, self.retention.clone()).ok().map(|mut x| {
+        GameLayout::load(path, self.retention_for_game(name)).ok().map(|mut x| {
             if x.mapping.name != name {
                 // This can happen if the game name changed in the manifest,
                 // but differs only by capitalization when we're on a case-insensitive OS.
@@ -2085,14 +2705,25 @@ impl BackupLayout {
         restoring: bool,
         redirects: &[RedirectConfig],
         toggled_paths: &ToggledPaths,
+    ) -> Option<LatestBackup> {
+        self.backup(name, &BackupId::Latest, restoring, redirects, toggled_paths)
+    }
+
+    pub fn backup(
+        &self,
+        name: &str,
+        id: &BackupId,
+        restoring: bool,
+        redirects: &[RedirectConfig],
+        toggled_paths: &ToggledPaths,
     ) -> Option<LatestBackup> {
         if self.contains_game(name) {
             let game_layout = self.game_layout(name);
-            let scan = game_layout.latest_backup(restoring, redirects, toggled_paths);
+            let scan = game_layout.backup_scan(id, restoring, redirects, toggled_paths);
             scan.map(|scan| LatestBackup {
                 scan,
                 registry_content: if cfg!(target_os = "windows") {
-                    game_layout.registry_content(&BackupId::Latest)
+                    game_layout.registry_content(id)
                 } else {
                     None
                 },
@@ -2122,6 +2753,14 @@ impl BackupLayout {
         }
     }
 
+    /// Find which game is stored in a folder directly under the backup path.
+    pub fn game_in_folder(&self, folder: &str) -> Option<String> {
+        self.games
+            .iter()
+            .find(|(_, path)| path.leaf().as_deref() == Some(folder))
+            .map(|(name, _)| name.clone())
+    }
+
     pub fn restorable_games(&self) -> Vec<String> {
         self.games.keys().cloned().collect()
     }
@@ -2195,6 +2834,45 @@ mod tests {
             );
         }
 
+        #[test]
+        fn can_apply_per_game_retention() {
+            let layout = layout().with_per_game_retention(btree_map! {
+                s("game1"): GameRetention {
+                    full: Some(5),
+                    differential: None,
+                },
+                s("nonexistent"): GameRetention {
+                    full: None,
+                    differential: Some(3),
+                },
+            });
+
+            assert_eq!(
+                Retention {
+                    full: 5,
+                    differential: 0,
+                    force_new_full: false,
+                },
+                layout.game_layout("game1").retention
+            );
+            assert_eq!(
+                Retention {
+                    full: 1,
+                    differential: 3,
+                    force_new_full: false,
+                },
+                layout.game_layout("nonexistent").retention
+            );
+            assert_eq!(Retention::default(), layout.game_layout("game3").retention);
+        }
+
+        #[test]
+        fn can_find_game_in_folder() {
+            assert_eq!(Some(s("game1")), layout().game_in_folder("game1"));
+            assert_eq!(Some(s("game3")), layout().game_in_folder("game3-renamed"));
+            assert_eq!(None, layout().game_in_folder("nonexistent"));
+        }
+
         #[test]
         fn can_determine_game_folder_that_does_not_exist_without_rename() {
             assert_eq!(
@@ -2276,23 +2954,189 @@ mod tests {
         }
 
         #[test]
-        fn can_plan_backup_when_empty() {
-            let scan = ScanInfo::default();
-            let layout = GameLayout {
-                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
-                mapping: IndividualMapping::new("game1".to_string()),
-                retention: Retention::default(),
-            };
-            assert_eq!(None, layout.plan_backup(&scan, &now(), &BackupFormats::default()));
-        }
-
-        #[test]
-        fn can_plan_backup_kind_when_first_time() {
-            let layout = GameLayout::default();
-            assert_eq!(BackupKind::Full, layout.plan_backup_kind());
-        }
+        fn can_partition_backup_by_date() {
+            let mut backup = Backup::Full(FullBackup {
+                name: format!("backup-{}", now_str()),
+                when: now(),
+                ..Default::default()
+            });
+            backup.partition_by_date();
+            assert_eq!(format!("2000/01/backup-{}", now_str()), backup.name());
 
-        #[test]
+            let mut backup = Backup::Differential(DifferentialBackup {
+                name: format!("backup-{}-diff.zip", now_str()),
+                when: now(),
+                ..Default::default()
+            });
+            backup.partition_by_date();
+            assert_eq!(format!("2000/01/backup-{}-diff.zip", now_str()), backup.name());
+
+            let mut backup = Backup::Full(FullBackup {
+                name: ".".to_string(),
+                when: now(),
+                ..Default::default()
+            });
+            backup.partition_by_date();
+            assert_eq!(".", backup.name());
+        }
+
+        #[test]
+        fn can_apply_backup_id_format() {
+            let mut backup = Backup::Full(FullBackup {
+                name: format!("backup-{}", now_str()),
+                when: now(),
+                ..Default::default()
+            });
+            backup.apply_id_format("{date}_{time}_{tag}", &HashSet::new());
+            assert_eq!("backup-2000-01-02_03-04-05_full", backup.name());
+
+            let mut backup = Backup::Differential(DifferentialBackup {
+                name: format!("backup-{}-diff.zip", now_str()),
+                when: now(),
+                ..Default::default()
+            });
+            backup.apply_id_format("{tag}/{date}", &HashSet::new());
+            assert_eq!("backup-diff_2000-01-02.zip", backup.name());
+
+            let mut backup = Backup::Full(FullBackup {
+                name: format!("backup-{}", now_str()),
+                when: now(),
+                ..Default::default()
+            });
+            backup.apply_id_format(
+                "{date}",
+                &hash_set! { s("backup-2000-01-02"), s("backup-2000-01-02-2") },
+            );
+            assert_eq!("backup-2000-01-02-3", backup.name());
+
+            let mut backup = Backup::Full(FullBackup {
+                name: ".".to_string(),
+                when: now(),
+                ..Default::default()
+            });
+            backup.apply_id_format("{date}", &HashSet::new());
+            assert_eq!(".", backup.name());
+        }
+
+        #[test]
+        fn can_store_backup_as_blobs() {
+            let mut backup = Backup::Full(FullBackup {
+                name: format!("backup-{}", now_str()),
+                files: btree_map! {
+                    s("/file1.txt"): IndividualMappingFile { hash: s("a"), size: 1, ..Default::default() },
+                    s("/file2.txt"): IndividualMappingFile { hash: s(""), size: 2, ..Default::default() },
+                },
+                ..Default::default()
+            });
+            backup.store_as_blobs();
+            assert_eq!(
+                Backup::Full(FullBackup {
+                    name: format!("backup-{}", now_str()),
+                    files: btree_map! {
+                        s("/file1.txt"): IndividualMappingFile { hash: s("a"), size: 1, blob: true, ..Default::default() },
+                        s("/file2.txt"): IndividualMappingFile { hash: s(""), size: 2, ..Default::default() },
+                    },
+                    ..Default::default()
+                }),
+                backup,
+            );
+
+            let mut backup = Backup::Differential(DifferentialBackup {
+                name: format!("backup-{}-diff.zip", now_str()),
+                files: btree_map! {
+                    s("/file1.txt"): Some(IndividualMappingFile { hash: s("a"), size: 1, ..Default::default() }),
+                },
+                ..Default::default()
+            });
+            let original = backup.clone();
+            backup.store_as_blobs();
+            assert_eq!(original, backup);
+        }
+
+        #[test]
+        fn can_compute_disk_usage_of_backups() {
+            let backups = vec![
+                Backup::Full(FullBackup {
+                    files: btree_map! {
+                        s("/file1.txt"): IndividualMappingFile { hash: s("a"), size: 1, blob: true, ..Default::default() },
+                        s("/file2.txt"): IndividualMappingFile { hash: s("b"), size: 2, ..Default::default() },
+                    },
+                    ..Default::default()
+                }),
+                Backup::Differential(DifferentialBackup {
+                    files: btree_map! {
+                        s("/file2.txt"): None,
+                        s("/file3.txt"): Some(IndividualMappingFile { hash: s("c"), size: 4, ..Default::default() }),
+                    },
+                    ..Default::default()
+                }),
+                Backup::Full(FullBackup {
+                    files: btree_map! {
+                        s("/file1.txt"): IndividualMappingFile { hash: s("a"), size: 1, blob: true, ..Default::default() },
+                        s("/file2.txt"): IndividualMappingFile { hash: s("b"), size: 2, ..Default::default() },
+                    },
+                    ..Default::default()
+                }),
+            ];
+            assert_eq!(9, Backup::disk_usage(&backups));
+        }
+
+        #[test]
+        fn can_plan_backup_when_empty() {
+            let scan = ScanInfo::default();
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping::new("game1".to_string()),
+                retention: Retention::default(),
+            };
+            assert_eq!(
+                None,
+                layout.plan_backup(&scan, &now(), &BackupFormats::default(), HashAlgorithm::default())
+            );
+        }
+
+        #[test]
+        fn can_plan_full_backup_when_hash_algorithm_changes() {
+            let scan = ScanInfo {
+                found_files: hash_set! {
+                    ScannedFile::with_change(repo_file("new"), 1, "n", ScanChange::New),
+                },
+                ..Default::default()
+            };
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    drives: drives(),
+                    backups: VecDeque::from_iter(vec![FullBackup::default()]),
+                    ..Default::default()
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let Some(Backup::Full(backup)) =
+                layout.plan_backup(&scan, &now(), &BackupFormats::default(), HashAlgorithm::Sha256)
+            else {
+                panic!("Expected a full backup");
+            };
+            assert_eq!(HashAlgorithm::Sha256, backup.hash_algorithm);
+
+            assert!(matches!(
+                layout.plan_backup(&scan, &now(), &BackupFormats::default(), HashAlgorithm::Sha1),
+                Some(Backup::Differential(_)),
+            ));
+        }
+
+        #[test]
+        fn can_plan_backup_kind_when_first_time() {
+            let layout = GameLayout::default();
+            assert_eq!(BackupKind::Full, layout.plan_backup_kind());
+        }
+
+        #[test]
         fn can_plan_backup_kind_when_merged_single_full() {
             let layout = GameLayout {
                 mapping: IndividualMapping {
@@ -2489,13 +3333,13 @@ mod tests {
                     when: now(),
                     os: Some(Os::HOST),
                     files: btree_map! {
-                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1 },
-                        StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2 },
-                        StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5 },
+                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1, ..Default::default() },
+                        StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2, ..Default::default() },
+                        StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5, ..Default::default() },
                     },
                     ..Default::default()
                 },
-                layout.plan_full_backup(&scan, &now(), &BackupFormats::default()),
+                layout.plan_full_backup(&scan, &now(), &BackupFormats::default(), HashAlgorithm::default()),
             );
         }
 
@@ -2543,7 +3387,7 @@ mod tests {
                     },
                     ..Default::default()
                 },
-                layout.plan_full_backup(&scan, &now(), &BackupFormats::default()),
+                layout.plan_full_backup(&scan, &now(), &BackupFormats::default(), HashAlgorithm::default()),
             );
         }
 
@@ -2566,9 +3410,9 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btree_map! {
-                            StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2 },
-                            StrictPath::new(repo_file("removed")).render(): IndividualMappingFile { hash: "r".into(), size: 3 },
-                            StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5 },
+                            StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2, ..Default::default() },
+                            StrictPath::new(repo_file("removed")).render(): IndividualMappingFile { hash: "r".into(), size: 3, ..Default::default() },
+                            StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -2582,8 +3426,8 @@ mod tests {
                     when: now(),
                     os: Some(Os::HOST),
                     files: btree_map! {
-                        StrictPath::new(repo_file("new")).render(): Some(IndividualMappingFile { hash: "n".into(), size: 1 }),
-                        StrictPath::new(repo_file("different")).render(): Some(IndividualMappingFile { hash: "d+".into(), size: 2 }),
+                        StrictPath::new(repo_file("new")).render(): Some(IndividualMappingFile { hash: "n".into(), size: 1, ..Default::default() }),
+                        StrictPath::new(repo_file("different")).render(): Some(IndividualMappingFile { hash: "d+".into(), size: 2, ..Default::default() }),
                         StrictPath::new(repo_file("removed")).render(): None,
                     },
                     registry: None,
@@ -2593,6 +3437,49 @@ mod tests {
             );
         }
 
+        #[test]
+        fn can_plan_differential_backup_with_only_registry() {
+            let scan = ScanInfo {
+                only_registry: true,
+                ..Default::default()
+            };
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    drives: drives(),
+                    backups: VecDeque::from_iter(vec![FullBackup {
+                        name: ".".to_string(),
+                        when: past(),
+                        files: btree_map! {
+                            StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5, ..Default::default() },
+                        },
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            assert_eq!(
+                DifferentialBackup {
+                    name: format!("backup-{}-diff", now_str()),
+                    when: now(),
+                    os: Some(Os::HOST),
+                    files: btree_map! {},
+                    registry: None,
+                    ..Default::default()
+                },
+                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default()),
+            );
+            assert!(!matches!(
+                layout.plan_backup(&scan, &now(), &BackupFormats::default(), HashAlgorithm::default()),
+                Some(Backup::Full(_)),
+            ));
+        }
+
         #[test]
         fn can_plan_second_differential_backup_with_different_ignored_files() {
             let scan = ScanInfo {
@@ -2613,9 +3500,9 @@ mod tests {
                         name: ".".to_string(),
                         when: past(),
                         files: btree_map! {
-                            StrictPath::new(repo_file("file1")).render(): IndividualMappingFile { hash: "1".into(), size: 1 },
-                            StrictPath::new(repo_file("file2")).render(): IndividualMappingFile { hash: "2".into(), size: 2 },
-                            StrictPath::new(repo_file("file3")).render(): IndividualMappingFile { hash: "3".into(), size: 3 },
+                            StrictPath::new(repo_file("file1")).render(): IndividualMappingFile { hash: "1".into(), size: 1, ..Default::default() },
+                            StrictPath::new(repo_file("file2")).render(): IndividualMappingFile { hash: "2".into(), size: 2, ..Default::default() },
+                            StrictPath::new(repo_file("file3")).render(): IndividualMappingFile { hash: "3".into(), size: 3, ..Default::default() },
                         },
                         children: VecDeque::from([DifferentialBackup {
                             name: format!("backup-{}-diff", now_str()),
@@ -3032,8 +3919,8 @@ mod tests {
                         name: "backup-1".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -3071,6 +3958,60 @@ mod tests {
             );
         }
 
+        #[test]
+        fn can_report_restorable_files_for_full_backup_in_simple_format_with_blobs() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "backup-1".into(),
+                        when: past(),
+                        files: btree_map! {
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, blob: true, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 1, blob: true, ..Default::default() },
+                        },
+                        ..Default::default()
+                    }]),
+                },
+                retention: Retention {
+                    full: 1,
+                    differential: 1,
+                    ..Default::default()
+                },
+            };
+            assert_eq!(
+                hash_set! {
+                    ScannedFile {
+                        path: StrictPath::relative(s("blobs/old"), Some(repo_file_raw("tests/backup/game1"))),
+                        size: 1,
+                        hash: "old".into(),
+                        original_path: Some(make_original_path("/file1.txt")),
+                        ignored: false,
+                        change: Default::default(),
+                        container: None,
+                        redirected: None,
+                    },
+                    ScannedFile {
+                        path: StrictPath::relative(s("blobs/old"), Some(repo_file_raw("tests/backup/game1"))),
+                        size: 1,
+                        hash: "old".into(),
+                        original_path: Some(make_original_path("/file2.txt")),
+                        ignored: false,
+                        change: Default::default(),
+                        container: None,
+                        redirected: None,
+                    },
+                },
+                layout.restorable_files(&BackupId::Latest, false, &[], &Default::default()),
+            );
+            assert_eq!(
+                Some((layout.mapping.clone(), vec![s("blobs/old")],)),
+                layout.plan_export(),
+            );
+        }
+
         #[test]
         fn can_report_restorable_files_for_full_backup_in_zip_format() {
             let layout = GameLayout {
@@ -3082,8 +4023,8 @@ mod tests {
                         name: "backup-1.zip".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -3132,17 +4073,17 @@ mod tests {
                         name: "backup-1".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3 },
+                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
+                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3, ..Default::default() },
                         },
                         children: VecDeque::from([DifferentialBackup {
                             name: "backup-2".into(),
                             when: past2(),
                             files: btree_map! {
-                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
                                 mapping_file_key("/delete.txt"): None,
-                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5, ..Default::default() }),
                             },
                             ..Default::default()
                         }]),
@@ -3203,17 +4144,17 @@ mod tests {
                         name: "backup-1.zip".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3 },
+                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2, ..Default::default() },
+                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3, ..Default::default() },
                         },
                         children: VecDeque::from([DifferentialBackup {
                             name: "backup-2.zip".into(),
                             when: past2(),
                             files: btree_map! {
-                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2, ..Default::default() }),
                                 mapping_file_key("/delete.txt"): None,
-                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5, ..Default::default() }),
                             },
                             ..Default::default()
                         }]),
@@ -3267,7 +4208,10 @@ mod tests {
     mod game_layout {
         use pretty_assertions::assert_eq;
 
-        use crate::testing::{drives_x_always, drives_x_static, repo_file_raw};
+        use crate::{
+            resource::config::ZipConfig,
+            testing::{drives_x_always, drives_x_static, repo_file_raw},
+        };
 
         use super::*;
 
@@ -3290,6 +4234,52 @@ mod tests {
             )
         }
 
+        #[test]
+        fn can_find_mapping_file_with_permissions_in_differential_backup() {
+            let layout = GameLayout::new(
+                StrictPath::new(format!("{}/tests/backup/game1", repo())),
+                IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: ".".into(),
+                        when: now(),
+                        files: btree_map! {
+                            mapping_file_key("/inherited.txt"): IndividualMappingFile { hash: "1".into(), size: 1, mode: Some(0o755), ..Default::default() },
+                            mapping_file_key("/overridden.txt"): IndividualMappingFile { hash: "2".into(), size: 2, mode: Some(0o644), ..Default::default() },
+                            mapping_file_key("/removed.txt"): IndividualMappingFile { hash: "3".into(), size: 3, ..Default::default() },
+                        },
+                        children: VecDeque::from(vec![DifferentialBackup {
+                            name: "backup-1".into(),
+                            when: now(),
+                            files: btree_map! {
+                                mapping_file_key("/overridden.txt"): Some(IndividualMappingFile { hash: "2+".into(), size: 2, symlink: Some(s("target.txt")), ..Default::default() }),
+                                mapping_file_key("/removed.txt"): None,
+                            },
+                            ..Default::default()
+                        }]),
+                        ..Default::default()
+                    }]),
+                },
+                Retention::default(),
+            );
+            let id = BackupId::Named(s("backup-1"));
+
+            assert_eq!(
+                Some(Some(0o755)),
+                layout
+                    .find_mapping_file(&id, &mapping_file_key("/inherited.txt"))
+                    .map(|x| x.mode),
+            );
+            assert_eq!(
+                Some(Some(s("target.txt"))),
+                layout
+                    .find_mapping_file(&id, &mapping_file_key("/overridden.txt"))
+                    .map(|x| x.symlink.clone()),
+            );
+            assert_eq!(None, layout.find_mapping_file(&id, &mapping_file_key("/removed.txt")));
+        }
+
         #[test]
         fn can_scan_game_for_restoration_with_files() {
             let mut layout = GameLayout::new(
@@ -3301,8 +4291,8 @@ mod tests {
                         name: ".".into(),
                         when: now(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -3320,10 +4310,12 @@ mod tests {
                     mapping_file_key("/file1.txt"): IndividualMappingFile {
                         hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
                         size: 1,
+                        ..Default::default()
                     },
                     mapping_file_key("/file2.txt"): IndividualMappingFile {
                         hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
                         size: 2,
+                        ..Default::default()
                     },
                 },
                 ..Default::default()
@@ -3355,9 +4347,16 @@ mod tests {
                         },
                     },
                     found_registry_keys: Default::default(),
+                    found_empty_dirs: Default::default(),
                     available_backups: backups.clone(),
                     backup: Some(backups[0].clone()),
                     has_backups: true,
+                    globally_ignored: 0,
+                    system_files_excluded: 0,
+                    preserved_registry_values: Default::default(),
+                    other_os_files: Default::default(),
+                    save_roots: Default::default(),
+                    only_registry: false,
                 },
                 layout.scan_for_restoration(
                     "game1",
@@ -3390,6 +4389,7 @@ mod tests {
                                 .with_value_same("qword")
                                 .with_value_same("sz")
                         },
+                        found_empty_dirs: Default::default(),
                         available_backups: vec![Backup::Full(FullBackup {
                             name: ".".to_string(),
                             when: now(),
@@ -3407,6 +4407,12 @@ mod tests {
                             ..Default::default()
                         })),
                         has_backups: true,
+                        globally_ignored: 0,
+                        system_files_excluded: 0,
+                        preserved_registry_values: Default::default(),
+                        other_os_files: Default::default(),
+                        save_roots: Default::default(),
+                        only_registry: false,
                     },
                     layout.scan_for_restoration(
                         "game3",
@@ -3422,6 +4428,7 @@ mod tests {
                         game_name: s("game3"),
                         found_files: Default::default(),
                         found_registry_keys: Default::default(),
+                        found_empty_dirs: Default::default(),
                         available_backups: vec![Backup::Full(FullBackup {
                             name: ".".to_string(),
                             when: now(),
@@ -3439,6 +4446,12 @@ mod tests {
                             ..Default::default()
                         })),
                         has_backups: true,
+                        globally_ignored: 0,
+                        system_files_excluded: 0,
+                        preserved_registry_values: Default::default(),
+                        other_os_files: Default::default(),
+                        save_roots: Default::default(),
+                        only_registry: false,
                     },
                     layout.scan_for_restoration(
                         "game3",
@@ -3459,8 +4472,8 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -3480,7 +4493,27 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
+                        },
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                ..Default::default()
+            };
+            assert!(!layout.validate(BackupId::Latest));
+        }
+
+        #[test]
+        fn can_validate_a_simple_full_backup_with_missing_blob() {
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    drives: drives_x_always(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: ".".into(),
+                        files: btree_map! {
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, blob: true, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -3500,14 +4533,14 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: ".".into(),
                             files: btree_map! {
                                 mapping_file_key("/file1.txt"): None,
-                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() }),
                             },
                             ..Default::default()
                         }]),
@@ -3529,13 +4562,13 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: ".".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: ".".into(),
                             files: btree_map! {
-                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() }),
                             },
                             ..Default::default()
                         }]),
@@ -3557,8 +4590,8 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -3578,7 +4611,7 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         ..Default::default()
                     }]),
@@ -3598,14 +4631,14 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: "test.zip".into(),
                             files: btree_map! {
                                 mapping_file_key("/file1.txt"): None,
-                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() }),
                             },
                             ..Default::default()
                         }]),
@@ -3627,13 +4660,13 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: "test.zip".into(),
                             files: btree_map! {
-                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, ..Default::default() }),
                             },
                             ..Default::default()
                         }]),
@@ -3665,7 +4698,7 @@ mod tests {
                 backups: VecDeque::from(vec![FullBackup {
                     name: ".".into(),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
                     },
                     ..Default::default()
                 }]),
@@ -3707,7 +4740,7 @@ mod tests {
                         .to_utc(),
                         os: Some(Os::Windows),
                         files: btree_map! {
-                            "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                            "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
                         },
                         ..Default::default()
                     },
@@ -3724,7 +4757,7 @@ mod tests {
                         .to_utc(),
                     os: Some(Os::Windows),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
                     },
                     ..Default::default()
                 }]),
@@ -3767,7 +4800,7 @@ mod tests {
                         .to_utc(),
                         os: Some(Os::Windows),
                         files: btree_map! {
-                            "X:/file1.txt".into(): Some(IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 }),
+                            "X:/file1.txt".into(): Some(IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() }),
                         },
                         ..Default::default()
                     }]),
@@ -3785,7 +4818,7 @@ mod tests {
                         .to_utc(),
                     os: Some(Os::Windows),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, ..Default::default() },
                     },
                     ..Default::default()
                 }]),
@@ -3811,5 +4844,187 @@ mod tests {
             game_layout.migrate_initial_empty_backup(false);
             assert_eq!(GameLayout::default().mapping, game_layout.mapping);
         }
+
+        #[test]
+        fn retries_failed_entries_within_the_same_backup() {
+            let dir = std::env::temp_dir().join("ludusavi-tests/retry-same-backup");
+            _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let present = dir.join("present.txt");
+            std::fs::write(&present, "data").unwrap();
+            let missing = dir.join("missing.txt");
+
+            let scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::with_change(StrictPath::from(present).render(), 4, "hash1", ScanChange::New),
+                    ScannedFile::with_change(StrictPath::from(missing).render(), 4, "hash2", ScanChange::New),
+                },
+                ..Default::default()
+            };
+
+            let mut layout = GameLayout::new(
+                StrictPath::from(dir.join("backup")),
+                IndividualMapping::new(s("game1")),
+                Retention {
+                    full: 3,
+                    differential: 3,
+                    ..Default::default()
+                },
+            );
+            let (backup_info, retried) = layout.back_up(
+                &scan,
+                &now(),
+                &BackupFormats::default(),
+                HashAlgorithm::default(),
+                false,
+                BackupLayoutStyle::default(),
+                false,
+                None,
+                2,
+            );
+
+            assert_eq!(1, backup_info.failed_files.len());
+            assert!(!retried);
+            assert_eq!(1, layout.mapping.backups.len());
+            assert!(layout.mapping.backups[0].children.is_empty());
+        }
+
+        #[test]
+        fn replaces_partial_blob_when_backing_up() {
+            let dir = std::env::temp_dir().join("ludusavi-tests/partial-blob");
+            _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let source = dir.join("save.txt");
+            std::fs::write(&source, "data").unwrap();
+            let blobs = dir.join("backup").join(BLOBS);
+            std::fs::create_dir_all(&blobs).unwrap();
+            std::fs::write(blobs.join("hash1"), "da").unwrap();
+
+            let scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::with_change(StrictPath::from(source).render(), 4, "hash1", ScanChange::New),
+                },
+                ..Default::default()
+            };
+
+            let mut layout = GameLayout::new(
+                StrictPath::from(dir.join("backup")),
+                IndividualMapping::new(s("game1")),
+                Retention::default(),
+            );
+            let (backup_info, _) = layout.back_up(
+                &scan,
+                &now(),
+                &BackupFormats::default(),
+                HashAlgorithm::default(),
+                false,
+                BackupLayoutStyle::default(),
+                true,
+                None,
+                0,
+            );
+
+            assert!(backup_info.successful());
+            assert_eq!("data", std::fs::read_to_string(blobs.join("hash1")).unwrap());
+            assert!(!blobs.join("hash1.tmp").exists());
+        }
+
+        fn back_up_zip_for_compression_test(
+            dir: &std::path::Path,
+            folder: &str,
+            size: usize,
+            compression: ZipCompression,
+            level: Option<i32>,
+        ) -> (std::time::Duration, zip::ZipArchive<std::fs::File>) {
+            // Pseudo-random bytes, so that neither method gets to take any shortcuts.
+            let source = dir.join(format!("{folder}.dat"));
+            let mut seed = 1u32;
+            let data: Vec<_> = (0..size)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    (seed >> 16) as u8
+                })
+                .collect();
+            std::fs::write(&source, &data).unwrap();
+
+            let scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_set! {
+                    ScannedFile::with_change(StrictPath::from(source).render(), size as u64, "hash", ScanChange::New),
+                },
+                ..Default::default()
+            };
+
+            let mut format = BackupFormats {
+                chosen: BackupFormat::Zip,
+                zip: ZipConfig { compression },
+                ..Default::default()
+            };
+            if let Some(level) = level {
+                format.set_level(level);
+            }
+            let mut layout = GameLayout::new(
+                StrictPath::from(dir.join(folder)),
+                IndividualMapping::new(s("game1")),
+                Retention::default(),
+            );
+
+            let started = std::time::Instant::now();
+            let (backup_info, _) = layout.back_up(
+                &scan,
+                &now(),
+                &format,
+                HashAlgorithm::default(),
+                false,
+                BackupLayoutStyle::default(),
+                false,
+                None,
+                0,
+            );
+            let elapsed = started.elapsed();
+            assert!(backup_info.successful());
+
+            let archive = dir
+                .join(folder)
+                .join(format!("backup-{}.zip", now().format("%Y%m%dT%H%M%SZ")));
+            let archive = zip::ZipArchive::new(std::fs::File::open(archive).unwrap()).unwrap();
+            (elapsed, archive)
+        }
+
+        #[test]
+        fn can_back_up_zip_with_stored_entries_without_compression() {
+            let dir = std::env::temp_dir().join("ludusavi-tests/zip-store-mode");
+            _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            let size = 64 * 1024;
+
+            let (_, mut archive) = back_up_zip_for_compression_test(&dir, "stored", size, ZipCompression::None, None);
+            let entry = archive.by_index(0).unwrap();
+            assert_eq!(zip::CompressionMethod::Stored, entry.compression());
+            assert_eq!(size as u64, entry.compressed_size());
+
+            let (_, mut archive) =
+                back_up_zip_for_compression_test(&dir, "deflate", size, ZipCompression::Deflate, Some(0));
+            let entry = archive.by_index(0).unwrap();
+            assert_eq!(zip::CompressionMethod::Deflated, entry.compression());
+        }
+
+        #[test]
+        #[ignore = "depends on timing, so run it manually"]
+        fn can_back_up_zip_without_compression_faster_than_deflate() {
+            let dir = std::env::temp_dir().join("ludusavi-tests/zip-store-mode-timing");
+            _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            let size = 8 * 1024 * 1024;
+
+            let (deflate, _) =
+                back_up_zip_for_compression_test(&dir, "deflate", size, ZipCompression::Deflate, Some(0));
+            let (stored, _) = back_up_zip_for_compression_test(&dir, "stored", size, ZipCompression::None, None);
+            assert!(stored * 2 < deflate, "stored: {stored:?}, deflate: {deflate:?}");
+        }
     }
 }
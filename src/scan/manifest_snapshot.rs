@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    prelude::{Error, Hasher, StrictPath},
+    resource::{config::HashAlgorithm, manifest::Manifest},
+};
+
+const SNAPSHOT_DIR: &str = ".ludusavi-manifests";
+const INDEX_FILE: &str = "index.yaml";
+
+/// Copies of the effective manifest used by each backup run,
+/// so that you can tell which path definitions were in effect at the time.
+///
+/// Each distinct manifest is stored once, named after its hash,
+/// and the index maps each run's timestamp to the manifest it used.
+pub struct ManifestSnapshots {
+    dir: StrictPath,
+}
+
+impl ManifestSnapshots {
+    pub fn new(backup_dir: &StrictPath) -> Self {
+        Self {
+            dir: backup_dir.joined(SNAPSHOT_DIR),
+        }
+    }
+
+    fn index_file(&self) -> StrictPath {
+        self.dir.joined(INDEX_FILE)
+    }
+
+    /// Runs that have been recorded so far, mapped to the hash of the manifest they used.
+    pub fn index(&self) -> BTreeMap<DateTime<Utc>, String> {
+        self.index_file()
+            .read()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// The stored manifest with this hash, if any.
+    pub fn file(&self, hash: &str) -> StrictPath {
+        self.dir.joined(&format!("{hash}.yaml"))
+    }
+
+    /// Record the manifest used by a run that started at `now`.
+    /// If the same manifest was already stored by an earlier run, then it is reused.
+    /// Returns the manifest's hash.
+    pub fn record(&self, manifest: &Manifest, now: &DateTime<Utc>) -> Result<String, Error> {
+        let fail = || Error::CannotWriteManifestSnapshot { path: self.dir.clone() };
+
+        let content = serde_yaml::to_string(manifest).map_err(|_| fail())?;
+        let mut hasher = Hasher::new(HashAlgorithm::Sha1);
+        hasher.update(content.as_bytes());
+        let hash = hasher.finalize();
+
+        self.dir.create_dirs().map_err(|_| fail())?;
+
+        let file = self.file(&hash);
+        if !file.is_file() {
+            file.write_with_content(&content).map_err(|_| fail())?;
+        }
+
+        let mut index = self.index();
+        index.insert(*now, hash.clone());
+        let index = serde_yaml::to_string(&index).map_err(|_| fail())?;
+        self.index_file().write_with_content(&index).map_err(|_| fail())?;
+
+        Ok(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::resource::ResourceFile;
+
+    #[test]
+    fn can_record_deduplicated_snapshots() {
+        let dir = std::env::temp_dir().join("ludusavi-tests/manifest-snapshots");
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = StrictPath::from(dir);
+
+        let first = Manifest::load_from_string("game1: {}").unwrap();
+        let second = Manifest::load_from_string("game2: {}").unwrap();
+        let now1 = Utc.with_ymd_and_hms(2000, 1, 2, 3, 4, 5).unwrap();
+        let now2 = Utc.with_ymd_and_hms(2000, 1, 3, 3, 4, 5).unwrap();
+        let now3 = Utc.with_ymd_and_hms(2000, 1, 4, 3, 4, 5).unwrap();
+
+        let snapshots = ManifestSnapshots::new(&dir);
+        let hash1 = snapshots.record(&first, &now1).unwrap();
+        let hash2 = snapshots.record(&first, &now2).unwrap();
+        let hash3 = snapshots.record(&second, &now3).unwrap();
+
+        assert_eq!(hash1, hash2);
+        assert_ne!(hash1, hash3);
+        assert_eq!(
+            BTreeMap::from([(now1, hash1.clone()), (now2, hash1.clone()), (now3, hash3.clone())]),
+            snapshots.index(),
+        );
+        assert_eq!(
+            Some(serde_yaml::to_string(&first).unwrap()),
+            snapshots.file(&hash1).read()
+        );
+        assert_eq!(
+            3,
+            std::fs::read_dir(dir.joined(SNAPSHOT_DIR).as_std_path_buf().unwrap())
+                .unwrap()
+                .count()
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{atomic::AtomicBool, Mutex},
+};
+
+use crate::{
+    prelude::{app_dir, StrictPath},
+    resource::{
+        config::{
+            BackupFilter, BackupFormat, BackupFormats, Config, DuplicateOwners, FileSize, GameFormat, GlobalIgnore,
+            HashAlgorithm, Root, ToggledPaths, ToggledRegistry,
+        },
+        manifest::{Manifest, Os},
+    },
+    scan::{layout::BackupLayout, scan_game_for_backup, BackupInfo, Launchers, ScanInfo, SteamShortcuts},
+};
+
+/// Settings for scanning and backing up each game,
+/// starting from the config and then adjusted for the current invocation.
+#[derive(Clone, Debug)]
+pub struct BackupOptions {
+    pub filter: BackupFilter,
+    pub toggled_paths: ToggledPaths,
+    pub toggled_registry: ToggledRegistry,
+    pub hash_algorithm: HashAlgorithm,
+    pub follow_symlinks: bool,
+    pub preserve_empty_dirs: bool,
+    pub only_registry: bool,
+    pub include_other_os_paths: bool,
+    pub max_file_size: Option<FileSize>,
+    pub global_ignore: GlobalIgnore,
+    pub exclude_system_files: bool,
+    pub duplicate_owners: DuplicateOwners,
+    pub format: BackupFormats,
+    pub per_game_format: BTreeMap<String, GameFormat>,
+    pub preserve_permissions: bool,
+    pub id_format: Option<String>,
+    pub retries: u8,
+}
+
+impl BackupOptions {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            filter: config.backup.filter.clone(),
+            toggled_paths: config.backup.toggled_paths.clone(),
+            toggled_registry: config.backup.toggled_registry.clone(),
+            hash_algorithm: config.backup.hash_algorithm,
+            follow_symlinks: config.backup.follow_symlinks,
+            preserve_empty_dirs: config.backup.preserve_empty_dirs,
+            only_registry: false,
+            include_other_os_paths: false,
+            max_file_size: config.backup.max_file_size,
+            global_ignore: GlobalIgnore::new(&config.backup.global_ignore, &config.backup.global_force_include),
+            exclude_system_files: config.backup.exclude_system_files,
+            duplicate_owners: config.backup.duplicate_owners.clone(),
+            format: config.backup.format.clone(),
+            per_game_format: config.backup.per_game_format.clone(),
+            preserve_permissions: false,
+            id_format: config.backup.id_format.clone(),
+            retries: 0,
+        }
+    }
+
+    /// Applies the settings that adjust an existing scan,
+    /// always in the same order, so that a rescan and a refresh agree.
+    /// `found_by` says whether another game finds the same file,
+    /// for `ScanInfo::apply_duplicate_owners`.
+    pub fn refine_scan(&self, scan_info: &mut ScanInfo, found_by: impl Fn(&str, &StrictPath) -> bool) {
+        scan_info.apply_duplicate_owners(&self.duplicate_owners, found_by);
+        scan_info.apply_max_file_size(self.max_file_size);
+        scan_info.apply_global_ignore(&self.global_ignore);
+        scan_info.apply_system_files(self.exclude_system_files, Os::HOST);
+    }
+
+    pub fn format_for_game(&self, name: &str) -> BackupFormats {
+        let mut format = self.format.clone();
+        if let Some(game_format) = self.per_game_format.get(name) {
+            game_format.apply(&mut format);
+        }
+        format
+    }
+
+    pub fn uses_git(&self) -> bool {
+        self.format.chosen == BackupFormat::Git
+            || self
+                .per_game_format
+                .values()
+                .any(|x| x.chosen == Some(BackupFormat::Git))
+    }
+}
+
+/// Scans and backs up individual games.
+/// This is shared by the `backup` and `watch` commands as well as the `embed` API,
+/// so that they all handle each game the same way.
+pub struct BackupPipeline<'a> {
+    config: &'a Config,
+    manifest: &'a Manifest,
+    layout: &'a BackupLayout,
+    roots: &'a [Root],
+    launchers: &'a Launchers,
+    steam_shortcuts: &'a SteamShortcuts,
+    options: &'a BackupOptions,
+    cancel: Option<&'a AtomicBool>,
+    /// Files found by each game that `backup.duplicateOwners` refers to,
+    /// so that each owner only needs to be scanned once.
+    owner_files: Mutex<HashMap<String, HashSet<StrictPath>>>,
+}
+
+impl<'a> BackupPipeline<'a> {
+    pub fn new(
+        config: &'a Config,
+        manifest: &'a Manifest,
+        layout: &'a BackupLayout,
+        roots: &'a [Root],
+        launchers: &'a Launchers,
+        steam_shortcuts: &'a SteamShortcuts,
+        options: &'a BackupOptions,
+    ) -> Self {
+        Self {
+            config,
+            manifest,
+            layout,
+            roots,
+            launchers,
+            steam_shortcuts,
+            options,
+            cancel: None,
+            owner_files: Default::default(),
+        }
+    }
+
+    /// Stop scanning as soon as this flag is set,
+    /// such as when the maximum backup duration is reached.
+    pub fn with_cancel(mut self, cancel: &'a AtomicBool) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Returns `None` if the game is excluded by the backup filter.
+    pub fn scan_game(&self, name: &str, games_specified: bool, wine_prefix: &Option<StrictPath>) -> Option<ScanInfo> {
+        let game = &self.manifest.0[name];
+        let previous =
+            self.layout
+                .latest_backup(name, false, &self.config.redirects, &self.config.restore.toggled_paths);
+
+        if self
+            .options
+            .filter
+            .excludes(games_specified, previous.is_some(), &game.cloud)
+        {
+            log::trace!("[{name}] excluded by backup filter");
+            return None;
+        }
+
+        let mut scan_info = scan_game_for_backup(
+            game,
+            name,
+            self.roots,
+            &app_dir(),
+            self.launchers,
+            wine_prefix,
+            previous,
+            &self.config.redirects,
+            self.steam_shortcuts,
+            self.options,
+            self.cancel,
+        );
+        self.options
+            .refine_scan(&mut scan_info, |owner, path| self.owner_finds(owner, path));
+        Some(scan_info)
+    }
+
+    fn owner_finds(&self, owner: &str, path: &StrictPath) -> bool {
+        if let Some(files) = self.owner_files.lock().unwrap().get(owner) {
+            return files.contains(path);
+        }
+
+        let Some(game) = self.manifest.0.get(owner) else {
+            return false;
+        };
+        let files: HashSet<_> = scan_game_for_backup(
+            game,
+            owner,
+            self.roots,
+            &app_dir(),
+            self.launchers,
+            &None,
+            None,
+            &self.config.redirects,
+            self.steam_shortcuts,
+            self.options,
+            self.cancel,
+        )
+        .found_files
+        .iter()
+        .map(|file| file.original_path().clone())
+        .collect();
+        let found = files.contains(path);
+
+        self.owner_files.lock().unwrap().insert(owner.to_string(), files);
+        found
+    }
+
+    /// Also returns whether the backup only succeeded because of a retry.
+    pub fn back_up_game(&self, name: &str, scan_info: &ScanInfo) -> (BackupInfo, bool) {
+        self.layout.game_layout(name).back_up(
+            scan_info,
+            &chrono::Utc::now(),
+            &self.options.format_for_game(name),
+            self.options.hash_algorithm,
+            self.options.preserve_permissions,
+            self.config.backup.layout_style,
+            self.config.backup.deduplicate,
+            self.options.id_format.as_deref(),
+            self.options.retries,
+        )
+    }
+}
@@ -1,8 +1,15 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use crate::{
-    resource::config::{ToggledPaths, ToggledRegistry},
-    scan::{layout::Backup, BackupInfo, ScanChange, ScanChangeCount, ScannedFile, ScannedRegistry},
+    path::StrictPath,
+    resource::{
+        config::{DuplicateOwners, FileSize, GlobalIgnore, ToggledPaths, ToggledRegistry},
+        manifest::Os,
+    },
+    scan::{
+        layout::Backup, registry_compat::RegistryItem, BackupInfo, ScanChange, ScanChangeCount, ScannedFile,
+        ScannedRegistry,
+    },
 };
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -10,15 +17,177 @@ pub struct ScanInfo {
     pub game_name: String,
     pub found_files: HashSet<ScannedFile>,
     pub found_registry_keys: HashSet<ScannedRegistry>,
+    /// Empty folders within the save locations.
+    /// For a backup scan, this is only populated when preserving empty folders.
+    pub found_empty_dirs: BTreeSet<StrictPath>,
     /// Only populated by a restoration scan.
     pub available_backups: Vec<Backup>,
     /// Only populated by a restoration scan.
     pub backup: Option<Backup>,
     /// Cheaper version of `!available_backups.is_empty()`, always populated.
     pub has_backups: bool,
+    /// How many files were skipped by `backup.globalIgnore`.
+    pub globally_ignored: usize,
+    /// How many files were skipped by `backup.excludeSystemFiles`.
+    pub system_files_excluded: usize,
+    /// Values under each restored registry key that are not in the backup.
+    /// Only populated by a restoration scan with `--merge-registry`.
+    pub preserved_registry_values: BTreeMap<RegistryItem, BTreeSet<String>>,
+    /// Files that came from a save location for a different operating system,
+    /// keyed by their original path.
+    /// Only populated with `--include-other-os-paths` or when restoring such a backup.
+    pub other_os_files: BTreeMap<StrictPath, Os>,
+    /// Existing folders where the game's saves may be found,
+    /// up to the first wildcard of each save location.
+    /// Only populated by a backup scan.
+    pub save_roots: BTreeSet<StrictPath>,
+    /// Whether save files were intentionally skipped
+    /// so that only registry entries were scanned.
+    /// Only populated by a backup scan with `--only-registry`.
+    pub only_registry: bool,
+}
+
+/// Whether a file is OS clutter like `Thumbs.db` or `.DS_Store`.
+/// Linux also checks for the Windows files,
+/// since it may be scanning Wine or Proton prefixes.
+fn is_system_file(name: &str, os: Os) -> bool {
+    const WINDOWS: &[&str] = &["desktop.ini", "thumbs.db", "ehthumbs.db", "ehthumbs_vista.db"];
+    const MAC: &[&str] = &[".DS_Store", ".localized"];
+    const LINUX: &[&str] = &[".directory"];
+
+    let windows = || WINDOWS.contains(&name.to_lowercase().as_str());
+    match os {
+        Os::Windows => windows(),
+        Os::Mac => MAC.contains(&name) || name.starts_with("._"),
+        Os::Linux => LINUX.contains(&name) || windows(),
+        Os::Other => false,
+    }
 }
 
 impl ScanInfo {
+    /// Narrow the scan down to the entries that failed, so that only those are retried.
+    pub fn only_failures(&self, info: &BackupInfo) -> Self {
+        let mut scan = self.clone();
+        scan.found_files.retain(|file| info.failed_files.contains_key(file));
+        scan.found_registry_keys
+            .retain(|key| info.failed_registry.contains_key(&key.path));
+        scan
+    }
+
+    /// Skip any files that `backup.duplicateOwners` assigns to a different game,
+    /// but only if `found_by` confirms that the owner game finds the same file.
+    /// Otherwise, the file isn't really shared, so this game still backs it up.
+    pub fn apply_duplicate_owners(&mut self, owners: &DuplicateOwners, found_by: impl Fn(&str, &StrictPath) -> bool) {
+        if owners.is_empty() {
+            return;
+        }
+
+        self.found_files = std::mem::take(&mut self.found_files)
+            .into_iter()
+            .map(|mut file| {
+                if owners
+                    .owner(file.original_path())
+                    .is_some_and(|owner| owner != self.game_name && found_by(owner, file.original_path()))
+                {
+                    file.ignored = true;
+                }
+                file
+            })
+            .collect();
+    }
+
+    /// Skip any files that are larger than the `backup.maxFileSize` config.
+    /// The scan already avoids hashing these files, so this just keeps the report consistent.
+    pub fn apply_max_file_size(&mut self, limit: Option<FileSize>) {
+        let Some(FileSize(limit)) = limit else {
+            return;
+        };
+
+        self.found_files = std::mem::take(&mut self.found_files)
+            .into_iter()
+            .map(|mut file| {
+                if file.size > limit {
+                    file.ignored = true;
+                }
+                file
+            })
+            .collect();
+    }
+
+    /// Skip any files that match `backup.globalIgnore`,
+    /// unless they also match `backup.globalForceInclude`.
+    pub fn apply_global_ignore(&mut self, rules: &GlobalIgnore) {
+        self.globally_ignored = 0;
+        if rules.is_empty() {
+            return;
+        }
+
+        let mut globally_ignored = 0;
+        self.found_files = std::mem::take(&mut self.found_files)
+            .into_iter()
+            .map(|mut file| {
+                if rules.is_ignored(file.original_path()) {
+                    file.ignored = true;
+                    globally_ignored += 1;
+                }
+                file
+            })
+            .collect();
+        self.globally_ignored = globally_ignored;
+    }
+
+    /// Skip any files that are OS clutter for the given platform,
+    /// if `backup.excludeSystemFiles` is enabled.
+    pub fn apply_system_files(&mut self, exclude: bool, os: Os) {
+        self.system_files_excluded = 0;
+        if !exclude {
+            return;
+        }
+
+        let mut system_files_excluded = 0;
+        self.found_files = std::mem::take(&mut self.found_files)
+            .into_iter()
+            .map(|mut file| {
+                if file
+                    .original_path()
+                    .leaf()
+                    .is_some_and(|name| is_system_file(&name, os))
+                {
+                    file.ignored = true;
+                    system_files_excluded += 1;
+                }
+                file
+            })
+            .collect();
+        self.system_files_excluded = system_files_excluded;
+    }
+
+    /// Find the values that already exist under each registry key to restore,
+    /// but which are not in the backup, so restoring will leave them as-is.
+    pub fn find_preserved_registry_values(&mut self) {
+        #[cfg(target_os = "windows")]
+        for entry in &self.found_registry_keys {
+            if entry.ignored {
+                continue;
+            }
+            let Some((hive, key)) = entry.path.split_hive() else {
+                continue;
+            };
+            let Some(live) = crate::scan::registry::try_read_registry_key(&hive, &key) else {
+                continue;
+            };
+
+            let preserved: BTreeSet<_> = live
+                .0
+                .into_keys()
+                .filter(|name| !entry.values.contains_key(name))
+                .collect();
+            if !preserved.is_empty() {
+                self.preserved_registry_values.insert(entry.path.clone(), preserved);
+            }
+        }
+    }
+
     pub fn sum_bytes(&self, backup_info: Option<&BackupInfo>) -> u64 {
         let successful_bytes = self
             .found_files
@@ -149,6 +318,11 @@ impl ScanInfo {
         self.backup.is_some()
     }
 
+    /// Which other operating system's save location this file came from, if any.
+    pub fn other_os(&self, file: &ScannedFile) -> Option<Os> {
+        self.other_os_files.get(file.original_path()).copied()
+    }
+
     fn is_brand_new(&self) -> bool {
         // We check the saves' un-normalized `change` because
         // ignored saves should still count toward being brand new.
@@ -233,14 +407,83 @@ impl ScanInfo {
     }
 }
 
+/// How a restore would affect the files that are currently on disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RestoreImpact {
+    /// Files that would be replaced by the backup, with their current size.
+    pub overwritten: BTreeMap<String, u64>,
+    /// Files in the game's save locations that are not part of the backup.
+    pub orphaned: BTreeSet<String>,
+    /// Orphaned files that were deleted after the restore.
+    pub cleaned: BTreeSet<String>,
+}
+
+impl RestoreImpact {
+    /// `restoration` comes from a restoration scan, while `live` comes from a backup scan
+    /// of the same game, if it's known to the manifest.
+    pub fn evaluate(restoration: &ScanInfo, live: Option<&ScanInfo>) -> Self {
+        let overwritten = restoration
+            .found_files
+            .iter()
+            .filter(|x| x.change() == ScanChange::Different)
+            .map(|x| (x.effective().render(), x.effective().size()))
+            .collect();
+
+        let targets: HashSet<_> = restoration.found_files.iter().map(|x| x.effective().render()).collect();
+        let orphaned = live
+            .map(|live| {
+                live.found_files
+                    .iter()
+                    .filter(|x| !x.ignored)
+                    .map(|x| x.path.render())
+                    .filter(|x| !targets.contains(x))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            overwritten,
+            orphaned,
+            cleaned: BTreeSet::new(),
+        }
+    }
+
+    /// Delete the orphaned files so that the save locations exactly match the backup.
+    /// Any files that can't be deleted remain in `orphaned`.
+    pub fn clean(&mut self, game: &str) {
+        for orphan in std::mem::take(&mut self.orphaned) {
+            let path = StrictPath::new(orphan.clone());
+            match path.remove() {
+                Ok(_) => {
+                    log::info!("[{game}] removed file not in backup: {orphan}");
+                    self.cleaned.insert(orphan);
+                }
+                Err(e) => {
+                    log::error!("[{game}] failed to remove file not in backup: {orphan} | {e}");
+                    self.orphaned.insert(orphan);
+                }
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overwritten.is_empty() && self.orphaned.is_empty() && self.cleaned.is_empty()
+    }
+
+    pub fn overwritten_bytes(&self) -> u64 {
+        self.overwritten.values().sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
-    use velcro::{btree_map, hash_set};
+    use velcro::{btree_map, btree_set, hash_set};
 
     use crate::{
         path::StrictPath,
         scan::{registry_compat::RegistryItem, ScannedRegistryValue},
+        testing::repo,
     };
 
     use super::*;
@@ -540,6 +783,101 @@ mod tests {
         assert_eq!(ScanChange::Different, scan.overall_change());
     }
 
+    #[test]
+    fn can_apply_max_file_size() {
+        let mut scan_info = ScanInfo {
+            game_name: "foo".into(),
+            found_files: hash_set! {
+                ScannedFile::new("/small", 10, "1"),
+                ScannedFile::new("/limit", 20, "2"),
+                ScannedFile::new("/large", 30, "3"),
+            },
+            ..Default::default()
+        };
+
+        scan_info.apply_max_file_size(None);
+        assert!(!scan_info.any_ignored());
+
+        scan_info.apply_max_file_size(Some(FileSize(20)));
+        assert_eq!(
+            hash_set! {
+                ScannedFile::new("/small", 10, "1"),
+                ScannedFile::new("/limit", 20, "2"),
+                ScannedFile::new("/large", 30, "3").ignored(),
+            },
+            scan_info.found_files,
+        );
+    }
+
+    #[test]
+    fn can_apply_global_ignore() {
+        let mut scan_info = ScanInfo {
+            game_name: "foo".into(),
+            found_files: hash_set! {
+                ScannedFile::new("/game/save.dat", 10, "1"),
+                ScannedFile::new("/game/debug.log", 20, "2"),
+                ScannedFile::new("/game/keep.log", 30, "3"),
+            },
+            ..Default::default()
+        };
+
+        scan_info.apply_global_ignore(&GlobalIgnore::default());
+        assert!(!scan_info.any_ignored());
+        assert_eq!(0, scan_info.globally_ignored);
+
+        scan_info.apply_global_ignore(&GlobalIgnore::new(&["*.log".into()], &["keep.log".into()]));
+        assert_eq!(
+            hash_set! {
+                ScannedFile::new("/game/save.dat", 10, "1"),
+                ScannedFile::new("/game/debug.log", 20, "2").ignored(),
+                ScannedFile::new("/game/keep.log", 30, "3"),
+            },
+            scan_info.found_files,
+        );
+        assert_eq!(1, scan_info.globally_ignored);
+    }
+
+    #[test]
+    fn can_apply_system_files() {
+        let mut scan_info = ScanInfo {
+            game_name: "foo".into(),
+            found_files: hash_set! {
+                ScannedFile::new("/game/save.dat", 10, "1"),
+                ScannedFile::new("/game/Thumbs.db", 20, "2"),
+                ScannedFile::new("/game/.DS_Store", 30, "3"),
+                ScannedFile::new("/game/._save.dat", 40, "4"),
+            },
+            ..Default::default()
+        };
+
+        scan_info.apply_system_files(false, Os::Mac);
+        assert!(!scan_info.any_ignored());
+        assert_eq!(0, scan_info.system_files_excluded);
+
+        scan_info.apply_system_files(true, Os::Mac);
+        assert_eq!(
+            hash_set! {
+                ScannedFile::new("/game/save.dat", 10, "1"),
+                ScannedFile::new("/game/Thumbs.db", 20, "2"),
+                ScannedFile::new("/game/.DS_Store", 30, "3").ignored(),
+                ScannedFile::new("/game/._save.dat", 40, "4").ignored(),
+            },
+            scan_info.found_files,
+        );
+        assert_eq!(2, scan_info.system_files_excluded);
+    }
+
+    #[test]
+    fn can_identify_system_files_by_platform() {
+        assert!(is_system_file("Thumbs.db", Os::Windows));
+        assert!(is_system_file("desktop.ini", Os::Linux));
+        assert!(!is_system_file("desktop.ini", Os::Mac));
+        assert!(is_system_file(".DS_Store", Os::Mac));
+        assert!(!is_system_file(".DS_Store", Os::Windows));
+        assert!(is_system_file(".directory", Os::Linux));
+        assert!(!is_system_file("save.dat", Os::Linux));
+    }
+
     #[test]
     fn no_can_report_game_when_total_removal() {
         let scan = ScanInfo {
@@ -599,4 +937,83 @@ mod tests {
         assert_eq!(ScanChange::Same, scan.overall_change());
         assert!(scan.can_report_game());
     }
+
+    #[test]
+    fn can_evaluate_restore_impact() {
+        let file1 = format!("{}/tests/root1/game1/subdir/file2.txt", repo());
+        let file2 = format!("{}/tests/root1/game1/ignored.txt", repo());
+        let file3 = format!("{}/tests/root2/game2/file1.txt", repo());
+
+        let restoration = ScanInfo {
+            found_files: hash_set! {
+                ScannedFile {
+                    path: StrictPath::new("backup/file2.txt".into()),
+                    original_path: Some(StrictPath::new(file1.clone())),
+                    change: ScanChange::Different,
+                    ..Default::default()
+                },
+                ScannedFile {
+                    path: StrictPath::new("backup/file1.txt".into()),
+                    original_path: Some(StrictPath::new(file3.clone())),
+                    change: ScanChange::Same,
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        };
+        let live = ScanInfo {
+            found_files: hash_set! {
+                ScannedFile::with_name(&file1),
+                ScannedFile::with_name(&file2),
+                ScannedFile::with_name(&file3),
+                ScannedFile::with_name("other.txt").ignored(),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            RestoreImpact {
+                overwritten: btree_map! { file1.clone(): 2 },
+                orphaned: btree_set! { file2.clone() },
+                cleaned: btree_set! {},
+            },
+            RestoreImpact::evaluate(&restoration, Some(&live)),
+        );
+        assert_eq!(
+            RestoreImpact {
+                overwritten: btree_map! { file1: 2 },
+                orphaned: btree_set! {},
+                cleaned: btree_set! {},
+            },
+            RestoreImpact::evaluate(&restoration, None),
+        );
+    }
+
+    #[test]
+    fn can_clean_restore_impact() {
+        let base = std::env::temp_dir().join("ludusavi-tests/restore-clean");
+        _ = std::fs::remove_dir_all(&base);
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("kept.txt"), "a").unwrap();
+        std::fs::write(base.join("extra.txt"), "b").unwrap();
+
+        let root = base.to_string_lossy().replace('\\', "/");
+        let kept = format!("{root}/kept.txt");
+        let extra = format!("{root}/extra.txt");
+        let mut impact = RestoreImpact {
+            orphaned: btree_set! { extra.clone() },
+            ..Default::default()
+        };
+        impact.clean("game");
+
+        assert_eq!(
+            RestoreImpact {
+                cleaned: btree_set! { extra.clone() },
+                ..Default::default()
+            },
+            impact,
+        );
+        assert!(StrictPath::new(kept).is_file());
+        assert!(!StrictPath::new(extra).exists());
+    }
 }
@@ -5,7 +5,10 @@ use winreg::types::{FromRegValue, ToRegValue};
 use crate::{
     prelude::{Error, StrictPath},
     resource::config::{BackupFilter, ToggledRegistry},
-    scan::{BackupError, RegistryItem, ScanChange, ScannedRegistry, ScannedRegistryValue, ScannedRegistryValues},
+    scan::{
+        registry_compat::RegistrySubkeys, BackupError, RegistryItem, ScanChange, ScannedRegistry, ScannedRegistryValue,
+        ScannedRegistryValues,
+    },
 };
 
 #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -93,6 +96,19 @@ impl From<RegistryKind> for winreg::enums::RegType {
     }
 }
 
+/// The real Windows registry.
+pub struct LiveRegistry;
+
+impl RegistrySubkeys for LiveRegistry {
+    fn subkeys(&self, key: &str) -> Option<Vec<String>> {
+        let item = RegistryItem::new(key.to_string());
+        let (hive_name, key) = item.split_hive().unwrap_or_else(|| (item.interpret(), String::new()));
+        let hive = get_hkey_from_name(&hive_name)?;
+        let opened_key = winreg::RegKey::predef(hive).open_subkey(key).ok()?;
+        Some(opened_key.enum_keys().filter_map(|x| x.ok()).collect())
+    }
+}
+
 pub fn scan_registry(
     game: &str,
     path: &str,
@@ -395,6 +411,31 @@ impl Hives {
         self.0.is_empty()
     }
 
+    /// Render the hives in the `.reg` format that the Windows Registry Editor can import.
+    pub fn to_reg_export(&self) -> String {
+        let mut lines = vec!["Windows Registry Editor Version 5.00".to_string()];
+
+        for (hive_name, keys) in &self.0 {
+            for (key_name, entries) in &keys.0 {
+                lines.push(String::new());
+                lines.push(format!("[{}\\{}]", hive_name, key_name));
+                for (entry_name, entry) in &entries.0 {
+                    let Some(value) = entry.to_reg_export() else {
+                        continue;
+                    };
+                    if entry_name.is_empty() {
+                        lines.push(format!("@={value}"));
+                    } else {
+                        lines.push(format!("\"{}\"={value}", escape_reg_string(entry_name)));
+                    }
+                }
+            }
+        }
+
+        lines.push(String::new());
+        lines.join("\r\n")
+    }
+
     fn get(&self, hive: &str, key: &str) -> Option<&Entries> {
         self.0.get(hive)?.0.get(key)
     }
@@ -409,6 +450,27 @@ impl Entry {
     fn is_set(&self) -> bool {
         *self != Self::Unknown
     }
+
+    /// The value's data in `.reg` syntax, such as `dword:00000001`.
+    fn to_reg_export(&self) -> Option<String> {
+        fn hex(bytes: &[u8]) -> String {
+            bytes.iter().map(|x| format!("{x:02x}")).collect::<Vec<_>>().join(",")
+        }
+
+        let value: Option<winreg::RegValue> = self.into();
+        let value = value?;
+
+        Some(match self {
+            Self::Sz(x) => format!("\"{}\"", escape_reg_string(x)),
+            Self::Dword(x) => format!("dword:{x:08x}"),
+            Self::Binary(x) => format!("hex:{}", hex(x)),
+            _ => format!("hex({:x}):{}", value.vtype.clone() as u32, hex(&value.bytes)),
+        })
+    }
+}
+
+fn escape_reg_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
 impl From<winreg::RegValue> for Entry {
@@ -639,4 +701,35 @@ HKEY_CURRENT_USER:
             .trim()
         )
     }
+
+    #[test]
+    fn can_render_reg_export() {
+        let hives = Hives(btree_map! {
+            s("HKEY_CURRENT_USER"): Keys(btree_map! {
+                s("Software\\Ludusavi\\game3"): Entries(btree_map! {
+                    s(""): Entry::Sz(s("default")),
+                    s("sz"): Entry::Sz(s("C:\\foo \"bar\"")),
+                    s("dword"): Entry::Dword(1),
+                    s("qword"): Entry::Qword(2),
+                    s("binary"): Entry::Binary(vec![1, 2, 255]),
+                }),
+            })
+        });
+
+        assert_eq!(
+            [
+                "Windows Registry Editor Version 5.00",
+                "",
+                "[HKEY_CURRENT_USER\\Software\\Ludusavi\\game3]",
+                "@=\"default\"",
+                "\"binary\"=hex:01,02,ff",
+                "\"dword\"=dword:00000001",
+                "\"qword\"=hex(b):02,00,00,00,00,00,00,00",
+                "\"sz\"=\"C:\\\\foo \\\"bar\\\"\"",
+                "",
+            ]
+            .join("\r\n"),
+            hives.to_reg_export(),
+        );
+    }
 }
@@ -1,3 +1,11 @@
+/// Lists the subkeys of a registry key.
+/// This lets us expand key patterns against the real registry or a fake one in tests.
+pub trait RegistrySubkeys {
+    /// The `key` includes the hive (e.g., `HKEY_CURRENT_USER\Software`).
+    /// Returns `None` if the key cannot be opened.
+    fn subkeys(&self, key: &str) -> Option<Vec<String>>;
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, schemars::JsonSchema)]
 pub struct RegistryItem {
     raw: String,
@@ -52,6 +60,53 @@ impl RegistryItem {
         (parts.len() == 2).then(|| (parts[0].to_string(), parts[1].to_string()))
     }
 
+    pub fn is_glob(&self) -> bool {
+        self.raw.contains(['*', '?', '['])
+    }
+
+    /// Expand any glob segments (e.g., `Profile*`) into the matching subkeys.
+    /// Literal keys are returned as-is, even if they don't exist,
+    /// while patterns that don't match anything produce an empty list.
+    pub fn expand_glob(&self, registry: &impl RegistrySubkeys) -> Vec<Self> {
+        if !self.is_glob() {
+            return vec![self.clone()];
+        }
+
+        let mut parts = self.split().into_iter();
+        let Some(hive) = parts.next() else {
+            return vec![];
+        };
+
+        let mut current = vec![hive];
+        for part in parts {
+            let mut next = vec![];
+
+            if Self::new(part.clone()).is_glob() {
+                let Ok(glob) = globset::GlobBuilder::new(&part).case_insensitive(true).build() else {
+                    log::trace!("Invalid registry glob: {} | {}", self.raw, part);
+                    return vec![];
+                };
+                let matcher = glob.compile_matcher();
+
+                for parent in current {
+                    for subkey in registry.subkeys(&parent).unwrap_or_default() {
+                        if matcher.is_match(&subkey) {
+                            next.push(format!("{}\\{}", parent, subkey));
+                        }
+                    }
+                }
+            } else {
+                for parent in current {
+                    next.push(format!("{}\\{}", parent, part));
+                }
+            }
+
+            current = next;
+        }
+
+        current.into_iter().map(Self::new).collect()
+    }
+
     pub fn is_prefix_of(&self, other: &Self) -> bool {
         let us_components = self.split();
         let them_components = other.split();
@@ -120,9 +175,34 @@ impl<'de> serde::Deserialize<'de> for RegistryItem {
 mod tests {
     use pretty_assertions::assert_eq;
 
+    use std::collections::HashMap;
+
     use super::*;
     use crate::testing::s;
 
+    struct FakeRegistry(HashMap<&'static str, Vec<&'static str>>);
+
+    impl RegistrySubkeys for FakeRegistry {
+        fn subkeys(&self, key: &str) -> Option<Vec<String>> {
+            self.0
+                .get(key)
+                .map(|subkeys| subkeys.iter().map(|x| x.to_string()).collect())
+        }
+    }
+
+    fn registry() -> FakeRegistry {
+        FakeRegistry(HashMap::from([
+            ("HKEY_CURRENT_USER", vec!["Software"]),
+            ("HKEY_CURRENT_USER\\Software", vec!["Game", "Other"]),
+            (
+                "HKEY_CURRENT_USER\\Software\\Game",
+                vec!["Profile1", "profile2", "Settings"],
+            ),
+            ("HKEY_CURRENT_USER\\Software\\Game\\Profile1", vec!["Saves"]),
+            ("HKEY_CURRENT_USER\\Software\\Game\\profile2", vec![]),
+        ]))
+    }
+
     #[test]
     fn is_prefix_of() {
         assert!(RegistryItem::new(s(r#"HKCU"#)).is_prefix_of(&RegistryItem::new(s("HKCU/foo"))));
@@ -152,4 +232,56 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn can_expand_literal_key() {
+        assert_eq!(
+            vec![RegistryItem::new(s("HKEY_CURRENT_USER/Software/Missing"))],
+            RegistryItem::new(s("HKEY_CURRENT_USER/Software/Missing")).expand_glob(&registry()),
+        );
+    }
+
+    #[test]
+    fn can_expand_glob_key() {
+        assert_eq!(
+            vec![
+                RegistryItem::new(s("HKEY_CURRENT_USER\\Software\\Game\\Profile1")),
+                RegistryItem::new(s("HKEY_CURRENT_USER\\Software\\Game\\profile2")),
+            ],
+            RegistryItem::new(s("HKEY_CURRENT_USER/Software/Game/Profile*")).expand_glob(&registry()),
+        );
+    }
+
+    #[test]
+    fn can_expand_glob_key_with_multiple_glob_segments() {
+        assert_eq!(
+            vec![RegistryItem::new(s(
+                "HKEY_CURRENT_USER\\Software\\Game\\Profile1\\Saves"
+            ))],
+            RegistryItem::new(s("HKEY_CURRENT_USER/Software/G?me/Profile*/*")).expand_glob(&registry()),
+        );
+    }
+
+    #[test]
+    fn can_expand_glob_key_with_literal_segment_after_glob() {
+        assert_eq!(
+            vec![
+                RegistryItem::new(s("HKEY_CURRENT_USER\\Software\\Game\\Profile1")),
+                RegistryItem::new(s("HKEY_CURRENT_USER\\Software\\Game\\profile2")),
+            ],
+            RegistryItem::new(s("HKEY_CURRENT_USER/Software/*/Profile[12]")).expand_glob(&registry()),
+        );
+    }
+
+    #[test]
+    fn can_expand_glob_key_without_matches() {
+        assert_eq!(
+            Vec::<RegistryItem>::new(),
+            RegistryItem::new(s("HKEY_CURRENT_USER/Software/Game/Missing*")).expand_glob(&registry()),
+        );
+        assert_eq!(
+            Vec::<RegistryItem>::new(),
+            RegistryItem::new(s("HKEY_CURRENT_USER/Software/Missing/*")).expand_glob(&registry()),
+        );
+    }
 }
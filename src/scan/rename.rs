@@ -0,0 +1,401 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    path::StrictPath,
+    prelude::{AnyError, Error},
+    scan::layout::{BackupLayout, GameLayout, IndividualMapping, BLOBS},
+};
+
+const MAPPING_FILE: &str = "mapping.yaml";
+
+/// Moving a game's backups to a different name,
+/// such as when the manifest renames the game.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenamePlan {
+    pub old_name: String,
+    pub new_name: String,
+    pub source: StrictPath,
+    pub target: StrictPath,
+    /// Whether the new name already has backups, which will be kept alongside the moved ones.
+    pub merge: bool,
+    /// Files relative to the source folder, not including the mapping file,
+    /// and where each one will be moved relative to the target folder.
+    pub files: BTreeMap<String, String>,
+    /// Source backups whose names are already taken under the new name,
+    /// mapped to the timestamped names that they will be stored as instead.
+    pub renamed_backups: BTreeMap<String, String>,
+    /// Files that exist under both names.
+    /// The rename can only proceed when this is empty.
+    pub conflicts: Vec<String>,
+}
+
+pub fn plan_rename(layout: &BackupLayout, old_name: &str, new_name: &str) -> Result<RenamePlan, Error> {
+    let Some(source) = layout.try_game_layout(old_name).map(|x| x.path) else {
+        return Err(Error::CliUnrecognizedGames {
+            games: vec![old_name.to_string()],
+        });
+    };
+    let source_mapping = load_mapping(&source)?;
+
+    let target = layout.game_folder(new_name);
+    let same_folder = source.equivalent(&target);
+    let target_mapping = if same_folder {
+        None
+    } else {
+        IndividualMapping::load(&target.joined(MAPPING_FILE)).ok()
+    };
+
+    let backups: Vec<_> = source_mapping
+        .backups
+        .iter()
+        .flat_map(|full| {
+            std::iter::once((&full.name, full.when, false))
+                .chain(full.children.iter().map(|diff| (&diff.name, diff.when, true)))
+        })
+        .collect();
+
+    // Backups with the same name can't share a folder, so we give ours a new name and merge them.
+    let mut renamed_backups = BTreeMap::new();
+    if let Some(target_mapping) = &target_mapping {
+        let mut taken: BTreeSet<_> = backups.iter().map(|(name, ..)| name.to_string()).collect();
+        for (name, when, differential) in &backups {
+            if !target_mapping.has_backup(name) {
+                continue;
+            }
+            let new_name = timestamped_name(name, when, *differential, |x| {
+                taken.contains(x) || target_mapping.has_backup(x)
+            });
+            taken.insert(new_name.clone());
+            renamed_backups.insert(name.to_string(), new_name);
+        }
+    }
+
+    let source_names: BTreeSet<_> = backups.iter().map(|(name, ..)| name.as_str()).collect();
+    let files: BTreeMap<_, _> = (if same_folder { vec![] } else { list_files(&source) })
+        .into_iter()
+        .map(|file| {
+            let moved = target_file(&file, &renamed_backups, &source_names);
+            (file, moved)
+        })
+        .collect();
+
+    let mut conflicts = BTreeSet::new();
+    for (file, moved) in &files {
+        // Blobs are named by their hash, so an existing one already has the same content.
+        if !file.starts_with(&format!("{BLOBS}/")) && target.joined(moved).exists() {
+            conflicts.insert(moved.clone());
+        }
+    }
+
+    Ok(RenamePlan {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        source,
+        target,
+        merge: target_mapping.is_some(),
+        files,
+        renamed_backups,
+        conflicts: conflicts.into_iter().collect(),
+    })
+}
+
+/// Move the files first and write the new mapping file last,
+/// so that an interrupted rename leaves the old backups recognizable.
+pub fn rename(plan: &RenamePlan) -> Result<(), Error> {
+    if !plan.conflicts.is_empty() {
+        return Err(Error::BackupRenameConflict {
+            game: plan.old_name.clone(),
+            conflicts: plan.conflicts.clone(),
+        });
+    }
+
+    try_rename(plan).map_err(|e| {
+        log::error!(
+            "Unable to rename backups: {:?} -> {:?} | {e}",
+            &plan.source,
+            &plan.target
+        );
+        Error::CannotRenameBackups {
+            game: plan.old_name.clone(),
+            path: plan.target.clone(),
+        }
+    })
+}
+
+fn try_rename(plan: &RenamePlan) -> Result<(), AnyError> {
+    let mut mapping = IndividualMapping::load(&plan.source.joined(MAPPING_FILE))?;
+    mapping.name = plan.new_name.clone();
+    for full in mapping.backups.iter_mut() {
+        if let Some(new_name) = plan.renamed_backups.get(&full.name) {
+            full.name = new_name.clone();
+        }
+        for diff in full.children.iter_mut() {
+            if let Some(new_name) = plan.renamed_backups.get(&diff.name) {
+                diff.name = new_name.clone();
+            }
+        }
+    }
+
+    if plan.source.equivalent(&plan.target) {
+        mapping.save(&plan.target.joined(MAPPING_FILE));
+        return Ok(());
+    }
+
+    for (file, moved) in &plan.files {
+        let source = plan.source.joined(file);
+        let target = plan.target.joined(moved);
+        if target.exists() {
+            continue;
+        }
+        log::debug!("[{}] moving: {:?} -> {:?}", &plan.old_name, &source, &target);
+        target.create_parent_dir()?;
+        source.move_to(&target)?;
+    }
+
+    if plan.merge {
+        let mut existing = IndividualMapping::load(&plan.target.joined(MAPPING_FILE))?;
+        for (folder, drive) in std::mem::take(&mut mapping.drives) {
+            existing.drives.entry(folder).or_insert(drive);
+        }
+        existing.backups.extend(std::mem::take(&mut mapping.backups));
+        existing.backups.make_contiguous().sort_by_key(|x| x.when);
+        mapping = existing;
+    }
+
+    mapping.save(&plan.target.joined(MAPPING_FILE));
+    plan.source.remove()?;
+
+    Ok(())
+}
+
+/// Like a newly generated backup name, but based on when the backup was originally made.
+fn timestamped_name(
+    name: &str,
+    when: &chrono::DateTime<chrono::Utc>,
+    differential: bool,
+    taken: impl Fn(&str) -> bool,
+) -> String {
+    let extension = if name.ends_with(".zip") { ".zip" } else { "" };
+    let kind = if differential { "-diff" } else { "" };
+    let stem = format!("backup-{}{kind}", GameLayout::generate_file_friendly_timestamp(when));
+
+    let mut candidate = format!("{stem}{extension}");
+    let mut counter = 2;
+    while taken(&candidate) {
+        candidate = format!("{stem}-{counter}{extension}");
+        counter += 1;
+    }
+    candidate
+}
+
+/// Files in a simple backup are stored under the backup's folder, except for the `.` backup,
+/// which uses the game folder itself. A zip backup is a single file named after the backup.
+fn target_file(file: &str, renamed_backups: &BTreeMap<String, String>, backups: &BTreeSet<&str>) -> String {
+    if file.starts_with(&format!("{BLOBS}/")) {
+        return file.to_string();
+    }
+
+    let owner = backups
+        .iter()
+        .find(|name| **name != "." && (file == **name || file.starts_with(&format!("{name}/"))))
+        .copied()
+        .or_else(|| backups.contains(".").then_some("."));
+
+    match owner.and_then(|owner| renamed_backups.get(owner).map(|new| (owner, new))) {
+        Some((".", new)) => format!("{new}/{file}"),
+        Some((owner, new)) => format!("{new}{}", &file[owner.len()..]),
+        None => file.to_string(),
+    }
+}
+
+fn load_mapping(folder: &StrictPath) -> Result<IndividualMapping, Error> {
+    IndividualMapping::load(&folder.joined(MAPPING_FILE))
+        .map_err(|_| Error::RestorationSourceInvalid { path: folder.clone() })
+}
+
+fn list_files(folder: &StrictPath) -> Vec<String> {
+    let Ok(base) = folder.interpret() else {
+        return vec![];
+    };
+
+    let mut files: Vec<_> = walkdir::WalkDir::new(&base)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(crate::scan::filter_map_walkdir)
+        .filter(|x| x.file_type().is_file())
+        .filter_map(|x| {
+            x.path()
+                .strip_prefix(&base)
+                .ok()
+                .map(|x| x.to_string_lossy().replace('\\', "/"))
+        })
+        .filter(|x| x != MAPPING_FILE)
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::btree_map;
+
+    use super::*;
+    use crate::{
+        resource::config::Retention,
+        testing::{repo, s},
+    };
+
+    fn layout(base: &str) -> BackupLayout {
+        BackupLayout::new(StrictPath::new(base.to_string()), Retention::default())
+    }
+
+    #[test]
+    fn can_plan_rename_to_new_name() {
+        let base = format!("{}/tests/backup", repo());
+        let plan = plan_rename(&layout(&base), "game1", "game1-new").unwrap();
+
+        assert_eq!(
+            RenamePlan {
+                old_name: s("game1"),
+                new_name: s("game1-new"),
+                source: StrictPath::new(format!("{base}/game1")),
+                target: StrictPath::new(format!("{base}/game1-new")),
+                merge: false,
+                files: btree_map! {
+                    s("drive-X/file1.txt"): s("drive-X/file1.txt"),
+                    s("drive-X/file2.txt"): s("drive-X/file2.txt"),
+                },
+                renamed_backups: btree_map! {},
+                conflicts: vec![],
+            },
+            plan,
+        );
+    }
+
+    #[test]
+    fn can_plan_rename_with_shared_backup_names() {
+        let base = format!("{}/tests/backup", repo());
+        let plan = plan_rename(&layout(&base), "game1", "game3").unwrap();
+
+        assert!(plan.merge);
+        assert_eq!(
+            btree_map! {
+                s("."): s("backup-20000102T030405Z"),
+            },
+            plan.renamed_backups,
+        );
+        assert_eq!(
+            btree_map! {
+                s("drive-X/file1.txt"): s("backup-20000102T030405Z/drive-X/file1.txt"),
+                s("drive-X/file2.txt"): s("backup-20000102T030405Z/drive-X/file2.txt"),
+            },
+            plan.files,
+        );
+        assert_eq!(Vec::<String>::new(), plan.conflicts);
+    }
+
+    #[test]
+    fn can_plan_rename_with_conflicts() {
+        let plan = RenamePlan {
+            old_name: s("game1"),
+            conflicts: vec![s("drive-X/file1.txt")],
+            ..Default::default()
+        };
+
+        assert!(matches!(rename(&plan), Err(Error::BackupRenameConflict { .. })));
+    }
+
+    #[test]
+    fn rejects_rename_of_unknown_game() {
+        let base = format!("{}/tests/backup", repo());
+
+        assert_eq!(
+            Err(Error::CliUnrecognizedGames {
+                games: vec![s("nonexistent")]
+            }),
+            plan_rename(&layout(&base), "nonexistent", "game1"),
+        );
+    }
+
+    #[test]
+    fn can_rename_and_merge_backups() {
+        let dir = std::env::temp_dir().join("ludusavi-tests/rename-merge");
+        _ = std::fs::remove_dir_all(&dir);
+        for (folder, name, backup, when) in [
+            ("old", "Old", "backup-1", "2000-01-01T00:00:00Z"),
+            ("new", "New", "backup-2", "2000-01-02T00:00:00Z"),
+        ] {
+            let game = dir.join(folder);
+            std::fs::create_dir_all(game.join(backup).join("drive-X")).unwrap();
+            std::fs::create_dir_all(game.join(BLOBS)).unwrap();
+            std::fs::write(game.join(backup).join("drive-X/file.txt"), backup).unwrap();
+            std::fs::write(game.join(BLOBS).join("abc"), "shared").unwrap();
+            std::fs::write(
+                game.join(MAPPING_FILE),
+                format!(
+                    "name: {name}\ndrives:\n  drive-X: \"X:\"\nbackups:\n  - name: {backup}\n    when: \"{when}\"\n"
+                ),
+            )
+            .unwrap();
+        }
+        let base = dir.to_string_lossy().replace('\\', "/");
+
+        let plan = plan_rename(&layout(&base), "Old", "New").unwrap();
+        assert!(plan.merge);
+        assert_eq!(Vec::<String>::new(), plan.conflicts);
+        rename(&plan).unwrap();
+
+        assert!(!dir.join("old").exists());
+        assert!(dir.join("new/backup-1/drive-X/file.txt").is_file());
+        assert!(dir.join("new/backup-2/drive-X/file.txt").is_file());
+
+        let mapping = IndividualMapping::load(&StrictPath::new(format!("{base}/new/{MAPPING_FILE}"))).unwrap();
+        assert_eq!("New", mapping.name);
+        assert_eq!(
+            vec![s("backup-1"), s("backup-2")],
+            mapping.backups.iter().map(|x| x.name.clone()).collect::<Vec<_>>(),
+        );
+        assert_eq!(vec![s("New")], layout(&base).restorable_games());
+    }
+
+    #[test]
+    fn can_rename_and_merge_backups_with_the_same_name() {
+        let dir = std::env::temp_dir().join("ludusavi-tests/rename-merge-same-name");
+        _ = std::fs::remove_dir_all(&dir);
+        for (folder, name, when) in [
+            ("old", "Old", "2000-01-01T00:00:00Z"),
+            ("new", "New", "2000-01-02T00:00:00Z"),
+        ] {
+            let game = dir.join(folder);
+            std::fs::create_dir_all(game.join("drive-X")).unwrap();
+            std::fs::write(game.join("drive-X/file.txt"), folder).unwrap();
+            std::fs::write(
+                game.join(MAPPING_FILE),
+                format!("name: {name}\ndrives:\n  drive-X: \"X:\"\nbackups:\n  - name: \".\"\n    when: \"{when}\"\n"),
+            )
+            .unwrap();
+        }
+        let base = dir.to_string_lossy().replace('\\', "/");
+
+        let plan = plan_rename(&layout(&base), "Old", "New").unwrap();
+        assert_eq!(Vec::<String>::new(), plan.conflicts);
+        rename(&plan).unwrap();
+
+        assert!(!dir.join("old").exists());
+        assert_eq!(
+            "old",
+            std::fs::read_to_string(dir.join("new/backup-20000101T000000Z/drive-X/file.txt")).unwrap()
+        );
+        assert_eq!(
+            "new",
+            std::fs::read_to_string(dir.join("new/drive-X/file.txt")).unwrap()
+        );
+
+        let mapping = IndividualMapping::load(&StrictPath::new(format!("{base}/new/{MAPPING_FILE}"))).unwrap();
+        assert_eq!(
+            vec![s("backup-20000101T000000Z"), s(".")],
+            mapping.backups.iter().map(|x| x.name.clone()).collect::<Vec<_>>(),
+        );
+    }
+}
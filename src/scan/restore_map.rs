@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    prelude::StrictPath,
+    resource::{config::Root, manifest::Store},
+    scan::{BackupInfo, ScanInfo},
+};
+
+const RESTORE_MAP_FILE: &str = "restore-map.yaml";
+
+/// Where one backed up file originally came from.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RestoreMapEntry {
+    /// The configured root that contained the file, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+    /// The store of that root.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<Store>,
+}
+
+/// A human-readable list of where each file in a game's backup originally lived,
+/// for restoring by hand or on a different machine.
+///
+/// Ludusavi itself does not read this file.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RestoreMap {
+    pub game: String,
+    /// Each key is the original absolute path of a file.
+    pub files: BTreeMap<String, RestoreMapEntry>,
+}
+
+impl RestoreMap {
+    pub fn new(scan: &ScanInfo, backup_info: &BackupInfo, roots: &[Root]) -> Self {
+        let root_paths: Vec<_> = roots.iter().map(|root| root.path().clone()).collect();
+
+        let files = scan
+            .found_files
+            .iter()
+            .filter(|file| !file.ignored && !backup_info.failed_files.contains_key(*file))
+            .map(|file| {
+                let original = file.original_path();
+                let root = original
+                    .nearest_prefix(root_paths.clone())
+                    .and_then(|prefix| roots.iter().find(|root| root.path() == &prefix));
+
+                (
+                    original.render(),
+                    RestoreMapEntry {
+                        root: root.map(|root| root.path().render()),
+                        store: root.map(|root| root.store()),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            game: scan.game_name.clone(),
+            files,
+        }
+    }
+
+    pub fn file(game_dir: &StrictPath) -> StrictPath {
+        game_dir.joined(RESTORE_MAP_FILE)
+    }
+
+    pub fn save(&self, game_dir: &StrictPath) {
+        let file = Self::file(game_dir);
+        let saved = serde_yaml::to_string(self)
+            .ok()
+            .and_then(|content| file.write_with_content(&content).ok());
+        if saved.is_none() {
+            log::error!("[{}] unable to save restore map: {:?}", self.game, &file);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        resource::config::root,
+        scan::{BackupError, ScannedFile},
+        testing::s,
+    };
+
+    #[test]
+    fn can_build_restore_map() {
+        let roots = vec![
+            Root::new("/steam", Store::Steam),
+            Root::new("/home", Store::OtherHome),
+            Root::Other(root::Other {
+                path: StrictPath::new(s("/home/games")),
+            }),
+        ];
+        let scan = ScanInfo {
+            game_name: s("game1"),
+            found_files: HashSet::from([
+                ScannedFile::with_name("/steam/userdata/save.dat"),
+                ScannedFile::with_name("/home/games/game1/save.dat"),
+                ScannedFile::with_name("/elsewhere/save.dat"),
+                ScannedFile::with_name("/home/ignored.dat").ignored(),
+                ScannedFile::with_name("/home/failed.dat"),
+            ]),
+            ..Default::default()
+        };
+        let backup_info = BackupInfo {
+            failed_files: HashMap::from([(
+                ScannedFile::with_name("/home/failed.dat"),
+                BackupError::Raw(s("failed")),
+            )]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            RestoreMap {
+                game: s("game1"),
+                files: BTreeMap::from([
+                    (s("/elsewhere/save.dat"), RestoreMapEntry::default()),
+                    (
+                        s("/home/games/game1/save.dat"),
+                        RestoreMapEntry {
+                            root: Some(s("/home/games")),
+                            store: Some(Store::Other),
+                        }
+                    ),
+                    (
+                        s("/steam/userdata/save.dat"),
+                        RestoreMapEntry {
+                            root: Some(s("/steam")),
+                            store: Some(Store::Steam),
+                        }
+                    ),
+                ]),
+            },
+            RestoreMap::new(&scan, &backup_info, &roots),
+        );
+    }
+}
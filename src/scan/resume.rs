@@ -0,0 +1,190 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::File,
+    io::{LineWriter, Write},
+    sync::Mutex,
+};
+
+use crate::{
+    prelude::{Hasher, StrictPath},
+    resource::config::{BackupFormats, GameFormat, HashAlgorithm},
+};
+
+const JOURNAL_FILE: &str = ".ludusavi-resume";
+
+/// Tracks which games a backup has completed,
+/// so that an interrupted run can be picked up with `backup --resume`.
+///
+/// The first line of the file identifies the run's parameters,
+/// and each subsequent line is the JSON-encoded name of a completed game.
+pub struct ResumeJournal {
+    path: StrictPath,
+    file: Mutex<Option<LineWriter<File>>>,
+    completed: HashSet<String>,
+}
+
+#[derive(serde::Serialize)]
+struct Fingerprint<'a> {
+    games: &'a [String],
+    format: &'a BackupFormats,
+    per_game_format: &'a BTreeMap<String, GameFormat>,
+}
+
+impl ResumeJournal {
+    pub fn fingerprint(
+        games: &[String],
+        format: &BackupFormats,
+        per_game_format: &BTreeMap<String, GameFormat>,
+    ) -> String {
+        let mut games = games.to_vec();
+        games.sort();
+
+        let content = serde_json::to_string(&Fingerprint {
+            games: &games,
+            format,
+            per_game_format,
+        })
+        .unwrap();
+
+        let mut hasher = Hasher::new(HashAlgorithm::Sha1);
+        hasher.update(content.as_bytes());
+        hasher.finalize()
+    }
+
+    /// Start a new journal in the backup folder.
+    /// When `resume` is set and the existing journal has a matching fingerprint,
+    /// its completed games are kept; otherwise, it is discarded.
+    pub fn open(backup_dir: &StrictPath, fingerprint: &str, resume: bool) -> Self {
+        let path = backup_dir.joined(JOURNAL_FILE);
+
+        let completed = if resume {
+            match path.read() {
+                Some(content) => Self::parse(&content, fingerprint).unwrap_or_else(|| {
+                    log::info!("Resume journal does not match the current backup parameters; starting over");
+                    HashSet::new()
+                }),
+                None => HashSet::new(),
+            }
+        } else {
+            HashSet::new()
+        };
+
+        let file = match Self::rewrite(&path, fingerprint, &completed) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                log::warn!("Unable to write resume journal: {path:?} | {e:?}");
+                None
+            }
+        };
+
+        Self {
+            path,
+            file: Mutex::new(file),
+            completed,
+        }
+    }
+
+    fn parse(content: &str, fingerprint: &str) -> Option<HashSet<String>> {
+        let mut lines = content.lines();
+        if lines.next()? != fingerprint {
+            return None;
+        }
+
+        // A partially written final line is simply ignored.
+        Some(lines.filter_map(|line| serde_json::from_str(line).ok()).collect())
+    }
+
+    fn rewrite(path: &StrictPath, fingerprint: &str, completed: &HashSet<String>) -> std::io::Result<LineWriter<File>> {
+        let mut file = LineWriter::new(path.create()?);
+        writeln!(file, "{fingerprint}")?;
+        for game in completed {
+            writeln!(file, "{}", serde_json::to_string(game).unwrap())?;
+        }
+        Ok(file)
+    }
+
+    pub fn completed(&self) -> usize {
+        self.completed.len()
+    }
+
+    pub fn is_completed(&self, game: &str) -> bool {
+        self.completed.contains(game)
+    }
+
+    pub fn record(&self, game: &str) {
+        let mut file = self.file.lock().unwrap();
+        if let Some(file) = file.as_mut() {
+            if let Err(e) = writeln!(file, "{}", serde_json::to_string(game).unwrap()) {
+                log::warn!("Unable to update resume journal: {:?} | {e:?}", &self.path);
+            }
+        }
+    }
+
+    /// Delete the journal once the backup has finished without failures.
+    pub fn remove(self) {
+        drop(self.file);
+        if let Err(e) = self.path.remove() {
+            log::warn!("Unable to remove resume journal: {:?} | {e:?}", &self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn games() -> Vec<String> {
+        vec!["game1".to_string(), "game\n2".to_string()]
+    }
+
+    #[test]
+    fn can_resume_from_journal() {
+        let dir = std::env::temp_dir().join("ludusavi-tests/resume-journal");
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = StrictPath::from(dir);
+
+        let formats = BackupFormats::default();
+        let fingerprint = ResumeJournal::fingerprint(&games(), &formats, &Default::default());
+
+        let journal = ResumeJournal::open(&dir, &fingerprint, false);
+        journal.record("game\n2");
+        drop(journal);
+
+        let journal = ResumeJournal::open(&dir, &fingerprint, true);
+        assert_eq!(1, journal.completed());
+        assert!(journal.is_completed("game\n2"));
+        assert!(!journal.is_completed("game1"));
+        drop(journal);
+
+        let journal = ResumeJournal::open(&dir, &fingerprint, true);
+        assert!(journal.is_completed("game\n2"));
+        journal.remove();
+        assert!(!dir.joined(JOURNAL_FILE).exists());
+    }
+
+    #[test]
+    fn journal_is_invalidated_when_parameters_change() {
+        let dir = std::env::temp_dir().join("ludusavi-tests/resume-journal-invalidated");
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = StrictPath::from(dir);
+
+        let formats = BackupFormats::default();
+        let fingerprint = ResumeJournal::fingerprint(&games(), &formats, &Default::default());
+        ResumeJournal::open(&dir, &fingerprint, false).record("game1");
+
+        let other = ResumeJournal::fingerprint(&games()[..1], &formats, &Default::default());
+        assert_ne!(fingerprint, other);
+        assert_eq!(0, ResumeJournal::open(&dir, &other, true).completed());
+
+        let formats = BackupFormats {
+            chosen: crate::resource::config::BackupFormat::Zip,
+            ..Default::default()
+        };
+        let other = ResumeJournal::fingerprint(&games(), &formats, &Default::default());
+        assert_ne!(fingerprint, other);
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::scan::ScanInfo;
+
+/// Which folders to watch for each game.
+/// The save locations from the manifest are watched recursively,
+/// so that new saves are noticed even if the game had none at first.
+/// Registry entries can't be watched, so they're only picked up
+/// when a file change triggers a backup.
+#[derive(Debug, Default)]
+pub struct WatchTargets {
+    /// Folders whose whole tree is watched.
+    roots: BTreeMap<PathBuf, BTreeSet<String>>,
+    /// Folders of individual save files that aren't within any of the game's roots.
+    dirs: BTreeMap<PathBuf, BTreeSet<String>>,
+}
+
+/// A folder that should start being watched.
+#[derive(Debug, PartialEq, Eq)]
+pub struct WatchTarget {
+    pub path: PathBuf,
+    pub recursive: bool,
+}
+
+impl WatchTargets {
+    /// Record the game's save locations and the folders of its save files.
+    /// Returns any folders that weren't already being watched.
+    pub fn add(&mut self, scan: &ScanInfo) -> Vec<WatchTarget> {
+        let mut added = vec![];
+
+        for root in &scan.save_roots {
+            let Ok(root) = root.as_std_path_buf() else {
+                continue;
+            };
+
+            let games = self.roots.entry(root.clone()).or_insert_with(|| {
+                added.push(WatchTarget {
+                    path: root,
+                    recursive: true,
+                });
+                BTreeSet::new()
+            });
+            games.insert(scan.game_name.clone());
+        }
+
+        for file in &scan.found_files {
+            if file.ignored {
+                continue;
+            }
+            let Some(dir) = file
+                .path
+                .as_std_path_buf()
+                .ok()
+                .and_then(|path| path.parent().map(Path::to_path_buf))
+            else {
+                continue;
+            };
+            if self
+                .roots
+                .iter()
+                .any(|(root, games)| games.contains(&scan.game_name) && dir.starts_with(root))
+            {
+                continue;
+            }
+
+            let games = self.dirs.entry(dir.clone()).or_insert_with(|| {
+                added.push(WatchTarget {
+                    path: dir,
+                    recursive: false,
+                });
+                BTreeSet::new()
+            });
+            games.insert(scan.game_name.clone());
+        }
+
+        added
+    }
+
+    /// Find the games whose watched folders contain this path.
+    pub fn games_for(&self, path: &Path) -> BTreeSet<String> {
+        let in_roots = path.ancestors().filter_map(|dir| self.roots.get(dir));
+        let in_dirs = [Some(path), path.parent()]
+            .into_iter()
+            .flatten()
+            .filter_map(|dir| self.dirs.get(dir));
+
+        in_roots.chain(in_dirs).flatten().cloned().collect()
+    }
+
+    pub fn games(&self) -> BTreeSet<String> {
+        self.roots
+            .values()
+            .chain(self.dirs.values())
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+/// Coalesces rapid changes so that each game is only backed up
+/// once its saves have been quiet for the delay.
+#[derive(Debug)]
+pub struct Debouncer {
+    delay: Duration,
+    pending: BTreeMap<String, Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Note a change, which restarts the game's delay.
+    pub fn note(&mut self, game: String, now: Instant) {
+        self.pending.insert(game, now);
+    }
+
+    /// Take the games whose delay has passed.
+    pub fn due(&mut self, now: Instant) -> Vec<String> {
+        let due: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_, changed)| now.saturating_duration_since(**changed) >= self.delay)
+            .map(|(game, _)| game.clone())
+            .collect();
+
+        for game in &due {
+            self.pending.remove(game);
+        }
+
+        due
+    }
+
+    /// Take all pending games, regardless of their delay.
+    pub fn drain(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending).into_keys().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{prelude::StrictPath, scan::ScannedFile, testing::s};
+
+    #[test]
+    fn can_find_games_by_changed_path() {
+        let mut targets = WatchTargets::default();
+
+        let added = targets.add(&ScanInfo {
+            game_name: s("game1"),
+            found_files: HashSet::from([
+                ScannedFile::with_name("/saves/shared/game1.dat"),
+                ScannedFile::with_name("/saves/game1/a.dat"),
+                ScannedFile::with_name("/saves/game1/b.dat"),
+                ScannedFile::with_name("/saves/ignored/c.dat").ignored(),
+            ]),
+            ..Default::default()
+        });
+        assert_eq!(2, added.len());
+
+        let added = targets.add(&ScanInfo {
+            game_name: s("game2"),
+            found_files: HashSet::from([ScannedFile::with_name("/saves/shared/game2.dat")]),
+            ..Default::default()
+        });
+        assert!(added.is_empty());
+
+        assert_eq!(
+            BTreeSet::from([s("game1")]),
+            targets.games_for(Path::new("/saves/game1/new.dat"))
+        );
+        assert_eq!(
+            BTreeSet::from([s("game1"), s("game2")]),
+            targets.games_for(Path::new("/saves/shared/game2.dat"))
+        );
+        assert_eq!(
+            BTreeSet::from([s("game1")]),
+            targets.games_for(Path::new("/saves/game1"))
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            targets.games_for(Path::new("/saves/ignored/c.dat"))
+        );
+        assert_eq!(BTreeSet::from([s("game1"), s("game2")]), targets.games());
+    }
+
+    #[test]
+    fn can_find_games_by_path_within_save_roots() {
+        let mut targets = WatchTargets::default();
+
+        let added = targets.add(&ScanInfo {
+            game_name: s("game1"),
+            found_files: HashSet::from([
+                ScannedFile::with_name("/saves/game1/slot1/a.dat"),
+                ScannedFile::with_name("/other/game1.cfg"),
+            ]),
+            save_roots: BTreeSet::from([StrictPath::from("/saves/game1")]),
+            ..Default::default()
+        });
+        assert_eq!(
+            vec![
+                WatchTarget {
+                    path: PathBuf::from("/saves/game1"),
+                    recursive: true,
+                },
+                WatchTarget {
+                    path: PathBuf::from("/other"),
+                    recursive: false,
+                },
+            ],
+            added
+        );
+
+        assert_eq!(
+            BTreeSet::from([s("game1")]),
+            targets.games_for(Path::new("/saves/game1/slot2/new/b.dat"))
+        );
+        assert_eq!(
+            BTreeSet::from([s("game1")]),
+            targets.games_for(Path::new("/other/game1.cfg"))
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            targets.games_for(Path::new("/other/nested/game1.cfg"))
+        );
+        assert_eq!(
+            BTreeSet::<String>::new(),
+            targets.games_for(Path::new("/saves/game2/a.dat"))
+        );
+    }
+
+    #[test]
+    fn can_debounce_changes() {
+        let start = Instant::now();
+        let delay = Duration::from_secs(5);
+        let mut debouncer = Debouncer::new(delay);
+
+        debouncer.note(s("game1"), start);
+        debouncer.note(s("game2"), start);
+        debouncer.note(s("game1"), start + Duration::from_secs(3));
+
+        assert!(debouncer.due(start + Duration::from_secs(1)).is_empty());
+        assert_eq!(vec![s("game2")], debouncer.due(start + delay));
+        assert!(debouncer.due(start + delay).is_empty());
+        assert_eq!(vec![s("game1")], debouncer.due(start + Duration::from_secs(8)));
+
+        debouncer.note(s("game3"), start);
+        assert_eq!(vec![s("game3")], debouncer.drain());
+        assert!(debouncer.drain().is_empty());
+    }
+}
@@ -15,3 +15,18 @@ where
 pub const fn default_true() -> bool {
     true
 }
+
+/// Serialize as JSON with the given number of spaces per indentation level.
+/// An indent of 0 produces compact output on a single line.
+pub fn to_json_string<T: Serialize>(value: &T, indent: usize) -> String {
+    if indent == 0 {
+        return serde_json::to_string(value).unwrap();
+    }
+
+    let spaces = " ".repeat(indent);
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(spaces.as_bytes());
+    let mut buffer = vec![];
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+    value.serialize(&mut serializer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}